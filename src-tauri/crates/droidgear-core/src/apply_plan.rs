@@ -0,0 +1,542 @@
+//! Dry-run apply plans (core).
+//!
+//! Each tool module exposes `plan_apply_*` functions that compute the exact
+//! file contents an apply would write without touching disk. This module holds
+//! the shared plan types plus the key-level diff used by both the desktop app
+//! and the TUI. Secrets are masked in both the key changes and the file contents.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use std::path::Path;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A single key-level change (values rendered as JSON, secrets masked)
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyChange {
+    /// Dotted key path, e.g. `model_providers.custom.base_url` or `customModels[0].apiKey`
+    pub key: String,
+    pub kind: KeyChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+/// Planned write of one file
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FilePlan {
+    pub path: String,
+    /// Current content with secrets masked (None if the file does not exist)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Content that would be written, with secrets masked
+    pub after: String,
+    pub changed: bool,
+    pub changes: Vec<KeyChange>,
+}
+
+/// Result of a dry-run apply
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyPlan {
+    /// Tool name ("codex", "claude", "opencode", "openclaw", "pi", "hermes", "factory")
+    pub tool: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_id: Option<String>,
+    pub files: Vec<FilePlan>,
+}
+
+impl ApplyPlan {
+    pub fn has_changes(&self) -> bool {
+        self.files.iter().any(|f| f.changed)
+    }
+}
+
+/// File format used to compute key-level changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlanFormat {
+    Json,
+    Toml,
+    Yaml,
+    /// Plain text (e.g. active-profile.txt); reported as a single `value` change
+    Text,
+}
+
+// ============================================================================
+// Building plans
+// ============================================================================
+
+/// Builds a [`FilePlan`] by reading the current content of `path`.
+pub(crate) fn file_plan(path: &Path, after: String, format: PlanFormat) -> FilePlan {
+    let before = std::fs::read_to_string(path).ok();
+    file_plan_from(path, before, after, format)
+}
+
+pub(crate) fn file_plan_from(
+    path: &Path,
+    before: Option<String>,
+    after: String,
+    format: PlanFormat,
+) -> FilePlan {
+    let changed = before.as_deref() != Some(after.as_str());
+    let changes = if changed {
+        key_changes(before.as_deref(), &after, format)
+    } else {
+        Vec::new()
+    };
+
+    let mut secrets = Vec::new();
+    for content in before.iter().chain(std::iter::once(&after)) {
+        if let Some(value) = parse_structured(content, format) {
+            collect_secrets("", &value, &mut secrets);
+        }
+    }
+    FilePlan {
+        path: path.to_string_lossy().to_string(),
+        before: before.map(|content| mask_content(&content, &secrets)),
+        after: mask_content(&after, &secrets),
+        changed,
+        changes,
+    }
+}
+
+fn parse_structured(content: &str, format: PlanFormat) -> Option<Value> {
+    if format != PlanFormat::Text && content.trim().is_empty() {
+        return Some(Value::Object(serde_json::Map::new()));
+    }
+    match format {
+        PlanFormat::Json => crate::json::parse_jsonc_value(content).ok(),
+        PlanFormat::Toml => toml::from_str::<toml::Value>(content)
+            .ok()
+            .and_then(|v| serde_json::to_value(v).ok()),
+        PlanFormat::Yaml => serde_yaml::from_str::<Value>(content).ok(),
+        PlanFormat::Text => Some(Value::String(content.trim().to_string())),
+    }
+}
+
+fn key_changes(before: Option<&str>, after: &str, format: PlanFormat) -> Vec<KeyChange> {
+    let before_v = match before {
+        Some(s) => parse_structured(s, format),
+        None => Some(Value::Null),
+    };
+    let after_v = parse_structured(after, format);
+    let (Some(before_v), Some(after_v)) = (before_v, after_v) else {
+        return Vec::new();
+    };
+
+    let mut out = Vec::new();
+    if format == PlanFormat::Text {
+        push_change(
+            &mut out,
+            "value",
+            "value",
+            non_null(&before_v),
+            Some(&after_v),
+        );
+        return out;
+    }
+    let before_v = if before_v.is_null() {
+        Value::Object(serde_json::Map::new())
+    } else {
+        before_v
+    };
+    diff_values("", &before_v, &after_v, &mut out);
+    out
+}
+
+fn non_null(v: &Value) -> Option<&Value> {
+    (!v.is_null()).then_some(v)
+}
+
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+fn diff_values(path: &str, before: &Value, after: &Value, out: &mut Vec<KeyChange>) {
+    if before == after {
+        return;
+    }
+    match (before, after) {
+        (Value::Object(b), Value::Object(a)) => {
+            for (k, bv) in b {
+                let key = join_key(path, k);
+                match a.get(k) {
+                    Some(av) => diff_values(&key, bv, av, out),
+                    None => push_change(out, &key, k, Some(bv), None),
+                }
+            }
+            for (k, av) in a {
+                if !b.contains_key(k) {
+                    push_change(out, &join_key(path, k), k, None, Some(av));
+                }
+            }
+        }
+        (Value::Array(b), Value::Array(a)) => {
+            let leaf = leaf_name(path);
+            for i in 0..b.len().max(a.len()) {
+                let key = format!("{path}[{i}]");
+                match (b.get(i), a.get(i)) {
+                    (Some(bv), Some(av)) => diff_values(&key, bv, av, out),
+                    (bv, av) => push_change(out, &key, leaf, bv, av),
+                }
+            }
+        }
+        _ => push_change(out, path, leaf_name(path), Some(before), Some(after)),
+    }
+}
+
+fn leaf_name(path: &str) -> &str {
+    let base = path.split('[').next().unwrap_or(path);
+    base.rsplit('.').next().unwrap_or(base)
+}
+
+fn push_change(
+    out: &mut Vec<KeyChange>,
+    key: &str,
+    leaf: &str,
+    before: Option<&Value>,
+    after: Option<&Value>,
+) {
+    let kind = match (before, after) {
+        (None, Some(_)) => KeyChangeKind::Added,
+        (Some(_), None) => KeyChangeKind::Removed,
+        _ => KeyChangeKind::Changed,
+    };
+    out.push(KeyChange {
+        key: key.to_string(),
+        kind,
+        before: before.map(|v| render_masked(leaf, v)),
+        after: after.map(|v| render_masked(leaf, v)),
+    });
+}
+
+// ============================================================================
+// Secret masking
+// ============================================================================
+
+/// Whether a key name looks like it holds a credential. Separators and case
+/// are ignored and only the end of the name counts, so `OPENAI_API_KEY`,
+/// `apiKey` and `bearer_token` match while `env_key` (which names a variable)
+/// and `max_tokens` do not.
+pub fn is_secret_key(key: &str) -> bool {
+    let k: String = key
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | '.' | ' '))
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    k == "key"
+        || [
+            "apikey",
+            "accesskey",
+            "secretkey",
+            "privatekey",
+            "token",
+            "secret",
+            "password",
+            "authorization",
+            "cookie",
+        ]
+        .iter()
+        .any(|suffix| k.ends_with(suffix))
+}
+
+/// Masks a secret, keeping only a short prefix/suffix for recognition.
+pub fn mask_secret(value: &str) -> String {
+    // Vault references carry no secret material.
+    if crate::secrets::is_secret_ref(value) {
+        return value.to_string();
+    }
+    let chars: Vec<char> = value.chars().collect();
+    if chars.is_empty() {
+        return String::new();
+    }
+    if chars.len() <= 8 {
+        return "****".to_string();
+    }
+    let prefix: String = chars[..3].iter().collect();
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("{prefix}****{suffix}")
+}
+
+/// Masks every string under a credential-looking key in a JSON tree.
+pub fn mask_secrets(value: &Value) -> Value {
+    mask_value("", value)
+}
+
+fn mask_value(key: &str, value: &Value) -> Value {
+    match value {
+        Value::String(s) if is_secret_key(key) => Value::String(mask_secret(s)),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), mask_value(k, v)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(|v| mask_value(key, v)).collect()),
+        other => other.clone(),
+    }
+}
+
+/// `(key, value)` pairs of secret strings (values under credential-looking
+/// keys) in a JSON tree.
+fn collect_secrets(key: &str, value: &Value, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::String(s)
+            if is_secret_key(key) && !s.is_empty() && !crate::secrets::is_secret_ref(s) =>
+        {
+            let pair = (key.to_string(), s.clone());
+            if !out.contains(&pair) {
+                out.push(pair);
+            }
+        }
+        Value::Object(map) => {
+            for (k, v) in map {
+                collect_secrets(k, v, out);
+            }
+        }
+        Value::Array(items) => {
+            for v in items {
+                collect_secrets(key, v, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Masks the secret values in raw file content, keeping the layout
+/// (comments, key order) intact. A value is only masked where it directly
+/// follows one of its secret keys (`"key": "v"`, `key = "v"`, `key: v`, or
+/// inside an array there), so the same text elsewhere (provider names, base
+/// URLs, model ids) is shown as written.
+fn mask_content(content: &str, secrets: &[(String, String)]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (key, value) in secrets {
+        for (start, _) in content.match_indices(key.as_str()) {
+            secret_value_ranges(content, start, key, value, &mut ranges);
+        }
+    }
+    ranges.sort_unstable();
+    ranges.dedup();
+
+    let mut out = String::with_capacity(content.len());
+    let mut pos = 0;
+    for (start, end) in ranges {
+        if start < pos {
+            continue;
+        }
+        out.push_str(&content[pos..start]);
+        out.push_str(&mask_secret(&content[start..end]));
+        pos = end;
+    }
+    out.push_str(&content[pos..]);
+    out
+}
+
+/// Byte ranges of `value` written as the value of the key occurrence at
+/// `key_start`.
+fn secret_value_ranges(
+    content: &str,
+    key_start: usize,
+    key: &str,
+    value: &str,
+    out: &mut Vec<(usize, usize)>,
+) {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    if content[..key_start]
+        .chars()
+        .next_back()
+        .is_some_and(is_name_char)
+    {
+        return;
+    }
+    let mut rest = &content[key_start + key.len()..];
+    rest = rest.strip_prefix(['"', '\'']).unwrap_or(rest);
+    let Some(after_sep) = rest
+        .trim_start_matches([' ', '\t'])
+        .strip_prefix([':', '='])
+    else {
+        return;
+    };
+    let value_part = after_sep.trim_start_matches([' ', '\t']);
+    let offset = content.len() - value_part.len();
+
+    let quoted = |text: &str, at: usize, out: &mut Vec<(usize, usize)>| {
+        for quote in ['"', '\''] {
+            if text[at..].starts_with(quote)
+                && text[at + 1..].starts_with(value)
+                && text[at + 1 + value.len()..].starts_with(quote)
+            {
+                out.push((at + 1, at + 1 + value.len()));
+            }
+        }
+    };
+
+    if let Some(items) = value_part.strip_prefix('[') {
+        let list = &items[..items.find(']').unwrap_or(items.len())];
+        let list_offset = offset + 1;
+        for (i, _) in list.match_indices(['"', '\'']) {
+            quoted(content, list_offset + i, out);
+        }
+    } else if value_part.starts_with(['"', '\'']) {
+        quoted(content, offset, out);
+    } else if value_part.starts_with(value)
+        && value_part[value.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| c.is_whitespace() || matches!(c, ',' | '}' | ']' | '#'))
+    {
+        out.push((offset, offset + value.len()));
+    }
+}
+
+fn render_masked(key: &str, value: &Value) -> String {
+    let masked = mask_value(key, value);
+    serde_json::to_string(&masked).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_changes_are_keyed_and_masked() {
+        let before = r#"{ "keep": 1, "env": { "ANTHROPIC_AUTH_TOKEN": "sk-old-secret-value" }, "gone": true }"#;
+        let after = r#"{ "keep": 1, "env": { "ANTHROPIC_AUTH_TOKEN": "sk-new-secret-value" }, "added": { "apiKey": "abcdefghijkl" } }"#;
+        let plan = file_plan_from(
+            Path::new("settings.json"),
+            Some(before.to_string()),
+            after.to_string(),
+            PlanFormat::Json,
+        );
+        assert!(plan.changed);
+
+        let token = plan
+            .changes
+            .iter()
+            .find(|c| c.key == "env.ANTHROPIC_AUTH_TOKEN")
+            .unwrap();
+        assert_eq!(token.kind, KeyChangeKind::Changed);
+        assert_eq!(token.before.as_deref(), Some("\"sk-****alue\""));
+
+        let added = plan.changes.iter().find(|c| c.key == "added").unwrap();
+        assert_eq!(added.kind, KeyChangeKind::Added);
+        assert_eq!(added.after.as_deref(), Some(r#"{"apiKey":"abc****ijkl"}"#));
+
+        let gone = plan.changes.iter().find(|c| c.key == "gone").unwrap();
+        assert_eq!(gone.kind, KeyChangeKind::Removed);
+        assert!(!plan.changes.iter().any(|c| c.key == "keep"));
+
+        // File contents keep their layout but never carry the raw keys
+        let before = plan.before.as_deref().unwrap();
+        assert!(before.contains(r#""ANTHROPIC_AUTH_TOKEN": "sk-****alue""#));
+        assert!(!before.contains("sk-old-secret-value"));
+        assert!(plan.after.contains(r#""apiKey": "abc****ijkl""#));
+        assert!(!plan.after.contains("sk-new-secret-value"));
+    }
+
+    #[test]
+    fn content_masks_only_values_at_secret_keys() {
+        let after = r#"{ "provider": { "ollama": { "name": "ollama", "baseURL": "http://ollama:11434", "apiKey": "ollama" } } }"#;
+        let plan = file_plan_from(
+            Path::new("opencode.json"),
+            None,
+            after.to_string(),
+            PlanFormat::Json,
+        );
+        assert_eq!(
+            plan.after,
+            r#"{ "provider": { "ollama": { "name": "ollama", "baseURL": "http://ollama:11434", "apiKey": "****" } } }"#
+        );
+
+        let toml = "[model_providers.relay]\nname = \"sk-relay-secret\"\nenv_key = \"RELAY_API_KEY\"\nexperimental_bearer_token = \"sk-relay-secret\"\n";
+        let plan = file_plan_from(
+            Path::new("config.toml"),
+            None,
+            toml.to_string(),
+            PlanFormat::Toml,
+        );
+        assert_eq!(
+            plan.after,
+            "[model_providers.relay]\nname = \"sk-relay-secret\"\nenv_key = \"RELAY_API_KEY\"\nexperimental_bearer_token = \"sk-****cret\"\n"
+        );
+        assert!(!is_secret_key("env_key"));
+        assert!(!is_secret_key("max_tokens"));
+        assert!(is_secret_key("OPENAI_API_KEY"));
+        assert!(is_secret_key("x-api-key"));
+    }
+
+    #[test]
+    fn toml_yaml_and_text_formats() {
+        let toml_plan = file_plan_from(
+            Path::new("config.toml"),
+            Some("model = \"a\"\n[projects]\nfoo = \"bar\"\n".to_string()),
+            "model = \"b\"\n[projects]\nfoo = \"bar\"\n".to_string(),
+            PlanFormat::Toml,
+        );
+        assert_eq!(toml_plan.changes.len(), 1);
+        assert_eq!(toml_plan.changes[0].key, "model");
+
+        let yaml_plan = file_plan_from(
+            Path::new("config.yaml"),
+            None,
+            "model:\n  api_key: sk-1234567890\n".to_string(),
+            PlanFormat::Yaml,
+        );
+        assert_eq!(yaml_plan.changes[0].key, "model");
+        assert_eq!(
+            yaml_plan.changes[0].after.as_deref(),
+            Some(r#"{"api_key":"sk-****7890"}"#)
+        );
+
+        let text_plan = file_plan_from(
+            Path::new("active-profile.txt"),
+            Some("old".to_string()),
+            "new".to_string(),
+            PlanFormat::Text,
+        );
+        assert_eq!(text_plan.changes[0].key, "value");
+        assert_eq!(text_plan.changes[0].kind, KeyChangeKind::Changed);
+    }
+
+    #[test]
+    fn unchanged_file_has_no_changes() {
+        let plan = file_plan_from(
+            Path::new("a.json"),
+            Some("{}".to_string()),
+            "{}".to_string(),
+            PlanFormat::Json,
+        );
+        assert!(!plan.changed);
+        assert!(plan.changes.is_empty());
+    }
+
+    #[test]
+    fn array_items_are_diffed_by_index() {
+        let plan = file_plan_from(
+            Path::new("settings.json"),
+            Some(r#"{ "customModels": [ { "model": "a", "apiKey": "sk-aaaaaaaaaa" } ] }"#.to_string()),
+            r#"{ "customModels": [ { "model": "b", "apiKey": "sk-aaaaaaaaaa" }, { "model": "c" } ] }"#
+                .to_string(),
+            PlanFormat::Json,
+        );
+        let keys: Vec<&str> = plan.changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, vec!["customModels[0].model", "customModels[1]"]);
+    }
+}
@@ -0,0 +1,510 @@
+//! Versioned config backups (core).
+//!
+//! Every write to a live tool config (Factory settings, MCP, Codex, Claude,
+//! OpenCode, OpenClaw, Hermes, Pi) snapshots the previous file content into
+//! `~/.droidgear/backups/<file-key>/` first. Snapshots are pruned per file
+//! according to the retention setting and can be listed, diffed and restored.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{paths, storage};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Default number of snapshots kept per file
+pub const DEFAULT_BACKUP_RETENTION: u32 = 10;
+
+const SETTINGS_KEY: &str = "backupRetention";
+const META_FILE: &str = "meta.json";
+
+/// A single snapshot of a config file
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    /// Backup id (`<file-key>/<stamp>`)
+    pub id: String,
+    /// Original file path
+    pub path: String,
+    /// Creation time (RFC 3339)
+    pub created_at: String,
+    /// What triggered the snapshot (e.g. "codex", "mcp", "restore")
+    pub source: String,
+    /// Snapshot size in bytes
+    pub size: u64,
+}
+
+/// Per-file overview of available backups
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupFileSummary {
+    pub path: String,
+    pub count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest: Option<BackupEntry>,
+}
+
+/// Diff between a backup and the current file content
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupDiff {
+    pub entry: BackupEntry,
+    pub backup_content: String,
+    /// Current file content (None if the file no longer exists)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_content: Option<String>,
+    /// Unified diff from backup to current
+    pub unified_diff: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupMeta {
+    path: String,
+    #[serde(default)]
+    entries: Vec<MetaEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetaEntry {
+    stamp: String,
+    created_at: String,
+    source: String,
+    size: u64,
+}
+
+// ============================================================================
+// Path Helpers
+// ============================================================================
+
+fn system_home_dir() -> Result<PathBuf, String> {
+    paths::get_home_dir()
+}
+
+pub fn backups_dir_for_home(home_dir: &Path) -> PathBuf {
+    paths::droidgear_dir_from_home(home_dir).join("backups")
+}
+
+/// FNV-1a, stable across runs and platforms (unlike `DefaultHasher`).
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn sanitize_component(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn file_key(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .map(sanitize_component)
+        .unwrap_or_else(|| "file".to_string());
+    let hash = fnv1a64(path.to_string_lossy().as_bytes());
+    format!("{name}-{hash:016x}")
+}
+
+fn is_safe_component(s: &str) -> bool {
+    !s.is_empty()
+        && s != "."
+        && s != ".."
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-')
+}
+
+fn parse_backup_id(id: &str) -> Result<(&str, &str), String> {
+    let (key, stamp) = id
+        .split_once('/')
+        .ok_or_else(|| format!("Invalid backup id: {id}"))?;
+    if !is_safe_component(key) || !is_safe_component(stamp) {
+        return Err(format!("Invalid backup id: {id}"));
+    }
+    Ok((key, stamp))
+}
+
+fn snapshot_file_name(stamp: &str) -> String {
+    format!("{stamp}.bak")
+}
+
+fn read_meta(dir: &Path) -> Result<Option<BackupMeta>, String> {
+    let meta_path = dir.join(META_FILE);
+    if !meta_path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&meta_path)
+        .map_err(|e| format!("Failed to read backup metadata: {e}"))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse backup metadata: {e}"))
+}
+
+fn write_meta(dir: &Path, meta: &BackupMeta) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(meta)
+        .map_err(|e| format!("Failed to serialize backup metadata: {e}"))?;
+    storage::atomic_write(&dir.join(META_FILE), content.as_bytes())
+}
+
+fn to_entry(key: &str, path: &str, m: &MetaEntry) -> BackupEntry {
+    BackupEntry {
+        id: format!("{key}/{}", m.stamp),
+        path: path.to_string(),
+        created_at: m.created_at.clone(),
+        source: m.source.clone(),
+        size: m.size,
+    }
+}
+
+// ============================================================================
+// Retention
+// ============================================================================
+
+pub fn get_backup_retention_for_home(home_dir: &Path) -> Result<u32, String> {
+    let settings_path = paths::get_droidgear_settings_path_for_home(home_dir);
+    let settings = paths::read_droidgear_settings_from_path_internal(&settings_path)?;
+    Ok(settings
+        .get(SETTINGS_KEY)
+        .and_then(|v| v.as_u64())
+        .map(|v| v.min(u64::from(u32::MAX)) as u32)
+        .unwrap_or(DEFAULT_BACKUP_RETENTION))
+}
+
+pub fn save_backup_retention_for_home(home_dir: &Path, retention: u32) -> Result<(), String> {
+    if retention == 0 {
+        return Err("Backup retention must be at least 1".to_string());
+    }
+    let settings_path = paths::get_droidgear_settings_path_for_home(home_dir);
+    let mut settings = paths::read_droidgear_settings_from_path_internal(&settings_path)?;
+    if !settings.is_object() {
+        settings = serde_json::json!({});
+    }
+    if let Some(obj) = settings.as_object_mut() {
+        obj.insert(SETTINGS_KEY.to_string(), Value::from(retention));
+    }
+    paths::write_droidgear_settings_to_path_internal(&settings_path, &settings)?;
+
+    // Apply the new limit right away
+    let root = backups_dir_for_home(home_dir);
+    if let Ok(dirs) = fs::read_dir(&root) {
+        for dir in dirs.flatten() {
+            let dir = dir.path();
+            if let Some(mut meta) = read_meta(&dir)? {
+                prune(&dir, &mut meta, retention)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn prune(dir: &Path, meta: &mut BackupMeta, retention: u32) -> Result<(), String> {
+    let keep = retention.max(1) as usize;
+    if meta.entries.len() <= keep {
+        return Ok(());
+    }
+    let excess = meta.entries.len() - keep;
+    for old in meta.entries.drain(..excess) {
+        let _ = fs::remove_file(dir.join(snapshot_file_name(&old.stamp)));
+    }
+    write_meta(dir, meta)
+}
+
+// ============================================================================
+// Snapshot / List / Diff / Restore
+// ============================================================================
+
+/// Snapshots `path` before it gets overwritten.
+///
+/// Returns `None` when the file does not exist yet or its content is identical
+/// to the latest snapshot.
+pub fn snapshot_file_for_home(
+    home_dir: &Path,
+    path: &Path,
+    source: &str,
+) -> Result<Option<BackupEntry>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read file for backup: {e}"))?;
+
+    let key = file_key(path);
+    let dir = backups_dir_for_home(home_dir).join(&key);
+    let path_str = path.to_string_lossy().to_string();
+    let mut meta = read_meta(&dir)?.unwrap_or_else(|| BackupMeta {
+        path: path_str.clone(),
+        entries: Vec::new(),
+    });
+
+    if let Some(last) = meta.entries.last() {
+        if let Ok(prev) = fs::read(dir.join(snapshot_file_name(&last.stamp))) {
+            if prev == bytes {
+                return Ok(None);
+            }
+        }
+    }
+
+    let now = chrono::Utc::now();
+    let short_id = uuid::Uuid::new_v4().simple().to_string();
+    let stamp = format!("{}-{}", now.format("%Y%m%dT%H%M%S%3fZ"), &short_id[..8]);
+    storage::atomic_write(&dir.join(snapshot_file_name(&stamp)), &bytes)
+        .map_err(|e| format!("Failed to write backup: {e}"))?;
+
+    let entry = MetaEntry {
+        stamp,
+        created_at: now.to_rfc3339(),
+        source: source.to_string(),
+        size: bytes.len() as u64,
+    };
+    let result = to_entry(&key, &meta.path, &entry);
+    meta.entries.push(entry);
+    write_meta(&dir, &meta)?;
+
+    let retention = get_backup_retention_for_home(home_dir)?;
+    prune(&dir, &mut meta, retention)?;
+
+    Ok(Some(result))
+}
+
+/// Lists all files that have backups, most recently backed up first.
+pub fn list_backup_files_for_home(home_dir: &Path) -> Result<Vec<BackupFileSummary>, String> {
+    let root = backups_dir_for_home(home_dir);
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+    let dirs = fs::read_dir(&root).map_err(|e| format!("Failed to read backups directory: {e}"))?;
+
+    let mut files = Vec::new();
+    for dir in dirs.flatten() {
+        let dir_path = dir.path();
+        let Some(key) = dir_path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let Ok(Some(meta)) = read_meta(&dir_path) else {
+            continue;
+        };
+        if meta.entries.is_empty() {
+            continue;
+        }
+        files.push(BackupFileSummary {
+            path: meta.path.clone(),
+            count: meta.entries.len() as u32,
+            latest: meta.entries.last().map(|m| to_entry(key, &meta.path, m)),
+        });
+    }
+    files.sort_by(|a, b| {
+        let a_at = a.latest.as_ref().map(|e| e.created_at.as_str());
+        let b_at = b.latest.as_ref().map(|e| e.created_at.as_str());
+        b_at.cmp(&a_at).then_with(|| a.path.cmp(&b.path))
+    });
+    Ok(files)
+}
+
+/// Lists backups of a single file, newest first.
+pub fn list_backups_for_home(home_dir: &Path, path: &Path) -> Result<Vec<BackupEntry>, String> {
+    let key = file_key(path);
+    let dir = backups_dir_for_home(home_dir).join(&key);
+    let Some(meta) = read_meta(&dir)? else {
+        return Ok(Vec::new());
+    };
+    Ok(meta
+        .entries
+        .iter()
+        .rev()
+        .map(|m| to_entry(&key, &meta.path, m))
+        .collect())
+}
+
+fn load_backup(home_dir: &Path, id: &str) -> Result<(BackupEntry, Vec<u8>), String> {
+    let (key, stamp) = parse_backup_id(id)?;
+    let dir = backups_dir_for_home(home_dir).join(key);
+    let meta = read_meta(&dir)?.ok_or_else(|| format!("Backup not found: {id}"))?;
+    let m = meta
+        .entries
+        .iter()
+        .find(|m| m.stamp == stamp)
+        .ok_or_else(|| format!("Backup not found: {id}"))?;
+    let bytes = fs::read(dir.join(snapshot_file_name(stamp)))
+        .map_err(|e| format!("Failed to read backup: {e}"))?;
+    Ok((to_entry(key, &meta.path, m), bytes))
+}
+
+/// Diffs a backup against the current content of the original file.
+pub fn diff_backup_for_home(home_dir: &Path, id: &str) -> Result<BackupDiff, String> {
+    let (entry, bytes) = load_backup(home_dir, id)?;
+    let backup_content = String::from_utf8_lossy(&bytes).to_string();
+    let current_content = fs::read(&entry.path)
+        .ok()
+        .map(|b| String::from_utf8_lossy(&b).to_string());
+
+    let current = current_content.as_deref().unwrap_or("");
+    let unified_diff = similar::TextDiff::from_lines(backup_content.as_str(), current)
+        .unified_diff()
+        .context_radius(3)
+        .header(
+            &format!("{} (backup)", entry.path),
+            &format!("{} (current)", entry.path),
+        )
+        .to_string();
+
+    Ok(BackupDiff {
+        entry,
+        backup_content,
+        current_content,
+        unified_diff,
+    })
+}
+
+/// Restores a backup over the original file.
+///
+/// The current content is snapshotted first (source "restore"), so a restore
+/// can itself be undone.
+pub fn restore_backup_for_home(home_dir: &Path, id: &str) -> Result<BackupEntry, String> {
+    let (entry, bytes) = load_backup(home_dir, id)?;
+    let path = PathBuf::from(&entry.path);
+
+    snapshot_file_for_home(home_dir, &path, "restore")?;
+
+    // Write through symlinks so linked configs stay linked
+    let target = fs::canonicalize(&path).unwrap_or(path);
+    storage::atomic_write(&target, &bytes)?;
+    Ok(entry)
+}
+
+// ============================================================================
+// System wrappers
+// ============================================================================
+
+pub fn snapshot_file(path: &Path, source: &str) -> Result<Option<BackupEntry>, String> {
+    snapshot_file_for_home(&system_home_dir()?, path, source)
+}
+
+pub fn list_backup_files() -> Result<Vec<BackupFileSummary>, String> {
+    list_backup_files_for_home(&system_home_dir()?)
+}
+
+pub fn list_backups(path: &Path) -> Result<Vec<BackupEntry>, String> {
+    list_backups_for_home(&system_home_dir()?, path)
+}
+
+pub fn diff_backup(id: &str) -> Result<BackupDiff, String> {
+    diff_backup_for_home(&system_home_dir()?, id)
+}
+
+pub fn restore_backup(id: &str) -> Result<BackupEntry, String> {
+    restore_backup_for_home(&system_home_dir()?, id)
+}
+
+pub fn get_backup_retention() -> Result<u32, String> {
+    get_backup_retention_for_home(&system_home_dir()?)
+}
+
+pub fn save_backup_retention(retention: u32) -> Result<(), String> {
+    save_backup_retention_for_home(&system_home_dir()?, retention)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn snapshot_skips_missing_and_unchanged_files() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let file = home.join(".codex").join("config.toml");
+
+        assert!(snapshot_file_for_home(home, &file, "codex")
+            .unwrap()
+            .is_none());
+
+        write(&file, "model = \"a\"\n");
+        let first = snapshot_file_for_home(home, &file, "codex").unwrap();
+        assert!(first.is_some());
+        assert!(snapshot_file_for_home(home, &file, "codex")
+            .unwrap()
+            .is_none());
+
+        write(&file, "model = \"b\"\n");
+        assert!(snapshot_file_for_home(home, &file, "codex")
+            .unwrap()
+            .is_some());
+        assert_eq!(list_backups_for_home(home, &file).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn retention_prunes_oldest_snapshots() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let file = home.join("settings.json");
+        save_backup_retention_for_home(home, 2).unwrap();
+
+        for i in 0..4 {
+            write(&file, &format!("{{\"v\": {i}}}"));
+            snapshot_file_for_home(home, &file, "test").unwrap();
+        }
+
+        let entries = list_backups_for_home(home, &file).unwrap();
+        assert_eq!(entries.len(), 2);
+        let (_, newest) = load_backup(home, &entries[0].id).unwrap();
+        assert_eq!(String::from_utf8(newest).unwrap(), "{\"v\": 3}");
+        let (_, older) = load_backup(home, &entries[1].id).unwrap();
+        assert_eq!(String::from_utf8(older).unwrap(), "{\"v\": 2}");
+        assert_eq!(get_backup_retention_for_home(home).unwrap(), 2);
+    }
+
+    #[test]
+    fn diff_and_restore_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let file = home.join("auth.json");
+        write(&file, "old\n");
+        let entry = snapshot_file_for_home(home, &file, "test")
+            .unwrap()
+            .unwrap();
+        write(&file, "new\n");
+
+        let diff = diff_backup_for_home(home, &entry.id).unwrap();
+        assert!(diff.unified_diff.contains("-old"));
+        assert!(diff.unified_diff.contains("+new"));
+
+        restore_backup_for_home(home, &entry.id).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "old\n");
+
+        // The pre-restore content is kept as a new snapshot
+        let entries = list_backups_for_home(home, &file).unwrap();
+        assert_eq!(entries[0].source, "restore");
+        let files = list_backup_files_for_home(home).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].count, 2);
+    }
+
+    #[test]
+    fn rejects_path_traversal_ids() {
+        let temp = TempDir::new().unwrap();
+        assert!(diff_backup_for_home(temp.path(), "../x/y").is_err());
+        assert!(restore_backup_for_home(temp.path(), "a/..").is_err());
+        assert!(restore_backup_for_home(temp.path(), "nokey").is_err());
+    }
+}
@@ -1,6 +1,8 @@
 //! API Channel management (core).
 //!
-//! Handles channel configuration and token management for New API, Sub2API, Ollama, etc.
+//! Handles channel configuration and token management for New API, Sub2API, Ollama,
+//! OpenRouter, LiteLLM, etc.
+//! Backend-specific network behavior lives in `channel_provider`.
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -8,7 +10,9 @@ use specta::Type;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::channel_provider;
 use crate::factory_settings::ModelInfo;
+use crate::secrets;
 
 // ============================================================================
 // Types
@@ -27,6 +31,10 @@ pub enum ChannelType {
     General,
     #[serde(rename = "deep-seek")]
     DeepSeek,
+    OpenRouter,
+    #[serde(rename = "litellm")]
+    LiteLlm,
+    OneApi,
 }
 
 /// Channel configuration
@@ -72,6 +80,48 @@ pub struct ChannelToken {
     pub group_name: Option<String>,
 }
 
+/// Token to create on a relay dashboard (New API, one-api, Sub2API)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NewChannelToken {
+    pub name: String,
+    /// Quota in the backend's own units (as in `ChannelToken::remain_quota`);
+    /// `None` for unlimited. Sub2API keys draw on the account balance and ignore it.
+    #[serde(default)]
+    pub remain_quota: Option<f64>,
+    /// New API group name, or Sub2API group id or name
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Sub2API platform; picks the first group of that platform when `group` is unset
+    #[serde(default)]
+    pub platform: Option<String>,
+}
+
+/// Account balance reported by a channel's dashboard API
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelQuota {
+    /// Remaining balance, in `unit`
+    pub remaining: Option<f64>,
+    /// Amount used so far, in `unit`
+    pub used: Option<f64>,
+    /// Whether the account has no spending limit
+    pub unlimited: bool,
+    /// Currency of the amounts (e.g. "USD", "CNY")
+    pub unit: String,
+}
+
+/// Link from a profile entry back to the channel (and token) it was
+/// provisioned from, so base URL and key can be re-synced later
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelRef {
+    pub channel_id: String,
+    /// Dashboard token id; `None` for API-key channels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<f64>,
+}
+
 /// Channel authentication data (stored in ~/.droidgear/auth/)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -80,6 +130,46 @@ pub enum ChannelAuth {
     ApiKey { api_key: String },
 }
 
+impl ChannelType {
+    /// Serialized identifier (e.g. `new-api`, `sub-2-api`)
+    pub fn id(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(id: &str) -> Option<ChannelType> {
+        serde_json::from_value(Value::String(id.to_string())).ok()
+    }
+
+    /// Whether the channel stores a dashboard login instead of an API key.
+    pub fn uses_login(&self) -> bool {
+        channel_provider::provider_for(self).uses_login()
+    }
+
+    pub fn default_base_url(&self) -> &'static str {
+        channel_provider::provider_for(self).default_base_url()
+    }
+}
+
+impl ChannelToken {
+    /// Single synthetic token for API-key channels.
+    pub fn from_api_key(key: &str) -> Self {
+        ChannelToken {
+            id: 0.0,
+            name: "API Key".to_string(),
+            key: key.to_string(),
+            status: 1,
+            remain_quota: 0.0,
+            used_quota: 0.0,
+            unlimited_quota: true,
+            platform: None,
+            group_name: None,
+        }
+    }
+}
+
 // ============================================================================
 // Paths
 // ============================================================================
@@ -139,6 +229,56 @@ fn read_channel_auth_for_home(
     Ok(Some(auth))
 }
 
+/// Reads channel auth with `secret://` references resolved from the vault.
+fn read_resolved_channel_auth_for_home(
+    home_dir: &Path,
+    channel_id: &str,
+) -> Result<Option<ChannelAuth>, String> {
+    let resolve = |value: String| secrets::resolve_secret_value_for_home(home_dir, &value);
+    Ok(match read_channel_auth_for_home(home_dir, channel_id)? {
+        Some(ChannelAuth::Credentials { username, password }) => Some(ChannelAuth::Credentials {
+            username,
+            password: resolve(password)?,
+        }),
+        Some(ChannelAuth::ApiKey { api_key }) => Some(ChannelAuth::ApiKey {
+            api_key: resolve(api_key)?,
+        }),
+        None => None,
+    })
+}
+
+/// Once the vault exists, secret fields are stored there and the auth file
+/// only keeps `secret://channel/{id}/...` references.
+fn protect_channel_auth_for_home(
+    home_dir: &Path,
+    channel_id: &str,
+    auth: &ChannelAuth,
+) -> Result<(ChannelAuth, Vec<String>), String> {
+    if !secrets::is_vault_initialized_for_home(home_dir) {
+        return Ok((auth.clone(), Vec::new()));
+    }
+    let mut stored = Vec::new();
+    let mut protect = |field: &str, value: &str| -> Result<String, String> {
+        if value.is_empty() || secrets::is_secret_ref(value) {
+            return Ok(value.to_string());
+        }
+        let name = format!("channel/{channel_id}/{field}");
+        let reference = secrets::set_secret_for_home(home_dir, &name, value)?;
+        stored.push(name);
+        Ok(reference)
+    };
+    let protected = match auth {
+        ChannelAuth::Credentials { username, password } => ChannelAuth::Credentials {
+            username: username.clone(),
+            password: protect("password", password)?,
+        },
+        ChannelAuth::ApiKey { api_key } => ChannelAuth::ApiKey {
+            api_key: protect("api-key", api_key)?,
+        },
+    };
+    Ok((protected, stored))
+}
+
 fn write_channel_auth_for_home(
     home_dir: &Path,
     channel_id: &str,
@@ -147,20 +287,73 @@ fn write_channel_auth_for_home(
     let dir = auth_dir_for_home(home_dir);
     fs::create_dir_all(&dir).map_err(|e| format!("Failed to create auth directory: {e}"))?;
     let path = auth_file_path_for_home(home_dir, channel_id);
-    let content =
-        serde_json::to_string_pretty(auth).map_err(|e| format!("Failed to serialize auth: {e}"))?;
+    let (auth, _) = protect_channel_auth_for_home(home_dir, channel_id, auth)?;
+    let content = serde_json::to_string_pretty(&auth)
+        .map_err(|e| format!("Failed to serialize auth: {e}"))?;
     fs::write(&path, content).map_err(|e| format!("Failed to write auth file: {e}"))?;
     Ok(())
 }
 
 fn delete_channel_auth_for_home(home_dir: &Path, channel_id: &str) -> Result<(), String> {
     let path = auth_file_path_for_home(home_dir, channel_id);
+    if let Some(auth) = read_channel_auth_for_home(home_dir, channel_id)? {
+        let value = match &auth {
+            ChannelAuth::Credentials { password, .. } => password,
+            ChannelAuth::ApiKey { api_key } => api_key,
+        };
+        if let Some(name) = secrets::parse_secret_ref(value) {
+            if name.starts_with(&format!("channel/{channel_id}/")) {
+                secrets::delete_secret_for_home(home_dir, name)?;
+            }
+        }
+    }
     if path.exists() {
         fs::remove_file(&path).map_err(|e| format!("Failed to delete auth file: {e}"))?;
     }
     Ok(())
 }
 
+/// Moves plaintext channel auth into the vault; returns the created secret names.
+pub(crate) fn migrate_channel_auth_to_vault_for_home(
+    home_dir: &Path,
+) -> Result<Vec<String>, String> {
+    let dir = auth_dir_for_home(home_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read auth directory: {e}"))?;
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                return None;
+            }
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(str::to_string)
+        })
+        .collect();
+    ids.sort();
+
+    let mut migrated = Vec::new();
+    for channel_id in ids {
+        let Some(auth) = read_channel_auth_for_home(home_dir, &channel_id)? else {
+            continue;
+        };
+        let (protected, stored) = protect_channel_auth_for_home(home_dir, &channel_id, &auth)?;
+        if stored.is_empty() {
+            continue;
+        }
+        let content = serde_json::to_string_pretty(&protected)
+            .map_err(|e| format!("Failed to serialize auth: {e}"))?;
+        fs::write(auth_file_path_for_home(home_dir, &channel_id), content)
+            .map_err(|e| format!("Failed to write auth file: {e}"))?;
+        migrated.extend(stored);
+    }
+    Ok(migrated)
+}
+
 // ============================================================================
 // Public API (CRUD)
 // ============================================================================
@@ -264,7 +457,7 @@ pub fn get_channel_credentials_for_home(
     home_dir: &Path,
     channel_id: &str,
 ) -> Result<Option<(String, String)>, String> {
-    match read_channel_auth_for_home(home_dir, channel_id)? {
+    match read_resolved_channel_auth_for_home(home_dir, channel_id)? {
         Some(ChannelAuth::Credentials { username, password }) => Ok(Some((username, password))),
         _ => Ok(None),
     }
@@ -293,7 +486,7 @@ pub fn get_channel_api_key_for_home(
     home_dir: &Path,
     channel_id: &str,
 ) -> Result<Option<String>, String> {
-    match read_channel_auth_for_home(home_dir, channel_id)? {
+    match read_resolved_channel_auth_for_home(home_dir, channel_id)? {
         Some(ChannelAuth::ApiKey { api_key }) => Ok(Some(api_key)),
         _ => Ok(None),
     }
@@ -318,6 +511,7 @@ pub fn delete_channel_credentials(channel_id: &str) -> Result<(), String> {
 // Network features
 // ============================================================================
 
+/// Detects channel type by probing each provider's characteristic endpoint.
 pub async fn detect_channel_type(base_url: &str) -> Result<ChannelType, String> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
@@ -325,62 +519,9 @@ pub async fn detect_channel_type(base_url: &str) -> Result<ChannelType, String>
         .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
 
     let base = base_url.trim_end_matches('/');
-
-    // 1. Ollama: GET / contains "Ollama"
-    if let Ok(resp) = client.get(base).send().await {
-        if resp.status().is_success() {
-            if let Ok(text) = resp.text().await {
-                if text.contains("Ollama") || text.contains("ollama") {
-                    return Ok(ChannelType::Ollama);
-                }
-            }
-        }
-    }
-
-    // 2. Sub2API: GET /health -> {"status":"ok"}
-    if let Ok(resp) = client.get(format!("{base}/health")).send().await {
-        if resp.status().is_success() {
-            if let Ok(data) = resp.json::<Value>().await {
-                if data.get("status").and_then(|s| s.as_str()) == Some("ok") {
-                    return Ok(ChannelType::Sub2Api);
-                }
-            }
-        }
-    }
-
-    // 3. New API: GET /api/status
-    if let Ok(resp) = client.get(format!("{base}/api/status")).send().await {
-        if resp.status().is_success() {
-            return Ok(ChannelType::NewApi);
-        }
-    }
-
-    // 3.5. DeepSeek: GET /models returns OpenAI format with owned_by field
-    if let Ok(resp) = client.get(format!("{base}/models")).send().await {
-        if resp.status().is_success() {
-            if let Ok(data) = resp.json::<Value>().await {
-                if let Some(arr) = data.get("data").and_then(|d| d.as_array()) {
-                    if arr.iter().any(|m| {
-                        m.get("id").is_some()
-                            && m.get("owned_by").and_then(|v| v.as_str()) == Some("deepseek")
-                    }) {
-                        return Ok(ChannelType::DeepSeek);
-                    }
-                }
-            }
-        }
-    }
-
-    // 4. CLI Proxy API: GET /v1/models returns OpenAI format
-    if let Ok(resp) = client.get(format!("{base}/v1/models")).send().await {
-        if resp.status().is_success() {
-            if let Ok(data) = resp.json::<Value>().await {
-                if let Some(arr) = data.get("data").and_then(|d| d.as_array()) {
-                    if arr.iter().any(|m| m.get("id").is_some()) {
-                        return Ok(ChannelType::CliProxyApi);
-                    }
-                }
-            }
+    for provider in channel_provider::detection_order() {
+        if provider.detect(&client, base).await {
+            return Ok(provider.channel_type());
         }
     }
 
@@ -393,24 +534,9 @@ pub async fn fetch_channel_tokens(
     username: &str,
     password: &str,
 ) -> Result<Vec<ChannelToken>, String> {
-    match channel_type {
-        ChannelType::NewApi => fetch_new_api_keys(base_url, username, password).await,
-        ChannelType::Sub2Api => fetch_sub2api_tokens(base_url, username, password).await,
-        ChannelType::CliProxyApi
-        | ChannelType::Ollama
-        | ChannelType::General
-        | ChannelType::DeepSeek => Ok(vec![ChannelToken {
-            id: 0.0,
-            name: "API Key".to_string(),
-            key: password.to_string(),
-            status: 1,
-            remain_quota: 0.0,
-            used_quota: 0.0,
-            unlimited_quota: true,
-            platform: None,
-            group_name: None,
-        }]),
-    }
+    let provider = channel_provider::provider_for(&channel_type);
+    let session = provider.login(base_url, username, password).await?;
+    provider.list_tokens(base_url, &session).await
 }
 
 /// Blocking version of `fetch_channel_tokens`.
@@ -433,387 +559,59 @@ pub fn fetch_channel_tokens_blocking(
     ))
 }
 
-async fn fetch_new_api_keys(
+/// Fetches the account balance of a channel (`None` if the backend has no quota API).
+/// API-key channels pass the key as `password`.
+pub async fn fetch_channel_quota(
+    channel_type: ChannelType,
     base_url: &str,
     username: &str,
     password: &str,
-) -> Result<Vec<ChannelToken>, String> {
-    let client = reqwest::Client::builder()
-        .cookie_store(true)
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
-
-    let base = base_url.trim_end_matches('/');
-
-    // Login
-    let login_url = format!("{base}/api/user/login");
-    let login_response = client
-        .post(&login_url)
-        .json(&serde_json::json!({ "username": username, "password": password }))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to login: {e}"))?;
-
-    if !login_response.status().is_success() {
-        let status = login_response.status();
-        let body = login_response.text().await.unwrap_or_default();
-        return Err(format!("Login failed {status}: {body}"));
-    }
-
-    let login_data: Value = {
-        let body = login_response
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read login response body: {e}"))?;
-        serde_json::from_str(&body).map_err(|e| {
-            let truncated = if body.len() > 500 {
-                format!("{}...", &body[..500])
-            } else {
-                body.clone()
-            };
-            log::warn!("Channel: failed to parse login response, body={truncated}");
-            format!("Failed to parse login response: {e}")
-        })?
-    };
-
-    if login_data.get("success").and_then(|v| v.as_bool()) != Some(true) {
-        let msg = login_data
-            .get("message")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown error");
-        return Err(format!("Login failed: {msg}"));
-    }
-
-    let user_id = login_data
-        .get("data")
-        .and_then(|d| d.get("id"))
-        .and_then(|v| v.as_i64())
-        .ok_or("Could not get user id from login response")?;
-
-    // Fetch tokens with pagination
-    let keys_url = format!("{base}/api/token");
-    let page_size: usize = 100;
-    let mut all_keys: Vec<ChannelToken> = Vec::new();
-    let mut page: usize = 1;
-
-    loop {
-        let response = client
-            .get(&keys_url)
-            .header("New-Api-User", user_id.to_string())
-            .query(&[("p", page.to_string()), ("size", page_size.to_string())])
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch keys: {e}"))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(format!("API error {status}: {body}"));
-        }
-
-        let data: Value = {
-            let body = response
-                .text()
-                .await
-                .map_err(|e| format!("Failed to read response body: {e}"))?;
-            serde_json::from_str(&body).map_err(|e| {
-                let truncated = if body.len() > 500 {
-                    format!("{}...", &body[..500])
-                } else {
-                    body.clone()
-                };
-                log::warn!("Channel: failed to parse keys response, body={truncated}");
-                format!("Failed to parse response: {e}")
-            })?
-        };
-
-        let items: Vec<Value> = data
-            .get("data")
-            .and_then(|d| d.get("items"))
-            .and_then(|v| v.as_array())
-            .cloned()
-            .unwrap_or_default();
-
-        let count = items.len();
-
-        for t in &items {
-            let token_id = match t.get("id").and_then(|v| v.as_i64()) {
-                Some(id) => id,
-                None => continue,
-            };
-
-            // Fetch unmasked key via POST /api/token/{id}/key
-            let raw_key = match client
-                .post(format!("{base}/api/token/{token_id}/key"))
-                .header("New-Api-User", user_id.to_string())
-                .send()
-                .await
-            {
-                Ok(resp) if resp.status().is_success() => {
-                    resp.json::<Value>().await.ok().and_then(|v| {
-                        v.get("data")
-                            .and_then(|d| d.get("key"))
-                            .and_then(|k| k.as_str())
-                            .map(String::from)
-                    })
-                }
-                _ => None,
-            };
-
-            let key = match raw_key {
-                Some(k) if k.starts_with("sk-") => k,
-                Some(k) => format!("sk-{k}"),
-                None => {
-                    // Fallback to masked key from list
-                    match t.get("key").and_then(|v| v.as_str()) {
-                        Some(k) if k.starts_with("sk-") => k.to_string(),
-                        Some(k) => format!("sk-{k}"),
-                        None => continue,
-                    }
-                }
-            };
-
-            if let (Some(name), Some(status)) = (
-                t.get("name").and_then(|v| v.as_str()),
-                t.get("status").and_then(|v| v.as_i64()),
-            ) {
-                all_keys.push(ChannelToken {
-                    id: token_id as f64,
-                    name: name.to_string(),
-                    key,
-                    status: status as i32,
-                    remain_quota: t
-                        .get("remain_quota")
-                        .and_then(|v| v.as_f64())
-                        .unwrap_or(0.0),
-                    used_quota: t.get("used_quota").and_then(|v| v.as_f64()).unwrap_or(0.0),
-                    unlimited_quota: t
-                        .get("unlimited_quota")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false),
-                    platform: None,
-                    group_name: None,
-                });
-            }
-        }
-
-        if count < page_size {
-            break;
-        }
-        page += 1;
-    }
-
-    Ok(all_keys)
+) -> Result<Option<ChannelQuota>, String> {
+    let provider = channel_provider::provider_for(&channel_type);
+    let session = provider.login(base_url, username, password).await?;
+    provider.quota(base_url, &session).await
 }
 
-async fn fetch_sub2api_tokens(
+/// Creates a token on the channel's dashboard and returns it with its full key.
+pub async fn create_channel_token(
+    channel_type: ChannelType,
     base_url: &str,
-    email: &str,
+    username: &str,
     password: &str,
-) -> Result<Vec<ChannelToken>, String> {
-    let client = reqwest::Client::new();
-    let base = base_url.trim_end_matches('/');
-
-    // Login
-    let login_url = format!("{base}/api/v1/auth/login");
-    let login_response = client
-        .post(&login_url)
-        .json(&serde_json::json!({ "email": email, "password": password }))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to login: {e}"))?;
-
-    if !login_response.status().is_success() {
-        let status = login_response.status();
-        let body = login_response.text().await.unwrap_or_default();
-        return Err(format!("Login failed {status}: {body}"));
-    }
-
-    let login_data: Value = {
-        let body = login_response
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read login response body: {e}"))?;
-        serde_json::from_str(&body).map_err(|e| {
-            let truncated = if body.len() > 500 {
-                format!("{}...", &body[..500])
-            } else {
-                body.clone()
-            };
-            log::warn!("Channel: failed to parse login response (v1), body={truncated}");
-            format!("Failed to parse login response: {e}")
-        })?
-    };
-
-    if login_data.get("code").and_then(|v| v.as_i64()) != Some(0) {
-        let msg = login_data
-            .get("message")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown error");
-        return Err(format!("Login failed: {msg}"));
-    }
-
-    let access_token = login_data
-        .get("data")
-        .and_then(|d| d.get("access_token"))
-        .and_then(|t| t.as_str())
-        .ok_or("Could not get access_token from login response")?;
-
-    // Fetch groups for platform info
-    let groups_url = format!("{base}/api/v1/groups/available");
-    let groups_response = client
-        .get(&groups_url)
-        .header("Authorization", format!("Bearer {access_token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch groups: {e}"))?;
-
-    let group_info: std::collections::HashMap<i64, (String, String)> =
-        if groups_response.status().is_success() {
-            let groups_data: Value = groups_response.json().await.unwrap_or_default();
-            groups_data
-                .get("data")
-                .and_then(|d| d.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|g| {
-                            let id = g.get("id")?.as_i64()?;
-                            let platform = g.get("platform")?.as_str()?.to_string();
-                            let name = g.get("name")?.as_str()?.to_string();
-                            Some((id, (platform, name)))
-                        })
-                        .collect()
-                })
-                .unwrap_or_default()
-        } else {
-            std::collections::HashMap::new()
-        };
-
-    // Fetch keys list with pagination
-    let keys_url = format!("{base}/api/v1/keys");
-    let page_size: usize = 100;
-    let mut all_items: Vec<Value> = Vec::new();
-    let mut page: usize = 1;
-
-    loop {
-        let keys_response = client
-            .get(&keys_url)
-            .header("Authorization", format!("Bearer {access_token}"))
-            .query(&[
-                ("page", page.to_string()),
-                ("page_size", page_size.to_string()),
-            ])
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch keys: {e}"))?;
-
-        if !keys_response.status().is_success() {
-            let status = keys_response.status();
-            let body = keys_response.text().await.unwrap_or_default();
-            return Err(format!("API error {status}: {body}"));
-        }
-
-        let keys_data: Value = {
-            let body = keys_response
-                .text()
-                .await
-                .map_err(|e| format!("Failed to read keys response body: {e}"))?;
-            serde_json::from_str(&body).map_err(|e| {
-                let truncated = if body.len() > 500 {
-                    format!("{}...", &body[..500])
-                } else {
-                    body.clone()
-                };
-                log::warn!("Channel: failed to parse keys response, body={truncated}");
-                format!("Failed to parse keys response: {e}")
-            })?
-        };
-
-        let items: Vec<Value> = keys_data
-            .get("data")
-            .and_then(|d| d.get("items"))
-            .and_then(|v| v.as_array())
-            .cloned()
-            .unwrap_or_default();
-
-        let count = items.len();
-        all_items.extend(items);
-
-        if count < page_size {
-            break;
-        }
-        page += 1;
-    }
+    token: &NewChannelToken,
+) -> Result<ChannelToken, String> {
+    let provider = channel_provider::provider_for(&channel_type);
+    let session = provider.login(base_url, username, password).await?;
+    provider.create_token(base_url, &session, token).await
+}
 
-    // Fetch usage (optional)
-    let usage_url = format!("{base}/api/v1/keys/usage");
-    let usage_response = client
-        .get(&usage_url)
-        .header("Authorization", format!("Bearer {access_token}"))
-        .send()
+/// Enables or disables a token on the channel's dashboard.
+pub async fn set_channel_token_enabled(
+    channel_type: ChannelType,
+    base_url: &str,
+    username: &str,
+    password: &str,
+    token_id: f64,
+    enabled: bool,
+) -> Result<(), String> {
+    let provider = channel_provider::provider_for(&channel_type);
+    let session = provider.login(base_url, username, password).await?;
+    provider
+        .set_token_enabled(base_url, &session, token_id, enabled)
         .await
-        .ok();
-
-    let usage_data: Option<Value> = match usage_response {
-        Some(resp) if resp.status().is_success() => resp.json::<Value>().await.ok(),
-        _ => None,
-    };
-
-    let usage_map: std::collections::HashMap<i64, Value> = usage_data
-        .as_ref()
-        .and_then(|v| v.get("data"))
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|u| Some((u.get("key_id")?.as_i64()?, u.clone())))
-                .collect()
-        })
-        .unwrap_or_default();
-
-    let tokens: Vec<ChannelToken> = all_items
-        .iter()
-        .filter_map(|k| {
-            let id = k.get("id")?.as_f64()?;
-            let status = k.get("status").and_then(|v| v.as_i64()).unwrap_or(1) as i32;
-
-            let usage = k
-                .get("id")
-                .and_then(|v| v.as_i64())
-                .and_then(|key_id| usage_map.get(&key_id));
-
-            let (platform, group_name) = k
-                .get("group")
-                .map(|g| {
-                    let platform = g.get("platform").and_then(|p| p.as_str()).map(String::from);
-                    let name = g.get("name").and_then(|n| n.as_str()).map(String::from);
-                    (platform, name)
-                })
-                .unwrap_or_else(|| {
-                    k.get("group_id")
-                        .and_then(|g| g.as_i64())
-                        .and_then(|group_id| group_info.get(&group_id).cloned())
-                        .map(|(platform, name)| (Some(platform), Some(name)))
-                        .unwrap_or((None, None))
-                });
-
-            Some(ChannelToken {
-                id,
-                name: k.get("name")?.as_str()?.to_string(),
-                key: k.get("key")?.as_str()?.to_string(),
-                status,
-                remain_quota: 0.0,
-                used_quota: usage
-                    .and_then(|u| u.get("total_actual_cost"))
-                    .and_then(|v| v.as_f64())
-                    .unwrap_or(0.0),
-                unlimited_quota: true,
-                platform,
-                group_name,
-            })
-        })
-        .collect();
+}
 
-    Ok(tokens)
+/// Deletes a token on the channel's dashboard.
+pub async fn delete_channel_token(
+    channel_type: ChannelType,
+    base_url: &str,
+    username: &str,
+    password: &str,
+    token_id: f64,
+) -> Result<(), String> {
+    let provider = channel_provider::provider_for(&channel_type);
+    let session = provider.login(base_url, username, password).await?;
+    provider.delete_token(base_url, &session, token_id).await
 }
 
 /// Fetch models using an API key (for quick model addition from channels)
@@ -822,83 +620,10 @@ pub async fn fetch_models_by_api_key(
     api_key: &str,
     platform: Option<&str>,
 ) -> Result<Vec<ModelInfo>, String> {
-    let trimmed_base = base_url.trim_end_matches('/');
-    let client = reqwest::Client::new();
-
-    if platform == Some("antigravity") {
-        let claude_url = format!("{trimmed_base}/antigravity/v1/models");
-        log::debug!("Channel: fetching models from {claude_url} (antigravity)");
-
-        let response = client
-            .get(&claude_url)
-            .header("Authorization", format!("Bearer {api_key}"))
-            .send()
-            .await
-            .map_err(|e| format!("Request failed: {e}"))?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            log::warn!(
-                "Channel: antigravity API error, url={claude_url} status={status} body={body}"
-            );
-            return Err(format!("API error {status}: {body}"));
-        }
-
-        let body = response
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read response body: {e}"))?;
-        let data: Value = serde_json::from_str(&body).map_err(|e| {
-            let truncated = if body.len() > 500 {
-                format!("{}...", &body[..500])
-            } else {
-                body.clone()
-            };
-            log::warn!("Channel: failed to parse antigravity response, url={claude_url} status={status} body={truncated}");
-            format!("Failed to parse response: {e}")
-        })?;
-
-        return Ok(parse_openai_models(&data));
-    }
-
-    let (url, parser): (String, fn(&Value) -> Vec<ModelInfo>) = match platform {
-        Some("gemini") => (format!("{trimmed_base}/v1beta/models"), parse_gemini_models),
-        Some("openai") => (format!("{trimmed_base}/v1/models"), parse_openai_models),
-        Some("deepseek") => (format!("{trimmed_base}/models"), parse_openai_models),
-        _ => (format!("{trimmed_base}/v1/models"), parse_openai_models),
-    };
-    log::debug!("Channel: fetching models from {url} (platform={platform:?})");
-
-    let response = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {api_key}"))
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {e}"))?;
-
-    let status = response.status();
-    if !status.is_success() {
-        let body = response.text().await.unwrap_or_default();
-        log::warn!("Channel: API error, url={url} status={status} body={body}");
-        return Err(format!("API error {status}: {body}"));
-    }
-
-    let body = response
-        .text()
+    log::debug!("Channel: fetching models (platform={platform:?})");
+    channel_provider::provider_for_platform(platform)
+        .list_models(base_url, api_key, platform)
         .await
-        .map_err(|e| format!("Failed to read response body: {e}"))?;
-    let data: Value = serde_json::from_str(&body).map_err(|e| {
-        let truncated = if body.len() > 500 {
-            format!("{}...", &body[..500])
-        } else {
-            body.clone()
-        };
-        log::warn!("Channel: failed to parse response, url={url} status={status} body={truncated}");
-        format!("Failed to parse response: {e}")
-    })?;
-
-    Ok(parser(&data))
 }
 
 pub fn fetch_models_by_api_key_blocking(
@@ -920,39 +645,3 @@ pub fn fetch_models_by_api_key_blocking(
         fetch_models_by_api_key(base_url, api_key, platform).await
     })
 }
-
-fn parse_openai_models(data: &Value) -> Vec<ModelInfo> {
-    data.get("data")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|m| {
-                    let id = m.get("id")?.as_str()?.to_string();
-                    Some(ModelInfo { id, name: None })
-                })
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
-fn parse_gemini_models(data: &Value) -> Vec<ModelInfo> {
-    data.get("models")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|m| {
-                    let raw_id = m.get("name")?.as_str()?;
-                    let id = raw_id.strip_prefix("models/").unwrap_or(raw_id).to_string();
-                    let display_name = m
-                        .get("displayName")
-                        .and_then(|n| n.as_str())
-                        .map(String::from);
-                    Some(ModelInfo {
-                        id,
-                        name: display_name,
-                    })
-                })
-                .collect()
-        })
-        .unwrap_or_default()
-}
@@ -4,18 +4,23 @@
 //! Each template is a "form" specifying:
 //! - Which channels/tokens/models to include (filters)
 //! - Which fields to output (field selectors + renames)
-//! - Output format (json/yaml/toml)
+//! - Output format (json/yaml/toml records, csv, or a tool-native artifact
+//!   such as a Codex `config.toml` fragment, Pi `models.json` or `.env` file)
 //! - Output path
 //!
-//! The engine collects data, applies filters, flattens into records,
-//! renders in the target format, and writes to disk — all at runtime
-//! without recompilation.
+//! The engine collects data, applies filters, shapes it as flat records or a
+//! nested channels → tokens → models document, renders the target format and
+//! writes it to disk, all at runtime without recompilation.
 
+use crate::apply_plan::mask_secret;
 use crate::channel::{
     fetch_channel_tokens, get_channel_api_key, get_channel_credentials, load_channels, Channel,
     ChannelToken, ChannelType,
 };
+use crate::channel_provisioning::{provision_profiles, ProvisionedProfiles};
+use crate::codex::{self, CodexProviderConfig};
 use crate::factory_settings::ModelInfo;
+use crate::{hermes, openclaw, opencode, pi};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
@@ -28,11 +33,44 @@ use std::path::{Path, PathBuf};
 
 /// Output format
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "kebab-case")]
 pub enum ExportFormat {
     Json,
     Yaml,
     Toml,
+    /// Codex `config.toml` `[model_providers.*]` fragment
+    CodexConfig,
+    /// OpenCode `opencode.json` with a `provider` block
+    OpencodeConfig,
+    /// Pi `models.json`
+    PiModels,
+    /// OpenClaw `models` block (`mode` + `providers`)
+    OpenclawProviders,
+    /// Hermes `config.yaml` `model` section
+    HermesConfig,
+    /// `.env` file with one API key and base URL per token
+    Env,
+    /// CSV with one row per flat record
+    Csv,
+    /// Rendered from the user text template at `templatePath`
+    Text,
+}
+
+impl ExportFormat {
+    /// File extension written for this format.
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json
+            | ExportFormat::OpencodeConfig
+            | ExportFormat::PiModels
+            | ExportFormat::OpenclawProviders => "json",
+            ExportFormat::Yaml | ExportFormat::HermesConfig => "yaml",
+            ExportFormat::Toml | ExportFormat::CodexConfig => "toml",
+            ExportFormat::Env => "env",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Text => "txt",
+        }
+    }
 }
 
 /// Output structure
@@ -109,6 +147,10 @@ pub struct ExportTemplate {
     pub output_structure: OutputStructure,
     /// Output file path (supports ~ and {timestamp})
     pub output_path: String,
+    /// MiniJinja template rendered when `format` is `text`
+    /// (supports ~; relative paths resolve against `~/.droidgear/`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_path: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -182,7 +224,7 @@ pub fn save_export_templates(templates: &[ExportTemplate]) -> Result<(), String>
 // ============================================================================
 
 /// Infer model protocol from model ID.
-fn infer_protocol_from_model_id(model_id: &str) -> &'static str {
+pub(crate) fn infer_protocol_from_model_id(model_id: &str) -> &'static str {
     let lower = model_id.to_lowercase();
 
     if lower.starts_with("claude-") {
@@ -198,6 +240,53 @@ fn infer_protocol_from_model_id(model_id: &str) -> &'static str {
     }
 }
 
+/// Infer model protocol for a channel token. Sub2API keys carry a platform
+/// that pins the protocol; `antigravity` serves both Claude and Gemini and
+/// defaults to Claude. Everything else falls back to the model ID.
+pub(crate) fn infer_channel_protocol(platform: Option<&str>, model_id: &str) -> &'static str {
+    let lower = model_id.to_lowercase();
+    match platform.map(|p| p.to_lowercase()).as_deref() {
+        Some("openai") => "openai",
+        Some("anthropic") => "anthropic",
+        Some("gemini") => "google-ai",
+        Some("antigravity") if lower.starts_with("gemini-") => "google-ai",
+        Some("antigravity") => "anthropic",
+        _ => infer_protocol_from_model_id(model_id),
+    }
+}
+
+/// Base URL a client should use to reach `protocol` on a channel.
+pub(crate) fn protocol_base_url(
+    channel_type: &ChannelType,
+    platform: Option<&str>,
+    protocol: &str,
+    base_url: &str,
+) -> String {
+    match channel_type {
+        ChannelType::Sub2Api => match (platform, protocol) {
+            (Some("antigravity"), "anthropic") => with_path_suffix(base_url, "/antigravity"),
+            (Some("antigravity"), "google-ai") => with_path_suffix(base_url, "/antigravity/v1beta"),
+            _ => base_url.to_string(),
+        },
+        ChannelType::DeepSeek if protocol == "anthropic" => {
+            format!("{}/anthropic", base_url.trim_end_matches('/'))
+        }
+        ChannelType::DeepSeek => base_url.trim_end_matches('/').to_string(),
+        _ if protocol == "anthropic" => base_url.to_string(),
+        _ => with_path_suffix(base_url, "/v1"),
+    }
+}
+
+/// Appends `suffix` to `base_url` unless it already ends with it.
+pub(crate) fn with_path_suffix(base_url: &str, suffix: &str) -> String {
+    let trimmed = base_url.trim_end_matches('/');
+    if trimmed.ends_with(suffix) {
+        trimmed.to_string()
+    } else {
+        format!("{trimmed}{suffix}")
+    }
+}
+
 /// Infer protocol with user overrides applied.
 fn resolve_protocol(model_id: &str, overrides: &HashMap<String, String>) -> String {
     // Check overrides first (simple prefix matching)
@@ -218,16 +307,9 @@ fn resolve_protocol(model_id: &str, overrides: &HashMap<String, String>) -> Stri
 // Field extraction helpers
 // ============================================================================
 
-/// Build a record from channel + token + model data, selecting only the fields specified.
-fn build_flat_record(
-    channel: &Channel,
-    token: &ChannelToken,
-    model: Option<&ModelInfo>,
-    protocol: Option<&str>,
-    fields: &HashMap<String, String>,
-) -> ExportRecord {
-    let all_fields: HashMap<&str, serde_json::Value> = HashMap::from([
-        // Channel fields
+/// Source fields of a channel, keyed by their `channel.*` selector path.
+fn channel_fields(channel: &Channel) -> Vec<(&'static str, serde_json::Value)> {
+    vec![
         ("channel.id", serde_json::Value::String(channel.id.clone())),
         (
             "channel.name",
@@ -242,7 +324,12 @@ fn build_flat_record(
             serde_json::Value::String(channel.base_url.clone()),
         ),
         ("channel.enabled", serde_json::Value::Bool(channel.enabled)),
-        // Token fields
+    ]
+}
+
+/// Source fields of a token, keyed by their `token.*` selector path.
+fn token_fields(token: &ChannelToken) -> Vec<(&'static str, serde_json::Value)> {
+    vec![
         ("token.name", serde_json::Value::String(token.name.clone())),
         ("token.key", serde_json::Value::String(token.key.clone())),
         (
@@ -285,58 +372,181 @@ fn build_flat_record(
             "token.unlimitedQuota",
             serde_json::Value::Bool(token.unlimited_quota),
         ),
-    ]);
-
-    let record: ExportRecord = if fields.is_empty() {
-        // No field selection = include all
-        // Convert all_fields from HashMap<&str, Value> to ExportRecord (HashMap<String, Value>)
-        let mut r = ExportRecord::new();
-        for (k, v) in &all_fields {
-            r.insert(k.to_string(), v.clone());
-        }
-        if let Some(m) = model {
-            r.insert(
-                "model.id".to_string(),
-                serde_json::Value::String(m.id.clone()),
-            );
-            r.insert(
-                "model.name".to_string(),
-                m.name.as_ref().map_or(serde_json::Value::Null, |n| {
+    ]
+}
+
+/// Source fields of a model, keyed by their `model.*` selector path.
+fn model_fields(
+    model: Option<&ModelInfo>,
+    protocol: Option<&str>,
+) -> Vec<(&'static str, serde_json::Value)> {
+    vec![
+        (
+            "model.id",
+            model.map_or(serde_json::Value::Null, |m| {
+                serde_json::Value::String(m.id.clone())
+            }),
+        ),
+        (
+            "model.name",
+            model
+                .and_then(|m| m.name.as_ref())
+                .map_or(serde_json::Value::Null, |n| {
                     serde_json::Value::String(n.clone())
                 }),
-            );
-            r.insert(
-                "model.protocol".to_string(),
-                serde_json::Value::String(protocol.unwrap_or("openai-compatible").to_string()),
-            );
+        ),
+        (
+            "model.protocol",
+            serde_json::Value::String(protocol.unwrap_or("openai-compatible").to_string()),
+        ),
+    ]
+}
+
+/// Build a record from channel + token + model data, selecting only the fields specified.
+fn build_flat_record(
+    channel: &Channel,
+    token: &ChannelToken,
+    model: Option<&ModelInfo>,
+    protocol: Option<&str>,
+    fields: &HashMap<String, String>,
+) -> ExportRecord {
+    let mut all_fields: HashMap<&str, serde_json::Value> = channel_fields(channel)
+        .into_iter()
+        .chain(token_fields(token))
+        .collect();
+
+    if fields.is_empty() {
+        // No field selection = include all; model fields only when there is a model
+        if model.is_some() {
+            all_fields.extend(model_fields(model, protocol));
         }
-        r
+        all_fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect()
     } else {
-        let mut r = ExportRecord::new();
-        for (source, output_name) in fields {
-            let value = if let Some(v) = all_fields.get(source.as_str()) {
-                v.clone()
-            } else if source == "model.id" {
-                model.map_or(serde_json::Value::Null, |m| {
-                    serde_json::Value::String(m.id.clone())
-                })
-            } else if source == "model.name" {
-                model
-                    .and_then(|m| m.name.as_ref())
-                    .map_or(serde_json::Value::Null, |n| {
-                        serde_json::Value::String(n.clone())
-                    })
-            } else if source == "model.protocol" {
-                serde_json::Value::String(protocol.unwrap_or("openai-compatible").to_string())
-            } else {
-                continue;
-            };
-            r.insert(output_name.clone(), value);
+        all_fields.extend(model_fields(model, protocol));
+        fields
+            .iter()
+            .filter_map(|(source, output_name)| {
+                all_fields
+                    .get(source.as_str())
+                    .map(|v| (output_name.clone(), v.clone()))
+            })
+            .collect()
+    }
+}
+
+/// Build one level of a nested document from that level's source fields.
+///
+/// With no field selection every field is included under its name without the
+/// level prefix (`channel.name` → `name`). Otherwise only the selected fields of
+/// this level are included, under their output names.
+fn build_level_record(
+    level_fields: Vec<(&'static str, serde_json::Value)>,
+    fields: &HashMap<String, String>,
+) -> serde_json::Map<String, serde_json::Value> {
+    if fields.is_empty() {
+        return level_fields
+            .into_iter()
+            .map(|(k, v)| {
+                let name = k.split_once('.').map_or(k, |(_, rest)| rest);
+                (name.to_string(), v)
+            })
+            .collect();
+    }
+    let level_fields: HashMap<&str, serde_json::Value> = level_fields.into_iter().collect();
+    fields
+        .iter()
+        .filter_map(|(source, output_name)| {
+            level_fields
+                .get(source.as_str())
+                .map(|v| (output_name.clone(), v.clone()))
+        })
+        .collect()
+}
+
+/// A token collected during an export run, with its models when they were fetched.
+struct CollectedToken {
+    token: ChannelToken,
+    /// Models with their resolved protocol; `None` when the template does not fetch models
+    models: Option<Vec<(ModelInfo, String)>>,
+}
+
+/// A channel collected during an export run.
+struct CollectedChannel<'a> {
+    channel: &'a Channel,
+    tokens: Vec<CollectedToken>,
+}
+
+/// Flatten collected data into one record per model (or per token when it has no models).
+fn build_flat_records(
+    collected: &[CollectedChannel<'_>],
+    fields: &HashMap<String, String>,
+) -> Vec<ExportRecord> {
+    let mut records = vec![];
+    for entry in collected {
+        for token in &entry.tokens {
+            match token.models.as_deref() {
+                Some(models) if !models.is_empty() => {
+                    for (model, protocol) in models {
+                        records.push(build_flat_record(
+                            entry.channel,
+                            &token.token,
+                            Some(model),
+                            Some(protocol),
+                            fields,
+                        ));
+                    }
+                }
+                _ => records.push(build_flat_record(
+                    entry.channel,
+                    &token.token,
+                    None,
+                    None,
+                    fields,
+                )),
+            }
         }
-        r
-    };
+    }
+    records
+}
 
-    record
+/// Group collected data as channels → `tokens` → `models`.
+///
+/// The `models` array is only present when the template fetches models.
+fn build_nested_document(
+    collected: &[CollectedChannel<'_>],
+    fields: &HashMap<String, String>,
+) -> Vec<serde_json::Value> {
+    collected
+        .iter()
+        .map(|entry| {
+            let tokens: Vec<serde_json::Value> = entry
+                .tokens
+                .iter()
+                .map(|token| {
+                    let mut token_record = build_level_record(token_fields(&token.token), fields);
+                    if let Some(models) = &token.models {
+                        let models: Vec<serde_json::Value> = models
+                            .iter()
+                            .map(|(model, protocol)| {
+                                serde_json::Value::Object(build_level_record(
+                                    model_fields(Some(model), Some(protocol)),
+                                    fields,
+                                ))
+                            })
+                            .collect();
+                        token_record.insert("models".to_string(), serde_json::Value::Array(models));
+                    }
+                    serde_json::Value::Object(token_record)
+                })
+                .collect();
+            let mut channel_record = build_level_record(channel_fields(entry.channel), fields);
+            channel_record.insert("tokens".to_string(), serde_json::Value::Array(tokens));
+            serde_json::Value::Object(channel_record)
+        })
+        .collect()
 }
 
 // ============================================================================
@@ -345,38 +555,19 @@ fn build_flat_record(
 
 /// Resolve auth and fetch tokens for a channel.
 fn fetch_tokens_for_channel(channel: &Channel) -> Result<Vec<ChannelToken>, String> {
-    match channel.channel_type {
-        ChannelType::NewApi | ChannelType::Sub2Api => {
-            let creds = get_channel_credentials(&channel.id)?;
-            match creds {
-                Some((username, password)) => {
-                    fetch_channel_tokens_blocking(channel, &username, &password)
-                }
-                None => Err(format!("No credentials for channel '{}'", channel.name)),
+    if channel.channel_type.uses_login() {
+        let creds = get_channel_credentials(&channel.id)?;
+        match creds {
+            Some((username, password)) => {
+                fetch_channel_tokens_blocking(channel, &username, &password)
             }
+            None => Err(format!("No credentials for channel '{}'", channel.name)),
         }
-        ChannelType::CliProxyApi
-        | ChannelType::Ollama
-        | ChannelType::General
-        | ChannelType::DeepSeek => {
-            let api_key = get_channel_api_key(&channel.id)?;
-            match api_key {
-                Some(key) => {
-                    // For API-key-based channels, return a single synthetic token
-                    Ok(vec![ChannelToken {
-                        id: 0.0,
-                        name: "API Key".to_string(),
-                        key,
-                        status: 1,
-                        remain_quota: 0.0,
-                        used_quota: 0.0,
-                        unlimited_quota: true,
-                        platform: None,
-                        group_name: None,
-                    }])
-                }
-                None => Err(format!("No API key for channel '{}'", channel.name)),
-            }
+    } else {
+        match get_channel_api_key(&channel.id)? {
+            // For API-key-based channels, return a single synthetic token
+            Some(key) => Ok(vec![ChannelToken::from_api_key(&key)]),
+            None => Err(format!("No API key for channel '{}'", channel.name)),
         }
     }
 }
@@ -434,7 +625,8 @@ pub struct ExportResult {
     pub models_count: usize,
     /// Output file path
     pub output_path: String,
-    /// Record count (rows written)
+    /// Record count (rows written; top-level channel entries for nested output
+    /// and text templates, providers for tool-native formats)
     pub record_count: usize,
     /// Any warnings
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -444,7 +636,7 @@ pub struct ExportResult {
 /// Run a single export template: collect data, format, write.
 pub fn run_export(template: &ExportTemplate) -> Result<ExportResult, String> {
     let mut warnings: Vec<String> = vec![];
-    let mut records: Vec<ExportRecord> = vec![];
+    let mut collected: Vec<CollectedChannel> = vec![];
     let mut token_count = 0;
     let mut model_count = 0;
 
@@ -499,10 +691,11 @@ pub fn run_export(template: &ExportTemplate) -> Result<ExportResult, String> {
             })
             .collect();
 
-        for token in &filtered_tokens {
+        let mut collected_tokens = vec![];
+        for token in filtered_tokens {
             token_count += 1;
 
-            if template.fetch_models {
+            let models = if template.fetch_models {
                 // Fetch models from the API
                 let platform = token.platform.as_deref();
                 let models = match fetch_models_blocking(&channel.base_url, &token.key, platform) {
@@ -515,31 +708,32 @@ pub fn run_export(template: &ExportTemplate) -> Result<ExportResult, String> {
                         vec![]
                     }
                 };
-
-                if models.is_empty() {
-                    // Still emit a record with channel + token info, no model
-                    let record = build_flat_record(channel, token, None, None, &template.fields);
-                    records.push(record);
-                } else {
-                    for m in &models {
-                        let protocol = resolve_protocol(&m.id, &template.model_protocol_overrides);
-                        let record = build_flat_record(
-                            channel,
-                            token,
-                            Some(m),
-                            Some(&protocol),
-                            &template.fields,
-                        );
-                        records.push(record);
-                        model_count += 1;
-                    }
-                }
+                model_count += models.len();
+                Some(
+                    models
+                        .into_iter()
+                        .map(|m| {
+                            let protocol =
+                                resolve_protocol(&m.id, &template.model_protocol_overrides);
+                            (m, protocol)
+                        })
+                        .collect(),
+                )
             } else {
                 // No model fetching — just channel + token info
-                let record = build_flat_record(channel, token, None, None, &template.fields);
-                records.push(record);
-            }
+                None
+            };
+
+            collected_tokens.push(CollectedToken {
+                token: token.clone(),
+                models,
+            });
         }
+
+        collected.push(CollectedChannel {
+            channel,
+            tokens: collected_tokens,
+        });
     }
 
     // 5. Resolve output path (expand ~ and {timestamp})
@@ -548,12 +742,55 @@ pub fn run_export(template: &ExportTemplate) -> Result<ExportResult, String> {
     // 6. Ensure file extension matches the selected format
     ensure_extension(&mut output_path, &template.format);
 
-    // 7. Render and write
-    match template.format {
-        ExportFormat::Json => render_json(&records, &output_path)?,
-        ExportFormat::Yaml => render_yaml(&records, &output_path)?,
-        ExportFormat::Toml => render_toml(&records, &output_path)?,
-    }
+    // 7. Shape, render and write
+    let record_count = match template.format {
+        ExportFormat::Json | ExportFormat::Yaml | ExportFormat::Toml => {
+            match template.output_structure {
+                OutputStructure::Flat => {
+                    let records = build_flat_records(&collected, &template.fields);
+                    render(&records, "records", &template.format, &output_path)?;
+                    records.len()
+                }
+                OutputStructure::Nested => {
+                    let document = build_nested_document(&collected, &template.fields);
+                    render(&document, "channels", &template.format, &output_path)?;
+                    document.len()
+                }
+            }
+        }
+        ExportFormat::Text => {
+            let template_path = resolve_template_path(template.template_path.as_deref())?;
+            let source = fs::read_to_string(&template_path).map_err(|e| {
+                format!(
+                    "Failed to read text template '{}': {e}",
+                    template_path.display()
+                )
+            })?;
+            let document = build_nested_document(&collected, &template.fields);
+            let name = template_path.to_string_lossy();
+            match render_text_template(&name, &source, &template.name, &document) {
+                Ok(content) => {
+                    write_atomic(&output_path, &content)?;
+                    document.len()
+                }
+                Err(e) => {
+                    // Template mistakes are reported, not fatal: nothing is written
+                    warnings.push(e);
+                    0
+                }
+            }
+        }
+        ExportFormat::Csv => {
+            let records = build_flat_records(&collected, &template.fields);
+            write_atomic(&output_path, &render_csv(&records))?;
+            records.len()
+        }
+        _ => {
+            let (content, count) = render_native(&collected, &template.format, &mut warnings)?;
+            write_atomic(&output_path, &content)?;
+            count
+        }
+    };
 
     Ok(ExportResult {
         template: template.name.clone(),
@@ -561,7 +798,7 @@ pub fn run_export(template: &ExportTemplate) -> Result<ExportResult, String> {
         tokens_count: token_count,
         models_count: model_count,
         output_path: output_path.to_string_lossy().to_string(),
-        record_count: records.len(),
+        record_count,
         warnings,
     })
 }
@@ -571,11 +808,21 @@ pub fn run_export(template: &ExportTemplate) -> Result<ExportResult, String> {
 /// Ensure the file extension matches the output format.
 /// Replaces the extension if it doesn't match; appends one if there's no extension.
 fn ensure_extension(path: &mut PathBuf, format: &ExportFormat) {
-    let expected = match format {
-        ExportFormat::Json => "json",
-        ExportFormat::Yaml => "yaml",
-        ExportFormat::Toml => "toml",
-    };
+    let expected = format.extension();
+
+    // Text templates can produce anything; keep the user's file name
+    if *format == ExportFormat::Text {
+        return;
+    }
+
+    // `.env`, `.env.local`, ... are already valid dotenv file names
+    if *format == ExportFormat::Env
+        && path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with(".env"))
+    {
+        return;
+    }
 
     if let Some(ext) = path.extension() {
         let current = ext.to_string_lossy().to_lowercase();
@@ -623,27 +870,42 @@ fn resolve_output_path(raw: &str) -> Result<PathBuf, String> {
 // Formatters
 // ============================================================================
 
+/// Render records in the template's format and write to file.
+/// `toml_key` names the top-level array, since TOML documents must be tables.
+fn render<T: Serialize>(
+    value: &T,
+    toml_key: &str,
+    format: &ExportFormat,
+    path: &Path,
+) -> Result<(), String> {
+    match format {
+        ExportFormat::Json => render_json(value, path),
+        ExportFormat::Yaml => render_yaml(value, path),
+        ExportFormat::Toml => render_toml(value, toml_key, path),
+        other => Err(format!("{other:?} is not a record format")),
+    }
+}
+
 /// Render records as JSON and write to file.
-fn render_json(records: &[ExportRecord], path: &Path) -> Result<(), String> {
-    let value: Vec<&ExportRecord> = records.iter().collect();
-    let content = serde_json::to_string_pretty(&value)
+fn render_json<T: Serialize>(value: &T, path: &Path) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(value)
         .map_err(|e| format!("JSON serialization failed: {e}"))?;
     write_atomic(path, &content)
 }
 
 /// Render records as YAML and write to file.
-fn render_yaml(records: &[ExportRecord], path: &Path) -> Result<(), String> {
+fn render_yaml<T: Serialize>(value: &T, path: &Path) -> Result<(), String> {
     let content =
-        serde_yaml::to_string(&records).map_err(|e| format!("YAML serialization failed: {e}"))?;
+        serde_yaml::to_string(value).map_err(|e| format!("YAML serialization failed: {e}"))?;
     write_atomic(path, &content)
 }
 
 /// Render records as TOML and write to file.
-/// TOML requires an array of tables — we serialize as `[[record]]` array.
-fn render_toml(records: &[ExportRecord], path: &Path) -> Result<(), String> {
+/// TOML requires an array of tables — we serialize as `[[<toml_key>]]` array.
+fn render_toml<T: Serialize>(value: &T, toml_key: &str, path: &Path) -> Result<(), String> {
     // TOML doesn't natively support top-level arrays of tables in the spec
-    // as a standalone document well. We wrap in a `records` key.
-    let wrapper = serde_json::json!({ "records": records });
+    // as a standalone document well. We wrap in a named key.
+    let wrapper = serde_json::json!({ toml_key: value });
     // Convert through serde_value to toml
     let toml_value: toml::Value =
         toml::Value::try_from(&wrapper).map_err(|e| format!("TOML conversion failed: {e}"))?;
@@ -652,6 +914,321 @@ fn render_toml(records: &[ExportRecord], path: &Path) -> Result<(), String> {
     write_atomic(path, &content)
 }
 
+/// Render flat records as CSV: a header row of sorted column names, then one row per record.
+fn render_csv(records: &[ExportRecord]) -> String {
+    let mut columns: Vec<&String> = records.iter().flat_map(|r| r.keys()).collect();
+    columns.sort();
+    columns.dedup();
+
+    let mut out = String::new();
+    let header: Vec<String> = columns.iter().map(|c| csv_escape(c)).collect();
+    out.push_str(&header.join(","));
+    out.push('\n');
+    for record in records {
+        let row: Vec<String> = columns
+            .iter()
+            .map(|c| match record.get(*c) {
+                None | Some(serde_json::Value::Null) => String::new(),
+                Some(serde_json::Value::String(s)) => csv_escape(s),
+                Some(v) => csv_escape(&v.to_string()),
+            })
+            .collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// ============================================================================
+// Tool-native formatters
+// ============================================================================
+
+/// Provision profiles for every collected token that has models.
+fn provision_collected(
+    collected: &[CollectedChannel<'_>],
+    warnings: &mut Vec<String>,
+) -> Vec<ProvisionedProfiles> {
+    let mut provisioned = vec![];
+    for entry in collected {
+        for token in &entry.tokens {
+            let models: Vec<ModelInfo> = token
+                .models
+                .iter()
+                .flatten()
+                .map(|(m, _)| m.clone())
+                .collect();
+            if models.is_empty() {
+                warnings.push(format!(
+                    "Channel '{}', token '{}': no models to export (enable model fetching)",
+                    entry.channel.name, token.token.name
+                ));
+                continue;
+            }
+            match provision_profiles(entry.channel, &token.token, &models) {
+                Ok(p) => provisioned.push(p),
+                Err(e) => warnings.push(format!(
+                    "Channel '{}', token '{}': {e}",
+                    entry.channel.name, token.token.name
+                )),
+            }
+        }
+    }
+    provisioned
+}
+
+/// `id`, or `id-2`, `id-3`, ... when several tokens map to the same provider key.
+fn unique_key(id: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut key = id.to_string();
+    let mut n = 2;
+    while taken(&key) {
+        key = format!("{id}-{n}");
+        n += 1;
+    }
+    key
+}
+
+/// Environment variable prefix for a provider key (`my-relay` → `MY_RELAY`).
+fn env_prefix(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Codex providers keyed by unique id, reading their key from `<ID>_API_KEY`
+/// (the variable the `.env` export writes).
+fn codex_providers(provisioned: &[ProvisionedProfiles]) -> Vec<(String, CodexProviderConfig)> {
+    let mut providers: Vec<(String, CodexProviderConfig)> = vec![];
+    for profiles in provisioned {
+        for (id, config) in &profiles.codex.providers {
+            let id = unique_key(id, |k| providers.iter().any(|(taken, _)| taken == k));
+            let config = CodexProviderConfig {
+                env_key: Some(format!("{}_API_KEY", env_prefix(&id))),
+                ..config.clone()
+            };
+            providers.push((id, config));
+        }
+    }
+    providers
+}
+
+/// Per-token `<ID>_API_KEY` variable names, in `provisioned` order. Every
+/// provisioned token has exactly one Codex provider, so these are the names
+/// the `.env` export writes.
+fn api_key_vars(provisioned: &[ProvisionedProfiles]) -> Vec<String> {
+    codex_providers(provisioned)
+        .into_iter()
+        .filter_map(|(_, config)| config.env_key)
+        .collect()
+}
+
+/// Render collected data as a tool-native artifact. Returns the content and
+/// the number of providers it contains.
+fn render_native(
+    collected: &[CollectedChannel<'_>],
+    format: &ExportFormat,
+    warnings: &mut Vec<String>,
+) -> Result<(String, usize), String> {
+    let provisioned = provision_collected(collected, warnings);
+    // Keys are never embedded; documents reference the `.env` variables
+    let vars = api_key_vars(&provisioned);
+
+    match format {
+        ExportFormat::CodexConfig => {
+            let providers = codex_providers(&provisioned);
+            let mut table = toml::map::Map::new();
+            for (id, config) in &providers {
+                table.insert(id.clone(), codex::provider_config_to_toml(config)?);
+            }
+            let mut root = toml::map::Map::new();
+            root.insert("model_providers".to_string(), toml::Value::Table(table));
+            let content = toml::to_string_pretty(&toml::Value::Table(root))
+                .map_err(|e| format!("TOML serialization failed: {e}"))?;
+            Ok((content, providers.len()))
+        }
+        ExportFormat::Env => {
+            let providers = codex_providers(&provisioned);
+            let mut content = String::new();
+            for (id, config) in &providers {
+                let prefix = env_prefix(id);
+                content.push_str(&format!("# {}\n", config.name.as_deref().unwrap_or(id)));
+                if let Some(base_url) = &config.base_url {
+                    content.push_str(&format!("{prefix}_BASE_URL={}\n", env_quote(base_url)));
+                }
+                if let Some(api_key) = &config.api_key {
+                    content.push_str(&format!("{prefix}_API_KEY={}\n", env_quote(api_key)));
+                }
+            }
+            Ok((content, providers.len()))
+        }
+        ExportFormat::OpencodeConfig => {
+            let mut providers = HashMap::new();
+            for (profiles, var) in provisioned.into_iter().zip(&vars) {
+                for (id, mut config) in profiles.opencode.providers {
+                    if let Some(options) = config.options.as_mut() {
+                        options.api_key = Some(format!("{{env:{var}}}"));
+                    }
+                    let id = unique_key(&id, |k| providers.contains_key(k));
+                    providers.insert(id, config);
+                }
+            }
+            let document =
+                serde_json::json!({ "provider": opencode::providers_to_value(&providers)? });
+            let content = serde_json::to_string_pretty(&document)
+                .map_err(|e| format!("JSON serialization failed: {e}"))?;
+            Ok((content, providers.len()))
+        }
+        ExportFormat::PiModels => {
+            let mut providers = HashMap::new();
+            for (profiles, var) in provisioned.into_iter().zip(&vars) {
+                for (id, mut config) in profiles.pi.providers {
+                    config.api_key = Some(var.clone());
+                    let id = unique_key(&id, |k| providers.contains_key(k));
+                    providers.insert(id, config);
+                }
+            }
+            let count = providers.len();
+            Ok((pi::render_providers_json(providers)?, count))
+        }
+        ExportFormat::OpenclawProviders => {
+            let mut providers = HashMap::new();
+            for (profiles, var) in provisioned.into_iter().zip(&vars) {
+                for (id, mut config) in profiles.openclaw.providers {
+                    config.api_key = Some(var.clone());
+                    let id = unique_key(&id, |k| providers.contains_key(k));
+                    providers.insert(id, config);
+                }
+            }
+            let document =
+                serde_json::json!({ "models": openclaw::render_models_block(&providers) });
+            let content = serde_json::to_string_pretty(&document)
+                .map_err(|e| format!("JSON serialization failed: {e}"))?;
+            Ok((content, providers.len()))
+        }
+        ExportFormat::HermesConfig => {
+            // config.yaml holds a single model section
+            let Some(first) = provisioned.first() else {
+                return Ok((String::new(), 0));
+            };
+            if provisioned.len() > 1 {
+                warnings.push(format!(
+                    "Hermes config holds one model; exported '{}' and skipped {} other token(s)",
+                    first.hermes.name,
+                    provisioned.len() - 1
+                ));
+            }
+            let model = hermes::HermesModelConfig {
+                api_key: vars.first().cloned(),
+                ..first.hermes.model.clone()
+            };
+            let mut root = serde_yaml::Mapping::new();
+            root.insert(
+                serde_yaml::Value::String("model".to_string()),
+                serde_yaml::Value::Mapping(hermes::model_section(&model)),
+            );
+            let content = serde_yaml::to_string(&root)
+                .map_err(|e| format!("YAML serialization failed: {e}"))?;
+            Ok((content, 1))
+        }
+        other => Err(format!("{other:?} is not a tool-native format")),
+    }
+}
+
+// ============================================================================
+// Text templates
+// ============================================================================
+
+/// Resolve a text template path: `~` expands to home, relative paths resolve
+/// against `~/.droidgear/` (next to `export-templates.yaml`).
+fn resolve_template_path(raw: Option<&str>) -> Result<PathBuf, String> {
+    let raw = raw
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .ok_or("Text exports need a templatePath")?;
+    if let Some(rest) = raw.strip_prefix('~') {
+        let rest = rest.trim_start_matches(['/', '\\']);
+        return Ok(crate::paths::get_home_dir()?.join(rest));
+    }
+    let path = PathBuf::from(raw);
+    if path.is_absolute() {
+        Ok(path)
+    } else {
+        Ok(droidgear_dir()?.join(path))
+    }
+}
+
+/// Render a MiniJinja text template over the nested channels → tokens → models
+/// document.
+///
+/// The context holds `channels`, `template` (the export template name) and
+/// `generated_at` (RFC 3339). Besides MiniJinja's built-in filters (`upper`,
+/// `default`, `join`, ...) a `mask` filter hides secrets. Undefined variables
+/// are errors, and block tags don't leave blank lines behind. Errors carry the
+/// template line number.
+fn render_text_template(
+    name: &str,
+    source: &str,
+    template_name: &str,
+    channels: &[serde_json::Value],
+) -> Result<String, String> {
+    let mut env = minijinja::Environment::new();
+    env.set_undefined_behavior(minijinja::UndefinedBehavior::Strict);
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_filter("mask", |value: String| mask_secret(&value));
+
+    let describe = |e: minijinja::Error| {
+        let message = match e.detail() {
+            Some(detail) => format!("{}: {detail}", e.kind()),
+            None => e.kind().to_string(),
+        };
+        match e.line() {
+            Some(line) => format!("Template '{name}' line {line}: {message}"),
+            None => format!("Template '{name}': {message}"),
+        }
+    };
+
+    let tmpl = env
+        .template_from_named_str(name, source)
+        .map_err(describe)?;
+    tmpl.render(minijinja::context! {
+        channels => channels,
+        template => template_name,
+        generated_at => chrono::Local::now().to_rfc3339(),
+    })
+    .map_err(describe)
+}
+
+/// Quote a dotenv value when it contains characters that would otherwise be
+/// misread. Single quotes are preferred since they disable `$` expansion.
+fn env_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\'' | '\\' | '$'));
+    if plain {
+        value.to_string()
+    } else if !value.contains('\'') {
+        format!("'{value}'")
+    } else {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
 /// Atomic file write: temp file + rename.
 fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
     let temp_path = path.with_extension("tmp");
@@ -688,6 +1265,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_infer_channel_protocol() {
+        assert_eq!(infer_channel_protocol(Some("openai"), "claude-x"), "openai");
+        assert_eq!(
+            infer_channel_protocol(Some("antigravity"), "gemini-2.5-pro"),
+            "google-ai"
+        );
+        assert_eq!(
+            infer_channel_protocol(Some("antigravity"), "other"),
+            "anthropic"
+        );
+        assert_eq!(infer_channel_protocol(None, "gpt-4o"), "openai");
+        assert_eq!(
+            protocol_base_url(&ChannelType::NewApi, None, "openai", "https://x.com/"),
+            "https://x.com/v1"
+        );
+        assert_eq!(
+            protocol_base_url(&ChannelType::DeepSeek, None, "anthropic", "https://x.com"),
+            "https://x.com/anthropic"
+        );
+    }
+
     #[test]
     fn test_resolve_protocol_with_overrides() {
         let mut overrides = HashMap::new();
@@ -734,6 +1333,7 @@ mod tests {
         let model = ModelInfo {
             id: "claude-sonnet-4".to_string(),
             name: Some("Claude Sonnet 4".to_string()),
+            ..Default::default()
         };
 
         let fields = HashMap::new(); // empty = all fields
@@ -781,6 +1381,7 @@ mod tests {
         let model = ModelInfo {
             id: "claude-sonnet-4".to_string(),
             name: None,
+            ..Default::default()
         };
 
         let mut fields = HashMap::new();
@@ -808,7 +1409,379 @@ mod tests {
             Some(&serde_json::Value::String("sk-test".to_string()))
         );
         // Unselected fields should NOT be present
-        assert!(record.get("token.name").is_none());
-        assert!(record.get("channel.baseUrl").is_none());
+        assert!(!record.contains_key("token.name"));
+        assert!(!record.contains_key("channel.baseUrl"));
+    }
+
+    fn nested_fixture() -> (Channel, ChannelToken, Vec<(ModelInfo, String)>) {
+        let channel = Channel {
+            id: "ch-1".to_string(),
+            name: "Test Channel".to_string(),
+            channel_type: ChannelType::NewApi,
+            base_url: "https://api.example.com".to_string(),
+            enabled: true,
+            created_at: 1000.0,
+        };
+        let token = ChannelToken {
+            id: 1.0,
+            name: "Default Key".to_string(),
+            key: "sk-test".to_string(),
+            status: 1,
+            remain_quota: 1000.0,
+            used_quota: 500.0,
+            unlimited_quota: false,
+            platform: Some("anthropic".to_string()),
+            group_name: Some("group-1".to_string()),
+        };
+        let models = vec![
+            (
+                ModelInfo {
+                    id: "claude-sonnet-4".to_string(),
+                    name: Some("Claude Sonnet 4".to_string()),
+                    ..Default::default()
+                },
+                "anthropic".to_string(),
+            ),
+            (
+                ModelInfo {
+                    id: "gpt-4o".to_string(),
+                    name: Some("GPT-4o".to_string()),
+                    ..Default::default()
+                },
+                "openai".to_string(),
+            ),
+        ];
+        (channel, token, models)
+    }
+
+    #[test]
+    fn test_build_nested_document_all_fields() {
+        let (channel, token, models) = nested_fixture();
+        let collected = vec![CollectedChannel {
+            channel: &channel,
+            tokens: vec![CollectedToken {
+                token,
+                models: Some(models),
+            }],
+        }];
+
+        let document = build_nested_document(&collected, &HashMap::new());
+
+        assert_eq!(document.len(), 1);
+        let channel = &document[0];
+        assert_eq!(channel["name"], "Test Channel");
+        assert_eq!(channel["baseUrl"], "https://api.example.com");
+        assert!(channel.get("channel.name").is_none());
+        let token = &channel["tokens"][0];
+        assert_eq!(token["key"], "sk-test");
+        assert_eq!(token["groupName"], "group-1");
+        let models = token["models"].as_array().unwrap();
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0]["id"], "claude-sonnet-4");
+        assert_eq!(models[1]["protocol"], "openai");
+        // Each level only carries its own fields
+        assert!(models[0].get("key").is_none());
+        assert!(token.get("baseUrl").is_none());
+    }
+
+    #[test]
+    fn test_build_nested_document_selected_fields_with_rename() {
+        let (channel, token, models) = nested_fixture();
+        let collected = vec![CollectedChannel {
+            channel: &channel,
+            tokens: vec![CollectedToken {
+                token,
+                models: Some(models),
+            }],
+        }];
+
+        let mut fields = HashMap::new();
+        fields.insert("channel.name".to_string(), "channel".to_string());
+        fields.insert("token.key".to_string(), "apiKey".to_string());
+        fields.insert("model.id".to_string(), "model".to_string());
+
+        let document = build_nested_document(&collected, &fields);
+
+        let channel = document[0].as_object().unwrap();
+        assert_eq!(channel.len(), 2); // channel + tokens
+        assert_eq!(channel["channel"], "Test Channel");
+        let token = channel["tokens"][0].as_object().unwrap();
+        assert_eq!(token.len(), 2); // apiKey + models
+        assert_eq!(token["apiKey"], "sk-test");
+        let model = token["models"][1].as_object().unwrap();
+        assert_eq!(model.len(), 1);
+        assert_eq!(model["model"], "gpt-4o");
+    }
+
+    #[test]
+    fn test_build_nested_document_without_models() {
+        let (channel, token, _) = nested_fixture();
+        let collected = vec![CollectedChannel {
+            channel: &channel,
+            tokens: vec![CollectedToken {
+                token,
+                models: None,
+            }],
+        }];
+
+        let document = build_nested_document(&collected, &HashMap::new());
+
+        assert!(document[0]["tokens"][0].get("models").is_none());
+        // Flat output keeps one record per token in the same situation
+        assert_eq!(build_flat_records(&collected, &HashMap::new()).len(), 1);
+    }
+
+    #[test]
+    fn test_render_nested_document_formats() {
+        let (channel, token, models) = nested_fixture();
+        let collected = vec![CollectedChannel {
+            channel: &channel,
+            tokens: vec![CollectedToken {
+                token,
+                models: Some(models),
+            }],
+        }];
+        let mut fields = HashMap::new();
+        fields.insert("channel.name".to_string(), "name".to_string());
+        fields.insert("token.name".to_string(), "name".to_string());
+        fields.insert("model.id".to_string(), "id".to_string());
+        let document = build_nested_document(&collected, &fields);
+        let dir = tempfile::TempDir::new().unwrap();
+
+        let json_path = dir.path().join("out.json");
+        render(&document, "channels", &ExportFormat::Json, &json_path).unwrap();
+        let parsed: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(parsed[0]["tokens"][0]["models"][1]["id"], "gpt-4o");
+
+        let yaml_path = dir.path().join("out.yaml");
+        render(&document, "channels", &ExportFormat::Yaml, &yaml_path).unwrap();
+        let parsed: serde_json::Value =
+            serde_yaml::from_str(&fs::read_to_string(&yaml_path).unwrap()).unwrap();
+        assert_eq!(parsed[0]["tokens"][0]["name"], "Default Key");
+
+        let toml_path = dir.path().join("out.toml");
+        render(&document, "channels", &ExportFormat::Toml, &toml_path).unwrap();
+        let parsed: toml::Value = toml::from_str(&fs::read_to_string(&toml_path).unwrap()).unwrap();
+        assert_eq!(
+            parsed["channels"][0]["tokens"][0]["models"][0]["id"].as_str(),
+            Some("claude-sonnet-4")
+        );
+    }
+
+    #[test]
+    fn test_render_native_codex_and_env_share_variable_names() {
+        let (channel, token, models) = nested_fixture();
+        // No platform: protocols are inferred per model, giving one provider each
+        let token = ChannelToken {
+            platform: None,
+            ..token
+        };
+        let second = ChannelToken {
+            id: 2.0,
+            name: "Backup Key".to_string(),
+            key: "sk test#2".to_string(),
+            ..token.clone()
+        };
+        let collected = vec![CollectedChannel {
+            channel: &channel,
+            tokens: vec![
+                CollectedToken {
+                    token,
+                    models: Some(models.clone()),
+                },
+                CollectedToken {
+                    token: second,
+                    models: Some(models),
+                },
+            ],
+        }];
+        let mut warnings = vec![];
+
+        let (codex, count) =
+            render_native(&collected, &ExportFormat::CodexConfig, &mut warnings).unwrap();
+        assert_eq!(count, 2);
+        let parsed: toml::Value = toml::from_str(&codex).unwrap();
+        let providers = parsed["model_providers"].as_table().unwrap();
+        assert_eq!(
+            providers["test-channel-openai"]["env_key"].as_str(),
+            Some("TEST_CHANNEL_OPENAI_API_KEY")
+        );
+        assert_eq!(
+            providers["test-channel-openai-2"]["env_key"].as_str(),
+            Some("TEST_CHANNEL_OPENAI_2_API_KEY")
+        );
+        assert!(!codex.contains("sk-test"));
+
+        let (env, _) = render_native(&collected, &ExportFormat::Env, &mut warnings).unwrap();
+        assert!(env.contains("TEST_CHANNEL_OPENAI_API_KEY=sk-test\n"));
+        assert!(env.contains("TEST_CHANNEL_OPENAI_2_API_KEY='sk test#2'\n"));
+        assert!(env.contains("TEST_CHANNEL_OPENAI_BASE_URL=https://api.example.com/v1\n"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_render_native_provider_documents() {
+        let (channel, token, models) = nested_fixture();
+        // No platform: protocols are inferred per model, giving one provider each
+        let token = ChannelToken {
+            platform: None,
+            ..token
+        };
+        let collected = vec![CollectedChannel {
+            channel: &channel,
+            tokens: vec![CollectedToken {
+                token,
+                models: Some(models),
+            }],
+        }];
+        let mut warnings = vec![];
+
+        let (opencode, count) =
+            render_native(&collected, &ExportFormat::OpencodeConfig, &mut warnings).unwrap();
+        assert_eq!(count, 2);
+        let parsed: serde_json::Value = serde_json::from_str(&opencode).unwrap();
+        let provider = &parsed["provider"]["test-channel-anthropic"];
+        assert!(provider["models"]["claude-sonnet-4"].is_object());
+        assert!(provider.get("channelRef").is_none());
+        assert_eq!(
+            provider["options"]["apiKey"],
+            "{env:TEST_CHANNEL_OPENAI_API_KEY}"
+        );
+        assert!(!opencode.contains("sk-test"));
+
+        let (pi, _) = render_native(&collected, &ExportFormat::PiModels, &mut warnings).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&pi).unwrap();
+        assert_eq!(
+            parsed["providers"]["test-channel-anthropic"]["api"],
+            "anthropic-messages"
+        );
+        assert!(parsed["providers"]["test-channel-openai"]
+            .get("channelRef")
+            .is_none());
+        assert_eq!(
+            parsed["providers"]["test-channel-anthropic"]["apiKey"],
+            "TEST_CHANNEL_OPENAI_API_KEY"
+        );
+        assert!(!pi.contains("sk-test"));
+
+        let (openclaw, _) =
+            render_native(&collected, &ExportFormat::OpenclawProviders, &mut warnings).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&openclaw).unwrap();
+        assert_eq!(parsed["models"]["mode"], "merge");
+        assert_eq!(
+            parsed["models"]["providers"]["test-channel-openai"]["api"],
+            "openai-responses"
+        );
+        assert!(!openclaw.contains("sk-test"));
+
+        let (hermes, count) =
+            render_native(&collected, &ExportFormat::HermesConfig, &mut warnings).unwrap();
+        assert_eq!(count, 1);
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&hermes).unwrap();
+        assert_eq!(parsed["model"]["default"].as_str(), Some("gpt-4o"));
+        assert_eq!(parsed["model"]["provider"].as_str(), Some("custom"));
+        assert_eq!(
+            parsed["model"]["api_key"].as_str(),
+            Some("TEST_CHANNEL_OPENAI_API_KEY")
+        );
+        assert!(!hermes.contains("sk-test"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_render_native_warns_about_tokens_without_models() {
+        let (channel, token, _) = nested_fixture();
+        let collected = vec![CollectedChannel {
+            channel: &channel,
+            tokens: vec![CollectedToken {
+                token,
+                models: None,
+            }],
+        }];
+        let mut warnings = vec![];
+
+        let (content, count) =
+            render_native(&collected, &ExportFormat::PiModels, &mut warnings).unwrap();
+
+        assert_eq!(count, 0);
+        assert!(content.contains("\"providers\": {}"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("no models to export"));
+    }
+
+    #[test]
+    fn test_render_csv_escapes_values() {
+        let mut record = ExportRecord::new();
+        record.insert(
+            "name".to_string(),
+            serde_json::Value::String("Relay, \"main\"".to_string()),
+        );
+        record.insert("enabled".to_string(), serde_json::Value::Bool(true));
+        record.insert("group".to_string(), serde_json::Value::Null);
+
+        let csv = render_csv(&[record]);
+
+        assert_eq!(csv, "enabled,group,name\ntrue,,\"Relay, \"\"main\"\"\"\n");
+    }
+
+    #[test]
+    fn test_ensure_extension_for_native_formats() {
+        let mut path = PathBuf::from("/tmp/providers.yaml");
+        ensure_extension(&mut path, &ExportFormat::CodexConfig);
+        assert_eq!(path, PathBuf::from("/tmp/providers.toml"));
+
+        let mut path = PathBuf::from("/tmp/team/.env");
+        ensure_extension(&mut path, &ExportFormat::Env);
+        assert_eq!(path, PathBuf::from("/tmp/team/.env"));
+
+        let mut path = PathBuf::from("/tmp/team/relay");
+        ensure_extension(&mut path, &ExportFormat::Env);
+        assert_eq!(path, PathBuf::from("/tmp/team/relay.env"));
+    }
+
+    #[test]
+    fn test_render_text_template_loops_conditionals_and_filters() {
+        let (channel, token, models) = nested_fixture();
+        let collected = vec![CollectedChannel {
+            channel: &channel,
+            tokens: vec![CollectedToken {
+                token,
+                models: Some(models),
+            }],
+        }];
+        let document = build_nested_document(&collected, &HashMap::new());
+        let source = "\
+# {{ template }}
+{% for channel in channels %}
+{% for token in channel.tokens %}
+export {{ channel.name | replace(' ', '_') | upper }}_KEY={{ token.key | mask }}
+{% for model in token.models if model.protocol == 'anthropic' %}
+model: {{ model.id }} ({{ model.description | default('n/a') }})
+{% endfor %}
+{% endfor %}
+{% endfor %}
+";
+
+        let out = render_text_template("relay.j2", source, "team", &document).unwrap();
+
+        assert_eq!(
+            out,
+            "# team\nexport TEST_CHANNEL_KEY=****\nmodel: claude-sonnet-4 (n/a)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_text_template_errors_carry_line_numbers() {
+        let syntax =
+            render_text_template("bad.j2", "ok\n{% for x in channels %}\n", "t", &[]).unwrap_err();
+        assert!(syntax.starts_with("Template 'bad.j2' line "), "{syntax}");
+
+        let undefined =
+            render_text_template("bad.j2", "line one\n{{ nope.field }}\n", "t", &[]).unwrap_err();
+        assert!(
+            undefined.starts_with("Template 'bad.j2' line 2:"),
+            "{undefined}"
+        );
     }
 }
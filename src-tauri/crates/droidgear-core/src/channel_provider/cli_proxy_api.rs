@@ -0,0 +1,60 @@
+//! CLI Proxy API (OpenAI-compatible `/v1/models`).
+
+use reqwest::Client;
+
+use super::{lists_models_matching, BoxFuture, ChannelProvider};
+use crate::channel::ChannelType;
+
+pub struct CliProxyApiProvider;
+
+impl ChannelProvider for CliProxyApiProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::CliProxyApi
+    }
+
+    fn detect_priority(&self) -> Option<u8> {
+        Some(50)
+    }
+
+    /// `GET /v1/models` returns an OpenAI-style model list.
+    fn detect<'a>(&'a self, client: &'a Client, base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(lists_models_matching(
+            client,
+            format!("{base_url}/v1/models"),
+            |m| m.get("id").is_some(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording};
+
+    #[test]
+    fn returns_the_api_key_as_the_only_token() {
+        let tokens = block_on(async {
+            let session = CliProxyApiProvider
+                .login("http://x", "", "sk-proxy")
+                .await?;
+            CliProxyApiProvider.list_tokens("http://x", &session).await
+        })
+        .unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].key, "sk-proxy");
+        assert!(tokens[0].unlimited_quota);
+    }
+
+    #[test]
+    fn lists_openai_models_with_bearer_key() {
+        let (base, requests) =
+            serve_recording(vec![(200, r#"{"data":[{"id":"gpt-5"}]}"#.to_string())]);
+        let models = block_on(CliProxyApiProvider.list_models(&base, "sk-proxy", None)).unwrap();
+        assert_eq!(models[0].id, "gpt-5");
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /v1/models"));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("authorization: bearer sk-proxy"));
+    }
+}
@@ -0,0 +1,139 @@
+//! DeepSeek (`/models`, `/user/balance`).
+
+use reqwest::Client;
+
+use super::{
+    fetch_model_list, lenient_f64, lists_models_matching, parse_openai_models, read_json,
+    BoxFuture, ChannelProvider, ChannelSession,
+};
+use crate::channel::{ChannelQuota, ChannelType};
+use crate::factory_settings::ModelInfo;
+
+pub struct DeepSeekProvider;
+
+impl ChannelProvider for DeepSeekProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::DeepSeek
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        "https://api.deepseek.com"
+    }
+
+    fn platforms(&self) -> &'static [&'static str] {
+        &["deepseek"]
+    }
+
+    fn detect_priority(&self) -> Option<u8> {
+        Some(40)
+    }
+
+    /// `GET /models` lists models owned by `deepseek`.
+    fn detect<'a>(&'a self, client: &'a Client, base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(lists_models_matching(
+            client,
+            format!("{base_url}/models"),
+            |m| {
+                m.get("id").is_some()
+                    && m.get("owned_by").and_then(|v| v.as_str()) == Some("deepseek")
+            },
+        ))
+    }
+
+    /// DeepSeek serves models without the `/v1` prefix.
+    fn list_models<'a>(
+        &'a self,
+        base_url: &'a str,
+        api_key: &'a str,
+        _platform: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<ModelInfo>, String>> {
+        Box::pin(async move {
+            let base = base_url.trim_end_matches('/');
+            fetch_model_list(&format!("{base}/models"), api_key, parse_openai_models).await
+        })
+    }
+
+    fn quota<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Option<ChannelQuota>, String>> {
+        Box::pin(quota(base_url, session))
+    }
+}
+
+/// Balance from `GET /user/balance` (first currency listed).
+async fn quota(base_url: &str, session: &ChannelSession) -> Result<Option<ChannelQuota>, String> {
+    let base = base_url.trim_end_matches('/');
+    let response = session
+        .get(&format!("{base}/user/balance"))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch balance: {e}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("API error {status}: {body}"));
+    }
+    let data = read_json(response, "balance response").await?;
+    let Some(info) = data
+        .get("balance_infos")
+        .and_then(|v| v.as_array())
+        .and_then(|arr| arr.first())
+    else {
+        return Ok(None);
+    };
+    // Amounts are decimal strings, e.g. "110.00".
+    let remaining = info.get("total_balance").and_then(lenient_f64);
+    Ok(Some(ChannelQuota {
+        remaining,
+        used: None,
+        unlimited: false,
+        unit: info
+            .get("currency")
+            .and_then(|v| v.as_str())
+            .unwrap_or("CNY")
+            .to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording, serve_responses};
+
+    #[test]
+    fn detects_deepseek_owned_models() {
+        let client = Client::new();
+        let base = serve_responses(vec![(
+            200,
+            r#"{"data":[{"id":"deepseek-chat","owned_by":"deepseek"}]}"#.to_string(),
+        )]);
+        assert!(block_on(DeepSeekProvider.detect(&client, &base)));
+        let base = serve_responses(vec![(
+            200,
+            r#"{"data":[{"id":"gpt-4o","owned_by":"openai"}]}"#.to_string(),
+        )]);
+        assert!(!block_on(DeepSeekProvider.detect(&client, &base)));
+    }
+
+    #[test]
+    fn reads_balance_with_the_api_key() {
+        let (base, requests) = serve_recording(vec![(
+            200,
+            r#"{"is_available":true,"balance_infos":[{"currency":"CNY","total_balance":"110.50"}]}"#
+                .to_string(),
+        )]);
+        let session = ChannelSession::with_api_key("sk-ds");
+        let quota = block_on(DeepSeekProvider.quota(&base, &session))
+            .unwrap()
+            .unwrap();
+        assert_eq!(quota.remaining, Some(110.5));
+        assert_eq!(quota.unit, "CNY");
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /user/balance"));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("authorization: bearer sk-ds"));
+    }
+}
@@ -0,0 +1,29 @@
+//! Any OpenAI-compatible endpoint with an API key (never auto-detected).
+
+use super::ChannelProvider;
+use crate::channel::ChannelType;
+
+pub struct GeneralProvider;
+
+impl ChannelProvider for GeneralProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::General
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording};
+
+    #[test]
+    fn lists_gemini_models_for_gemini_tokens() {
+        let (base, requests) = serve_recording(vec![(
+            200,
+            r#"{"models":[{"name":"models/gemini-2.5-flash"}]}"#.to_string(),
+        )]);
+        let models = block_on(GeneralProvider.list_models(&base, "key", Some("gemini"))).unwrap();
+        assert_eq!(models[0].id, "gemini-2.5-flash");
+        assert!(requests.lock().unwrap()[0].starts_with("GET /v1beta/models"));
+    }
+}
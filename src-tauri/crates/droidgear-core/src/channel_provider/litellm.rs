@@ -0,0 +1,243 @@
+//! LiteLLM proxy (`/model/info`, `/key/info`).
+
+use reqwest::Client;
+use serde_json::Value;
+
+use super::{
+    fetch_model_list, lenient_f64, parse_openai_models, read_json, BoxFuture, ChannelProvider,
+    ChannelSession,
+};
+use crate::channel::{ChannelQuota, ChannelToken, ChannelType};
+use crate::factory_settings::{ModelInfo, ModelPricing};
+
+pub struct LiteLlmProvider;
+
+impl ChannelProvider for LiteLlmProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::LiteLlm
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        "http://localhost:4000"
+    }
+
+    fn detect_priority(&self) -> Option<u8> {
+        Some(15)
+    }
+
+    /// `GET /health/liveliness` answers "I'm alive!".
+    fn detect<'a>(&'a self, client: &'a Client, base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            let Ok(resp) = client
+                .get(format!("{base_url}/health/liveliness"))
+                .send()
+                .await
+            else {
+                return false;
+            };
+            if !resp.status().is_success() {
+                return false;
+            }
+            resp.text()
+                .await
+                .is_ok_and(|text| text.contains("I'm alive"))
+        })
+    }
+
+    /// Names the token after the virtual key's alias and fills in its spend.
+    fn list_tokens<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Vec<ChannelToken>, String>> {
+        Box::pin(async move {
+            let Some(api_key) = session.api_key() else {
+                return Ok(Vec::new());
+            };
+            let mut token = ChannelToken::from_api_key(api_key);
+            if let Ok(info) = key_info(base_url, session).await {
+                if let Some(alias) = info.get("key_alias").and_then(|v| v.as_str()) {
+                    token.name = alias.to_string();
+                }
+                let quota = quota_from_key_info(&info);
+                token.used_quota = quota.used.unwrap_or(0.0);
+                token.remain_quota = quota.remaining.unwrap_or(0.0);
+                token.unlimited_quota = quota.unlimited;
+            }
+            Ok(vec![token])
+        })
+    }
+
+    /// Uses `/model/info` for limits and prices, falling back to `/v1/models`
+    /// for keys that may not read it.
+    fn list_models<'a>(
+        &'a self,
+        base_url: &'a str,
+        api_key: &'a str,
+        _platform: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<ModelInfo>, String>> {
+        Box::pin(async move {
+            let base = base_url.trim_end_matches('/');
+            match fetch_model_list(&format!("{base}/model/info"), api_key, parse_model_info).await {
+                Ok(models) if !models.is_empty() => Ok(models),
+                _ => {
+                    fetch_model_list(&format!("{base}/v1/models"), api_key, parse_openai_models)
+                        .await
+                }
+            }
+        })
+    }
+
+    fn quota<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Option<ChannelQuota>, String>> {
+        Box::pin(async move {
+            let info = key_info(base_url, session).await?;
+            Ok(Some(quota_from_key_info(&info)))
+        })
+    }
+}
+
+/// `info` of `GET /key/info` for the calling key.
+async fn key_info(base_url: &str, session: &ChannelSession) -> Result<Value, String> {
+    let base = base_url.trim_end_matches('/');
+    let response = session
+        .get(&format!("{base}/key/info"))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch key info: {e}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("API error {status}: {body}"));
+    }
+    let data = read_json(response, "key info response").await?;
+    data.get("info")
+        .cloned()
+        .ok_or_else(|| "Key info response has no info".to_string())
+}
+
+fn quota_from_key_info(info: &Value) -> ChannelQuota {
+    let used = info.get("spend").and_then(lenient_f64);
+    let budget = info.get("max_budget").and_then(lenient_f64);
+    ChannelQuota {
+        remaining: budget.map(|b| b - used.unwrap_or(0.0)),
+        used,
+        unlimited: budget.is_none(),
+        unit: "USD".to_string(),
+    }
+}
+
+fn parse_model_info(data: &Value) -> Vec<ModelInfo> {
+    data.get("data")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(parse_model).collect())
+        .unwrap_or_default()
+}
+
+fn parse_model(m: &Value) -> Option<ModelInfo> {
+    let id = m.get("model_name")?.as_str()?.to_string();
+    let info = m.get("model_info").cloned().unwrap_or_default();
+    let as_u32 = |key: &str| {
+        info.get(key)
+            .and_then(|v| v.as_u64())
+            .and_then(|n| u32::try_from(n).ok())
+    };
+    let price = |key: &str| {
+        info.get(key)
+            .and_then(lenient_f64)
+            .map(ModelPricing::per_million)
+    };
+    let pricing = ModelPricing {
+        input: price("input_cost_per_token"),
+        output: price("output_cost_per_token"),
+        cache_read: price("cache_read_input_token_cost"),
+        cache_write: price("cache_creation_input_token_cost"),
+    };
+    let mut input_modalities = Vec::new();
+    if !info.is_null() {
+        input_modalities.push("text".to_string());
+        if info.get("supports_vision").and_then(|v| v.as_bool()) == Some(true) {
+            input_modalities.push("image".to_string());
+        }
+    }
+    Some(ModelInfo {
+        id,
+        name: None,
+        context_window: as_u32("max_input_tokens").or_else(|| as_u32("max_tokens")),
+        max_output_tokens: as_u32("max_output_tokens"),
+        input_modalities,
+        supports_reasoning: info.get("supports_reasoning").and_then(|v| v.as_bool()),
+        pricing: (!pricing.is_empty()).then_some(pricing),
+        created_at: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording, serve_responses};
+
+    #[test]
+    fn imports_model_info_limits_and_prices() {
+        let (base, requests) = serve_recording(vec![(
+            200,
+            r#"{"data":[{"model_name":"gpt-4o","model_info":{
+                "max_input_tokens":128000,"max_output_tokens":16384,
+                "input_cost_per_token":0.0000025,"output_cost_per_token":0.00001,
+                "supports_vision":true}}]}"#
+                .to_string(),
+        )]);
+        let models = block_on(LiteLlmProvider.list_models(&base, "sk-master", None)).unwrap();
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "gpt-4o");
+        assert_eq!(models[0].context_window, Some(128_000));
+        assert_eq!(models[0].max_output_tokens, Some(16_384));
+        assert_eq!(models[0].input_modalities, vec!["text", "image"]);
+        let pricing = models[0].pricing.as_ref().unwrap();
+        assert_eq!(pricing.input, Some(2.5));
+        assert_eq!(pricing.output, Some(10.0));
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /model/info"));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("authorization: bearer sk-master"));
+    }
+
+    #[test]
+    fn falls_back_to_openai_models_list() {
+        let (base, requests) = serve_recording(vec![
+            (403, r#"{"error":"not allowed"}"#.to_string()),
+            (200, r#"{"data":[{"id":"claude-haiku"}]}"#.to_string()),
+        ]);
+        let models = block_on(LiteLlmProvider.list_models(&base, "sk-virtual", None)).unwrap();
+        assert_eq!(models[0].id, "claude-haiku");
+        assert!(requests.lock().unwrap()[1].starts_with("GET /v1/models"));
+    }
+
+    #[test]
+    fn computes_remaining_budget_from_key_info() {
+        let base = serve_responses(vec![(
+            200,
+            r#"{"key":"sk-virtual","info":{"spend":3.5,"max_budget":20,"key_alias":"ci"}}"#
+                .to_string(),
+        )]);
+        let session = ChannelSession::with_api_key("sk-virtual");
+        let quota = block_on(LiteLlmProvider.quota(&base, &session))
+            .unwrap()
+            .unwrap();
+        assert_eq!(quota.remaining, Some(16.5));
+        assert_eq!(quota.used, Some(3.5));
+        assert!(!quota.unlimited);
+    }
+
+    #[test]
+    fn detects_liveliness_probe() {
+        let client = Client::new();
+        let base = serve_responses(vec![(200, r#""I'm alive!""#.to_string())]);
+        assert!(block_on(LiteLlmProvider.detect(&client, &base)));
+    }
+}
@@ -0,0 +1,615 @@
+//! Channel providers (core).
+//!
+//! Each relay backend (New API, Sub2API, Ollama, ...) implements
+//! [`ChannelProvider`] in its own module: how to detect it from a base URL,
+//! log in, list and manage tokens, list models and read the account quota. The
+//! functions in `channel` dispatch through [`provider_for`], so supporting a
+//! new backend means adding a `ChannelType` variant, a module here and one
+//! entry in [`PROVIDERS`].
+
+use reqwest::{Client, RequestBuilder};
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::channel::{ChannelQuota, ChannelToken, ChannelType, NewChannelToken};
+use crate::connectivity::truncate_body;
+use crate::factory_settings::{ModelInfo, ModelPricing};
+
+mod cli_proxy_api;
+mod deepseek;
+mod general;
+mod litellm;
+mod new_api;
+mod ollama;
+mod one_api;
+mod openrouter;
+mod sub2api;
+
+pub use cli_proxy_api::CliProxyApiProvider;
+pub use deepseek::DeepSeekProvider;
+pub use general::GeneralProvider;
+pub use litellm::LiteLlmProvider;
+pub use new_api::NewApiProvider;
+pub use ollama::OllamaProvider;
+pub use one_api::OneApiProvider;
+pub use openrouter::OpenRouterProvider;
+pub use sub2api::Sub2ApiProvider;
+
+/// Boxed future returned by provider methods (keeps the trait object-safe).
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Registered providers, in the order they are offered to the user.
+static PROVIDERS: &[&dyn ChannelProvider] = &[
+    &NewApiProvider,
+    &Sub2ApiProvider,
+    &CliProxyApiProvider,
+    &OllamaProvider,
+    &GeneralProvider,
+    &DeepSeekProvider,
+    &OpenRouterProvider,
+    &LiteLlmProvider,
+    &OneApiProvider,
+];
+
+// ============================================================================
+// Trait
+// ============================================================================
+
+/// A relay backend that a channel can point at.
+pub trait ChannelProvider: Send + Sync {
+    fn channel_type(&self) -> ChannelType;
+
+    /// Base URL suggested when the type is picked for a new channel
+    fn default_base_url(&self) -> &'static str {
+        ""
+    }
+
+    /// Whether the channel stores a dashboard login (username/password)
+    /// rather than a single API key
+    fn uses_login(&self) -> bool {
+        false
+    }
+
+    /// Model platforms (as reported on tokens) whose models this provider
+    /// lists, e.g. Sub2API's `antigravity` groups
+    fn platforms(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Order in which [`ChannelProvider::detect`] runs; lower probes first,
+    /// `None` never auto-detects
+    fn detect_priority(&self) -> Option<u8> {
+        None
+    }
+
+    /// Probes `base_url` (no trailing slash) for this backend.
+    fn detect<'a>(&'a self, _client: &'a Client, _base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(async { false })
+    }
+
+    /// Opens an authenticated session. API-key channels pass the key as `password`.
+    fn login<'a>(
+        &'a self,
+        _base_url: &'a str,
+        _username: &'a str,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<ChannelSession, String>> {
+        Box::pin(async move { Ok(ChannelSession::with_api_key(password)) })
+    }
+
+    /// Lists the tokens of the logged-in account. API-key channels return
+    /// their key as a single token.
+    fn list_tokens<'a>(
+        &'a self,
+        _base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Vec<ChannelToken>, String>> {
+        Box::pin(async move {
+            Ok(session
+                .api_key()
+                .map(|key| vec![ChannelToken::from_api_key(key)])
+                .unwrap_or_default())
+        })
+    }
+
+    /// Whether tokens can be created, disabled and deleted through the
+    /// dashboard API
+    fn manages_tokens(&self) -> bool {
+        false
+    }
+
+    /// Creates a token and returns it with its full key.
+    fn create_token<'a>(
+        &'a self,
+        _base_url: &'a str,
+        _session: &'a ChannelSession,
+        _token: &'a NewChannelToken,
+    ) -> BoxFuture<'a, Result<ChannelToken, String>> {
+        Box::pin(async move { Err(token_management_unsupported(self)) })
+    }
+
+    /// Enables or disables a token.
+    fn set_token_enabled<'a>(
+        &'a self,
+        _base_url: &'a str,
+        _session: &'a ChannelSession,
+        _token_id: f64,
+        _enabled: bool,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move { Err(token_management_unsupported(self)) })
+    }
+
+    /// Deletes a token.
+    fn delete_token<'a>(
+        &'a self,
+        _base_url: &'a str,
+        _session: &'a ChannelSession,
+        _token_id: f64,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move { Err(token_management_unsupported(self)) })
+    }
+
+    /// Lists the models available to `api_key`.
+    fn list_models<'a>(
+        &'a self,
+        base_url: &'a str,
+        api_key: &'a str,
+        platform: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<ModelInfo>, String>> {
+        Box::pin(fetch_platform_models(base_url, api_key, platform))
+    }
+
+    /// Reads the account balance, or `None` if the backend has no quota API.
+    fn quota<'a>(
+        &'a self,
+        _base_url: &'a str,
+        _session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Option<ChannelQuota>, String>> {
+        Box::pin(async { Ok(None) })
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// All registered providers.
+pub fn providers() -> &'static [&'static dyn ChannelProvider] {
+    PROVIDERS
+}
+
+pub fn provider_for(channel_type: &ChannelType) -> &'static dyn ChannelProvider {
+    PROVIDERS
+        .iter()
+        .copied()
+        .find(|p| &p.channel_type() == channel_type)
+        .unwrap_or(&GeneralProvider)
+}
+
+/// Provider that lists models for a token `platform` (falls back to the
+/// generic OpenAI/Gemini listing).
+pub fn provider_for_platform(platform: Option<&str>) -> &'static dyn ChannelProvider {
+    platform
+        .and_then(|platform| {
+            PROVIDERS
+                .iter()
+                .copied()
+                .find(|p| p.platforms().contains(&platform))
+        })
+        .unwrap_or(&GeneralProvider)
+}
+
+/// Providers that can be auto-detected, in probing order.
+pub fn detection_order() -> Vec<&'static dyn ChannelProvider> {
+    let mut providers: Vec<_> = PROVIDERS
+        .iter()
+        .copied()
+        .filter(|p| p.detect_priority().is_some())
+        .collect();
+    providers.sort_by_key(|p| p.detect_priority());
+    providers
+}
+
+// ============================================================================
+// Session
+// ============================================================================
+
+/// An authenticated connection to a channel's dashboard API.
+pub struct ChannelSession {
+    client: Client,
+    headers: Vec<(String, String)>,
+    api_key: Option<String>,
+}
+
+impl ChannelSession {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            headers: Vec::new(),
+            api_key: None,
+        }
+    }
+
+    /// Session that sends `api_key` as a bearer token.
+    pub fn with_api_key(api_key: &str) -> Self {
+        let mut session = Self::new(Client::new()).header("Authorization", bearer(api_key));
+        session.api_key = Some(api_key.to_string());
+        session
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// The API key this session was opened with, for API-key channels.
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.with_headers(self.client.get(url))
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.with_headers(self.client.post(url))
+    }
+
+    pub fn put(&self, url: &str) -> RequestBuilder {
+        self.with_headers(self.client.put(url))
+    }
+
+    pub fn delete(&self, url: &str) -> RequestBuilder {
+        self.with_headers(self.client.delete(url))
+    }
+
+    fn with_headers(&self, mut request: RequestBuilder) -> RequestBuilder {
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+}
+
+// ============================================================================
+// Shared helpers
+// ============================================================================
+
+fn token_management_unsupported(provider: &(impl ChannelProvider + ?Sized)) -> String {
+    format!(
+        "Channel type '{}' does not support managing tokens",
+        provider.channel_type().id()
+    )
+}
+
+/// The most recently created token called `name` (highest id), for backends
+/// whose create endpoint does not return the new key.
+pub(crate) fn newest_token_named(tokens: Vec<ChannelToken>, name: &str) -> Option<ChannelToken> {
+    tokens
+        .into_iter()
+        .filter(|t| t.name == name)
+        .max_by(|a, b| a.id.total_cmp(&b.id))
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {token}")
+}
+
+/// Parses a JSON response body, logging a truncated copy when it is not JSON.
+pub(crate) fn parse_json_body(body: &str, what: &str) -> Result<Value, String> {
+    serde_json::from_str(body).map_err(|e| {
+        let truncated = truncate_body(body);
+        log::warn!("Channel: failed to parse {what}, body={truncated}");
+        format!("Failed to parse {what}: {e}")
+    })
+}
+
+/// Reads a number that may be encoded as a decimal string (e.g. `"0.000003"`).
+pub(crate) fn lenient_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        other => other.as_f64(),
+    }
+}
+
+/// Reads and parses a JSON response body.
+pub(crate) async fn read_json(response: reqwest::Response, what: &str) -> Result<Value, String> {
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {what} body: {e}"))?;
+    parse_json_body(&body, what)
+}
+
+/// `GET url` with a bearer key and parses the model list with `parser`.
+pub(crate) async fn fetch_model_list(
+    url: &str,
+    api_key: &str,
+    parser: fn(&Value) -> Vec<ModelInfo>,
+) -> Result<Vec<ModelInfo>, String> {
+    log::debug!("Channel: fetching models from {url}");
+    let response = Client::new()
+        .get(url)
+        .header("Authorization", bearer(api_key))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        log::warn!("Channel: API error, url={url} status={status} body={body}");
+        return Err(format!("API error {status}: {body}"));
+    }
+
+    let data = read_json(response, "response").await?;
+    Ok(parser(&data))
+}
+
+/// Lists models from the OpenAI-style `/v1/models` endpoint, or Gemini's
+/// `/v1beta/models` for `gemini` tokens.
+pub(crate) async fn fetch_platform_models(
+    base_url: &str,
+    api_key: &str,
+    platform: Option<&str>,
+) -> Result<Vec<ModelInfo>, String> {
+    let base = base_url.trim_end_matches('/');
+    match platform {
+        Some("gemini") => {
+            fetch_model_list(
+                &format!("{base}/v1beta/models"),
+                api_key,
+                parse_gemini_models,
+            )
+            .await
+        }
+        _ => fetch_model_list(&format!("{base}/v1/models"), api_key, parse_openai_models).await,
+    }
+}
+
+/// Whether a `GET url` succeeds with an OpenAI-style model list matching `pred`.
+pub(crate) async fn lists_models_matching(
+    client: &Client,
+    url: String,
+    pred: impl Fn(&Value) -> bool,
+) -> bool {
+    let Ok(resp) = client.get(&url).send().await else {
+        return false;
+    };
+    if !resp.status().is_success() {
+        return false;
+    }
+    let Ok(data) = resp.json::<Value>().await else {
+        return false;
+    };
+    data.get("data")
+        .and_then(|d| d.as_array())
+        .is_some_and(|arr| arr.iter().any(pred))
+}
+
+/// Parses an OpenAI-style `{"data": [...]}` model list, keeping the metadata
+/// that aggregators add (OpenRouter's `context_length`, `pricing`,
+/// `architecture`, vLLM's `max_model_len`, ...).
+pub(crate) fn parse_openai_models(data: &Value) -> Vec<ModelInfo> {
+    data.get("data")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(parse_openai_model).collect())
+        .unwrap_or_default()
+}
+
+fn parse_openai_model(m: &Value) -> Option<ModelInfo> {
+    let id = m.get("id")?.as_str()?.to_string();
+    let price = |key: &str| {
+        m.get("pricing")
+            .and_then(|p| p.get(key))
+            .and_then(lenient_f64)
+            .map(ModelPricing::per_million)
+    };
+    let pricing = ModelPricing {
+        input: price("prompt"),
+        output: price("completion"),
+        cache_read: price("input_cache_read"),
+        cache_write: price("input_cache_write"),
+    };
+    let supported_parameters: Vec<&str> = m
+        .get("supported_parameters")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    Some(ModelInfo {
+        id,
+        name: m.get("name").and_then(|n| n.as_str()).map(String::from),
+        context_window: ["context_length", "context_window", "max_model_len"]
+            .iter()
+            .find_map(|key| m.get(*key).and_then(as_u32)),
+        max_output_tokens: m
+            .get("top_provider")
+            .and_then(|p| p.get("max_completion_tokens"))
+            .or_else(|| m.get("max_output_tokens"))
+            .and_then(as_u32),
+        input_modalities: m
+            .get("architecture")
+            .and_then(|a| a.get("input_modalities"))
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        supports_reasoning: (!supported_parameters.is_empty())
+            .then(|| supported_parameters.contains(&"reasoning")),
+        pricing: (!pricing.is_empty()).then_some(pricing),
+        created_at: None,
+    })
+}
+
+/// Parses Gemini's `{"models": [...]}` list. Models that cannot
+/// `generateContent` (embeddings, AQA) are skipped.
+pub(crate) fn parse_gemini_models(data: &Value) -> Vec<ModelInfo> {
+    data.get("models")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|m| {
+                    let methods = m
+                        .get("supportedGenerationMethods")
+                        .and_then(|v| v.as_array());
+                    if methods.is_some_and(|methods| {
+                        !methods
+                            .iter()
+                            .any(|v| v.as_str() == Some("generateContent"))
+                    }) {
+                        return None;
+                    }
+                    let raw_id = m.get("name")?.as_str()?;
+                    let id = raw_id.strip_prefix("models/").unwrap_or(raw_id).to_string();
+                    Some(ModelInfo {
+                        id,
+                        name: m
+                            .get("displayName")
+                            .and_then(|n| n.as_str())
+                            .map(String::from),
+                        context_window: m.get("inputTokenLimit").and_then(as_u32),
+                        max_output_tokens: m.get("outputTokenLimit").and_then(as_u32),
+                        supports_reasoning: m.get("thinking").and_then(|v| v.as_bool()),
+                        ..Default::default()
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn as_u32(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|n| u32::try_from(n).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json_body_handles_multibyte_bodies() {
+        // The 500th byte falls inside a three-byte character
+        let body = format!("<{}", "错".repeat(300));
+        let err = parse_json_body(&body, "models").unwrap_err();
+        assert!(err.starts_with("Failed to parse models"));
+    }
+
+    #[test]
+    fn registers_every_channel_type_once() {
+        for provider in providers() {
+            let channel_type = provider.channel_type();
+            assert_eq!(
+                provider_for(&channel_type).channel_type(),
+                channel_type,
+                "{channel_type:?}"
+            );
+            assert_eq!(
+                providers()
+                    .iter()
+                    .filter(|p| p.channel_type() == channel_type)
+                    .count(),
+                1
+            );
+            assert_eq!(
+                ChannelType::parse(&channel_type.id()),
+                Some(channel_type.clone())
+            );
+        }
+        let order: Vec<ChannelType> = detection_order().iter().map(|p| p.channel_type()).collect();
+        assert_eq!(
+            order,
+            vec![
+                ChannelType::Ollama,
+                ChannelType::LiteLlm,
+                ChannelType::Sub2Api,
+                ChannelType::OneApi,
+                ChannelType::NewApi,
+                ChannelType::OpenRouter,
+                ChannelType::DeepSeek,
+                ChannelType::CliProxyApi,
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_first_matching_provider_in_priority_order() {
+        use crate::test_support::{block_on, serve_recording};
+
+        // `/` is not Ollama, no LiteLLM liveliness probe, `/health` answers like Sub2API.
+        let (base, requests) = serve_recording(vec![
+            (404, "{}".to_string()),
+            (404, "{}".to_string()),
+            (200, r#"{"status":"ok"}"#.to_string()),
+        ]);
+        let detected = block_on(crate::channel::detect_channel_type(&format!("{base}/"))).unwrap();
+        assert_eq!(detected, ChannelType::Sub2Api);
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET / "));
+        assert!(requests[1].starts_with("GET /health/liveliness "));
+        assert!(requests[2].starts_with("GET /health "));
+    }
+
+    #[test]
+    fn routes_token_platforms_to_their_provider() {
+        assert_eq!(
+            provider_for_platform(Some("antigravity")).channel_type(),
+            ChannelType::Sub2Api
+        );
+        assert_eq!(
+            provider_for_platform(Some("deepseek")).channel_type(),
+            ChannelType::DeepSeek
+        );
+        assert_eq!(
+            provider_for_platform(Some("openai")).channel_type(),
+            ChannelType::General
+        );
+        assert_eq!(
+            provider_for_platform(None).channel_type(),
+            ChannelType::General
+        );
+    }
+
+    #[test]
+    fn parses_gemini_limits_and_skips_embedding_models() {
+        let data = serde_json::json!({
+            "models": [
+                {
+                    "name": "models/gemini-2.5-pro",
+                    "displayName": "Gemini 2.5 Pro",
+                    "inputTokenLimit": 1048576,
+                    "outputTokenLimit": 65536,
+                    "supportedGenerationMethods": ["generateContent", "countTokens"],
+                    "thinking": true
+                },
+                {
+                    "name": "models/text-embedding-004",
+                    "supportedGenerationMethods": ["embedContent"]
+                }
+            ]
+        });
+        let models = parse_gemini_models(&data);
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "gemini-2.5-pro");
+        assert_eq!(models[0].name.as_deref(), Some("Gemini 2.5 Pro"));
+        assert_eq!(models[0].context_window, Some(1_048_576));
+        assert_eq!(models[0].max_output_tokens, Some(65_536));
+        assert_eq!(models[0].supports_reasoning, Some(true));
+    }
+
+    #[test]
+    fn keeps_plain_openai_lists_unannotated() {
+        let models = parse_openai_models(&serde_json::json!({
+            "data": [{ "id": "gpt-4o", "object": "model" }, { "id": "qwen", "max_model_len": 32768 }]
+        }));
+        assert_eq!(models[0].id, "gpt-4o");
+        assert_eq!(models[0].context_window, None);
+        assert_eq!(models[0].supports_reasoning, None);
+        assert!(models[0].pricing.is_none());
+        assert_eq!(models[1].context_window, Some(32_768));
+    }
+}
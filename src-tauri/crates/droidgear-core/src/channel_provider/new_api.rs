@@ -0,0 +1,433 @@
+//! New API (`/api/user/login`, `/api/token`).
+
+use reqwest::Client;
+use serde_json::Value;
+
+use super::{newest_token_named, read_json, BoxFuture, ChannelProvider, ChannelSession};
+use crate::channel::{ChannelQuota, ChannelToken, ChannelType, NewChannelToken};
+
+/// New API's default `quota_per_unit` (quota units per USD).
+const DEFAULT_QUOTA_PER_UNIT: f64 = 500_000.0;
+
+pub struct NewApiProvider;
+
+impl ChannelProvider for NewApiProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::NewApi
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        "https://api.newapi.ai"
+    }
+
+    fn uses_login(&self) -> bool {
+        true
+    }
+
+    fn detect_priority(&self) -> Option<u8> {
+        Some(30)
+    }
+
+    /// `GET /api/status` succeeds.
+    fn detect<'a>(&'a self, client: &'a Client, base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            matches!(
+                client.get(format!("{base_url}/api/status")).send().await,
+                Ok(resp) if resp.status().is_success()
+            )
+        })
+    }
+
+    fn login<'a>(
+        &'a self,
+        base_url: &'a str,
+        username: &'a str,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<ChannelSession, String>> {
+        Box::pin(login(base_url, username, password))
+    }
+
+    fn list_tokens<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Vec<ChannelToken>, String>> {
+        Box::pin(list_tokens(base_url, session))
+    }
+
+    fn manages_tokens(&self) -> bool {
+        true
+    }
+
+    fn create_token<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token: &'a NewChannelToken,
+    ) -> BoxFuture<'a, Result<ChannelToken, String>> {
+        Box::pin(async move {
+            add_token(base_url, session, token).await?;
+            newest_token_named(list_tokens(base_url, session).await?, &token.name)
+                .ok_or_else(|| "Created token not found in token list".to_string())
+        })
+    }
+
+    fn set_token_enabled<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token_id: f64,
+        enabled: bool,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(set_token_status(base_url, session, token_id, enabled))
+    }
+
+    fn delete_token<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token_id: f64,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(delete_token(base_url, session, token_id))
+    }
+
+    fn quota<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Option<ChannelQuota>, String>> {
+        Box::pin(quota(base_url, session))
+    }
+}
+
+/// Cookie session login, shared with one-api (which ignores `New-Api-User`).
+pub(super) async fn login(
+    base_url: &str,
+    username: &str,
+    password: &str,
+) -> Result<ChannelSession, String> {
+    let client = Client::builder()
+        .cookie_store(true)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+
+    let base = base_url.trim_end_matches('/');
+    let login_response = client
+        .post(format!("{base}/api/user/login"))
+        .json(&serde_json::json!({ "username": username, "password": password }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to login: {e}"))?;
+
+    if !login_response.status().is_success() {
+        let status = login_response.status();
+        let body = login_response.text().await.unwrap_or_default();
+        return Err(format!("Login failed {status}: {body}"));
+    }
+
+    let login_data = read_json(login_response, "login response").await?;
+    if login_data.get("success").and_then(|v| v.as_bool()) != Some(true) {
+        let msg = login_data
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown error");
+        return Err(format!("Login failed: {msg}"));
+    }
+
+    let user_id = login_data
+        .get("data")
+        .and_then(|d| d.get("id"))
+        .and_then(|v| v.as_i64())
+        .ok_or("Could not get user id from login response")?;
+
+    Ok(ChannelSession::new(client).header("New-Api-User", user_id.to_string()))
+}
+
+async fn list_tokens(
+    base_url: &str,
+    session: &ChannelSession,
+) -> Result<Vec<ChannelToken>, String> {
+    let base = base_url.trim_end_matches('/');
+    let keys_url = format!("{base}/api/token");
+    let page_size: usize = 100;
+    let mut all_keys: Vec<ChannelToken> = Vec::new();
+    let mut page: usize = 1;
+
+    loop {
+        let response = session
+            .get(&keys_url)
+            .query(&[("p", page.to_string()), ("size", page_size.to_string())])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch keys: {e}"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("API error {status}: {body}"));
+        }
+
+        let data = read_json(response, "keys response").await?;
+        let items: Vec<Value> = data
+            .get("data")
+            .and_then(|d| d.get("items"))
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let count = items.len();
+
+        for t in &items {
+            let token_id = match t.get("id").and_then(|v| v.as_i64()) {
+                Some(id) => id,
+                None => continue,
+            };
+
+            // Fetch unmasked key via POST /api/token/{id}/key
+            let raw_key = match session
+                .post(&format!("{base}/api/token/{token_id}/key"))
+                .send()
+                .await
+            {
+                Ok(resp) if resp.status().is_success() => {
+                    resp.json::<Value>().await.ok().and_then(|v| {
+                        v.get("data")
+                            .and_then(|d| d.get("key"))
+                            .and_then(|k| k.as_str())
+                            .map(String::from)
+                    })
+                }
+                _ => None,
+            };
+
+            let key = match raw_key {
+                Some(k) if k.starts_with("sk-") => k,
+                Some(k) => format!("sk-{k}"),
+                None => {
+                    // Fallback to masked key from list
+                    match t.get("key").and_then(|v| v.as_str()) {
+                        Some(k) if k.starts_with("sk-") => k.to_string(),
+                        Some(k) => format!("sk-{k}"),
+                        None => continue,
+                    }
+                }
+            };
+
+            if let (Some(name), Some(status)) = (
+                t.get("name").and_then(|v| v.as_str()),
+                t.get("status").and_then(|v| v.as_i64()),
+            ) {
+                all_keys.push(ChannelToken {
+                    id: token_id as f64,
+                    name: name.to_string(),
+                    key,
+                    status: status as i32,
+                    remain_quota: t
+                        .get("remain_quota")
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(0.0),
+                    used_quota: t.get("used_quota").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                    unlimited_quota: t
+                        .get("unlimited_quota")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                    platform: None,
+                    group_name: None,
+                });
+            }
+        }
+
+        if count < page_size {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(all_keys)
+}
+
+/// Fails with the server's message unless the response reports `success`.
+async fn expect_success(response: reqwest::Response, action: &str) -> Result<(), String> {
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("API error {status}: {body}"));
+    }
+    let data = read_json(response, &format!("{action} response")).await?;
+    if data.get("success").and_then(|v| v.as_bool()) == Some(true) {
+        return Ok(());
+    }
+    let msg = data
+        .get("message")
+        .and_then(|v| v.as_str())
+        .unwrap_or("Unknown error");
+    Err(format!("Failed to {action}: {msg}"))
+}
+
+/// `POST /api/token/`; the response carries no key, so callers look the
+/// token up by name afterwards (same API in one-api).
+pub(super) async fn add_token(
+    base_url: &str,
+    session: &ChannelSession,
+    token: &NewChannelToken,
+) -> Result<(), String> {
+    let base = base_url.trim_end_matches('/');
+    let response = session
+        .post(&format!("{base}/api/token/"))
+        .json(&serde_json::json!({
+            "name": token.name,
+            "remain_quota": token.remain_quota.unwrap_or(0.0) as i64,
+            "unlimited_quota": token.remain_quota.is_none(),
+            "expired_time": -1,
+            "group": token.group.clone().unwrap_or_default(),
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to create token: {e}"))?;
+    expect_success(response, "create token").await
+}
+
+/// `PUT /api/token/?status_only=true` (1 = enabled, 2 = disabled).
+pub(super) async fn set_token_status(
+    base_url: &str,
+    session: &ChannelSession,
+    token_id: f64,
+    enabled: bool,
+) -> Result<(), String> {
+    let base = base_url.trim_end_matches('/');
+    let response = session
+        .put(&format!("{base}/api/token/?status_only=true"))
+        .json(&serde_json::json!({
+            "id": token_id as i64,
+            "status": if enabled { 1 } else { 2 },
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to update token: {e}"))?;
+    expect_success(response, "update token").await
+}
+
+/// `DELETE /api/token/{id}`.
+pub(super) async fn delete_token(
+    base_url: &str,
+    session: &ChannelSession,
+    token_id: f64,
+) -> Result<(), String> {
+    let base = base_url.trim_end_matches('/');
+    let response = session
+        .delete(&format!("{base}/api/token/{}", token_id as i64))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to delete token: {e}"))?;
+    expect_success(response, "delete token").await
+}
+
+/// Account quota from `GET /api/user/self`, converted to USD with the
+/// site's `quota_per_unit` from `/api/status` (same API in one-api).
+pub(super) async fn quota(
+    base_url: &str,
+    session: &ChannelSession,
+) -> Result<Option<ChannelQuota>, String> {
+    let base = base_url.trim_end_matches('/');
+    let response = session
+        .get(&format!("{base}/api/user/self"))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch user info: {e}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("API error {status}: {body}"));
+    }
+    let data = read_json(response, "user response").await?;
+    let Some(user) = data.get("data") else {
+        return Ok(None);
+    };
+
+    let quota_per_unit = match session.get(&format!("{base}/api/status")).send().await {
+        Ok(resp) if resp.status().is_success() => resp
+            .json::<Value>()
+            .await
+            .ok()
+            .and_then(|v| v.get("data")?.get("quota_per_unit")?.as_f64())
+            .filter(|v| *v > 0.0),
+        _ => None,
+    }
+    .unwrap_or(DEFAULT_QUOTA_PER_UNIT);
+
+    let amount = |key: &str| {
+        user.get(key)
+            .and_then(|v| v.as_f64())
+            .map(|v| v / quota_per_unit)
+    };
+    Ok(Some(ChannelQuota {
+        remaining: amount("quota"),
+        used: amount("used_quota"),
+        unlimited: false,
+        unit: "USD".to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording};
+
+    #[test]
+    fn logs_in_and_lists_unmasked_tokens() {
+        let (base, requests) = serve_recording(vec![
+            (200, r#"{"success":true,"data":{"id":7}}"#.to_string()),
+            (
+                200,
+                r#"{"data":{"items":[{"id":3,"name":"main","key":"abc***","status":1,"remain_quota":10,"used_quota":5,"unlimited_quota":false}]}}"#
+                    .to_string(),
+            ),
+            (200, r#"{"data":{"key":"abcdef"}}"#.to_string()),
+        ]);
+
+        let tokens = block_on(async {
+            let session = NewApiProvider.login(&base, "alice", "pw").await?;
+            NewApiProvider.list_tokens(&base, &session).await
+        })
+        .unwrap();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].key, "sk-abcdef");
+        assert_eq!(tokens[0].remain_quota, 10.0);
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("POST /api/user/login"));
+        assert!(requests[1].starts_with("GET /api/token?p=1&size=100"));
+        assert!(requests[1].to_lowercase().contains("new-api-user: 7"));
+        assert!(requests[2].starts_with("POST /api/token/3/key"));
+    }
+
+    #[test]
+    fn reports_login_failure_message() {
+        let base = crate::test_support::serve_responses(vec![(
+            200,
+            r#"{"success":false,"message":"bad password"}"#.to_string(),
+        )]);
+        let err = match block_on(NewApiProvider.login(&base, "alice", "pw")) {
+            Ok(_) => panic!("login should fail"),
+            Err(e) => e,
+        };
+        assert_eq!(err, "Login failed: bad password");
+    }
+
+    #[test]
+    fn converts_quota_to_usd() {
+        let base = crate::test_support::serve_responses(vec![
+            (
+                200,
+                r#"{"data":{"quota":1000000,"used_quota":250000}}"#.to_string(),
+            ),
+            (200, r#"{"data":{"quota_per_unit":500000}}"#.to_string()),
+        ]);
+        let quota = block_on(NewApiProvider.quota(&base, &ChannelSession::new(Client::new())))
+            .unwrap()
+            .unwrap();
+        assert_eq!(quota.remaining, Some(2.0));
+        assert_eq!(quota.used, Some(0.5));
+        assert_eq!(quota.unit, "USD");
+    }
+}
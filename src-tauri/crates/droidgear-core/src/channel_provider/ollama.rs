@@ -0,0 +1,52 @@
+//! Ollama (local OpenAI-compatible server).
+
+use reqwest::Client;
+
+use super::{BoxFuture, ChannelProvider};
+use crate::channel::ChannelType;
+
+pub struct OllamaProvider;
+
+impl ChannelProvider for OllamaProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::Ollama
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        "http://localhost:11434"
+    }
+
+    fn detect_priority(&self) -> Option<u8> {
+        Some(10)
+    }
+
+    /// `GET /` answers "Ollama is running".
+    fn detect<'a>(&'a self, client: &'a Client, base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            let Ok(resp) = client.get(base_url).send().await else {
+                return false;
+            };
+            if !resp.status().is_success() {
+                return false;
+            }
+            resp.text()
+                .await
+                .is_ok_and(|text| text.contains("Ollama") || text.contains("ollama"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_responses};
+
+    #[test]
+    fn detects_root_banner() {
+        let client = Client::new();
+        let base = serve_responses(vec![(200, "Ollama is running".to_string())]);
+        assert!(block_on(OllamaProvider.detect(&client, &base)));
+        let base = serve_responses(vec![(200, "<html>relay</html>".to_string())]);
+        assert!(!block_on(OllamaProvider.detect(&client, &base)));
+    }
+}
@@ -0,0 +1,239 @@
+//! one-api (`/api/user/login`, `/api/token/`), the project New API forked from.
+
+use reqwest::Client;
+use serde_json::Value;
+
+use super::{new_api, newest_token_named, read_json, BoxFuture, ChannelProvider, ChannelSession};
+use crate::channel::{ChannelQuota, ChannelToken, ChannelType, NewChannelToken};
+
+/// one-api's fixed `ItemsPerPage`.
+const PAGE_SIZE: usize = 10;
+/// Upper bound on pages fetched, in case a server ignores `p`.
+const MAX_PAGES: usize = 100;
+/// `/api/status` keys only New API reports.
+const NEW_API_STATUS_KEYS: &[&str] = &["setup", "self_use_mode_enabled", "data_export_enabled"];
+
+pub struct OneApiProvider;
+
+impl ChannelProvider for OneApiProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::OneApi
+    }
+
+    fn uses_login(&self) -> bool {
+        true
+    }
+
+    /// Runs before New API, whose `/api/status` is a superset of one-api's.
+    fn detect_priority(&self) -> Option<u8> {
+        Some(25)
+    }
+
+    /// `GET /api/status` succeeds without New API's extra keys.
+    fn detect<'a>(&'a self, client: &'a Client, base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            let Ok(resp) = client.get(format!("{base_url}/api/status")).send().await else {
+                return false;
+            };
+            if !resp.status().is_success() {
+                return false;
+            }
+            let Ok(data) = resp.json::<Value>().await else {
+                return false;
+            };
+            data.get("data")
+                .and_then(|d| d.as_object())
+                .is_some_and(|status| {
+                    status.contains_key("system_name")
+                        && !NEW_API_STATUS_KEYS.iter().any(|k| status.contains_key(*k))
+                })
+        })
+    }
+
+    fn login<'a>(
+        &'a self,
+        base_url: &'a str,
+        username: &'a str,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<ChannelSession, String>> {
+        Box::pin(new_api::login(base_url, username, password))
+    }
+
+    fn list_tokens<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Vec<ChannelToken>, String>> {
+        Box::pin(list_tokens(base_url, session))
+    }
+
+    fn manages_tokens(&self) -> bool {
+        true
+    }
+
+    fn create_token<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token: &'a NewChannelToken,
+    ) -> BoxFuture<'a, Result<ChannelToken, String>> {
+        Box::pin(async move {
+            new_api::add_token(base_url, session, token).await?;
+            newest_token_named(list_tokens(base_url, session).await?, &token.name)
+                .ok_or_else(|| "Created token not found in token list".to_string())
+        })
+    }
+
+    fn set_token_enabled<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token_id: f64,
+        enabled: bool,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(new_api::set_token_status(
+            base_url, session, token_id, enabled,
+        ))
+    }
+
+    fn delete_token<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token_id: f64,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(new_api::delete_token(base_url, session, token_id))
+    }
+
+    fn quota<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Option<ChannelQuota>, String>> {
+        Box::pin(new_api::quota(base_url, session))
+    }
+}
+
+/// Lists tokens from `GET /api/token/?p=N` (0-based pages, unmasked keys).
+async fn list_tokens(
+    base_url: &str,
+    session: &ChannelSession,
+) -> Result<Vec<ChannelToken>, String> {
+    let base = base_url.trim_end_matches('/');
+    let keys_url = format!("{base}/api/token/");
+    let mut tokens = Vec::new();
+
+    for page in 0..MAX_PAGES {
+        let response = session
+            .get(&keys_url)
+            .query(&[("p", page.to_string())])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch keys: {e}"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("API error {status}: {body}"));
+        }
+
+        let data = read_json(response, "keys response").await?;
+        if data.get("success").and_then(|v| v.as_bool()) == Some(false) {
+            let msg = data
+                .get("message")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown error");
+            return Err(format!("Failed to fetch keys: {msg}"));
+        }
+        let items: Vec<Value> = data
+            .get("data")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        tokens.extend(items.iter().filter_map(|t| {
+            let key = t.get("key")?.as_str()?;
+            Some(ChannelToken {
+                id: t.get("id")?.as_f64()?,
+                name: t.get("name")?.as_str()?.to_string(),
+                key: if key.starts_with("sk-") {
+                    key.to_string()
+                } else {
+                    format!("sk-{key}")
+                },
+                status: t.get("status").and_then(|v| v.as_i64()).unwrap_or(1) as i32,
+                remain_quota: t
+                    .get("remain_quota")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0),
+                used_quota: t.get("used_quota").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                unlimited_quota: t
+                    .get("unlimited_quota")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+                platform: None,
+                group_name: None,
+            })
+        }));
+
+        if items.len() < PAGE_SIZE {
+            break;
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording, serve_responses};
+
+    #[test]
+    fn detects_one_api_but_not_new_api_status() {
+        let client = Client::new();
+        let base = serve_responses(vec![(
+            200,
+            r#"{"success":true,"data":{"system_name":"One API","version":"v0.6"}}"#.to_string(),
+        )]);
+        assert!(block_on(OneApiProvider.detect(&client, &base)));
+        let base = serve_responses(vec![(
+            200,
+            r#"{"success":true,"data":{"system_name":"New API","setup":true}}"#.to_string(),
+        )]);
+        assert!(!block_on(OneApiProvider.detect(&client, &base)));
+    }
+
+    #[test]
+    fn pages_tokens_from_zero_and_prefixes_keys() {
+        let page: Vec<String> = (0..PAGE_SIZE)
+            .map(|i| format!(r#"{{"id":{i},"name":"t{i}","key":"k{i}","status":1}}"#))
+            .collect();
+        let (base, requests) = serve_recording(vec![
+            (200, r#"{"success":true,"data":{"id":1}}"#.to_string()),
+            (
+                200,
+                format!(r#"{{"success":true,"data":[{}]}}"#, page.join(",")),
+            ),
+            (
+                200,
+                r#"{"success":true,"data":[{"id":10,"name":"last","key":"sk-last","unlimited_quota":true}]}"#
+                    .to_string(),
+            ),
+        ]);
+
+        let tokens = block_on(async {
+            let session = OneApiProvider.login(&base, "root", "pw").await?;
+            OneApiProvider.list_tokens(&base, &session).await
+        })
+        .unwrap();
+
+        assert_eq!(tokens.len(), PAGE_SIZE + 1);
+        assert_eq!(tokens[0].key, "sk-k0");
+        assert_eq!(tokens[PAGE_SIZE].key, "sk-last");
+        assert!(tokens[PAGE_SIZE].unlimited_quota);
+        let requests = requests.lock().unwrap();
+        assert!(requests[1].starts_with("GET /api/token/?p=0"));
+        assert!(requests[2].starts_with("GET /api/token/?p=1"));
+    }
+}
@@ -0,0 +1,172 @@
+//! OpenRouter (`/api/v1/models` with pricing, `/api/v1/key` credits).
+//!
+//! The channel base URL is `https://openrouter.ai/api`, so the OpenAI-compatible
+//! endpoint is `{base}/v1` like every other channel and the model list (with
+//! context, pricing and modalities) is read by the shared OpenAI-style parser.
+
+use reqwest::Client;
+use serde_json::Value;
+
+use super::{
+    lenient_f64, lists_models_matching, read_json, BoxFuture, ChannelProvider, ChannelSession,
+};
+use crate::channel::{ChannelQuota, ChannelToken, ChannelType};
+
+pub struct OpenRouterProvider;
+
+impl ChannelProvider for OpenRouterProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::OpenRouter
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        "https://openrouter.ai/api"
+    }
+
+    fn detect_priority(&self) -> Option<u8> {
+        Some(35)
+    }
+
+    /// `GET /v1/models` lists models with `context_length` and `pricing`.
+    fn detect<'a>(&'a self, client: &'a Client, base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(lists_models_matching(
+            client,
+            format!("{base_url}/v1/models"),
+            |m| m.get("context_length").is_some() && m.get("pricing").is_some(),
+        ))
+    }
+
+    /// Names the token after the key's label and fills in its credit usage.
+    fn list_tokens<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Vec<ChannelToken>, String>> {
+        Box::pin(async move {
+            let Some(api_key) = session.api_key() else {
+                return Ok(Vec::new());
+            };
+            let mut token = ChannelToken::from_api_key(api_key);
+            if let Ok(info) = key_info(base_url, session).await {
+                if let Some(label) = info.get("label").and_then(|v| v.as_str()) {
+                    token.name = label.to_string();
+                }
+                let quota = quota_from_key_info(&info);
+                token.used_quota = quota.used.unwrap_or(0.0);
+                token.remain_quota = quota.remaining.unwrap_or(0.0);
+                token.unlimited_quota = quota.unlimited;
+            }
+            Ok(vec![token])
+        })
+    }
+
+    fn quota<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Option<ChannelQuota>, String>> {
+        Box::pin(async move {
+            let info = key_info(base_url, session).await?;
+            Ok(Some(quota_from_key_info(&info)))
+        })
+    }
+}
+
+/// `data` of `GET /v1/key` (label, usage and limit of the calling key).
+async fn key_info(base_url: &str, session: &ChannelSession) -> Result<Value, String> {
+    let base = base_url.trim_end_matches('/');
+    let response = session
+        .get(&format!("{base}/v1/key"))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch key info: {e}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("API error {status}: {body}"));
+    }
+    let data = read_json(response, "key response").await?;
+    data.get("data")
+        .cloned()
+        .ok_or_else(|| "Key response has no data".to_string())
+}
+
+fn quota_from_key_info(info: &Value) -> ChannelQuota {
+    let used = info.get("usage").and_then(lenient_f64);
+    let limit = info.get("limit").and_then(lenient_f64);
+    let remaining = info
+        .get("limit_remaining")
+        .and_then(lenient_f64)
+        .or_else(|| Some(limit? - used.unwrap_or(0.0)));
+    ChannelQuota {
+        remaining,
+        used,
+        unlimited: limit.is_none(),
+        unit: "USD".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording, serve_responses};
+
+    const MODELS: &str = r#"{"data":[{
+        "id": "anthropic/claude-sonnet-4",
+        "name": "Anthropic: Claude Sonnet 4",
+        "context_length": 200000,
+        "architecture": {"input_modalities": ["text", "image"]},
+        "pricing": {"prompt": "0.000003", "completion": "0.000015", "input_cache_read": "0.0000003"},
+        "supported_parameters": ["tools", "reasoning"],
+        "top_provider": {"max_completion_tokens": 64000}
+    }]}"#;
+
+    #[test]
+    fn imports_context_pricing_and_modalities() {
+        let (base, requests) = serve_recording(vec![(200, MODELS.to_string())]);
+        let models = block_on(OpenRouterProvider.list_models(&base, "sk-or", None)).unwrap();
+
+        let model = &models[0];
+        assert_eq!(model.id, "anthropic/claude-sonnet-4");
+        assert_eq!(model.context_window, Some(200_000));
+        assert_eq!(model.max_output_tokens, Some(64_000));
+        assert_eq!(model.input_modalities, vec!["text", "image"]);
+        assert_eq!(model.supports_reasoning, Some(true));
+        let pricing = model.pricing.as_ref().unwrap();
+        assert_eq!(pricing.input, Some(3.0));
+        assert_eq!(pricing.output, Some(15.0));
+        assert_eq!(pricing.cache_read, Some(0.3));
+        assert_eq!(pricing.cache_write, None);
+        assert!(requests.lock().unwrap()[0].starts_with("GET /v1/models"));
+    }
+
+    #[test]
+    fn detects_models_with_pricing() {
+        let client = Client::new();
+        let base = serve_responses(vec![(200, MODELS.to_string())]);
+        assert!(block_on(OpenRouterProvider.detect(&client, &base)));
+    }
+
+    #[test]
+    fn reads_key_credits() {
+        let body = r#"{"data":{"label":"team","usage":2.5,"limit":10,"limit_remaining":7.5}}"#;
+        let (base, requests) = serve_recording(vec![
+            (200, body.to_string()),
+            (200, r#"{"data":{"usage":1,"limit":null}}"#.to_string()),
+        ]);
+        let session = ChannelSession::with_api_key("sk-or");
+        let tokens = block_on(OpenRouterProvider.list_tokens(&base, &session)).unwrap();
+        assert_eq!(tokens[0].name, "team");
+        assert_eq!(tokens[0].key, "sk-or");
+        assert_eq!(tokens[0].remain_quota, 7.5);
+        assert!(!tokens[0].unlimited_quota);
+
+        let quota = block_on(OpenRouterProvider.quota(&base, &session))
+            .unwrap()
+            .unwrap();
+        assert!(quota.unlimited);
+        assert_eq!(quota.used, Some(1.0));
+        assert_eq!(quota.remaining, None);
+        assert!(requests.lock().unwrap()[0].starts_with("GET /v1/key"));
+    }
+}
@@ -0,0 +1,530 @@
+//! Sub2API (`/api/v1/auth/login`, `/api/v1/keys`).
+
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::{
+    fetch_model_list, fetch_platform_models, parse_openai_models, read_json, BoxFuture,
+    ChannelProvider, ChannelSession,
+};
+use crate::channel::{ChannelQuota, ChannelToken, ChannelType, NewChannelToken};
+use crate::factory_settings::ModelInfo;
+
+pub struct Sub2ApiProvider;
+
+impl ChannelProvider for Sub2ApiProvider {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::Sub2Api
+    }
+
+    fn uses_login(&self) -> bool {
+        true
+    }
+
+    /// Antigravity groups serve Claude models under `/antigravity/v1`.
+    fn platforms(&self) -> &'static [&'static str] {
+        &["antigravity"]
+    }
+
+    fn detect_priority(&self) -> Option<u8> {
+        Some(20)
+    }
+
+    /// `GET /health` returns `{"status":"ok"}`.
+    fn detect<'a>(&'a self, client: &'a Client, base_url: &'a str) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            let Ok(resp) = client.get(format!("{base_url}/health")).send().await else {
+                return false;
+            };
+            if !resp.status().is_success() {
+                return false;
+            }
+            resp.json::<Value>()
+                .await
+                .is_ok_and(|data| data.get("status").and_then(|s| s.as_str()) == Some("ok"))
+        })
+    }
+
+    fn login<'a>(
+        &'a self,
+        base_url: &'a str,
+        email: &'a str,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<ChannelSession, String>> {
+        Box::pin(login(base_url, email, password))
+    }
+
+    fn list_tokens<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Vec<ChannelToken>, String>> {
+        Box::pin(list_tokens(base_url, session))
+    }
+
+    fn list_models<'a>(
+        &'a self,
+        base_url: &'a str,
+        api_key: &'a str,
+        platform: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<ModelInfo>, String>> {
+        Box::pin(async move {
+            if platform == Some("antigravity") {
+                let base = base_url.trim_end_matches('/');
+                let url = format!("{base}/antigravity/v1/models");
+                return fetch_model_list(&url, api_key, parse_openai_models).await;
+            }
+            fetch_platform_models(base_url, api_key, platform).await
+        })
+    }
+
+    fn manages_tokens(&self) -> bool {
+        true
+    }
+
+    fn create_token<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token: &'a NewChannelToken,
+    ) -> BoxFuture<'a, Result<ChannelToken, String>> {
+        Box::pin(create_token(base_url, session, token))
+    }
+
+    fn set_token_enabled<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token_id: f64,
+        enabled: bool,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let base = base_url.trim_end_matches('/');
+            let status = if enabled { "active" } else { "inactive" };
+            let response = session
+                .put(&format!("{base}/api/v1/keys/{}", token_id as i64))
+                .json(&serde_json::json!({ "status": status }))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to update key: {e}"))?;
+            expect_ok(response, "update key").await.map(|_| ())
+        })
+    }
+
+    fn delete_token<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+        token_id: f64,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let base = base_url.trim_end_matches('/');
+            let response = session
+                .delete(&format!("{base}/api/v1/keys/{}", token_id as i64))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to delete key: {e}"))?;
+            expect_ok(response, "delete key").await.map(|_| ())
+        })
+    }
+
+    fn quota<'a>(
+        &'a self,
+        base_url: &'a str,
+        session: &'a ChannelSession,
+    ) -> BoxFuture<'a, Result<Option<ChannelQuota>, String>> {
+        Box::pin(quota(base_url, session))
+    }
+}
+
+async fn login(base_url: &str, email: &str, password: &str) -> Result<ChannelSession, String> {
+    let client = Client::new();
+    let base = base_url.trim_end_matches('/');
+
+    let login_response = client
+        .post(format!("{base}/api/v1/auth/login"))
+        .json(&serde_json::json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to login: {e}"))?;
+
+    if !login_response.status().is_success() {
+        let status = login_response.status();
+        let body = login_response.text().await.unwrap_or_default();
+        return Err(format!("Login failed {status}: {body}"));
+    }
+
+    let login_data = read_json(login_response, "login response").await?;
+    if login_data.get("code").and_then(|v| v.as_i64()) != Some(0) {
+        let msg = login_data
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown error");
+        return Err(format!("Login failed: {msg}"));
+    }
+
+    let access_token = login_data
+        .get("data")
+        .and_then(|d| d.get("access_token"))
+        .and_then(|t| t.as_str())
+        .ok_or("Could not get access_token from login response")?;
+
+    Ok(ChannelSession::new(client).header("Authorization", format!("Bearer {access_token}")))
+}
+
+/// Available groups by id, as `(platform, name)`.
+async fn fetch_groups(
+    base: &str,
+    session: &ChannelSession,
+) -> Result<HashMap<i64, (String, String)>, String> {
+    let groups_response = session
+        .get(&format!("{base}/api/v1/groups/available"))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch groups: {e}"))?;
+
+    if !groups_response.status().is_success() {
+        return Ok(HashMap::new());
+    }
+    let groups_data: Value = groups_response.json().await.unwrap_or_default();
+    Ok(groups_data
+        .get("data")
+        .and_then(|d| d.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|g| {
+                    let id = g.get("id")?.as_i64()?;
+                    let platform = g.get("platform")?.as_str()?.to_string();
+                    let name = g.get("name")?.as_str()?.to_string();
+                    Some((id, (platform, name)))
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Returns `data` of a `{"code": 0, "data": ...}` response, or the server's message.
+async fn expect_ok(response: reqwest::Response, action: &str) -> Result<Value, String> {
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("API error {status}: {body}"));
+    }
+    let data = read_json(response, &format!("{action} response")).await?;
+    if data.get("code").and_then(|v| v.as_i64()) != Some(0) {
+        let msg = data
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown error");
+        return Err(format!("Failed to {action}: {msg}"));
+    }
+    Ok(data.get("data").cloned().unwrap_or(Value::Null))
+}
+
+/// `POST /api/v1/keys` in the group named by `token.group` (id or name) or
+/// the first group of `token.platform`.
+async fn create_token(
+    base_url: &str,
+    session: &ChannelSession,
+    token: &NewChannelToken,
+) -> Result<ChannelToken, String> {
+    let base = base_url.trim_end_matches('/');
+    let groups = fetch_groups(base, session).await?;
+    let mut sorted: Vec<(i64, &(String, String))> = groups.iter().map(|(id, g)| (*id, g)).collect();
+    sorted.sort_by_key(|(id, _)| *id);
+    let find = |pred: &dyn Fn(&(String, String)) -> bool| {
+        sorted.iter().find(|(_, g)| pred(g)).map(|(id, _)| *id)
+    };
+    let group_id = match (&token.group, &token.platform) {
+        (Some(group), _) => Some(
+            group
+                .parse::<i64>()
+                .ok()
+                .or_else(|| find(&|(_, name)| name == group))
+                .ok_or_else(|| format!("Group '{group}' not found"))?,
+        ),
+        (None, Some(platform)) => Some(
+            find(&|(p, _)| p == platform)
+                .ok_or_else(|| format!("No group available for platform '{platform}'"))?,
+        ),
+        (None, None) => None,
+    };
+
+    let mut body = serde_json::json!({ "name": token.name });
+    if let Some(id) = group_id {
+        body["group_id"] = Value::from(id);
+    }
+    let response = session
+        .post(&format!("{base}/api/v1/keys"))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to create key: {e}"))?;
+    let key = expect_ok(response, "create key").await?;
+
+    let (platform, group_name) = group_id
+        .and_then(|id| groups.get(&id).cloned())
+        .map(|(platform, name)| (Some(platform), Some(name)))
+        .unwrap_or((None, None));
+    Ok(ChannelToken {
+        id: key
+            .get("id")
+            .and_then(|v| v.as_f64())
+            .ok_or("Created key has no id")?,
+        name: token.name.clone(),
+        key: key
+            .get("key")
+            .and_then(|v| v.as_str())
+            .ok_or("Created key has no key")?
+            .to_string(),
+        status: 1,
+        remain_quota: 0.0,
+        used_quota: 0.0,
+        unlimited_quota: true,
+        platform,
+        group_name,
+    })
+}
+
+async fn list_tokens(
+    base_url: &str,
+    session: &ChannelSession,
+) -> Result<Vec<ChannelToken>, String> {
+    let base = base_url.trim_end_matches('/');
+
+    // Fetch groups for platform info
+    let group_info = fetch_groups(base, session).await?;
+
+    // Fetch keys list with pagination
+    let keys_url = format!("{base}/api/v1/keys");
+    let page_size: usize = 100;
+    let mut all_items: Vec<Value> = Vec::new();
+    let mut page: usize = 1;
+
+    loop {
+        let keys_response = session
+            .get(&keys_url)
+            .query(&[
+                ("page", page.to_string()),
+                ("page_size", page_size.to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch keys: {e}"))?;
+
+        if !keys_response.status().is_success() {
+            let status = keys_response.status();
+            let body = keys_response.text().await.unwrap_or_default();
+            return Err(format!("API error {status}: {body}"));
+        }
+
+        let keys_data = read_json(keys_response, "keys response").await?;
+        let items: Vec<Value> = keys_data
+            .get("data")
+            .and_then(|d| d.get("items"))
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let count = items.len();
+        all_items.extend(items);
+
+        if count < page_size {
+            break;
+        }
+        page += 1;
+    }
+
+    // Fetch usage (optional)
+    let usage_data: Option<Value> = match session
+        .get(&format!("{base}/api/v1/keys/usage"))
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => resp.json::<Value>().await.ok(),
+        _ => None,
+    };
+
+    let usage_map: HashMap<i64, Value> = usage_data
+        .as_ref()
+        .and_then(|v| v.get("data"))
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|u| Some((u.get("key_id")?.as_i64()?, u.clone())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let tokens: Vec<ChannelToken> = all_items
+        .iter()
+        .filter_map(|k| {
+            let id = k.get("id")?.as_f64()?;
+            let status = k.get("status").and_then(|v| v.as_i64()).unwrap_or(1) as i32;
+
+            let usage = k
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .and_then(|key_id| usage_map.get(&key_id));
+
+            let (platform, group_name) = k
+                .get("group")
+                .map(|g| {
+                    let platform = g.get("platform").and_then(|p| p.as_str()).map(String::from);
+                    let name = g.get("name").and_then(|n| n.as_str()).map(String::from);
+                    (platform, name)
+                })
+                .unwrap_or_else(|| {
+                    k.get("group_id")
+                        .and_then(|g| g.as_i64())
+                        .and_then(|group_id| group_info.get(&group_id).cloned())
+                        .map(|(platform, name)| (Some(platform), Some(name)))
+                        .unwrap_or((None, None))
+                });
+
+            Some(ChannelToken {
+                id,
+                name: k.get("name")?.as_str()?.to_string(),
+                key: k.get("key")?.as_str()?.to_string(),
+                status,
+                remain_quota: 0.0,
+                used_quota: usage
+                    .and_then(|u| u.get("total_actual_cost"))
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0),
+                unlimited_quota: true,
+                platform,
+                group_name,
+            })
+        })
+        .collect();
+
+    Ok(tokens)
+}
+
+/// Account balance (USD) from `GET /api/v1/auth/me`.
+async fn quota(base_url: &str, session: &ChannelSession) -> Result<Option<ChannelQuota>, String> {
+    let base = base_url.trim_end_matches('/');
+    let response = session
+        .get(&format!("{base}/api/v1/auth/me"))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch user info: {e}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("API error {status}: {body}"));
+    }
+    let data = read_json(response, "user response").await?;
+    let Some(balance) = data
+        .get("data")
+        .and_then(|d| d.get("balance"))
+        .and_then(|v| v.as_f64())
+    else {
+        return Ok(None);
+    };
+    Ok(Some(ChannelQuota {
+        remaining: Some(balance),
+        used: None,
+        unlimited: false,
+        unit: "USD".to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording, serve_responses};
+
+    #[test]
+    fn lists_tokens_with_group_platforms_and_usage() {
+        let (base, requests) = serve_recording(vec![
+            (
+                200,
+                r#"{"code":0,"data":{"access_token":"jwt-1"}}"#.to_string(),
+            ),
+            (
+                200,
+                r#"{"data":[{"id":2,"platform":"antigravity","name":"AG"}]}"#.to_string(),
+            ),
+            (
+                200,
+                r#"{"data":{"items":[{"id":5,"name":"k","key":"sk-5","group_id":2}]}}"#.to_string(),
+            ),
+            (
+                200,
+                r#"{"data":[{"key_id":5,"total_actual_cost":1.25}]}"#.to_string(),
+            ),
+        ]);
+
+        let tokens = block_on(async {
+            let session = Sub2ApiProvider.login(&base, "a@b.c", "pw").await?;
+            Sub2ApiProvider.list_tokens(&base, &session).await
+        })
+        .unwrap();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].platform.as_deref(), Some("antigravity"));
+        assert_eq!(tokens[0].group_name.as_deref(), Some("AG"));
+        assert_eq!(tokens[0].used_quota, 1.25);
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("POST /api/v1/auth/login"));
+        assert!(requests[2]
+            .to_lowercase()
+            .contains("authorization: bearer jwt-1"));
+    }
+
+    #[test]
+    fn lists_antigravity_models_under_their_prefix() {
+        let (base, requests) = serve_recording(vec![(
+            200,
+            r#"{"data":[{"id":"claude-opus"}]}"#.to_string(),
+        )]);
+        let models =
+            block_on(Sub2ApiProvider.list_models(&base, "sk-1", Some("antigravity"))).unwrap();
+        assert_eq!(models[0].id, "claude-opus");
+        assert!(requests.lock().unwrap()[0].starts_with("GET /antigravity/v1/models"));
+    }
+
+    #[test]
+    fn detects_health_endpoint() {
+        let base = serve_responses(vec![(200, r#"{"status":"ok"}"#.to_string())]);
+        let client = Client::new();
+        assert!(block_on(Sub2ApiProvider.detect(&client, &base)));
+    }
+
+    #[test]
+    fn creates_keys_in_the_first_group_of_a_platform() {
+        let (base, requests) = serve_recording(vec![
+            (
+                200,
+                r#"{"data":[{"id":4,"platform":"anthropic","name":"CC"},{"id":2,"platform":"antigravity","name":"AG"}]}"#
+                    .to_string(),
+            ),
+            (
+                200,
+                r#"{"code":0,"data":{"id":9,"name":"ci","key":"sk-9"}}"#.to_string(),
+            ),
+            (200, r#"{"code":0,"message":"success"}"#.to_string()),
+        ]);
+        let session = ChannelSession::new(Client::new());
+        let spec = NewChannelToken {
+            name: "ci".to_string(),
+            platform: Some("antigravity".to_string()),
+            ..Default::default()
+        };
+
+        let token = block_on(Sub2ApiProvider.create_token(&base, &session, &spec)).unwrap();
+        assert_eq!(token.id, 9.0);
+        assert_eq!(token.key, "sk-9");
+        assert_eq!(token.group_name.as_deref(), Some("AG"));
+        block_on(Sub2ApiProvider.set_token_enabled(&base, &session, 9.0, false)).unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[1].starts_with("POST /api/v1/keys"));
+        assert!(requests[1].contains(r#""group_id":2"#));
+        assert!(requests[2].starts_with("PUT /api/v1/keys/9"));
+        assert!(requests[2].contains(r#""status":"inactive""#));
+    }
+}
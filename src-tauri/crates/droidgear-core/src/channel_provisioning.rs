@@ -0,0 +1,698 @@
+//! Provisioning tool profiles from a relay channel (core).
+//!
+//! Given a channel, one of its tokens and a selection of models, builds a
+//! ready-to-save profile for every supported tool plus Factory custom
+//! models. Each model's protocol is inferred per platform (see
+//! `channel_export::infer_protocol_from_model_id`) and models that speak
+//! different protocols end up under separate providers. Every generated
+//! entry carries a `ChannelRef` so `channel_sync` can keep it up to date.
+//! When the vault is initialized the token key is stored there and profiles
+//! only hold a `secret://` reference.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::channel::{Channel, ChannelRef, ChannelToken};
+use crate::channel_export::{infer_channel_protocol, protocol_base_url, with_path_suffix};
+use crate::claude::{self, ClaudeCodeProfile, ClaudeThinkingMode};
+use crate::codex::{self, CodexProfile, CodexProviderConfig};
+use crate::factory_settings::{
+    self, custom_model_from_model_info, CustomModel, ModelInfo, Provider,
+};
+use crate::hermes::{self, HermesModelConfig, HermesProfile};
+use crate::openclaw::{
+    self, openclaw_model_from_model_info, OpenClawProfile, OpenClawProviderConfig,
+};
+use crate::opencode::{
+    self, get_opencode_provider_templates, opencode_model_from_model_info, OpenCodeProfile,
+    OpenCodeProviderConfig, OpenCodeProviderOptions,
+};
+use crate::paths;
+use crate::pi::{self, pi_model_from_model_info, PiProfile, PiProviderConfig};
+use crate::secrets;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Profiles generated from one channel token
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionedProfiles {
+    pub codex: CodexProfile,
+    /// Only generated when at least one selected model speaks the Anthropic protocol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude: Option<ClaudeCodeProfile>,
+    pub opencode: OpenCodeProfile,
+    pub openclaw: OpenClawProfile,
+    pub pi: PiProfile,
+    pub hermes: HermesProfile,
+    pub custom_models: Vec<CustomModel>,
+}
+
+/// Selected models sharing one protocol
+struct ProtocolGroup<'a> {
+    protocol: &'static str,
+    /// Channel base URL adjusted for the protocol
+    base_url: String,
+    models: Vec<&'a ModelInfo>,
+}
+
+// ============================================================================
+// Provisioning
+// ============================================================================
+
+/// Builds a profile for every tool from `token` and the selected `models`,
+/// with the plain key. Nothing is written; see [`provision_profiles_for_home`].
+pub fn provision_profiles(
+    channel: &Channel,
+    token: &ChannelToken,
+    models: &[ModelInfo],
+) -> Result<ProvisionedProfiles, String> {
+    build_profiles(channel, token, models, &token.key)
+}
+
+/// Like [`provision_profiles`], but when the vault is initialized the key is
+/// stored as `channel/{id}/token-{token id}` and profiles reference it.
+///
+/// Factory custom models keep the plain key: Factory reads them straight from
+/// its settings file, so a reference would never be resolved.
+pub fn provision_profiles_for_home(
+    home_dir: &Path,
+    channel: &Channel,
+    token: &ChannelToken,
+    models: &[ModelInfo],
+) -> Result<ProvisionedProfiles, String> {
+    if !secrets::is_vault_initialized_for_home(home_dir) {
+        return provision_profiles(channel, token, models);
+    }
+    if models.is_empty() {
+        return Err("Select at least one model".to_string());
+    }
+    let name = format!("channel/{}/token-{}", channel.id, token.id);
+    let reference = secrets::set_secret_for_home(home_dir, &name, &token.key)?;
+    build_profiles(channel, token, models, &reference)
+}
+
+/// `key` is written into tool profiles; custom models always get `token.key`.
+fn build_profiles(
+    channel: &Channel,
+    token: &ChannelToken,
+    models: &[ModelInfo],
+    key: &str,
+) -> Result<ProvisionedProfiles, String> {
+    if models.is_empty() {
+        return Err("Select at least one model".to_string());
+    }
+    let platform = token.platform.as_deref();
+    let mut groups: Vec<ProtocolGroup> = Vec::new();
+    for model in models {
+        let protocol = infer_channel_protocol(platform, &model.id);
+        match groups.iter_mut().find(|g| g.protocol == protocol) {
+            Some(group) => group.models.push(model),
+            None => groups.push(ProtocolGroup {
+                protocol,
+                base_url: protocol_base_url(
+                    &channel.channel_type,
+                    platform,
+                    protocol,
+                    &channel.base_url,
+                ),
+                models: vec![model],
+            }),
+        }
+    }
+
+    let name = if token.name.trim().is_empty() || token.name == channel.name {
+        channel.name.clone()
+    } else {
+        format!("{} ({})", channel.name, token.name)
+    };
+    let description = Some(format!("Provisioned from channel {}", channel.name));
+    let slug = provider_slug(&channel.name);
+    let provider_id = |protocol: &str| {
+        if groups.len() == 1 {
+            slug.clone()
+        } else {
+            format!("{slug}-{protocol}")
+        }
+    };
+    let now = Utc::now().to_rfc3339();
+    let key = key.to_string();
+    let link = Some(ChannelRef {
+        channel_id: channel.id.clone(),
+        token_id: channel.channel_type.uses_login().then_some(token.id),
+    });
+
+    // Codex talks to a single provider: prefer an OpenAI model (Responses
+    // API), otherwise fall back to Chat Completions.
+    let codex_group = group_for(&groups, &["openai", "openai-compatible", "google-ai"]);
+    let codex_model = codex_group.models[0];
+    let codex_provider_id = provider_id(codex_group.protocol);
+    let codex = CodexProfile {
+        id: Uuid::new_v4().to_string(),
+        name: name.clone(),
+        description: description.clone(),
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        providers: HashMap::from([(
+            codex_provider_id.clone(),
+            CodexProviderConfig {
+                name: Some(channel.name.clone()),
+                base_url: Some(with_path_suffix(&codex_group.base_url, "/v1")),
+                wire_api: Some(
+                    if codex_group.protocol == "openai" {
+                        "responses"
+                    } else {
+                        "chat"
+                    }
+                    .to_string(),
+                ),
+                requires_openai_auth: None,
+                env_key: None,
+                env_key_instructions: None,
+                http_headers: None,
+                query_params: None,
+                model: Some(codex_model.id.clone()),
+                model_reasoning_effort: None,
+                api_key: Some(key.clone()),
+                channel_ref: link.clone(),
+            },
+        )]),
+        model_provider: codex_provider_id,
+        model: codex_model.id.clone(),
+        model_reasoning_effort: None,
+        api_key: None,
+    };
+
+    let claude = groups
+        .iter()
+        .find(|g| g.protocol == "anthropic")
+        .map(|group| ClaudeCodeProfile {
+            id: Uuid::new_v4().to_string(),
+            name: name.clone(),
+            description: description.clone(),
+            base_url: Some(group.base_url.clone()),
+            bearer_token: Some(key.clone()),
+            model: Some(group.models[0].id.clone()),
+            small_model_uses_main_model: false,
+            small_model: None,
+            reasoning_effort: None,
+            thinking_mode: ClaudeThinkingMode::Inherit,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            channel_ref: link.clone(),
+        });
+
+    let templates = get_opencode_provider_templates();
+    let opencode = OpenCodeProfile {
+        id: Uuid::new_v4().to_string(),
+        name: name.clone(),
+        description: description.clone(),
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        providers: groups
+            .iter()
+            .map(|group| {
+                let (template_id, suffix) = match group.protocol {
+                    "anthropic" => (Some("anthropic"), "/v1"),
+                    "openai" => (Some("openai"), "/v1"),
+                    "google-ai" => (Some("gemini"), "/v1beta"),
+                    _ => (None, "/v1"),
+                };
+                let npm = template_id
+                    .and_then(|id| templates.iter().find(|t| t.id == id))
+                    .and_then(|t| t.npm.clone())
+                    .unwrap_or_else(|| "@ai-sdk/openai-compatible".to_string());
+                let config = OpenCodeProviderConfig {
+                    npm: Some(npm),
+                    name: Some(channel.name.clone()),
+                    options: Some(OpenCodeProviderOptions {
+                        base_url: Some(with_path_suffix(&group.base_url, suffix)),
+                        api_key: Some(key.clone()),
+                        timeout: None,
+                        headers: None,
+                    }),
+                    models: Some(
+                        group
+                            .models
+                            .iter()
+                            .map(|m| (m.id.clone(), opencode_model_from_model_info(m)))
+                            .collect(),
+                    ),
+                    channel_ref: link.clone(),
+                };
+                (provider_id(group.protocol), config)
+            })
+            .collect(),
+        auth: HashMap::new(),
+    };
+
+    let openclaw_providers: HashMap<String, OpenClawProviderConfig> = groups
+        .iter()
+        .map(|group| {
+            let (api, base_url) = match group.protocol {
+                "anthropic" => ("anthropic-messages", group.base_url.clone()),
+                "openai" => ("openai-responses", with_path_suffix(&group.base_url, "/v1")),
+                _ => (
+                    "openai-completions",
+                    with_path_suffix(&group.base_url, "/v1"),
+                ),
+            };
+            let config = OpenClawProviderConfig {
+                base_url: Some(base_url),
+                api_key: Some(key.clone()),
+                api: Some(api.to_string()),
+                models: group
+                    .models
+                    .iter()
+                    .map(|m| openclaw_model_from_model_info(m))
+                    .collect(),
+                channel_ref: link.clone(),
+            };
+            (provider_id(group.protocol), config)
+        })
+        .collect();
+    let openclaw_default = format!("{}/{}", provider_id(groups[0].protocol), models[0].id);
+    let openclaw = OpenClawProfile {
+        id: Uuid::new_v4().to_string(),
+        name: name.clone(),
+        description: description.clone(),
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        default_model: Some(openclaw_default),
+        failover_models: None,
+        providers: openclaw_providers,
+        block_streaming_config: None,
+    };
+
+    let pi = PiProfile {
+        id: Uuid::new_v4().to_string(),
+        name: name.clone(),
+        description: description.clone(),
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        providers: groups
+            .iter()
+            .map(|group| {
+                let api = match group.protocol {
+                    "anthropic" => "anthropic-messages",
+                    "google-ai" => "google-generative-ai",
+                    _ => "openai-completions",
+                };
+                let config = PiProviderConfig {
+                    base_url: Some(group.base_url.clone()),
+                    api: Some(api.to_string()),
+                    api_key: Some(key.clone()),
+                    headers: None,
+                    auth_header: None,
+                    models: group
+                        .models
+                        .iter()
+                        .map(|m| pi_model_from_model_info(m))
+                        .collect(),
+                    model_overrides: None,
+                    compat: None,
+                    channel_ref: link.clone(),
+                };
+                (provider_id(group.protocol), config)
+            })
+            .collect(),
+    };
+
+    // Hermes always uses its "custom" OpenAI-compatible provider.
+    let hermes_group = group_for(&groups, &["openai", "openai-compatible"]);
+    let hermes = HermesProfile {
+        id: Uuid::new_v4().to_string(),
+        name: name.clone(),
+        description: description.clone(),
+        created_at: now.clone(),
+        updated_at: now,
+        model: HermesModelConfig {
+            default: Some(hermes_group.models[0].id.clone()),
+            provider: Some("custom".to_string()),
+            base_url: Some(with_path_suffix(&hermes_group.base_url, "/v1")),
+            api_key: Some(key.clone()),
+            channel_ref: link.clone(),
+        },
+    };
+
+    let custom_models = groups
+        .iter()
+        .flat_map(|group| {
+            let provider = match group.protocol {
+                "anthropic" => Provider::Anthropic,
+                "openai" => Provider::Openai,
+                _ => Provider::GenericChatCompletionApi,
+            };
+            let key = &token.key;
+            let link = &link;
+            group.models.iter().map(move |m| CustomModel {
+                channel_ref: link.clone(),
+                ..custom_model_from_model_info(m, &group.base_url, key, provider.clone())
+            })
+        })
+        .collect();
+
+    Ok(ProvisionedProfiles {
+        codex,
+        claude,
+        opencode,
+        openclaw,
+        pi,
+        hermes,
+        custom_models,
+    })
+}
+
+/// First group speaking one of `protocols` (in order), else the first group.
+fn group_for<'g, 'a>(groups: &'g [ProtocolGroup<'a>], protocols: &[&str]) -> &'g ProtocolGroup<'a> {
+    protocols
+        .iter()
+        .find_map(|p| groups.iter().find(|g| g.protocol == *p))
+        .unwrap_or(&groups[0])
+}
+
+/// Provider key derived from the channel name. `openai` is reserved by
+/// Codex, so it gets a suffix.
+fn provider_slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-').to_string();
+    match slug.as_str() {
+        "" => "channel".to_string(),
+        "openai" => "openai-relay".to_string(),
+        _ => slug,
+    }
+}
+
+// ============================================================================
+// Saving
+// ============================================================================
+
+/// Saves every provisioned profile and appends the custom models to the
+/// Factory config (models already configured for the same endpoint are kept).
+pub fn save_provisioned_profiles_for_home(
+    home_dir: &Path,
+    profiles: ProvisionedProfiles,
+) -> Result<(), String> {
+    save_profiles_with(
+        home_dir,
+        profiles,
+        || factory_settings::load_custom_models_for_home(home_dir),
+        |models| factory_settings::save_custom_models_for_home(home_dir, models),
+    )
+}
+
+/// Saves the tool profiles under `home_dir` and merges the custom models
+/// through `load_models` / `save_models`, so the desktop can target the
+/// active Droid settings file.
+fn save_profiles_with(
+    home_dir: &Path,
+    profiles: ProvisionedProfiles,
+    load_models: impl FnOnce() -> Result<Vec<CustomModel>, String>,
+    save_models: impl FnOnce(Vec<CustomModel>) -> Result<(), String>,
+) -> Result<(), String> {
+    codex::save_codex_profile_for_home(home_dir, profiles.codex)?;
+    if let Some(profile) = profiles.claude {
+        claude::save_claude_profile_for_home(home_dir, profile)?;
+    }
+    opencode::save_opencode_profile_for_home(home_dir, profiles.opencode)?;
+    openclaw::save_openclaw_profile_for_home(home_dir, profiles.openclaw)?;
+    pi::save_pi_profile_for_home(home_dir, profiles.pi)?;
+    hermes::save_hermes_profile_for_home(home_dir, profiles.hermes)?;
+
+    if !profiles.custom_models.is_empty() {
+        let mut existing = load_models()?;
+        for model in profiles.custom_models {
+            let duplicate = existing
+                .iter()
+                .any(|m| m.model == model.model && m.base_url == model.base_url);
+            if !duplicate {
+                existing.push(model);
+            }
+        }
+        save_models(existing)?;
+    }
+    Ok(())
+}
+
+pub fn provision_channel_profiles(
+    channel: &Channel,
+    token: &ChannelToken,
+    models: &[ModelInfo],
+) -> Result<ProvisionedProfiles, String> {
+    provision_profiles_for_home(&paths::get_home_dir()?, channel, token, models)
+}
+
+/// Like [`save_provisioned_profiles_for_home`], but custom models go to the
+/// active Droid settings file the desktop app edits.
+pub fn save_provisioned_profiles(profiles: ProvisionedProfiles) -> Result<(), String> {
+    save_profiles_with(
+        &paths::get_home_dir()?,
+        profiles,
+        factory_settings::load_custom_models,
+        factory_settings::save_custom_models,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::ChannelType;
+    use tempfile::TempDir;
+
+    fn channel(channel_type: ChannelType) -> Channel {
+        Channel {
+            id: "ch-1".to_string(),
+            name: "My Relay".to_string(),
+            channel_type,
+            base_url: "https://relay.example.com".to_string(),
+            enabled: true,
+            created_at: 0.0,
+        }
+    }
+
+    fn token(platform: Option<&str>) -> ChannelToken {
+        ChannelToken {
+            id: 1.0,
+            name: "dev".to_string(),
+            key: "sk-test".to_string(),
+            status: 1,
+            remain_quota: 0.0,
+            used_quota: 0.0,
+            unlimited_quota: true,
+            platform: platform.map(str::to_string),
+            group_name: None,
+        }
+    }
+
+    fn model(id: &str) -> ModelInfo {
+        ModelInfo {
+            id: id.to_string(),
+            context_window: Some(200000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn splits_providers_by_protocol() {
+        let models = [model("claude-sonnet-4"), model("gpt-5"), model("qwen3")];
+        let p = provision_profiles(&channel(ChannelType::NewApi), &token(None), &models).unwrap();
+
+        assert_eq!(p.codex.model, "gpt-5");
+        assert_eq!(p.codex.model_provider, "my-relay-openai");
+        let codex_provider = &p.codex.providers["my-relay-openai"];
+        assert_eq!(codex_provider.wire_api.as_deref(), Some("responses"));
+        assert_eq!(
+            codex_provider.base_url.as_deref(),
+            Some("https://relay.example.com/v1")
+        );
+
+        let claude = p.claude.unwrap();
+        assert_eq!(claude.model.as_deref(), Some("claude-sonnet-4"));
+        assert_eq!(
+            claude.base_url.as_deref(),
+            Some("https://relay.example.com")
+        );
+        assert_eq!(claude.bearer_token.as_deref(), Some("sk-test"));
+
+        assert_eq!(
+            p.opencode.providers["my-relay-anthropic"].npm.as_deref(),
+            Some("@ai-sdk/anthropic")
+        );
+        assert_eq!(
+            p.opencode.providers["my-relay-openai-compatible"]
+                .npm
+                .as_deref(),
+            Some("@ai-sdk/openai-compatible")
+        );
+        assert_eq!(
+            p.openclaw.default_model.as_deref(),
+            Some("my-relay-anthropic/claude-sonnet-4")
+        );
+        assert_eq!(
+            p.pi.providers["my-relay-anthropic"].api.as_deref(),
+            Some("anthropic-messages")
+        );
+        assert_eq!(p.hermes.model.default.as_deref(), Some("gpt-5"));
+        assert_eq!(p.custom_models.len(), 3);
+        assert_eq!(p.custom_models[0].provider, Provider::Anthropic);
+        assert_eq!(
+            p.custom_models[0].channel_ref,
+            Some(ChannelRef {
+                channel_id: "ch-1".to_string(),
+                token_id: Some(1.0),
+            })
+        );
+    }
+
+    #[test]
+    fn uses_the_token_platform_for_sub2api() {
+        let models = [model("claude-opus-4"), model("gemini-2.5-pro")];
+        let p = provision_profiles(
+            &channel(ChannelType::Sub2Api),
+            &token(Some("antigravity")),
+            &models,
+        )
+        .unwrap();
+
+        assert_eq!(
+            p.claude.unwrap().base_url.as_deref(),
+            Some("https://relay.example.com/antigravity")
+        );
+        let gemini = &p.pi.providers["my-relay-google-ai"];
+        assert_eq!(gemini.api.as_deref(), Some("google-generative-ai"));
+        assert_eq!(
+            gemini.base_url.as_deref(),
+            Some("https://relay.example.com/antigravity/v1beta")
+        );
+        assert_eq!(
+            p.codex.providers[&p.codex.model_provider]
+                .wire_api
+                .as_deref(),
+            Some("chat")
+        );
+        assert_eq!(
+            p.hermes.model.base_url.as_deref(),
+            Some("https://relay.example.com/antigravity/v1")
+        );
+
+        let single = provision_profiles(
+            &channel(ChannelType::Sub2Api),
+            &token(Some("openai")),
+            &[model("some-model")],
+        )
+        .unwrap();
+        assert!(single.claude.is_none());
+        assert_eq!(single.codex.model_provider, "my-relay");
+        assert_eq!(
+            single.opencode.providers["my-relay"].npm.as_deref(),
+            Some("@ai-sdk/openai")
+        );
+    }
+
+    #[test]
+    fn saves_profiles_and_skips_known_custom_models() {
+        let home = TempDir::new().unwrap();
+        let models = [model("claude-sonnet-4"), model("gpt-5")];
+        let ch = channel(ChannelType::NewApi);
+        let p = provision_profiles(&ch, &token(None), &models).unwrap();
+        save_provisioned_profiles_for_home(home.path(), p).unwrap();
+        let again = provision_profiles(&ch, &token(None), &models).unwrap();
+        save_provisioned_profiles_for_home(home.path(), again).unwrap();
+
+        assert_eq!(
+            factory_settings::load_custom_models_for_home(home.path())
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(pi::list_pi_profiles_for_home(home.path()).unwrap().len(), 2);
+        assert!(claude::list_claude_profiles_for_home(home.path())
+            .unwrap()
+            .iter()
+            .any(|p| p.name == "My Relay (dev)"));
+    }
+
+    #[test]
+    fn saves_custom_models_to_the_active_settings_file() {
+        let home = TempDir::new().unwrap();
+        let team = paths::droidgear_dir_from_home(home.path()).join("droid-settings/team.json");
+        std::fs::create_dir_all(team.parent().unwrap()).unwrap();
+        std::fs::write(&team, "{}").unwrap();
+        crate::droid_settings_files::set_active_settings_file_for_home(
+            home.path(),
+            Some("team".to_string()),
+        )
+        .unwrap();
+
+        let p = provision_profiles(
+            &channel(ChannelType::NewApi),
+            &token(None),
+            &[model("gpt-5")],
+        )
+        .unwrap();
+        save_profiles_with(
+            home.path(),
+            p,
+            || factory_settings::load_active_custom_models_for_home(home.path()),
+            |models| factory_settings::save_active_custom_models_for_home(home.path(), models),
+        )
+        .unwrap();
+
+        assert_eq!(
+            factory_settings::load_active_custom_models_for_home(home.path())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(factory_settings::load_custom_models_for_home(home.path())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn stores_the_key_in_an_initialized_vault() {
+        let home = TempDir::new().unwrap();
+        secrets::init_vault_for_home(home.path(), "passphrase").unwrap();
+        let p = provision_profiles_for_home(
+            home.path(),
+            &channel(ChannelType::NewApi),
+            &token(None),
+            &[model("claude-sonnet-4"), model("gpt-5")],
+        )
+        .unwrap();
+
+        let reference = "secret://channel/ch-1/token-1";
+        assert_eq!(p.claude.unwrap().bearer_token.as_deref(), Some(reference));
+        assert_eq!(
+            p.codex.providers[&p.codex.model_provider]
+                .api_key
+                .as_deref(),
+            Some(reference)
+        );
+        assert_eq!(p.hermes.model.api_key.as_deref(), Some(reference));
+        assert!(p.custom_models.iter().all(|m| m.api_key == "sk-test"));
+        assert_eq!(
+            secrets::get_secret_for_home(home.path(), "channel/ch-1/token-1").unwrap(),
+            "sk-test"
+        );
+    }
+
+    #[test]
+    fn rejects_an_empty_selection() {
+        let err = provision_profiles(&channel(ChannelType::General), &token(None), &[]);
+        assert!(err.is_err());
+    }
+}
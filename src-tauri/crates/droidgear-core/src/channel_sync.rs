@@ -0,0 +1,893 @@
+//! Syncing channel-linked profile entries (core).
+//!
+//! Provider entries (and Factory custom models) that carry a `ChannelRef`
+//! are re-resolved against their channel: the base URL is rebased onto the
+//! channel's current base URL, the key is re-read from the channel token (or
+//! stored API key) and models the channel no longer offers are flagged. The
+//! drift is reported and, when requested, applied. Saving a channel's base
+//! URL or auth applies the drift of the entries linked to that channel.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::apply_plan::mask_secret;
+use crate::channel::{self, Channel, ChannelRef};
+use crate::channel_export::with_path_suffix;
+use crate::{claude, codex, factory_settings, hermes, openclaw, opencode, paths, pi, secrets};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Linked value that no longer matches the channel
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LinkField {
+    BaseUrl,
+    ApiKey,
+    /// Models the channel no longer offers
+    Models,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkDrift {
+    /// Tool name (`codex`, `claude`, ...) or `factory` for custom models
+    pub target: String,
+    pub profile_id: String,
+    pub profile_name: String,
+    /// Provider key inside the profile (model id for custom models)
+    pub entry: String,
+    pub field: LinkField,
+    /// Current value (keys masked; missing model ids for `models`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+    /// Value resolved from the channel (keys masked)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// Whether the fix was written (single selected models are only reported)
+    pub applied: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkSyncReport {
+    /// Number of linked entries checked
+    pub checked: u32,
+    pub drift: Vec<LinkDrift>,
+    pub errors: Vec<String>,
+}
+
+/// Current connection details of a linked channel token
+struct ResolvedLink {
+    base_url: String,
+    key: String,
+    /// Model ids offered by the channel (`None` if they could not be listed)
+    models: Option<HashSet<String>>,
+}
+
+/// Where a linked entry lives
+struct EntryAt<'a> {
+    target: &'a str,
+    profile_id: &'a str,
+    profile_name: &'a str,
+    entry: &'a str,
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// API paths provisioning appends to a channel base URL; kept when rebasing.
+const API_PATH_SUFFIXES: [&str; 7] = [
+    "/antigravity/v1beta",
+    "/antigravity/v1",
+    "/antigravity",
+    "/anthropic/v1",
+    "/anthropic",
+    "/v1beta",
+    "/v1",
+];
+
+/// Rebases `current` onto `channel_base`, keeping the API path the entry
+/// added on top of the channel's URL.
+fn rebase_base_url(current: &str, channel_base: &str) -> String {
+    let base = channel_base.trim_end_matches('/');
+    let current = current.trim_end_matches('/');
+    if current == base || current.starts_with(&format!("{base}/")) {
+        return current.to_string();
+    }
+    match API_PATH_SUFFIXES.iter().find(|s| current.ends_with(*s)) {
+        Some(suffix) => with_path_suffix(base, suffix),
+        None => base.to_string(),
+    }
+}
+
+async fn resolve_link(
+    home_dir: &Path,
+    channels: &[Channel],
+    link: &ChannelRef,
+) -> Result<(ResolvedLink, Option<String>), String> {
+    let channel = channels
+        .iter()
+        .find(|c| c.id == link.channel_id)
+        .ok_or_else(|| format!("Channel {} no longer exists", link.channel_id))?;
+    let (key, platform) = match link.token_id {
+        Some(token_id) => {
+            let (username, password) =
+                channel::get_channel_credentials_for_home(home_dir, &channel.id)?
+                    .ok_or_else(|| format!("No credentials stored for '{}'", channel.name))?;
+            let token = channel::fetch_channel_tokens(
+                channel.channel_type.clone(),
+                &channel.base_url,
+                &username,
+                &password,
+            )
+            .await?
+            .into_iter()
+            .find(|t| t.id == token_id)
+            .ok_or_else(|| {
+                format!(
+                    "Token {token_id} no longer exists on channel '{}'",
+                    channel.name
+                )
+            })?;
+            (token.key, token.platform)
+        }
+        None => {
+            let key = channel::get_channel_api_key_for_home(home_dir, &channel.id)?
+                .ok_or_else(|| format!("No API key stored for '{}'", channel.name))?;
+            (key, None)
+        }
+    };
+
+    let (models, warning) = match channel::fetch_models_by_api_key(
+        &channel.base_url,
+        &key,
+        platform.as_deref(),
+    )
+    .await
+    {
+        Ok(models) => (Some(models.into_iter().map(|m| m.id).collect()), None),
+        Err(e) => (
+            None,
+            Some(format!(
+                "Channel '{}': failed to list models: {e}",
+                channel.name
+            )),
+        ),
+    };
+    Ok((
+        ResolvedLink {
+            base_url: channel.base_url.clone(),
+            key,
+            models,
+        },
+        warning,
+    ))
+}
+
+// ============================================================================
+// Checking
+// ============================================================================
+
+/// Which Factory settings file holds the custom models to sync
+#[derive(Debug, Clone, Copy)]
+enum CustomModelsFile {
+    /// `~/.factory/settings.json` (TUI and `_for_home` callers)
+    Global,
+    /// The active Droid settings file the desktop app edits
+    Active,
+}
+
+impl CustomModelsFile {
+    fn load(self, home_dir: &Path) -> Result<Vec<factory_settings::CustomModel>, String> {
+        match self {
+            Self::Global => factory_settings::load_custom_models_for_home(home_dir),
+            Self::Active => factory_settings::load_active_custom_models_for_home(home_dir),
+        }
+    }
+
+    fn save(
+        self,
+        home_dir: &Path,
+        models: Vec<factory_settings::CustomModel>,
+    ) -> Result<(), String> {
+        match self {
+            Self::Global => factory_settings::save_custom_models_for_home(home_dir, models),
+            Self::Active => factory_settings::save_active_custom_models_for_home(home_dir, models),
+        }
+    }
+}
+
+struct Checker<'a> {
+    home_dir: &'a Path,
+    custom_models: CustomModelsFile,
+    links: Vec<(ChannelRef, Option<ResolvedLink>)>,
+    apply: bool,
+    report: LinkSyncReport,
+}
+
+impl Checker<'_> {
+    fn drift(
+        &mut self,
+        at: &EntryAt,
+        field: LinkField,
+        current: Option<String>,
+        expected: Option<String>,
+        applied: bool,
+    ) {
+        self.report.drift.push(LinkDrift {
+            target: at.target.to_string(),
+            profile_id: at.profile_id.to_string(),
+            profile_name: at.profile_name.to_string(),
+            entry: at.entry.to_string(),
+            field,
+            current,
+            expected,
+            applied,
+        });
+    }
+
+    /// Checks one linked entry; returns whether it changed and which of
+    /// `model_ids` to drop (only when `prune` and applying).
+    fn check(
+        &mut self,
+        at: &EntryAt,
+        link: &ChannelRef,
+        base_url: &mut Option<String>,
+        api_key: &mut Option<String>,
+        model_ids: &[&str],
+        prune: bool,
+    ) -> (bool, Vec<String>) {
+        // Links outside the synced channel are skipped entirely.
+        let Some((_, resolved)) = self.links.iter().find(|(l, _)| l == link) else {
+            return (false, Vec::new());
+        };
+        self.report.checked += 1;
+        let Some(resolved) = resolved else {
+            // Resolution errors are reported once per link.
+            return (false, Vec::new());
+        };
+        let (expected_key, channel_base, available) = (
+            resolved.key.clone(),
+            resolved.base_url.clone(),
+            resolved.models.clone(),
+        );
+        let mut changed = false;
+
+        if let Some(current) = base_url.clone() {
+            let expected = rebase_base_url(&current, &channel_base);
+            if current.trim_end_matches('/') != expected {
+                self.drift(
+                    at,
+                    LinkField::BaseUrl,
+                    Some(current),
+                    Some(expected.clone()),
+                    self.apply,
+                );
+                if self.apply {
+                    *base_url = Some(expected);
+                    changed = true;
+                }
+            }
+        }
+
+        let current_key = api_key.clone().unwrap_or_default();
+        match secrets::resolve_secret_value_for_home(self.home_dir, &current_key) {
+            Ok(value) if value == expected_key => {}
+            Ok(value) => {
+                let mut applied = false;
+                if self.apply {
+                    match secrets::parse_secret_ref(&current_key) {
+                        // Keep the reference and update the vault entry it points at.
+                        Some(name) => {
+                            match secrets::set_secret_for_home(self.home_dir, name, &expected_key) {
+                                Ok(_) => applied = true,
+                                Err(e) => self.report.errors.push(format!(
+                                    "{} '{}' / {}: {e}",
+                                    at.target, at.profile_name, at.entry
+                                )),
+                            }
+                        }
+                        None => {
+                            *api_key = Some(expected_key.clone());
+                            changed = true;
+                            applied = true;
+                        }
+                    }
+                }
+                self.drift(
+                    at,
+                    LinkField::ApiKey,
+                    Some(mask_secret(&value)),
+                    Some(mask_secret(&expected_key)),
+                    applied,
+                );
+            }
+            Err(e) => self.report.errors.push(format!(
+                "{} '{}' / {}: {e}",
+                at.target, at.profile_name, at.entry
+            )),
+        }
+
+        let mut pruned = Vec::new();
+        if let Some(available) = available {
+            let missing: Vec<String> = model_ids
+                .iter()
+                .filter(|id| !available.contains(**id))
+                .map(|id| id.to_string())
+                .collect();
+            if !missing.is_empty() {
+                let applied = self.apply && prune;
+                self.drift(
+                    at,
+                    LinkField::Models,
+                    Some(missing.join(", ")),
+                    None,
+                    applied,
+                );
+                if applied {
+                    pruned = missing;
+                    changed = true;
+                }
+            }
+        }
+        (changed, pruned)
+    }
+
+    fn error(&mut self, target: &str, e: String) {
+        self.report.errors.push(format!("{target}: {e}"));
+    }
+}
+
+fn sorted_keys<V>(map: &std::collections::HashMap<String, V>) -> Vec<String> {
+    let mut keys: Vec<String> = map.keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+fn sync_codex(c: &mut Checker) -> Result<(), String> {
+    for mut profile in codex::list_codex_profiles_for_home(c.home_dir)? {
+        let mut changed = false;
+        for id in sorted_keys(&profile.providers) {
+            let provider = profile.providers.get_mut(&id).expect("key from map");
+            let Some(link) = provider.channel_ref.clone() else {
+                continue;
+            };
+            let mut models: Vec<&str> = provider.model.as_deref().into_iter().collect();
+            if profile.model_provider == id && !models.contains(&profile.model.as_str()) {
+                models.push(&profile.model);
+            }
+            let models: Vec<String> = models.into_iter().map(str::to_string).collect();
+            let model_refs: Vec<&str> = models.iter().map(String::as_str).collect();
+            let at = EntryAt {
+                target: "codex",
+                profile_id: &profile.id,
+                profile_name: &profile.name,
+                entry: &id,
+            };
+            let (entry_changed, _) = c.check(
+                &at,
+                &link,
+                &mut provider.base_url,
+                &mut provider.api_key,
+                &model_refs,
+                false,
+            );
+            changed |= entry_changed;
+        }
+        if changed {
+            codex::save_codex_profile_for_home(c.home_dir, profile)?;
+        }
+    }
+    Ok(())
+}
+
+fn sync_claude(c: &mut Checker) -> Result<(), String> {
+    for mut profile in claude::list_claude_profiles_for_home(c.home_dir)? {
+        let Some(link) = profile.channel_ref.clone() else {
+            continue;
+        };
+        let models: Vec<String> = profile
+            .model
+            .iter()
+            .chain(profile.small_model.iter())
+            .cloned()
+            .collect();
+        let model_refs: Vec<&str> = models.iter().map(String::as_str).collect();
+        let (id, name) = (profile.id.clone(), profile.name.clone());
+        let at = EntryAt {
+            target: "claude",
+            profile_id: &id,
+            profile_name: &name,
+            entry: &name,
+        };
+        let (changed, _) = c.check(
+            &at,
+            &link,
+            &mut profile.base_url,
+            &mut profile.bearer_token,
+            &model_refs,
+            false,
+        );
+        if changed {
+            claude::save_claude_profile_for_home(c.home_dir, profile)?;
+        }
+    }
+    Ok(())
+}
+
+fn sync_opencode(c: &mut Checker) -> Result<(), String> {
+    for mut profile in opencode::list_opencode_profiles_for_home(c.home_dir)? {
+        let mut changed = false;
+        for id in sorted_keys(&profile.providers) {
+            let provider = profile.providers.get_mut(&id).expect("key from map");
+            let Some(link) = provider.channel_ref.clone() else {
+                continue;
+            };
+            let models: Vec<String> = provider
+                .models
+                .as_ref()
+                .map(sorted_keys)
+                .unwrap_or_default();
+            let model_refs: Vec<&str> = models.iter().map(String::as_str).collect();
+            let options = provider.options.get_or_insert_with(Default::default);
+            let at = EntryAt {
+                target: "opencode",
+                profile_id: &profile.id,
+                profile_name: &profile.name,
+                entry: &id,
+            };
+            let (entry_changed, pruned) = c.check(
+                &at,
+                &link,
+                &mut options.base_url,
+                &mut options.api_key,
+                &model_refs,
+                true,
+            );
+            if let Some(models) = provider.models.as_mut() {
+                models.retain(|id, _| !pruned.contains(id));
+            }
+            changed |= entry_changed;
+        }
+        if changed {
+            opencode::save_opencode_profile_for_home(c.home_dir, profile)?;
+        }
+    }
+    Ok(())
+}
+
+fn sync_openclaw(c: &mut Checker) -> Result<(), String> {
+    for mut profile in openclaw::list_openclaw_profiles_for_home(c.home_dir)? {
+        let mut changed = false;
+        for id in sorted_keys(&profile.providers) {
+            let provider = profile.providers.get_mut(&id).expect("key from map");
+            let Some(link) = provider.channel_ref.clone() else {
+                continue;
+            };
+            let models: Vec<String> = provider.models.iter().map(|m| m.id.clone()).collect();
+            let model_refs: Vec<&str> = models.iter().map(String::as_str).collect();
+            let at = EntryAt {
+                target: "openclaw",
+                profile_id: &profile.id,
+                profile_name: &profile.name,
+                entry: &id,
+            };
+            let (entry_changed, pruned) = c.check(
+                &at,
+                &link,
+                &mut provider.base_url,
+                &mut provider.api_key,
+                &model_refs,
+                true,
+            );
+            provider.models.retain(|m| !pruned.contains(&m.id));
+            changed |= entry_changed;
+        }
+        if changed {
+            openclaw::save_openclaw_profile_for_home(c.home_dir, profile)?;
+        }
+    }
+    Ok(())
+}
+
+fn sync_pi(c: &mut Checker) -> Result<(), String> {
+    for mut profile in pi::list_pi_profiles_for_home(c.home_dir)? {
+        let mut changed = false;
+        for id in sorted_keys(&profile.providers) {
+            let provider = profile.providers.get_mut(&id).expect("key from map");
+            let Some(link) = provider.channel_ref.clone() else {
+                continue;
+            };
+            let models: Vec<String> = provider.models.iter().map(|m| m.id.clone()).collect();
+            let model_refs: Vec<&str> = models.iter().map(String::as_str).collect();
+            let at = EntryAt {
+                target: "pi",
+                profile_id: &profile.id,
+                profile_name: &profile.name,
+                entry: &id,
+            };
+            let (entry_changed, pruned) = c.check(
+                &at,
+                &link,
+                &mut provider.base_url,
+                &mut provider.api_key,
+                &model_refs,
+                true,
+            );
+            provider.models.retain(|m| !pruned.contains(&m.id));
+            changed |= entry_changed;
+        }
+        if changed {
+            pi::save_pi_profile_for_home(c.home_dir, profile)?;
+        }
+    }
+    Ok(())
+}
+
+fn sync_hermes(c: &mut Checker) -> Result<(), String> {
+    for mut profile in hermes::list_hermes_profiles_for_home(c.home_dir)? {
+        let Some(link) = profile.model.channel_ref.clone() else {
+            continue;
+        };
+        let models: Vec<String> = profile.model.default.iter().cloned().collect();
+        let model_refs: Vec<&str> = models.iter().map(String::as_str).collect();
+        let (id, name) = (profile.id.clone(), profile.name.clone());
+        let at = EntryAt {
+            target: "hermes",
+            profile_id: &id,
+            profile_name: &name,
+            entry: "model",
+        };
+        let (changed, _) = c.check(
+            &at,
+            &link,
+            &mut profile.model.base_url,
+            &mut profile.model.api_key,
+            &model_refs,
+            false,
+        );
+        if changed {
+            hermes::save_hermes_profile_for_home(c.home_dir, profile)?;
+        }
+    }
+    Ok(())
+}
+
+fn sync_custom_models(c: &mut Checker) -> Result<(), String> {
+    let mut models = c.custom_models.load(c.home_dir)?;
+    let mut changed = false;
+    for model in models.iter_mut() {
+        let Some(link) = model.channel_ref.clone() else {
+            continue;
+        };
+        let id = model.id.clone().unwrap_or_else(|| model.model.clone());
+        let name = model
+            .display_name
+            .clone()
+            .unwrap_or_else(|| model.model.clone());
+        let model_id = model.model.clone();
+        let mut base_url = Some(model.base_url.clone());
+        let mut api_key = Some(model.api_key.clone());
+        let at = EntryAt {
+            target: "factory",
+            profile_id: &id,
+            profile_name: &name,
+            entry: &model_id,
+        };
+        let (entry_changed, _) =
+            c.check(&at, &link, &mut base_url, &mut api_key, &[&model_id], false);
+        model.base_url = base_url.unwrap_or_default();
+        model.api_key = api_key.unwrap_or_default();
+        changed |= entry_changed;
+    }
+    if changed {
+        c.custom_models.save(c.home_dir, models)?;
+    }
+    Ok(())
+}
+
+type SyncStep = fn(&mut Checker) -> Result<(), String>;
+
+/// Every distinct `ChannelRef` used by profiles and custom models.
+fn collect_links(home_dir: &Path, custom_models: CustomModelsFile) -> Vec<ChannelRef> {
+    let mut links: Vec<ChannelRef> = Vec::new();
+    let mut push = |link: &Option<ChannelRef>| {
+        if let Some(link) = link {
+            if !links.contains(link) {
+                links.push(link.clone());
+            }
+        }
+    };
+    for p in codex::list_codex_profiles_for_home(home_dir).unwrap_or_default() {
+        p.providers.values().for_each(|v| push(&v.channel_ref));
+    }
+    for p in claude::list_claude_profiles_for_home(home_dir).unwrap_or_default() {
+        push(&p.channel_ref);
+    }
+    for p in opencode::list_opencode_profiles_for_home(home_dir).unwrap_or_default() {
+        p.providers.values().for_each(|v| push(&v.channel_ref));
+    }
+    for p in openclaw::list_openclaw_profiles_for_home(home_dir).unwrap_or_default() {
+        p.providers.values().for_each(|v| push(&v.channel_ref));
+    }
+    for p in pi::list_pi_profiles_for_home(home_dir).unwrap_or_default() {
+        p.providers.values().for_each(|v| push(&v.channel_ref));
+    }
+    for p in hermes::list_hermes_profiles_for_home(home_dir).unwrap_or_default() {
+        push(&p.model.channel_ref);
+    }
+    for m in custom_models.load(home_dir).unwrap_or_default() {
+        push(&m.channel_ref);
+    }
+    links
+}
+
+// ============================================================================
+// Sync
+// ============================================================================
+
+/// Compares every channel-linked entry with its channel. With `apply`, base
+/// URLs and keys are rewritten (vault references are kept and the secret
+/// updated) and models the channel dropped are removed from model lists.
+pub async fn sync_linked_profiles_for_home(
+    home_dir: &Path,
+    apply: bool,
+) -> Result<LinkSyncReport, String> {
+    sync_links(home_dir, CustomModelsFile::Global, None, apply).await
+}
+
+/// Like [`sync_linked_profiles_for_home`], against the active Droid settings
+/// file the desktop app edits.
+pub async fn sync_linked_profiles(apply: bool) -> Result<LinkSyncReport, String> {
+    sync_links(
+        &paths::get_home_dir()?,
+        CustomModelsFile::Active,
+        None,
+        apply,
+    )
+    .await
+}
+
+/// Applies the drift of every entry linked to `channel_id`; run after the
+/// channel's base URL, key or credentials are saved. Does nothing (and makes
+/// no requests) when no entry links to the channel.
+pub async fn sync_channel_links_for_home(
+    home_dir: &Path,
+    channel_id: &str,
+) -> Result<LinkSyncReport, String> {
+    sync_links(home_dir, CustomModelsFile::Global, Some(channel_id), true).await
+}
+
+/// Blocking variant of [`sync_channel_links_for_home`] (for TUI usage).
+pub fn sync_channel_links_blocking(
+    home_dir: &Path,
+    channel_id: &str,
+) -> Result<LinkSyncReport, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create tokio runtime: {e}"))?;
+    runtime.block_on(sync_channel_links_for_home(home_dir, channel_id))
+}
+
+/// Desktop variant of [`sync_channel_links_for_home`].
+pub async fn sync_channel_links(channel_id: &str) -> Result<LinkSyncReport, String> {
+    sync_links(
+        &paths::get_home_dir()?,
+        CustomModelsFile::Active,
+        Some(channel_id),
+        true,
+    )
+    .await
+}
+
+async fn sync_links(
+    home_dir: &Path,
+    custom_models: CustomModelsFile,
+    channel_id: Option<&str>,
+    apply: bool,
+) -> Result<LinkSyncReport, String> {
+    let mut report = LinkSyncReport::default();
+    let wanted: Vec<ChannelRef> = collect_links(home_dir, custom_models)
+        .into_iter()
+        .filter(|link| channel_id.is_none_or(|id| link.channel_id == id))
+        .collect();
+    if wanted.is_empty() {
+        return Ok(report);
+    }
+    let channels = channel::load_channels_for_home(home_dir)?;
+    let mut links = Vec::new();
+    for link in wanted {
+        match resolve_link(home_dir, &channels, &link).await {
+            Ok((resolved, warning)) => {
+                report.errors.extend(warning);
+                links.push((link, Some(resolved)));
+            }
+            Err(e) => {
+                report.errors.push(e);
+                links.push((link, None));
+            }
+        }
+    }
+
+    let mut checker = Checker {
+        home_dir,
+        custom_models,
+        links,
+        apply,
+        report,
+    };
+    let steps: [(&str, SyncStep); 7] = [
+        ("codex", sync_codex),
+        ("claude", sync_claude),
+        ("opencode", sync_opencode),
+        ("openclaw", sync_openclaw),
+        ("pi", sync_pi),
+        ("hermes", sync_hermes),
+        ("factory", sync_custom_models),
+    ];
+    for (target, step) in steps {
+        if let Err(e) = step(&mut checker) {
+            checker.error(target, e);
+        }
+    }
+    Ok(checker.report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::ChannelType;
+    use crate::pi::{PiModel, PiProfile, PiProviderConfig};
+    use crate::test_support::{block_on, serve_responses};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn pi_model(id: &str) -> PiModel {
+        serde_json::from_value(serde_json::json!({ "id": id })).unwrap()
+    }
+
+    fn linked_pi_profile(link: ChannelRef) -> PiProfile {
+        PiProfile {
+            id: "pi-1".to_string(),
+            name: "Relay".to_string(),
+            description: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            providers: HashMap::from([(
+                "relay".to_string(),
+                PiProviderConfig {
+                    base_url: Some("https://old.example.com/v1".to_string()),
+                    api: Some("openai-completions".to_string()),
+                    api_key: Some("sk-old-key-123".to_string()),
+                    headers: None,
+                    auth_header: None,
+                    models: vec![pi_model("kept"), pi_model("gone")],
+                    model_overrides: None,
+                    compat: None,
+                    channel_ref: Some(link),
+                },
+            )]),
+        }
+    }
+
+    fn setup(base_url: &str) -> (TempDir, ChannelRef) {
+        let home = TempDir::new().unwrap();
+        channel::save_channels_for_home(
+            home.path(),
+            vec![Channel {
+                id: "ch-1".to_string(),
+                name: "Local".to_string(),
+                channel_type: ChannelType::General,
+                base_url: base_url.to_string(),
+                enabled: true,
+                created_at: 0.0,
+            }],
+        )
+        .unwrap();
+        channel::save_channel_api_key_for_home(home.path(), "ch-1", "sk-new-key-456").unwrap();
+        let link = ChannelRef {
+            channel_id: "ch-1".to_string(),
+            token_id: None,
+        };
+        pi::save_pi_profile_for_home(home.path(), linked_pi_profile(link.clone())).unwrap();
+        (home, link)
+    }
+
+    #[test]
+    fn rebases_onto_the_channel_keeping_the_api_path() {
+        assert_eq!(
+            rebase_base_url("https://old.com/antigravity/v1beta", "https://new.com/"),
+            "https://new.com/antigravity/v1beta"
+        );
+        assert_eq!(
+            rebase_base_url("https://new.com/v1", "https://new.com"),
+            "https://new.com/v1"
+        );
+        assert_eq!(
+            rebase_base_url("https://old.com/custom", "https://new.com"),
+            "https://new.com"
+        );
+    }
+
+    #[test]
+    fn reports_drift_without_writing() {
+        let base = serve_responses(vec![(200, r#"{"data":[{"id":"kept"}]}"#.to_string())]);
+        let (home, _) = setup(&base);
+
+        let report = block_on(sync_linked_profiles_for_home(home.path(), false)).unwrap();
+
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.checked, 1);
+        let fields: Vec<LinkField> = report.drift.iter().map(|d| d.field).collect();
+        assert_eq!(
+            fields,
+            vec![LinkField::BaseUrl, LinkField::ApiKey, LinkField::Models]
+        );
+        assert_eq!(report.drift[0].expected, Some(format!("{base}/v1")));
+        assert_eq!(report.drift[2].current.as_deref(), Some("gone"));
+        assert!(report.drift.iter().all(|d| !d.applied));
+
+        let stored = pi::get_pi_profile_for_home(home.path(), "pi-1").unwrap();
+        assert_eq!(
+            stored.providers["relay"].api_key.as_deref(),
+            Some("sk-old-key-123")
+        );
+    }
+
+    #[test]
+    fn applies_drift_to_linked_entries() {
+        let base = serve_responses(vec![(200, r#"{"data":[{"id":"kept"}]}"#.to_string())]);
+        let (home, _) = setup(&base);
+
+        let report = block_on(sync_linked_profiles_for_home(home.path(), true)).unwrap();
+        assert!(report.drift.iter().all(|d| d.applied));
+
+        let provider = pi::get_pi_profile_for_home(home.path(), "pi-1")
+            .unwrap()
+            .providers
+            .remove("relay")
+            .unwrap();
+        assert_eq!(provider.base_url, Some(format!("{base}/v1")));
+        assert_eq!(provider.api_key.as_deref(), Some("sk-new-key-456"));
+        let ids: Vec<&str> = provider.models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["kept"]);
+    }
+
+    #[test]
+    fn channel_sync_only_touches_entries_linked_to_that_channel() {
+        let base = serve_responses(vec![(200, r#"{"data":[{"id":"kept"}]}"#.to_string())]);
+        let (home, _) = setup(&base);
+
+        let other = block_on(sync_channel_links_for_home(home.path(), "ch-2")).unwrap();
+        assert_eq!(other.checked, 0);
+        assert!(other.drift.is_empty() && other.errors.is_empty());
+
+        let report = block_on(sync_channel_links_for_home(home.path(), "ch-1")).unwrap();
+        assert_eq!(report.checked, 1);
+        assert!(report.drift.iter().all(|d| d.applied));
+        let stored = pi::get_pi_profile_for_home(home.path(), "pi-1").unwrap();
+        assert_eq!(
+            stored.providers["relay"].api_key.as_deref(),
+            Some("sk-new-key-456")
+        );
+    }
+
+    #[test]
+    fn reports_links_to_deleted_channels() {
+        let base = serve_responses(vec![]);
+        let (home, _) = setup(&base);
+        channel::save_channels_for_home(home.path(), Vec::new()).unwrap();
+
+        let report = block_on(sync_linked_profiles_for_home(home.path(), true)).unwrap();
+
+        assert_eq!(report.checked, 1);
+        assert!(report.drift.is_empty());
+        assert_eq!(report.errors, vec!["Channel ch-1 no longer exists"]);
+    }
+}
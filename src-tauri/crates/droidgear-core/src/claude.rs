@@ -10,7 +10,9 @@ use specta::Type;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{paths, storage};
+use crate::apply_plan::{file_plan, ApplyPlan, PlanFormat};
+use crate::channel::ChannelRef;
+use crate::{backup, paths, secrets, storage};
 
 // ============================================================================
 // Types
@@ -57,6 +59,9 @@ pub struct ClaudeCodeProfile {
     pub reasoning_effort: Option<ClaudeReasoningEffort>,
     #[serde(default)]
     pub thinking_mode: ClaudeThinkingMode,
+    /// Channel this entry was provisioned from (DroidGear-only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ref: Option<ChannelRef>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -517,10 +522,11 @@ fn build_current_config_from_settings(
     })
 }
 
-fn apply_profile_to_settings_path(
+/// Internal: render the settings.json content an apply would write.
+fn render_profile_settings(
     profile: &ClaudeCodeProfile,
     settings_path: &Path,
-) -> Result<(), String> {
+) -> Result<String, String> {
     let mut root = read_settings_object_from_path(settings_path)?;
     let mut env = get_env_object(&root)?.cloned().unwrap_or_default();
 
@@ -549,8 +555,15 @@ fn apply_profile_to_settings_path(
     }
     cleanup_env_if_empty(&mut root);
 
-    let content = serde_json::to_string_pretty(&Value::Object(root))
-        .map_err(|e| format!("Failed to serialize settings.json: {e}"))?;
+    serde_json::to_string_pretty(&Value::Object(root))
+        .map_err(|e| format!("Failed to serialize settings.json: {e}"))
+}
+
+fn apply_profile_to_settings_path(
+    profile: &ClaudeCodeProfile,
+    settings_path: &Path,
+) -> Result<(), String> {
+    let content = render_profile_settings(profile, settings_path)?;
     storage::atomic_write(settings_path, content.as_bytes())
 }
 
@@ -695,6 +708,7 @@ pub fn create_default_claude_profile_for_home(
         thinking_mode: ClaudeThinkingMode::Inherit,
         created_at: now.clone(),
         updated_at: now,
+        channel_ref: None,
     };
 
     write_profile_file(home_dir, &profile)?;
@@ -740,13 +754,36 @@ pub fn set_active_claude_profile_id_for_home(home_dir: &Path, id: &str) -> Resul
 // ============================================================================
 
 pub fn apply_claude_profile_for_home(home_dir: &Path, id: &str) -> Result<(), String> {
-    let profile = load_profile_by_id(home_dir, id)?;
+    let profile =
+        secrets::resolve_secret_refs_for_home(home_dir, &load_profile_by_id(home_dir, id)?)?;
     let settings_path = claude_settings_path_for_home(home_dir)?;
+    backup::snapshot_file_for_home(home_dir, &settings_path, "claude")?;
     apply_profile_to_settings_path(&profile, &settings_path)?;
     set_active_claude_profile_id_for_home(home_dir, id)?;
     Ok(())
 }
 
+/// Dry-run of [`apply_claude_profile_for_home`]: returns the planned writes without touching disk.
+pub fn plan_apply_claude_profile_for_home(home_dir: &Path, id: &str) -> Result<ApplyPlan, String> {
+    let profile =
+        secrets::resolve_secret_refs_for_home(home_dir, &load_profile_by_id(home_dir, id)?)?;
+    let settings_path = claude_settings_path_for_home(home_dir)?;
+    let content = render_profile_settings(&profile, &settings_path)?;
+
+    Ok(ApplyPlan {
+        tool: "claude".to_string(),
+        profile_id: Some(id.to_string()),
+        files: vec![
+            file_plan(&settings_path, content, PlanFormat::Json),
+            file_plan(
+                &active_profile_path_for_home(home_dir)?,
+                id.trim().to_string(),
+                PlanFormat::Text,
+            ),
+        ],
+    })
+}
+
 pub fn get_claude_config_status_for_home(home_dir: &Path) -> Result<ClaudeConfigStatus, String> {
     let config_dir = claude_config_dir_for_home(home_dir)?;
     let settings_path = claude_settings_path_for_home(home_dir)?;
@@ -813,6 +850,10 @@ pub fn apply_claude_profile(id: &str) -> Result<(), String> {
     apply_claude_profile_for_home(&system_home_dir()?, id)
 }
 
+pub fn plan_apply_claude_profile(id: &str) -> Result<ApplyPlan, String> {
+    plan_apply_claude_profile_for_home(&system_home_dir()?, id)
+}
+
 pub fn get_claude_config_status() -> Result<ClaudeConfigStatus, String> {
     get_claude_config_status_for_home(&system_home_dir()?)
 }
@@ -848,6 +889,7 @@ mod tests {
             thinking_mode: ClaudeThinkingMode::On,
             created_at: "2026-01-01T00:00:00Z".to_string(),
             updated_at: "2026-01-01T00:00:00Z".to_string(),
+            channel_ref: None,
         }
     }
 
@@ -961,6 +1003,7 @@ mod tests {
             thinking_mode: ClaudeThinkingMode::Inherit,
             created_at: String::new(),
             updated_at: String::new(),
+            channel_ref: None,
         };
 
         save_claude_profile_for_home(home, profile).unwrap();
@@ -10,7 +10,7 @@ use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{claude, paths};
+use crate::{claude, paths, secrets};
 
 const CLAUDE_CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
 const CLAUDE_ENV_FILE_ENV: &str = "CLAUDE_ENV_FILE";
@@ -556,9 +556,10 @@ pub fn build_temporary_run_plan_for_home(
     launcher_args: &[String],
 ) -> Result<ClaudeTemporaryLaunchPlan, String> {
     let process_env: HashMap<String, String> = std::env::vars().collect();
+    let profile = secrets::resolve_secret_refs_for_home(home_dir, profile)?;
     build_temporary_run_plan_for_home_with_env(
         home_dir,
-        profile,
+        &profile,
         &process_env,
         launcher_program,
         launcher_args,
@@ -1076,6 +1077,7 @@ mod tests {
             thinking_mode: claude::ClaudeThinkingMode::On,
             created_at: "2026-01-01T00:00:00Z".to_string(),
             updated_at: "2026-01-01T00:00:00Z".to_string(),
+            channel_ref: None,
         }
     }
 
@@ -16,7 +16,7 @@ use serde_json::{Map, Value};
 use specta::Type;
 use std::path::{Path, PathBuf};
 
-use crate::{claude, paths, storage};
+use crate::{backup, claude, paths, storage};
 
 const CLAUDE_SETTINGS_DIR: &str = "claude-settings";
 const ACTIVE_FILE_KEY: &str = "claudeSettingsActiveFile";
@@ -583,6 +583,7 @@ pub fn save_settings_file_for_home(
 
     let bytes = serde_json::to_vec_pretty(value)
         .map_err(|e| format!("Failed to serialize settings file: {e}"))?;
+    backup::snapshot_file_for_home(home_dir, &path, "claude")?;
     storage::atomic_write(&path, &bytes)
 }
 
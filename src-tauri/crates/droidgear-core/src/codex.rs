@@ -11,7 +11,9 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{json, paths, storage};
+use crate::apply_plan::{file_plan, ApplyPlan, PlanFormat};
+use crate::channel::ChannelRef;
+use crate::{backup, json, paths, secrets, storage};
 
 // ============================================================================
 // Types
@@ -47,6 +49,8 @@ pub struct CodexProviderConfig {
     pub model_reasoning_effort: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ref: Option<ChannelRef>,
 }
 
 /// Codex Profile（用于在 DroidGear 内部保存并切换）
@@ -438,6 +442,7 @@ fn toml_to_provider_config(value: &toml::Value) -> Result<CodexProviderConfig, S
         model: None,
         model_reasoning_effort: None,
         api_key: None,
+        channel_ref: None,
     })
 }
 
@@ -647,6 +652,7 @@ pub fn create_default_codex_profile_for_home(home_dir: &Path) -> Result<CodexPro
             model: Some("gpt-5.2".to_string()),
             model_reasoning_effort: Some("high".to_string()),
             api_key: Some(String::new()),
+            channel_ref: None,
         },
     );
 
@@ -695,14 +701,10 @@ fn set_active_profile_id_for_home(home_dir: &Path, id: &str) -> Result<(), Strin
 // Apply + status
 // ============================================================================
 
-/// 应用指定 Profile 到 `~/.codex/*`
-///
-/// 只替换 config.toml 中的模型相关配置（model_provider, model, model_reasoning_effort,
-/// [model_providers]），保留其他所有配置（projects, network_access 等）。
-pub fn apply_codex_profile_for_home(home_dir: &Path, id: &str) -> Result<(), String> {
-    let profile = load_profile_by_id(home_dir, id)?;
-    let (_, active_provider) = resolve_active_provider(&profile);
-    let resolved_api_key = resolved_api_key(&profile, active_provider);
+/// Internal: render the config.toml and auth.json contents an apply would write.
+fn render_apply_files(home_dir: &Path, profile: &CodexProfile) -> Result<(String, String), String> {
+    let (_, active_provider) = resolve_active_provider(profile);
+    let resolved_api_key = resolved_api_key(profile, active_provider);
 
     let config_path = codex_config_path_for_home(home_dir)?;
     let mut config = if config_path.exists() {
@@ -718,23 +720,74 @@ pub fn apply_codex_profile_for_home(home_dir: &Path, id: &str) -> Result<(), Str
         toml::map::Map::new()
     };
 
-    apply_profile_to_config_map(&mut config, &profile)?;
+    apply_profile_to_config_map(&mut config, profile)?;
 
     let toml_str = toml::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config.toml: {e}"))?;
-    storage::atomic_write(&config_path, toml_str.as_bytes())?;
 
     let auth_path = codex_auth_path_for_home(home_dir)?;
     let mut auth = json::read_json_object_file(&auth_path).unwrap_or_default();
 
     apply_api_key_to_auth_map(&mut auth, resolved_api_key.as_deref());
 
-    json::write_json_object_file(&auth_path, &auth)?;
+    // Sorted so that the output is stable across runs (HashMap order is not)
+    let auth: std::collections::BTreeMap<String, Value> = auth.into_iter().collect();
+    let auth_str = serde_json::to_string_pretty(&auth)
+        .map_err(|e| format!("Failed to serialize JSON: {e}"))?;
+
+    Ok((toml_str, auth_str))
+}
+
+/// 应用指定 Profile 到 `~/.codex/*`
+///
+/// 只替换 config.toml 中的模型相关配置（model_provider, model, model_reasoning_effort,
+/// [model_providers]），保留其他所有配置（projects, network_access 等）。
+pub fn apply_codex_profile_for_home(home_dir: &Path, id: &str) -> Result<(), String> {
+    let profile =
+        secrets::resolve_secret_refs_for_home(home_dir, &load_profile_by_id(home_dir, id)?)?;
+    let (toml_str, auth_str) = render_apply_files(home_dir, &profile)?;
+
+    let config_path = codex_config_path_for_home(home_dir)?;
+    backup::snapshot_file_for_home(home_dir, &config_path, "codex")?;
+    storage::atomic_write(&config_path, toml_str.as_bytes())?;
+
+    let auth_path = codex_auth_path_for_home(home_dir)?;
+    backup::snapshot_file_for_home(home_dir, &auth_path, "codex")?;
+    storage::atomic_write(&auth_path, auth_str.as_bytes())?;
 
     set_active_profile_id_for_home(home_dir, id)?;
     Ok(())
 }
 
+/// Dry-run of [`apply_codex_profile_for_home`]: returns the planned writes without touching disk.
+pub fn plan_apply_codex_profile_for_home(home_dir: &Path, id: &str) -> Result<ApplyPlan, String> {
+    let profile =
+        secrets::resolve_secret_refs_for_home(home_dir, &load_profile_by_id(home_dir, id)?)?;
+    let (toml_str, auth_str) = render_apply_files(home_dir, &profile)?;
+
+    Ok(ApplyPlan {
+        tool: "codex".to_string(),
+        profile_id: Some(id.to_string()),
+        files: vec![
+            file_plan(
+                &codex_config_path_for_home(home_dir)?,
+                toml_str,
+                PlanFormat::Toml,
+            ),
+            file_plan(
+                &codex_auth_path_for_home(home_dir)?,
+                auth_str,
+                PlanFormat::Json,
+            ),
+            file_plan(
+                &active_profile_path_for_home(home_dir)?,
+                id.to_string(),
+                PlanFormat::Text,
+            ),
+        ],
+    })
+}
+
 pub fn get_codex_config_status_for_home(home_dir: &Path) -> Result<CodexConfigStatus, String> {
     let auth_path = codex_auth_path_for_home(home_dir)?;
     let config_path = codex_config_path_for_home(home_dir)?;
@@ -873,6 +926,10 @@ pub fn apply_codex_profile(id: &str) -> Result<(), String> {
     apply_codex_profile_for_home(&system_home_dir()?, id)
 }
 
+pub fn plan_apply_codex_profile(id: &str) -> Result<ApplyPlan, String> {
+    plan_apply_codex_profile_for_home(&system_home_dir()?, id)
+}
+
 pub fn get_codex_config_status() -> Result<CodexConfigStatus, String> {
     get_codex_config_status_for_home(&system_home_dir()?)
 }
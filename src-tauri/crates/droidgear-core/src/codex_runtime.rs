@@ -9,7 +9,7 @@ use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{codex, json, paths, storage};
+use crate::{codex, json, paths, secrets, storage};
 
 const CODEX_CONFIG_SUPPORT_MIN_VERSION: &str = "0.128.0";
 const CODEX_RUNTIME_DIR: &str = "runtime/codex";
@@ -477,6 +477,7 @@ pub fn build_temporary_run_plan_for_home(
     home_dir: &Path,
     profile: &codex::CodexProfile,
 ) -> Result<CodexTemporaryLaunchPlan, String> {
+    let profile = &secrets::resolve_secret_refs_for_home(home_dir, profile)?;
     let (provider_id, provider) = codex::resolve_active_provider(profile);
     validate_provider_id(&provider_id)?;
 
@@ -548,6 +549,7 @@ mod tests {
                 model: Some("gpt-5.5".to_string()),
                 model_reasoning_effort: Some("high".to_string()),
                 api_key: Some("sk-provider".to_string()),
+                channel_ref: None,
             },
         );
 
@@ -5,24 +5,98 @@
 //! - **Ping**: lightweight HTTP probe on `/v1/models` (free, fast)
 //! - **Inference**: real model call via reqwest (costs tokens, slower, but
 //!   validates the model is actually usable)
+//! - **Streaming / ToolUse / Vision**: capability probes that send an SSE,
+//!   tool-calling or image request and validate the protocol-specific
+//!   response shape
+//!
+//! Failed attempts are retried with exponential backoff and batch runs test
+//! several models at once, both driven by [`TestConfig`].
+//!
+//! Every mode speaks Anthropic Messages, OpenAI Chat Completions, the OpenAI
+//! Responses API or Gemini `generateContent` (see [`WireProtocol`]).
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use crate::factory_settings::{self, Provider};
+use crate::connectivity_history::{self, HistoryRecord};
+use crate::factory_settings::{self, CustomModel, Provider};
+use crate::paths;
 
 // ============================================================================
 // Types
 // ============================================================================
 
-/// Test mode: ping (HTTP probe) or inference (real model call).
+/// Test mode: ping (HTTP probe), inference (real model call) or a capability probe.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub enum TestMode {
     Ping,
     Inference,
+    /// SSE streaming request; validates the event stream shape
+    Streaming,
+    /// Forced tool call; validates the returned tool call
+    ToolUse,
+    /// Image input; validates the model answers a prompt with an image attached
+    Vision,
+}
+
+/// HTTP API shape used to talk to an endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum WireProtocol {
+    /// Anthropic `/v1/messages`
+    AnthropicMessages,
+    /// OpenAI-compatible `/v1/chat/completions`
+    ChatCompletions,
+    /// OpenAI Responses API `/v1/responses` (Codex `wire_api = "responses"`)
+    Responses,
+    /// Google Gemini `models/{model}:generateContent`
+    Gemini,
+}
+
+/// Capabilities detected by the probe modes.
+///
+/// `None` means not probed or inconclusive (e.g. network error); `Some(false)`
+/// means the endpoint rejected the request or answered with the wrong shape.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_use: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vision: Option<bool>,
+}
+
+impl ModelCapabilities {
+    /// Copies every determined capability from `other`.
+    pub fn merge(&mut self, other: &ModelCapabilities) {
+        self.streaming = other.streaming.or(self.streaming);
+        self.tool_use = other.tool_use.or(self.tool_use);
+        self.vision = other.vision.or(self.vision);
+    }
+
+    fn has_unsupported(&self) -> bool {
+        [self.streaming, self.tool_use, self.vision].contains(&Some(false))
+    }
+}
+
+/// Result of probing all capabilities of one model.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityReport {
+    pub model_id: String,
+    pub model_name: String,
+    pub capabilities: ModelCapabilities,
+    /// Diagnostics of each probe (streaming, tool use, vision)
+    pub probes: Vec<ConnectionDiagnostics>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -34,6 +108,9 @@ pub struct ConnectionDiagnostics {
     pub latency_ms: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// HTTP status of the failed request, when the endpoint answered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
     pub timestamp: String,
     pub test_mode: TestMode,
     /// Actual model response text (inference mode only).
@@ -42,6 +119,45 @@ pub struct ConnectionDiagnostics {
     /// The prompt that was sent (inference mode only).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt_used: Option<String>,
+    /// Every attempt made for this test, including retries (oldest first).
+    #[serde(default)]
+    pub attempts: Vec<AttemptDiagnostics>,
+    /// Capability detected by a probe mode (None for ping/inference).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<ModelCapabilities>,
+}
+
+impl ConnectionDiagnostics {
+    /// Failed diagnostics for a test that never produced its own (e.g. a panicked task).
+    pub(crate) fn failed(provider: &str, model_id: &str, mode: &TestMode, error: String) -> Self {
+        ConnectionDiagnostics {
+            success: false,
+            provider: provider.to_string(),
+            model_id: model_id.to_string(),
+            latency_ms: 0,
+            error: Some(error),
+            status_code: None,
+            timestamp: Utc::now().to_rfc3339(),
+            test_mode: mode.clone(),
+            response_text: None,
+            prompt_used: None,
+            attempts: Vec::new(),
+            capabilities: None,
+        }
+    }
+}
+
+/// Outcome of a single attempt within a (possibly retried) test.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AttemptDiagnostics {
+    /// 1-based attempt number
+    pub attempt: u32,
+    pub success: bool,
+    pub latency_ms: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub timestamp: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -62,6 +178,12 @@ pub struct TestConfig {
     pub retry_on_failure: bool,
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
+    /// Delay before the first retry; doubled for every further retry.
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u32,
+    /// Maximum number of models tested at the same time.
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: u32,
 }
 
 fn default_timeout() -> u32 {
@@ -73,6 +195,12 @@ fn default_retry() -> bool {
 fn default_max_retries() -> u32 {
     2
 }
+fn default_retry_backoff_ms() -> u32 {
+    500
+}
+fn default_max_concurrency() -> u32 {
+    4
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
@@ -91,6 +219,8 @@ pub struct ConnectivitySummary {
 pub struct ModelTester {
     config: TestConfig,
     client: reqwest::Client,
+    /// Sent with every request (e.g. a model's `extra_headers`)
+    extra_headers: Vec<(String, String)>,
 }
 
 impl ModelTester {
@@ -98,6 +228,7 @@ impl ModelTester {
         Self {
             config: TestConfig::default(),
             client: reqwest::Client::new(),
+            extra_headers: Vec::new(),
         }
     }
 
@@ -105,11 +236,31 @@ impl ModelTester {
         Self {
             client: reqwest::Client::new(),
             config,
+            extra_headers: Vec::new(),
+        }
+    }
+
+    /// Adds headers sent with every request.
+    pub fn with_extra_headers(mut self, headers: &HashMap<String, String>) -> Self {
+        let mut headers: Vec<(String, String)> = headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        headers.sort();
+        self.extra_headers.extend(headers);
+        self
+    }
+
+    fn with_extra(&self, mut req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        for (name, value) in &self.extra_headers {
+            req = req.header(name, value);
         }
+        req
     }
 
     /// Test connectivity by hitting a lightweight endpoint (e.g. /v1/models).
     /// This avoids inference costs while verifying API key and network.
+    /// Failed attempts are retried according to [`TestConfig`].
     pub async fn test_model(
         &self,
         provider: &Provider,
@@ -117,54 +268,15 @@ impl ModelTester {
         api_key: &str,
         model_id: &str,
     ) -> ConnectionDiagnostics {
-        let start = Instant::now();
-        let provider_str = provider_to_string(provider);
-        log::info!("Connectivity: ping test for model={model_id} provider={provider_str} base_url={base_url}");
-
-        if api_key.is_empty() {
-            return ConnectionDiagnostics {
-                success: false,
-                provider: provider_str,
-                model_id: model_id.to_string(),
-                latency_ms: start.elapsed().as_millis() as u32,
-                error: Some("No API key configured".to_string()),
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Ping,
-                response_text: None,
-                prompt_used: None,
-            };
-        }
-
-        let result = self.probe_endpoint(provider, base_url, api_key).await;
-        let latency_ms = start.elapsed().as_millis() as u32;
-
-        match result {
-            Ok(()) => ConnectionDiagnostics {
-                success: true,
-                provider: provider_str,
-                model_id: model_id.to_string(),
-                latency_ms,
-                error: None,
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Ping,
-                response_text: None,
-                prompt_used: None,
-            },
-            Err(e) => ConnectionDiagnostics {
-                success: false,
-                provider: provider_str,
-                model_id: model_id.to_string(),
-                latency_ms,
-                error: Some(e),
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Ping,
-                response_text: None,
-                prompt_used: None,
-            },
-        }
+        let label = provider_to_string(provider);
+        let target = Target::for_provider(provider, &label, base_url, api_key, model_id);
+        self.run(target, &TestMode::Ping, DEFAULT_INFERENCE_PROMPT)
+            .await
     }
 
     /// Test connectivity with explicit provider string (for direct API testing).
+    /// Besides Factory providers this accepts protocol names such as
+    /// `gemini` and `responses` (see [`WireProtocol::from_name`]).
     pub async fn test_model_direct(
         &self,
         provider: &str,
@@ -172,54 +284,18 @@ impl ModelTester {
         api_key: &str,
         model_id: &str,
     ) -> ConnectionDiagnostics {
-        let start = Instant::now();
-        log::info!("Connectivity: direct ping test for model={model_id} provider={provider} base_url={base_url}");
-
-        if api_key.is_empty() {
-            return ConnectionDiagnostics {
-                success: false,
-                provider: provider.to_string(),
-                model_id: model_id.to_string(),
-                latency_ms: start.elapsed().as_millis() as u32,
-                error: Some("No API key configured".to_string()),
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Ping,
-                response_text: None,
-                prompt_used: None,
-            };
-        }
-
-        let p = string_to_provider(provider);
-        let result = self.probe_endpoint(&p, base_url, api_key).await;
-        let latency_ms = start.elapsed().as_millis() as u32;
-
-        match result {
-            Ok(()) => ConnectionDiagnostics {
-                success: true,
-                provider: provider.to_string(),
-                model_id: model_id.to_string(),
-                latency_ms,
-                error: None,
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Ping,
-                response_text: None,
-                prompt_used: None,
-            },
-            Err(e) => ConnectionDiagnostics {
-                success: false,
-                provider: provider.to_string(),
-                model_id: model_id.to_string(),
-                latency_ms,
-                error: Some(e),
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Ping,
-                response_text: None,
-                prompt_used: None,
-            },
-        }
+        let target = Target {
+            protocol: WireProtocol::from_name(provider),
+            label: provider,
+            base_url,
+            api_key,
+            model_id,
+        };
+        self.run(target, &TestMode::Ping, DEFAULT_INFERENCE_PROMPT)
+            .await
     }
 
-    /// Test model by sending a real inference request via aisdk.
+    /// Test model by sending a real inference request.
     /// This validates the model is actually usable (key valid, model exists, quota OK).
     pub async fn test_model_inference(
         &self,
@@ -229,82 +305,258 @@ impl ModelTester {
         model_id: &str,
         prompt: &str,
     ) -> ConnectionDiagnostics {
-        let start = Instant::now();
-        let provider_str = provider_to_string(provider);
-        log::info!("Connectivity: inference test for model={model_id} provider={provider_str} base_url={base_url}");
-        log::debug!("Connectivity: inference prompt={prompt:?}");
-
-        if api_key.is_empty() {
-            return ConnectionDiagnostics {
-                success: false,
-                provider: provider_str,
-                model_id: model_id.to_string(),
-                latency_ms: start.elapsed().as_millis() as u32,
-                error: Some("No API key configured".to_string()),
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Inference,
-                response_text: None,
-                prompt_used: Some(prompt.to_string()),
-            };
-        }
+        let label = provider_to_string(provider);
+        let target = Target::for_provider(provider, &label, base_url, api_key, model_id);
+        self.run(target, &TestMode::Inference, prompt).await
+    }
 
-        let result = self
-            .run_inference(provider, base_url, api_key, model_id, prompt)
-            .await;
-        let latency_ms = start.elapsed().as_millis() as u32;
+    /// Test a model in any mode; used for the capability probes (streaming,
+    /// tool use, vision).
+    pub async fn test_model_capability(
+        &self,
+        mode: &TestMode,
+        provider: &Provider,
+        base_url: &str,
+        api_key: &str,
+        model_id: &str,
+        prompt: &str,
+    ) -> ConnectionDiagnostics {
+        let label = provider_to_string(provider);
+        let target = Target::for_provider(provider, &label, base_url, api_key, model_id);
+        self.run(target, mode, prompt).await
+    }
 
-        match result {
-            Ok(text) => ConnectionDiagnostics {
-                success: true,
-                provider: provider_str,
-                model_id: model_id.to_string(),
-                latency_ms,
-                error: None,
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Inference,
-                response_text: Some(text),
-                prompt_used: Some(prompt.to_string()),
-            },
-            Err(e) => ConnectionDiagnostics {
-                success: false,
-                provider: provider_str,
-                model_id: model_id.to_string(),
-                latency_ms,
-                error: Some(e),
-                timestamp: Utc::now().to_rfc3339(),
-                test_mode: TestMode::Inference,
-                response_text: None,
-                prompt_used: Some(prompt.to_string()),
-            },
+    /// Test an endpoint over an explicit wire protocol in any mode.
+    pub async fn test_endpoint(
+        &self,
+        protocol: WireProtocol,
+        mode: &TestMode,
+        base_url: &str,
+        api_key: &str,
+        model_id: &str,
+        prompt: &str,
+    ) -> ConnectionDiagnostics {
+        let target = Target {
+            protocol,
+            label: protocol.name(),
+            base_url,
+            api_key,
+            model_id,
+        };
+        self.run(target, mode, prompt).await
+    }
+
+    /// Probe streaming, tool use and vision of a custom model one after another.
+    pub async fn probe_custom_model(&self, model: &CustomModel) -> CapabilityReport {
+        let mut capabilities = ModelCapabilities::default();
+        let mut probes = Vec::new();
+        for mode in [TestMode::Streaming, TestMode::ToolUse, TestMode::Vision] {
+            let result = self
+                .test_custom_model(model, &mode, DEFAULT_INFERENCE_PROMPT)
+                .await;
+            if let Some(caps) = &result.diagnostics.capabilities {
+                capabilities.merge(caps);
+            }
+            probes.push(result.diagnostics);
+        }
+        CapabilityReport {
+            model_id: model.id.clone().unwrap_or_else(|| model.model.clone()),
+            model_name: model
+                .display_name
+                .clone()
+                .unwrap_or_else(|| model.model.clone()),
+            capabilities,
+            probes,
         }
     }
 
-    async fn probe_endpoint(
+    /// Test a configured custom model in the given mode.
+    pub async fn test_custom_model(
         &self,
-        provider: &Provider,
+        model: &CustomModel,
+        mode: &TestMode,
+        prompt: &str,
+    ) -> ModelTestResult {
+        let label = provider_to_string(&model.provider);
+        let target = Target::for_provider(
+            &model.provider,
+            &label,
+            &model.base_url,
+            &model.api_key,
+            &model.model,
+        );
+        let diag = self.run(target, mode, prompt).await;
+        let available = diag.success;
+        ModelTestResult {
+            model_id: model.id.clone().unwrap_or_else(|| model.model.clone()),
+            model_name: model
+                .display_name
+                .clone()
+                .unwrap_or_else(|| model.model.clone()),
+            diagnostics: diag,
+            is_available: available,
+        }
+    }
+
+    /// POSTs `body` to the protocol's generation endpoint and returns the raw
+    /// status and body.
+    pub async fn send_message(
+        &self,
+        protocol: WireProtocol,
         base_url: &str,
         api_key: &str,
-    ) -> Result<(), String> {
-        let base = base_url.trim_end_matches('/');
-        let url = format!("{base}/v1/models");
-        log::debug!("Connectivity: probe_endpoint url={url} provider={provider:?}");
+        model_id: &str,
+        body: &serde_json::Value,
+    ) -> Result<(reqwest::StatusCode, String), String> {
+        let target = Target {
+            protocol,
+            label: protocol.name(),
+            base_url,
+            api_key,
+            model_id,
+        };
+        self.post(target, body, false, "Probe").await
+    }
 
-        let mut req = self.client.get(&url);
+    async fn run(
+        &self,
+        target: Target<'_>,
+        mode: &TestMode,
+        prompt: &str,
+    ) -> ConnectionDiagnostics {
+        // A missing URL or key fails the same way every time
+        let retry = target.is_configured();
+        self.with_retries(retry, || self.run_once(target, mode, prompt))
+            .await
+    }
 
-        match provider {
-            Provider::Anthropic => {
-                req = req
-                    .header("x-api-key", api_key)
-                    .header("anthropic-version", "2023-06-01");
-            }
-            _ => {
-                req = req.header("Authorization", format!("Bearer {api_key}"));
+    /// Runs `attempt_fn` until it succeeds, the error is not worth retrying, or
+    /// the retry budget from [`TestConfig`] is used up. Every attempt is
+    /// recorded in the returned diagnostics.
+    async fn with_retries<F, Fut>(&self, retry: bool, mut attempt_fn: F) -> ConnectionDiagnostics
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ConnectionDiagnostics>,
+    {
+        let max_attempts = if retry && self.config.retry_on_failure {
+            self.config.max_retries.saturating_add(1)
+        } else {
+            1
+        };
+        let mut attempts = Vec::new();
+        let mut attempt = 1;
+        loop {
+            let mut diag = attempt_fn().await;
+            attempts.push(AttemptDiagnostics {
+                attempt,
+                success: diag.success,
+                latency_ms: diag.latency_ms,
+                error: diag.error.clone(),
+                timestamp: diag.timestamp.clone(),
+            });
+            // A capability the endpoint rejected will be rejected again
+            let unsupported = diag
+                .capabilities
+                .as_ref()
+                .is_some_and(ModelCapabilities::has_unsupported);
+            if diag.success
+                || unsupported
+                || attempt >= max_attempts
+                || !is_retryable(diag.status_code)
+            {
+                diag.attempts = attempts;
+                return diag;
             }
+            let delay = retry_delay(&self.config, attempt);
+            log::info!(
+                "Connectivity: attempt {attempt}/{max_attempts} failed for model={}, retrying in {}ms",
+                diag.model_id,
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
         }
+    }
+
+    /// A single attempt in any mode.
+    async fn run_once(
+        &self,
+        target: Target<'_>,
+        mode: &TestMode,
+        prompt: &str,
+    ) -> ConnectionDiagnostics {
+        let start = Instant::now();
+        let capability = Capability::from_mode(mode);
+        let prompt_used = match (mode, capability) {
+            (TestMode::Ping, _) => None,
+            (_, Some(c)) => Some(c.prompt(prompt).to_string()),
+            _ => Some(prompt.to_string()),
+        };
+        log::info!(
+            "Connectivity: {mode:?} test for model={} provider={} protocol={:?} base_url={}",
+            target.model_id,
+            target.label,
+            target.protocol,
+            target.base_url
+        );
+
+        let (outcome, supported): (Result<Option<String>, RequestError>, Option<bool>) =
+            if target.base_url.trim().is_empty() {
+                (Err("No base URL configured".to_string().into()), None)
+            } else if target.api_key.is_empty() {
+                (Err("No API key configured".to_string().into()), None)
+            } else {
+                match (mode, capability) {
+                    (TestMode::Ping, _) => (self.probe_endpoint(target).await.map(|()| None), None),
+                    (_, Some(c)) => {
+                        let prompt = prompt_used.as_deref().unwrap_or(prompt);
+                        match self.run_capability_probe(c, target, prompt).await {
+                            Ok(text) => (Ok(Some(text)), Some(true)),
+                            Err(ProbeFailure::Unsupported(e)) => (Err(e.into()), Some(false)),
+                            Err(ProbeFailure::Failed(e)) => (Err(e), None),
+                        }
+                    }
+                    _ => (self.run_inference(target, prompt).await.map(Some), None),
+                }
+            };
+        let latency_ms = start.elapsed().as_millis() as u32;
+        let (success, error, status_code, response_text) = match outcome {
+            Ok(text) => (true, None, None, text),
+            Err(e) => (false, Some(e.message), e.status, None),
+        };
+
+        ConnectionDiagnostics {
+            success,
+            provider: target.label.to_string(),
+            model_id: target.model_id.to_string(),
+            latency_ms,
+            error,
+            status_code,
+            timestamp: Utc::now().to_rfc3339(),
+            test_mode: mode.clone(),
+            response_text,
+            prompt_used,
+            attempts: Vec::new(),
+            capabilities: capability
+                .zip(supported)
+                .map(|(c, supported)| c.report(supported)),
+        }
+    }
+
+    async fn probe_endpoint(&self, target: Target<'_>) -> Result<(), RequestError> {
+        let url = target.protocol.models_url(target.base_url);
+        log::debug!(
+            "Connectivity: probe_endpoint url={url} protocol={:?}",
+            target.protocol
+        );
+
+        let req = target
+            .protocol
+            .authorize(self.client.get(&url), target.api_key);
 
         let resp = tokio::time::timeout(
             Duration::from_secs(self.config.timeout_seconds as u64),
-            req.send(),
+            self.with_extra(req).send(),
         )
         .await
         .map_err(|_| "Request timed out".to_string())?
@@ -319,138 +571,184 @@ impl ModelTester {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unable to read response body".to_string());
-            let truncated = if body.len() > 500 {
-                format!("{}...", &body[..500])
-            } else {
-                body
-            };
+            let truncated = truncate_body(&body);
             log::warn!(
                 "Connectivity: probe_endpoint failed, url={url} status={status} body={truncated}"
             );
-            Err(format!("HTTP {status}: {truncated}"))
+            Err(RequestError::http(
+                status,
+                format!("HTTP {status}: {truncated}"),
+            ))
         }
     }
 
     /// Run a real inference request via reqwest and return the response text.
     async fn run_inference(
         &self,
-        provider: &Provider,
-        base_url: &str,
-        api_key: &str,
-        model_id: &str,
+        target: Target<'_>,
         prompt: &str,
-    ) -> Result<String, String> {
-        let base = base_url.trim_end_matches('/');
-        let timeout_secs = self.config.timeout_seconds.max(30) as u64;
-        log::debug!(
-            "Connectivity: run_inference model={model_id} base_url={base} timeout={timeout_secs}s"
-        );
-
-        let (url, req) = match provider {
-            Provider::Anthropic => {
-                let url = format!("{base}/v1/messages");
-                let body = serde_json::json!({
-                    "model": model_id,
-                    "max_tokens": 32,
-                    "messages": [{"role": "user", "content": prompt}]
-                });
-                let r = self
-                    .client
-                    .post(&url)
-                    .header("Content-Type", "application/json")
-                    .header("x-api-key", api_key)
-                    .header("anthropic-version", "2023-06-01")
-                    .json(&body);
-                (url, r)
-            }
-            _ => {
-                let url = format!("{base}/v1/chat/completions");
-                let body = serde_json::json!({
-                    "model": model_id,
-                    "max_tokens": 32,
-                    "messages": [{"role": "user", "content": prompt}]
-                });
-                let r = self
-                    .client
-                    .post(&url)
-                    .header("Authorization", format!("Bearer {api_key}"))
-                    .json(&body);
-                (url, r)
-            }
-        };
-
-        log::debug!("Connectivity: inference POST {url}");
-
-        let resp = tokio::time::timeout(Duration::from_secs(timeout_secs), req.send())
-            .await
-            .map_err(|_| "Inference request timed out".to_string())?
-            .map_err(|e| format!("Inference network error: {e}"))?;
-
-        let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read inference response body: {e}"))?;
+    ) -> Result<String, RequestError> {
+        let body = inference_body(target.protocol, target.model_id, prompt);
+        let (status, body) = self.post(target, &body, false, "Inference").await?;
 
         if !status.is_success() {
-            let truncated = if body.len() > 500 {
-                format!("{}...", &body[..500])
-            } else {
-                body
-            };
+            let truncated = truncate_body(&body);
             log::warn!(
-                "Connectivity: inference failed, url={url} status={status} body={truncated}"
+                "Connectivity: inference failed, model={} status={status} body={truncated}",
+                target.model_id
             );
-            return Err(format!("Inference HTTP {status}: {truncated}"));
+            return Err(RequestError::http(
+                status,
+                format!("Inference HTTP {status}: {truncated}"),
+            ));
         }
 
         let data: serde_json::Value = serde_json::from_str(&body).map_err(|e| {
-            let truncated = if body.len() > 500 {
-                format!("{}...", &body[..500])
-            } else {
-                body.clone()
-            };
             log::warn!(
-                "Connectivity: failed to parse inference response, url={url} body={truncated}"
+                "Connectivity: failed to parse inference response, model={} body={}",
+                target.model_id,
+                truncate_body(&body)
             );
             format!("Failed to parse inference response: {e}")
         })?;
 
-        // Extract response text based on provider format
-        let text = match provider {
-            Provider::Anthropic => data
-                .get("content")
-                .and_then(|c| c.as_array())
-                .and_then(|arr| arr.first())
-                .and_then(|block| block.get("text"))
-                .and_then(|t| t.as_str())
-                .unwrap_or("")
-                .to_string(),
-            _ => data
-                .get("choices")
-                .and_then(|c| c.as_array())
-                .and_then(|arr| arr.first())
-                .and_then(|choice| choice.get("message"))
-                .and_then(|msg| msg.get("content"))
-                .and_then(|t| t.as_str())
-                .unwrap_or("")
-                .to_string(),
-        };
-
+        let text = message_text(target.protocol, &data).unwrap_or("");
         log::info!(
-            "Connectivity: inference OK for model={model_id}, response_len={}",
+            "Connectivity: inference OK for model={}, response_len={}",
+            target.model_id,
             text.len()
         );
 
         // Truncate very long responses
-        let truncated = if text.len() > 200 {
-            format!("{}…", &text[..200])
-        } else {
-            text
+        let truncated = match text.char_indices().nth(200) {
+            Some((idx, _)) => format!("{}…", &text[..idx]),
+            None => text.to_string(),
         };
-
         Ok(truncated)
     }
+
+    /// Send a capability probe and validate the protocol-specific response shape.
+    async fn run_capability_probe(
+        &self,
+        capability: Capability,
+        target: Target<'_>,
+        prompt: &str,
+    ) -> Result<String, ProbeFailure> {
+        let body = capability_request_body(capability, target.protocol, target.model_id, prompt);
+        let stream = capability == Capability::Streaming;
+        let (status, text) = self
+            .post(target, &body, stream, "Probe")
+            .await
+            .map_err(|e| ProbeFailure::Failed(e.into()))?;
+        if !status.is_success() {
+            let msg = format!("HTTP {status}: {}", truncate_body(&text));
+            log::warn!("Connectivity: {} probe failed, {msg}", capability.label());
+            // 400/415/422 mean the request shape (stream, tools, image) was rejected
+            return Err(if matches!(status.as_u16(), 400 | 415 | 422) {
+                ProbeFailure::Unsupported(msg)
+            } else {
+                ProbeFailure::Failed(RequestError::http(status, msg))
+            });
+        }
+
+        match capability {
+            Capability::Streaming => parse_stream(target.protocol, &text),
+            Capability::ToolUse => parse_tool_call(target.protocol, &text),
+            Capability::Vision => parse_vision_answer(target.protocol, &text),
+        }
+    }
+
+    /// POSTs `body` to the target's generation endpoint. `what` prefixes
+    /// transport errors (e.g. "Inference request timed out").
+    async fn post(
+        &self,
+        target: Target<'_>,
+        body: &serde_json::Value,
+        stream: bool,
+        what: &str,
+    ) -> Result<(reqwest::StatusCode, String), String> {
+        let url = target
+            .protocol
+            .message_url(target.base_url, target.model_id, stream);
+        let timeout_secs = self.config.timeout_seconds.max(30) as u64;
+        log::debug!("Connectivity: POST {url} timeout={timeout_secs}s");
+
+        let req = target
+            .protocol
+            .authorize(self.client.post(&url), target.api_key);
+        let resp = tokio::time::timeout(
+            Duration::from_secs(timeout_secs),
+            self.with_extra(req).json(body).send(),
+        )
+        .await
+        .map_err(|_| format!("{what} request timed out"))?
+        .map_err(|e| format!("{what} network error: {e}"))?;
+
+        let status = resp.status();
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read {} response body: {e}", what.to_lowercase()))?;
+        Ok((status, text))
+    }
+}
+
+/// What a single test talks to.
+#[derive(Clone, Copy)]
+struct Target<'a> {
+    protocol: WireProtocol,
+    /// Provider name reported in diagnostics
+    label: &'a str,
+    base_url: &'a str,
+    api_key: &'a str,
+    model_id: &'a str,
+}
+
+/// A failed attempt: the message shown to the user and the HTTP status, when
+/// the endpoint answered.
+struct RequestError {
+    message: String,
+    status: Option<u16>,
+}
+
+impl RequestError {
+    fn http(status: reqwest::StatusCode, message: String) -> Self {
+        Self {
+            message,
+            status: Some(status.as_u16()),
+        }
+    }
+}
+
+impl From<String> for RequestError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            status: None,
+        }
+    }
+}
+
+impl<'a> Target<'a> {
+    fn for_provider(
+        provider: &Provider,
+        label: &'a str,
+        base_url: &'a str,
+        api_key: &'a str,
+        model_id: &'a str,
+    ) -> Self {
+        Self {
+            protocol: WireProtocol::from_provider(provider),
+            label,
+            base_url,
+            api_key,
+            model_id,
+        }
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.base_url.trim().is_empty() && !self.api_key.is_empty()
+    }
 }
 
 impl Default for ModelTester {
@@ -465,10 +763,105 @@ impl Default for TestConfig {
             timeout_seconds: default_timeout(),
             retry_on_failure: default_retry(),
             max_retries: default_max_retries(),
+            retry_backoff_ms: default_retry_backoff_ms(),
+            max_concurrency: default_max_concurrency(),
         }
     }
 }
 
+// ============================================================================
+// Wire protocols
+// ============================================================================
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+impl WireProtocol {
+    /// Protocol used for a Factory custom model.
+    pub fn from_provider(provider: &Provider) -> Self {
+        match provider {
+            Provider::Anthropic => Self::AnthropicMessages,
+            Provider::Openai | Provider::GenericChatCompletionApi => Self::ChatCompletions,
+        }
+    }
+
+    /// Parses a provider or protocol name, including the `api` values used by
+    /// Pi/OpenClaw (`anthropic-messages`, `openai-responses`,
+    /// `google-generative-ai`); unknown names fall back to chat completions.
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "anthropic" | "anthropic-messages" => Self::AnthropicMessages,
+            "responses" | "openai-responses" => Self::Responses,
+            "gemini" | "google" | "google-generative-ai" => Self::Gemini,
+            _ => Self::ChatCompletions,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::AnthropicMessages => "anthropic",
+            Self::ChatCompletions => "openai",
+            Self::Responses => "responses",
+            Self::Gemini => "gemini",
+        }
+    }
+
+    /// Lightweight endpoint used by ping tests.
+    pub fn models_url(self, base_url: &str) -> String {
+        match self {
+            Self::Gemini => format!("{}/models", gemini_base(base_url)),
+            _ => format!("{}/v1/models", api_base(base_url)),
+        }
+    }
+
+    /// Generation endpoint. Gemini streams through a different method.
+    pub fn message_url(self, base_url: &str, model_id: &str, stream: bool) -> String {
+        let base = api_base(base_url);
+        match self {
+            Self::AnthropicMessages => format!("{base}/v1/messages"),
+            Self::ChatCompletions => format!("{base}/v1/chat/completions"),
+            Self::Responses => format!("{base}/v1/responses"),
+            Self::Gemini => {
+                let model = model_id.trim_start_matches("models/");
+                if stream {
+                    format!(
+                        "{}/models/{model}:streamGenerateContent?alt=sse",
+                        gemini_base(base_url)
+                    )
+                } else {
+                    format!("{}/models/{model}:generateContent", gemini_base(base_url))
+                }
+            }
+        }
+    }
+
+    fn authorize(self, req: reqwest::RequestBuilder, api_key: &str) -> reqwest::RequestBuilder {
+        match self {
+            Self::AnthropicMessages => req
+                .header("x-api-key", api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION),
+            Self::ChatCompletions | Self::Responses => {
+                req.header("Authorization", format!("Bearer {api_key}"))
+            }
+            Self::Gemini => req.header("x-goog-api-key", api_key),
+        }
+    }
+}
+
+/// Base URL without a trailing `/v1`, which most tools include in their config.
+fn api_base(base_url: &str) -> &str {
+    base_url.trim_end_matches('/').trim_end_matches("/v1")
+}
+
+/// Gemini base URL including the API version (`/v1beta` unless one is given).
+fn gemini_base(base_url: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    if base.ends_with("/v1beta") || base.ends_with("/v1") {
+        base.to_string()
+    } else {
+        format!("{base}/v1beta")
+    }
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
@@ -481,11 +874,478 @@ fn provider_to_string(provider: &Provider) -> String {
     }
 }
 
-fn string_to_provider(s: &str) -> Provider {
-    match s {
-        "anthropic" => Provider::Anthropic,
-        "openai" => Provider::Openai,
-        _ => Provider::GenericChatCompletionApi,
+/// Upper bound for a single backoff delay.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+fn retry_delay(config: &TestConfig, attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(16);
+    Duration::from_millis(u64::from(config.retry_backoff_ms).saturating_mul(factor))
+        .min(MAX_RETRY_DELAY)
+}
+
+/// Whether a failed attempt with this HTTP status is worth retrying.
+///
+/// Client errors (4xx other than 408/429) will fail the same way again, so
+/// they are reported immediately. Failures without a status (timeouts,
+/// network errors) are retried.
+fn is_retryable(status: Option<u16>) -> bool {
+    match status {
+        Some(408) | Some(429) => true,
+        Some(code) => !(400..500).contains(&code),
+        None => true,
+    }
+}
+
+// ============================================================================
+// Capability probes
+// ============================================================================
+
+/// Name of the tool the tool-use probe forces the model to call.
+const PROBE_TOOL_NAME: &str = "get_weather";
+const TOOL_USE_PROMPT: &str = "What is the weather in Paris? Use the get_weather tool.";
+const VISION_PROMPT: &str = "What color is this image? Answer in one word.";
+/// 1x1 PNG used by the vision probe.
+const PROBE_IMAGE_PNG_BASE64: &str =
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8z8BQDwAEhQGAhKmMIQAAAABJRU5ErkJggg==";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Capability {
+    Streaming,
+    ToolUse,
+    Vision,
+}
+
+impl Capability {
+    fn from_mode(mode: &TestMode) -> Option<Self> {
+        match mode {
+            TestMode::Streaming => Some(Self::Streaming),
+            TestMode::ToolUse => Some(Self::ToolUse),
+            TestMode::Vision => Some(Self::Vision),
+            TestMode::Ping | TestMode::Inference => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Streaming => "streaming",
+            Self::ToolUse => "tool-use",
+            Self::Vision => "vision",
+        }
+    }
+
+    /// Streaming reuses the caller's prompt; the other probes need a fixed one.
+    fn prompt(self, user_prompt: &str) -> &str {
+        match self {
+            Self::Streaming => user_prompt,
+            Self::ToolUse => TOOL_USE_PROMPT,
+            Self::Vision => VISION_PROMPT,
+        }
+    }
+
+    fn report(self, supported: bool) -> ModelCapabilities {
+        let mut caps = ModelCapabilities::default();
+        match self {
+            Self::Streaming => caps.streaming = Some(supported),
+            Self::ToolUse => caps.tool_use = Some(supported),
+            Self::Vision => caps.vision = Some(supported),
+        }
+        caps
+    }
+}
+
+enum ProbeFailure {
+    /// The endpoint rejected the request or answered with the wrong shape
+    Unsupported(String),
+    /// Inconclusive (network, auth, server error)
+    Failed(RequestError),
+}
+
+/// Minimal text generation request.
+fn inference_body(protocol: WireProtocol, model_id: &str, prompt: &str) -> serde_json::Value {
+    match protocol {
+        WireProtocol::AnthropicMessages | WireProtocol::ChatCompletions => serde_json::json!({
+            "model": model_id,
+            "max_tokens": 32,
+            "messages": [{"role": "user", "content": prompt}]
+        }),
+        WireProtocol::Responses => serde_json::json!({
+            "model": model_id,
+            "input": prompt,
+            "max_output_tokens": 32
+        }),
+        WireProtocol::Gemini => serde_json::json!({
+            "contents": [{"role": "user", "parts": [{"text": prompt}]}],
+            "generationConfig": {"maxOutputTokens": 32}
+        }),
+    }
+}
+
+fn capability_request_body(
+    capability: Capability,
+    protocol: WireProtocol,
+    model_id: &str,
+    prompt: &str,
+) -> serde_json::Value {
+    let image_data_url = format!("data:image/png;base64,{PROBE_IMAGE_PNG_BASE64}");
+    let mut body = match protocol {
+        WireProtocol::AnthropicMessages | WireProtocol::ChatCompletions => {
+            let content = match capability {
+                Capability::Vision if protocol == WireProtocol::AnthropicMessages => {
+                    serde_json::json!([
+                        {
+                            "type": "image",
+                            "source": {
+                                "type": "base64",
+                                "media_type": "image/png",
+                                "data": PROBE_IMAGE_PNG_BASE64
+                            }
+                        },
+                        {"type": "text", "text": prompt}
+                    ])
+                }
+                Capability::Vision => serde_json::json!([
+                    {"type": "text", "text": prompt},
+                    {"type": "image_url", "image_url": {"url": image_data_url}}
+                ]),
+                _ => serde_json::json!(prompt),
+            };
+            serde_json::json!({
+                "model": model_id,
+                "max_tokens": 64,
+                "messages": [{"role": "user", "content": content}]
+            })
+        }
+        WireProtocol::Responses => {
+            let input = match capability {
+                Capability::Vision => serde_json::json!([{
+                    "role": "user",
+                    "content": [
+                        {"type": "input_text", "text": prompt},
+                        {"type": "input_image", "image_url": image_data_url}
+                    ]
+                }]),
+                _ => serde_json::json!(prompt),
+            };
+            serde_json::json!({
+                "model": model_id,
+                "max_output_tokens": 64,
+                "input": input
+            })
+        }
+        WireProtocol::Gemini => {
+            let parts = match capability {
+                Capability::Vision => serde_json::json!([
+                    {"inlineData": {"mimeType": "image/png", "data": PROBE_IMAGE_PNG_BASE64}},
+                    {"text": prompt}
+                ]),
+                _ => serde_json::json!([{"text": prompt}]),
+            };
+            serde_json::json!({
+                "contents": [{"role": "user", "parts": parts}],
+                "generationConfig": {"maxOutputTokens": 64}
+            })
+        }
+    };
+
+    match capability {
+        // Gemini selects streaming through the URL instead of the body
+        Capability::Streaming if protocol != WireProtocol::Gemini => {
+            body["stream"] = serde_json::json!(true);
+        }
+        Capability::Streaming => {}
+        Capability::ToolUse => {
+            let parameters = serde_json::json!({
+                "type": "object",
+                "properties": {"city": {"type": "string"}},
+                "required": ["city"]
+            });
+            let description = "Get the current weather for a city";
+            match protocol {
+                WireProtocol::AnthropicMessages => {
+                    body["tools"] = serde_json::json!([{
+                        "name": PROBE_TOOL_NAME,
+                        "description": description,
+                        "input_schema": parameters
+                    }]);
+                    body["tool_choice"] =
+                        serde_json::json!({"type": "tool", "name": PROBE_TOOL_NAME});
+                }
+                WireProtocol::ChatCompletions => {
+                    body["tools"] = serde_json::json!([{
+                        "type": "function",
+                        "function": {
+                            "name": PROBE_TOOL_NAME,
+                            "description": description,
+                            "parameters": parameters
+                        }
+                    }]);
+                    body["tool_choice"] = serde_json::json!({
+                        "type": "function",
+                        "function": {"name": PROBE_TOOL_NAME}
+                    });
+                }
+                WireProtocol::Responses => {
+                    body["tools"] = serde_json::json!([{
+                        "type": "function",
+                        "name": PROBE_TOOL_NAME,
+                        "description": description,
+                        "parameters": parameters
+                    }]);
+                    body["tool_choice"] =
+                        serde_json::json!({"type": "function", "name": PROBE_TOOL_NAME});
+                }
+                WireProtocol::Gemini => {
+                    // Gemini uses OpenAPI-style upper-case schema types
+                    body["tools"] = serde_json::json!([{
+                        "functionDeclarations": [{
+                            "name": PROBE_TOOL_NAME,
+                            "description": description,
+                            "parameters": {
+                                "type": "OBJECT",
+                                "properties": {"city": {"type": "STRING"}},
+                                "required": ["city"]
+                            }
+                        }]
+                    }]);
+                    body["toolConfig"] = serde_json::json!({
+                        "functionCallingConfig": {
+                            "mode": "ANY",
+                            "allowedFunctionNames": [PROBE_TOOL_NAME]
+                        }
+                    });
+                }
+            }
+        }
+        Capability::Vision => {}
+    }
+    body
+}
+
+/// Text of the first message/choice/candidate in a non-streaming response.
+fn message_text(protocol: WireProtocol, data: &serde_json::Value) -> Option<&str> {
+    match protocol {
+        WireProtocol::AnthropicMessages => data
+            .get("content")
+            .and_then(|c| c.as_array())
+            .and_then(|arr| arr.iter().find_map(|block| block.get("text")))
+            .and_then(|t| t.as_str()),
+        WireProtocol::ChatCompletions => data
+            .pointer("/choices/0/message/content")
+            .and_then(|t| t.as_str()),
+        WireProtocol::Responses => data
+            .get("output_text")
+            .and_then(|t| t.as_str())
+            .or_else(|| {
+                data.get("output")?
+                    .as_array()?
+                    .iter()
+                    .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("message"))
+                    .filter_map(|item| item.get("content")?.as_array())
+                    .flatten()
+                    .filter(|c| c.get("type").and_then(|t| t.as_str()) == Some("output_text"))
+                    .find_map(|c| c.get("text")?.as_str())
+            }),
+        WireProtocol::Gemini => gemini_text(data),
+    }
+}
+
+fn gemini_text(data: &serde_json::Value) -> Option<&str> {
+    data.pointer("/candidates/0/content/parts")?
+        .as_array()?
+        .iter()
+        .find_map(|part| part.get("text")?.as_str())
+}
+
+fn parse_probe_json(body: &str) -> Result<serde_json::Value, ProbeFailure> {
+    serde_json::from_str(body).map_err(|e| {
+        ProbeFailure::Unsupported(format!(
+            "Failed to parse probe response: {e}: {}",
+            truncate_body(body)
+        ))
+    })
+}
+
+fn stream_error(event: &serde_json::Value) -> ProbeFailure {
+    let msg = event
+        .pointer("/error/message")
+        .or_else(|| event.pointer("/response/error/message"))
+        .or_else(|| event.get("message"))
+        .and_then(|m| m.as_str())
+        .unwrap_or("unknown error");
+    ProbeFailure::Failed(format!("Stream error: {msg}").into())
+}
+
+/// Validates an SSE body and returns the concatenated streamed text.
+///
+/// Anthropic streams `content_block_delta` events ending in `message_stop`;
+/// chat completions stream `choices[].delta` chunks ending in `data: [DONE]`
+/// or a `finish_reason`; the Responses API streams `response.output_text.delta`
+/// events ending in `response.completed`; Gemini streams candidates ending in
+/// a `finishReason`.
+fn parse_stream(protocol: WireProtocol, body: &str) -> Result<String, ProbeFailure> {
+    let events: Vec<&str> = body
+        .lines()
+        .filter_map(|l| l.strip_prefix("data:"))
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .collect();
+    if events.is_empty() {
+        return Err(ProbeFailure::Unsupported(format!(
+            "Response was not an SSE stream: {}",
+            truncate_body(body)
+        )));
+    }
+
+    let mut text = String::new();
+    let mut deltas = 0;
+    let mut finished = false;
+    for data in events {
+        if data == "[DONE]" {
+            finished = true;
+            continue;
+        }
+        let event: serde_json::Value = serde_json::from_str(data)
+            .map_err(|e| ProbeFailure::Unsupported(format!("Malformed SSE event: {e}: {data}")))?;
+        match protocol {
+            WireProtocol::AnthropicMessages => match event.get("type").and_then(|t| t.as_str()) {
+                Some("content_block_delta") => {
+                    deltas += 1;
+                    if let Some(t) = event.pointer("/delta/text").and_then(|t| t.as_str()) {
+                        text.push_str(t);
+                    }
+                }
+                Some("message_stop") => finished = true,
+                Some("error") => return Err(stream_error(&event)),
+                _ => {}
+            },
+            WireProtocol::ChatCompletions => {
+                let Some(choice) = event.pointer("/choices/0") else {
+                    continue;
+                };
+                deltas += 1;
+                if let Some(t) = choice.pointer("/delta/content").and_then(|t| t.as_str()) {
+                    text.push_str(t);
+                }
+                if choice.get("finish_reason").is_some_and(|f| !f.is_null()) {
+                    finished = true;
+                }
+            }
+            WireProtocol::Responses => match event.get("type").and_then(|t| t.as_str()) {
+                Some("response.output_text.delta") => {
+                    deltas += 1;
+                    if let Some(t) = event.get("delta").and_then(|t| t.as_str()) {
+                        text.push_str(t);
+                    }
+                }
+                Some("response.completed") => finished = true,
+                Some("error") | Some("response.failed") => return Err(stream_error(&event)),
+                _ => {}
+            },
+            WireProtocol::Gemini => {
+                if event.get("error").is_some() {
+                    return Err(stream_error(&event));
+                }
+                let Some(candidate) = event.pointer("/candidates/0") else {
+                    continue;
+                };
+                deltas += 1;
+                if let Some(t) = gemini_text(&event) {
+                    text.push_str(t);
+                }
+                if candidate.get("finishReason").is_some_and(|f| !f.is_null()) {
+                    finished = true;
+                }
+            }
+        }
+    }
+
+    if deltas == 0 {
+        return Err(ProbeFailure::Unsupported(
+            "Stream contained no content deltas".to_string(),
+        ));
+    }
+    if !finished {
+        return Err(ProbeFailure::Unsupported(
+            "Stream ended without a terminal event".to_string(),
+        ));
+    }
+    Ok(text)
+}
+
+/// Validates that the model called the probe tool with JSON object arguments.
+fn parse_tool_call(protocol: WireProtocol, body: &str) -> Result<String, ProbeFailure> {
+    let data = parse_probe_json(body)?;
+    // Chat completions and Responses encode arguments as a JSON string
+    let parse_args = |name: &serde_json::Value, args: &serde_json::Value| {
+        let name = name.as_str()?;
+        let args: serde_json::Value = serde_json::from_str(args.as_str()?).ok()?;
+        args.is_object().then(|| (name.to_string(), args))
+    };
+    let call = match protocol {
+        WireProtocol::AnthropicMessages => data
+            .get("content")
+            .and_then(|c| c.as_array())
+            .and_then(|blocks| {
+                blocks
+                    .iter()
+                    .find(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+            })
+            .and_then(|b| {
+                let name = b.get("name")?.as_str()?;
+                let input = b.get("input").filter(|i| i.is_object())?;
+                Some((name.to_string(), input.clone()))
+            }),
+        WireProtocol::ChatCompletions => data
+            .pointer("/choices/0/message/tool_calls/0/function")
+            .and_then(|f| parse_args(f.get("name")?, f.get("arguments")?)),
+        WireProtocol::Responses => data
+            .get("output")
+            .and_then(|o| o.as_array())
+            .and_then(|items| {
+                items
+                    .iter()
+                    .find(|item| item.get("type").and_then(|t| t.as_str()) == Some("function_call"))
+            })
+            .and_then(|item| parse_args(item.get("name")?, item.get("arguments")?)),
+        WireProtocol::Gemini => data
+            .pointer("/candidates/0/content/parts")
+            .and_then(|p| p.as_array())
+            .and_then(|parts| parts.iter().find_map(|part| part.get("functionCall")))
+            .and_then(|f| {
+                let name = f.get("name")?.as_str()?;
+                let args = f.get("args").filter(|a| a.is_object())?;
+                Some((name.to_string(), args.clone()))
+            }),
+    };
+
+    match call {
+        Some((name, args)) if name == PROBE_TOOL_NAME => Ok(format!("{name}({args})")),
+        Some((name, _)) => Err(ProbeFailure::Unsupported(format!(
+            "Model called unexpected tool {name}"
+        ))),
+        None => Err(ProbeFailure::Unsupported(
+            "Response contained no tool call".to_string(),
+        )),
+    }
+}
+
+/// Validates that the model answered the image prompt with text.
+fn parse_vision_answer(protocol: WireProtocol, body: &str) -> Result<String, ProbeFailure> {
+    let data = parse_probe_json(body)?;
+    match message_text(protocol, &data) {
+        Some(text) if !text.trim().is_empty() => Ok(text.trim().to_string()),
+        _ => Err(ProbeFailure::Unsupported(
+            "Response contained no answer text".to_string(),
+        )),
+    }
+}
+
+/// Truncates a response body for error messages (char-boundary safe).
+pub(crate) fn truncate_body(body: &str) -> String {
+    const LIMIT: usize = 500;
+    match body.char_indices().nth(LIMIT) {
+        Some((idx, _)) => format!("{}...", &body[..idx]),
+        None => body.to_string(),
     }
 }
 
@@ -493,71 +1353,214 @@ fn string_to_provider(s: &str) -> Provider {
 // Public API
 // ============================================================================
 
-const DEFAULT_INFERENCE_PROMPT: &str = "Hi";
+pub(crate) const DEFAULT_INFERENCE_PROMPT: &str = "Hi";
+
+/// Key in `~/.droidgear/settings.json` holding the saved [`TestConfig`].
+const TEST_CONFIG_SETTINGS_KEY: &str = "connectivityTestConfig";
+
+pub fn get_test_config_for_home(home_dir: &Path) -> Result<TestConfig, String> {
+    let settings_path = paths::get_droidgear_settings_path_for_home(home_dir);
+    let settings = paths::read_droidgear_settings_from_path_internal(&settings_path)?;
+    match settings.get(TEST_CONFIG_SETTINGS_KEY) {
+        Some(v) => serde_json::from_value(v.clone())
+            .map_err(|e| format!("Failed to parse connectivity test config: {e}")),
+        None => Ok(TestConfig::default()),
+    }
+}
+
+pub fn save_test_config_for_home(home_dir: &Path, config: &TestConfig) -> Result<(), String> {
+    let settings_path = paths::get_droidgear_settings_path_for_home(home_dir);
+    let mut settings = paths::read_droidgear_settings_from_path_internal(&settings_path)?;
+    if !settings.is_object() {
+        settings = serde_json::json!({});
+    }
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize connectivity test config: {e}"))?;
+    if let Some(obj) = settings.as_object_mut() {
+        obj.insert(TEST_CONFIG_SETTINGS_KEY.to_string(), value);
+    }
+    paths::write_droidgear_settings_to_path_internal(&settings_path, &settings)
+}
+
+pub fn get_test_config() -> Result<TestConfig, String> {
+    get_test_config_for_home(&paths::get_home_dir()?)
+}
+
+pub fn save_test_config(config: TestConfig) -> Result<(), String> {
+    save_test_config_for_home(&paths::get_home_dir()?, &config)
+}
+
+pub fn record_diagnostics(diagnostics: &ConnectionDiagnostics, base_url: &str) {
+    match paths::get_home_dir() {
+        Ok(home_dir) => record_diagnostics_for_home(&home_dir, diagnostics, base_url),
+        Err(e) => log::warn!("Connectivity: failed to record history: {e}"),
+    }
+}
+
+/// Tests `models` with at most `config.max_concurrency` requests in flight.
+/// Results are returned in the same order as `models`.
+pub async fn test_models(
+    models: Vec<CustomModel>,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
+) -> Vec<ModelTestResult> {
+    let limit = config.max_concurrency.max(1) as usize;
+    let config = Arc::new(config);
+    let prompt: Arc<str> =
+        Arc::from(prompt.unwrap_or_else(|| DEFAULT_INFERENCE_PROMPT.to_string()));
+    let failed_mode = mode.clone();
+
+    run_bounded(
+        models,
+        limit,
+        move |model| {
+            let config = Arc::clone(&config);
+            let prompt = Arc::clone(&prompt);
+            let mode = mode.clone();
+            async move {
+                custom_model_tester((*config).clone(), &model)
+                    .test_custom_model(&model, &mode, &prompt)
+                    .await
+            }
+        },
+        move |model, error| ModelTestResult {
+            model_id: model.id.clone().unwrap_or_else(|| model.model.clone()),
+            model_name: model
+                .display_name
+                .clone()
+                .unwrap_or_else(|| model.model.clone()),
+            diagnostics: ConnectionDiagnostics::failed(
+                &provider_to_string(&model.provider),
+                &model.model,
+                &failed_mode,
+                error,
+            ),
+            is_available: false,
+        },
+    )
+    .await
+}
+
+/// Runs `task` for every item with at most `limit` in flight, keeping input order.
+///
+/// A task that panics or is cancelled yields `failed(item, error)` in its slot,
+/// so every item has a result.
+pub(crate) async fn run_bounded<T, R, F, Fut, G>(
+    items: Vec<T>,
+    limit: usize,
+    task: F,
+    failed: G,
+) -> Vec<R>
+where
+    T: Clone + Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = R> + Send + 'static,
+    G: Fn(T, String) -> R,
+{
+    let mut results: Vec<Option<R>> = std::iter::repeat_with(|| None).take(items.len()).collect();
+    let mut in_flight = HashMap::new();
+    let mut pending = items.into_iter().enumerate();
+    let mut running = tokio::task::JoinSet::new();
+    loop {
+        while running.len() < limit.max(1) {
+            let Some((idx, item)) = pending.next() else {
+                break;
+            };
+            let fut = task(item.clone());
+            let handle = running.spawn(async move { (idx, fut.await) });
+            in_flight.insert(handle.id(), (idx, item));
+        }
+        match running.join_next_with_id().await {
+            Some(Ok((id, (idx, result)))) => {
+                in_flight.remove(&id);
+                results[idx] = Some(result);
+            }
+            Some(Err(e)) => {
+                log::warn!("Connectivity: test task failed: {e}");
+                if let Some((idx, item)) = in_flight.remove(&e.id()) {
+                    results[idx] = Some(failed(item, format!("Test task failed: {e}")));
+                }
+            }
+            None => break,
+        }
+    }
+
+    results.into_iter().flatten().collect()
+}
 
 pub async fn test_all_model_connections() -> Result<Vec<ModelTestResult>, String> {
     test_all_model_connections_with_mode(TestMode::Ping, None).await
 }
 
+/// Tests all custom models using the saved [`TestConfig`].
 pub async fn test_all_model_connections_with_mode(
     mode: TestMode,
     prompt: Option<String>,
 ) -> Result<Vec<ModelTestResult>, String> {
-    let tester = ModelTester::new();
-    let mut results = Vec::new();
-    let prompt_str = prompt.unwrap_or_else(|| DEFAULT_INFERENCE_PROMPT.to_string());
+    let config = get_test_config()?;
+    test_all_model_connections_with_config(mode, prompt, config).await
+}
 
+pub async fn test_all_model_connections_with_config(
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
+) -> Result<Vec<ModelTestResult>, String> {
     let models = factory_settings::load_custom_models()?;
-    for model in &models {
-        let display = model
-            .display_name
-            .clone()
-            .unwrap_or_else(|| model.model.clone());
-        let id = model.id.clone().unwrap_or_else(|| model.model.clone());
-
-        let diag = match mode {
-            TestMode::Ping => {
-                tester
-                    .test_model(
-                        &model.provider,
-                        &model.base_url,
-                        &model.api_key,
-                        &model.model,
-                    )
-                    .await
-            }
-            TestMode::Inference => {
-                tester
-                    .test_model_inference(
-                        &model.provider,
-                        &model.base_url,
-                        &model.api_key,
-                        &model.model,
-                        &prompt_str,
-                    )
-                    .await
-            }
-        };
-        let available = diag.success;
-        results.push(ModelTestResult {
-            model_id: id,
-            model_name: display,
-            diagnostics: diag,
-            is_available: available,
-        });
-    }
+    let results = test_models(models.clone(), mode, prompt, config).await;
+    record_history(&paths::get_home_dir()?, &models, &results);
+    Ok(results)
+}
 
+pub async fn test_all_model_connections_for_home(
+    home_dir: &Path,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
+) -> Result<Vec<ModelTestResult>, String> {
+    let models = factory_settings::load_custom_models_for_home(home_dir)?;
+    let results = test_models(models.clone(), mode, prompt, config).await;
+    record_history(home_dir, &models, &results);
     Ok(results)
 }
 
+/// Blocking variant of [`test_all_model_connections_for_home`] for sync callers (TUI).
+pub fn test_all_model_connections_blocking_for_home(
+    home_dir: &Path,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
+) -> Result<Vec<ModelTestResult>, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create async runtime: {e}"))?;
+    runtime.block_on(test_all_model_connections_for_home(
+        home_dir, mode, prompt, config,
+    ))
+}
+
 pub async fn test_specific_model_connection(model_id: &str) -> Result<ModelTestResult, String> {
     test_specific_model_connection_with_mode(model_id, TestMode::Ping, None).await
 }
 
+/// Tests one custom model using the saved [`TestConfig`].
 pub async fn test_specific_model_connection_with_mode(
     model_id: &str,
     mode: TestMode,
     prompt: Option<String>,
+) -> Result<ModelTestResult, String> {
+    let config = get_test_config()?;
+    test_specific_model_connection_with_config(model_id, mode, prompt, config).await
+}
+
+pub async fn test_specific_model_connection_with_config(
+    model_id: &str,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
 ) -> Result<ModelTestResult, String> {
     let models = factory_settings::load_custom_models()?;
     let model = models
@@ -565,44 +1568,99 @@ pub async fn test_specific_model_connection_with_mode(
         .find(|m| m.id.as_deref() == Some(model_id))
         .ok_or_else(|| format!("Model {model_id} not found"))?;
 
-    let tester = ModelTester::new();
-    let display = model
-        .display_name
-        .clone()
-        .unwrap_or_else(|| model.model.clone());
+    let tester = custom_model_tester(config, model);
     let prompt_str = prompt.unwrap_or_else(|| DEFAULT_INFERENCE_PROMPT.to_string());
+    let result = tester.test_custom_model(model, &mode, &prompt_str).await;
+    record_history(
+        &paths::get_home_dir()?,
+        std::slice::from_ref(model),
+        std::slice::from_ref(&result),
+    );
+    Ok(result)
+}
 
-    let diag = match mode {
-        TestMode::Ping => {
-            tester
-                .test_model(
-                    &model.provider,
-                    &model.base_url,
-                    &model.api_key,
-                    &model.model,
-                )
-                .await
-        }
-        TestMode::Inference => {
-            tester
-                .test_model_inference(
-                    &model.provider,
-                    &model.base_url,
-                    &model.api_key,
-                    &model.model,
-                    &prompt_str,
-                )
-                .await
-        }
+/// Probes streaming, tool use and vision of a custom model.
+pub async fn probe_custom_model_capabilities(
+    model: &CustomModel,
+    config: TestConfig,
+) -> CapabilityReport {
+    custom_model_tester(config, model)
+        .probe_custom_model(model)
+        .await
+}
+
+/// Tester that sends the model's `extra_headers` with every request.
+fn custom_model_tester(config: TestConfig, model: &CustomModel) -> ModelTester {
+    let tester = ModelTester::with_config(config);
+    match &model.extra_headers {
+        Some(headers) => tester.with_extra_headers(headers),
+        None => tester,
+    }
+}
+
+/// Blocking variant of [`probe_custom_model_capabilities`] for sync callers (TUI).
+pub fn probe_custom_model_capabilities_blocking(
+    model: &CustomModel,
+    config: TestConfig,
+) -> Result<CapabilityReport, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create async runtime: {e}"))?;
+    Ok(runtime.block_on(probe_custom_model_capabilities(model, config)))
+}
+
+pub async fn probe_model_capabilities_with_config(
+    model_id: &str,
+    config: TestConfig,
+) -> Result<CapabilityReport, String> {
+    let models = factory_settings::load_custom_models()?;
+    let model = models
+        .iter()
+        .find(|m| m.id.as_deref() == Some(model_id))
+        .ok_or_else(|| format!("Model {model_id} not found"))?;
+    Ok(probe_custom_model_capabilities(model, config).await)
+}
+
+/// Appends results to the connectivity history. Failures are logged only, so
+/// a broken history file never fails a test run.
+///
+/// Capability probes are skipped: an unsupported feature is not downtime.
+fn record_history(home_dir: &Path, models: &[CustomModel], results: &[ModelTestResult]) {
+    let base_url_for = |model_id: &str| {
+        models
+            .iter()
+            .find(|m| m.id.as_deref().unwrap_or(&m.model) == model_id)
+            .map(|m| m.base_url.as_str())
     };
-    let available = diag.success;
+    let records = results
+        .iter()
+        .map(|r| HistoryRecord::from_result(r, base_url_for(&r.model_id)))
+        .collect();
+    append_history(home_dir, records);
+}
 
-    Ok(ModelTestResult {
-        model_id: model_id.to_string(),
-        model_name: display,
-        diagnostics: diag,
-        is_available: available,
-    })
+/// Appends records to the connectivity history, skipping capability probes
+/// and only logging failures (see [`record_history`]).
+pub(crate) fn append_history(home_dir: &Path, records: Vec<HistoryRecord>) {
+    let records: Vec<HistoryRecord> = records
+        .into_iter()
+        .filter(|r| Capability::from_mode(&r.test_mode).is_none())
+        .collect();
+    if let Err(e) = connectivity_history::append_records_for_home(home_dir, &records) {
+        log::warn!("Connectivity: failed to record history: {e}");
+    }
+}
+
+/// Records a direct provider/endpoint test (not tied to a saved model).
+pub fn record_diagnostics_for_home(
+    home_dir: &Path,
+    diagnostics: &ConnectionDiagnostics,
+    base_url: &str,
+) {
+    let record =
+        HistoryRecord::from_diagnostics(diagnostics, &diagnostics.model_id, Some(base_url));
+    append_history(home_dir, vec![record]);
 }
 
 pub fn get_connectivity_summary(results: &[ModelTestResult]) -> ConnectivitySummary {
@@ -637,6 +1695,7 @@ pub fn get_connectivity_summary(results: &[ModelTestResult]) -> ConnectivitySumm
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{block_on, serve_recording, serve_responses};
 
     #[test]
     fn test_testconfig_default() {
@@ -644,6 +1703,8 @@ mod tests {
         assert_eq!(config.timeout_seconds, 10);
         assert!(config.retry_on_failure);
         assert_eq!(config.max_retries, 2);
+        assert_eq!(config.retry_backoff_ms, 500);
+        assert_eq!(config.max_concurrency, 4);
     }
 
     #[test]
@@ -658,10 +1719,13 @@ mod tests {
                     model_id: "model1".to_string(),
                     latency_ms: 100,
                     error: None,
+                    status_code: None,
                     timestamp: Utc::now().to_rfc3339(),
                     test_mode: TestMode::Ping,
                     response_text: None,
                     prompt_used: None,
+                    attempts: Vec::new(),
+                    capabilities: None,
                 },
                 is_available: true,
             },
@@ -674,10 +1738,13 @@ mod tests {
                     model_id: "model2".to_string(),
                     latency_ms: 0,
                     error: Some("Failed".to_string()),
+                    status_code: None,
                     timestamp: Utc::now().to_rfc3339(),
                     test_mode: TestMode::Ping,
                     response_text: None,
                     prompt_used: None,
+                    attempts: Vec::new(),
+                    capabilities: None,
                 },
                 is_available: false,
             },
@@ -698,6 +1765,351 @@ mod tests {
         assert_eq!(summary.avg_latency_ms, 0.0);
     }
 
+    fn serve_statuses(statuses: Vec<u16>) -> String {
+        serve_responses(
+            statuses
+                .into_iter()
+                .map(|status| (status, r#"{"data":[]}"#.to_string()))
+                .collect(),
+        )
+    }
+
+    fn fast_config(max_retries: u32) -> TestConfig {
+        TestConfig {
+            timeout_seconds: 5,
+            retry_on_failure: true,
+            max_retries,
+            retry_backoff_ms: 1,
+            max_concurrency: 2,
+        }
+    }
+
+    #[test]
+    fn retries_transient_failures_and_records_attempts() {
+        let base = serve_statuses(vec![503, 200]);
+        let tester = ModelTester::with_config(fast_config(2));
+        let diag = block_on(tester.test_model(&Provider::Openai, &base, "sk-test", "m"));
+        assert!(diag.success);
+        assert_eq!(diag.attempts.len(), 2);
+        assert!(!diag.attempts[0].success);
+        assert!(diag.attempts[0].error.as_deref().unwrap().contains("503"));
+        assert_eq!(diag.attempts[1].attempt, 2);
+    }
+
+    #[test]
+    fn does_not_retry_client_errors_or_when_disabled() {
+        let base = serve_statuses(vec![401, 200]);
+        let tester = ModelTester::with_config(fast_config(2));
+        let diag = block_on(tester.test_model(&Provider::Openai, &base, "sk-test", "m"));
+        assert!(!diag.success);
+        assert_eq!(diag.status_code, Some(401));
+        assert_eq!(diag.attempts.len(), 1);
+
+        let diag = block_on(tester.test_model(&Provider::Openai, &base, "", "m"));
+        assert_eq!(diag.error.as_deref(), Some("No API key configured"));
+        assert_eq!(diag.attempts.len(), 1);
+
+        let base = serve_statuses(vec![500, 200]);
+        let mut config = fast_config(2);
+        config.retry_on_failure = false;
+        let tester = ModelTester::with_config(config);
+        let diag = block_on(tester.test_model(&Provider::Openai, &base, "sk-test", "m"));
+        assert!(!diag.success);
+        assert_eq!(diag.attempts.len(), 1);
+    }
+
+    #[test]
+    fn test_models_keeps_input_order() {
+        let base = serve_statuses(vec![200, 200, 200]);
+        let models: Vec<CustomModel> = ["a", "b", "c"]
+            .iter()
+            .map(|name| CustomModel {
+                model: name.to_string(),
+                id: Some(format!("custom:{name}")),
+                index: None,
+                display_name: None,
+                base_url: base.clone(),
+                api_key: "sk-test".to_string(),
+                provider: Provider::Openai,
+                max_output_tokens: None,
+                no_image_support: None,
+                extra_args: None,
+                extra_headers: None,
+                channel_ref: None,
+            })
+            .collect();
+        let results = block_on(test_models(models, TestMode::Ping, None, fast_config(0)));
+        let ids: Vec<&str> = results.iter().map(|r| r.model_id.as_str()).collect();
+        assert_eq!(ids, vec!["custom:a", "custom:b", "custom:c"]);
+        assert!(results.iter().all(|r| r.is_available));
+    }
+
+    #[test]
+    fn test_models_sends_model_extra_headers() {
+        let (base, requests) = serve_recording(vec![(200, r#"{"data":[]}"#.to_string())]);
+        let model = CustomModel {
+            model: "m".to_string(),
+            id: Some("custom:m".to_string()),
+            index: None,
+            display_name: None,
+            base_url: base,
+            api_key: "sk-test".to_string(),
+            provider: Provider::Openai,
+            max_output_tokens: None,
+            no_image_support: None,
+            extra_args: None,
+            extra_headers: Some(HashMap::from([("X-Relay".to_string(), "team".to_string())])),
+            channel_ref: None,
+        };
+        let results = block_on(test_models(
+            vec![model],
+            TestMode::Ping,
+            None,
+            fast_config(0),
+        ));
+        assert!(
+            results[0].is_available,
+            "{:?}",
+            results[0].diagnostics.error
+        );
+        let req = requests.lock().unwrap()[0].to_lowercase();
+        assert!(req.contains("x-relay: team"), "{req}");
+    }
+
+    #[test]
+    fn run_bounded_reports_panicked_tasks_in_place() {
+        let results = block_on(run_bounded(
+            vec![1, 2, 3],
+            2,
+            |n| async move {
+                if n == 2 {
+                    panic!("boom");
+                }
+                Ok(n * 10)
+            },
+            |n, error| Err(format!("{n}: {error}")),
+        ));
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok(10));
+        assert!(matches!(&results[1], Err(e) if e.starts_with("2: Test task failed")));
+        assert_eq!(results[2], Ok(30));
+    }
+
+    fn probe(mode: TestMode, provider: Provider, base: &str) -> ConnectionDiagnostics {
+        let tester = ModelTester::with_config(fast_config(0));
+        block_on(tester.test_model_capability(&mode, &provider, base, "sk-test", "m", "Hi"))
+    }
+
+    #[test]
+    fn streaming_probe_validates_sse_shape() {
+        let sse = concat!(
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"lo\"},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n"
+        );
+        let base = serve_responses(vec![(200, sse.to_string())]);
+        let diag = probe(TestMode::Streaming, Provider::Openai, &base);
+        assert!(diag.success, "{:?}", diag.error);
+        assert_eq!(diag.response_text.as_deref(), Some("Hello"));
+        assert_eq!(diag.capabilities.unwrap().streaming, Some(true));
+
+        let anthropic_sse = concat!(
+            "event: message_start\ndata: {\"type\":\"message_start\"}\n\n",
+            "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n",
+            "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"
+        );
+        let base = serve_responses(vec![(200, anthropic_sse.to_string())]);
+        let diag = probe(TestMode::Streaming, Provider::Anthropic, &base);
+        assert!(diag.success, "{:?}", diag.error);
+        assert_eq!(diag.response_text.as_deref(), Some("Hi"));
+
+        // Relay ignored `stream: true` and answered with plain JSON
+        let base = serve_responses(vec![(
+            200,
+            r#"{"choices":[{"message":{"content":"Hi"}}]}"#.to_string(),
+        )]);
+        let diag = probe(TestMode::Streaming, Provider::Openai, &base);
+        assert!(!diag.success);
+        assert_eq!(diag.capabilities.unwrap().streaming, Some(false));
+    }
+
+    #[test]
+    fn tool_use_probe_validates_tool_call() {
+        let body = r#"{"content":[{"type":"tool_use","id":"t1","name":"get_weather","input":{"city":"Paris"}}]}"#;
+        let base = serve_responses(vec![(200, body.to_string())]);
+        let diag = probe(TestMode::ToolUse, Provider::Anthropic, &base);
+        assert!(diag.success, "{:?}", diag.error);
+        assert_eq!(diag.capabilities.unwrap().tool_use, Some(true));
+
+        let body = r#"{"choices":[{"message":{"tool_calls":[{"type":"function","function":{"name":"get_weather","arguments":"{\"city\":\"Paris\"}"}}]}}]}"#;
+        let base = serve_responses(vec![(200, body.to_string())]);
+        let diag = probe(TestMode::ToolUse, Provider::Openai, &base);
+        assert!(diag.success, "{:?}", diag.error);
+
+        let body = r#"{"choices":[{"message":{"content":"It is sunny."}}]}"#;
+        let base = serve_responses(vec![(200, body.to_string())]);
+        let diag = probe(TestMode::ToolUse, Provider::Openai, &base);
+        assert!(!diag.success);
+        assert_eq!(diag.capabilities.unwrap().tool_use, Some(false));
+    }
+
+    #[test]
+    fn vision_probe_distinguishes_rejection_from_outage() {
+        let body = r#"{"choices":[{"message":{"content":"Red"}}]}"#;
+        let base = serve_responses(vec![(200, body.to_string())]);
+        let diag = probe(TestMode::Vision, Provider::Openai, &base);
+        assert!(diag.success);
+        assert_eq!(diag.capabilities.unwrap().vision, Some(true));
+
+        let base = serve_responses(vec![(
+            400,
+            r#"{"error":"image input not supported"}"#.to_string(),
+        )]);
+        let tester = ModelTester::with_config(fast_config(2));
+        let diag = block_on(tester.test_model_capability(
+            &TestMode::Vision,
+            &Provider::Openai,
+            &base,
+            "sk-test",
+            "m",
+            "Hi",
+        ));
+        assert_eq!(diag.capabilities.unwrap().vision, Some(false));
+        assert_eq!(diag.attempts.len(), 1);
+
+        let base = serve_responses(vec![(503, "{}".to_string())]);
+        let diag = probe(TestMode::Vision, Provider::Openai, &base);
+        assert!(!diag.success);
+        assert!(diag.capabilities.is_none());
+    }
+
+    fn endpoint(protocol: WireProtocol, mode: TestMode, base: &str) -> ConnectionDiagnostics {
+        let tester = ModelTester::with_config(fast_config(0));
+        block_on(tester.test_endpoint(protocol, &mode, base, "sk-test", "m", "Hi"))
+    }
+
+    #[test]
+    fn wire_protocol_urls_and_names() {
+        assert_eq!(WireProtocol::from_name("gemini"), WireProtocol::Gemini);
+        assert_eq!(
+            WireProtocol::from_name("Responses"),
+            WireProtocol::Responses
+        );
+        assert_eq!(
+            WireProtocol::from_name("generic"),
+            WireProtocol::ChatCompletions
+        );
+        assert_eq!(
+            WireProtocol::Gemini.models_url("https://g.example/"),
+            "https://g.example/v1beta/models"
+        );
+        assert_eq!(
+            WireProtocol::Gemini.message_url("https://g.example/v1", "models/gemini-2.5-pro", true),
+            "https://g.example/v1/models/gemini-2.5-pro:streamGenerateContent?alt=sse"
+        );
+        assert_eq!(
+            WireProtocol::Responses.message_url("https://api.example", "m", true),
+            "https://api.example/v1/responses"
+        );
+    }
+
+    #[test]
+    fn gemini_ping_uses_models_endpoint_and_goog_key() {
+        let (base, requests) = serve_recording(vec![(200, r#"{"models":[]}"#.to_string())]);
+        let diag = block_on(
+            ModelTester::with_config(fast_config(0))
+                .test_model_direct("gemini", &base, "g-key", "m"),
+        );
+        assert!(diag.success, "{:?}", diag.error);
+        let req = requests.lock().unwrap()[0].to_lowercase();
+        assert!(req.starts_with("get /v1beta/models "));
+        assert!(req.contains("x-goog-api-key: g-key"));
+        assert!(!req.contains("authorization:"));
+    }
+
+    #[test]
+    fn responses_inference_and_tool_call() {
+        let body = r#"{"output":[{"type":"reasoning"},{"type":"message","content":[{"type":"output_text","text":"Hello!"}]}]}"#;
+        let (base, requests) = serve_recording(vec![(200, body.to_string())]);
+        let diag = endpoint(WireProtocol::Responses, TestMode::Inference, &base);
+        assert_eq!(diag.response_text.as_deref(), Some("Hello!"));
+        let req = requests.lock().unwrap()[0].clone();
+        assert!(req.starts_with("POST /v1/responses "));
+        assert!(req.contains(r#""input":"Hi""#));
+
+        let body = r#"{"output":[{"type":"function_call","name":"get_weather","arguments":"{\"city\":\"Paris\"}"}]}"#;
+        let base = serve_responses(vec![(200, body.to_string())]);
+        let diag = endpoint(WireProtocol::Responses, TestMode::ToolUse, &base);
+        assert!(diag.success, "{:?}", diag.error);
+        assert_eq!(diag.capabilities.and_then(|c| c.tool_use), Some(true));
+
+        let sse = "event: response.output_text.delta\ndata: {\"type\":\"response.output_text.delta\",\"delta\":\"Hi\"}\n\nevent: response.completed\ndata: {\"type\":\"response.completed\"}\n\n";
+        let base = serve_responses(vec![(200, sse.to_string())]);
+        let diag = endpoint(WireProtocol::Responses, TestMode::Streaming, &base);
+        assert_eq!(diag.response_text.as_deref(), Some("Hi"));
+    }
+
+    #[test]
+    fn gemini_streaming_tool_use_and_inference() {
+        let sse = "data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"Hel\"}]}}]}\n\ndata: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"lo\"}]},\"finishReason\":\"STOP\"}]}\n\n";
+        let (base, requests) = serve_recording(vec![(200, sse.to_string())]);
+        let diag = endpoint(WireProtocol::Gemini, TestMode::Streaming, &base);
+        assert!(diag.success, "{:?}", diag.error);
+        assert_eq!(diag.response_text.as_deref(), Some("Hello"));
+        assert!(requests.lock().unwrap()[0]
+            .starts_with("POST /v1beta/models/m:streamGenerateContent?alt=sse "));
+
+        let body = r#"{"candidates":[{"content":{"parts":[{"functionCall":{"name":"get_weather","args":{"city":"Paris"}}}]}}]}"#;
+        let base = serve_responses(vec![(200, body.to_string())]);
+        let diag = endpoint(WireProtocol::Gemini, TestMode::ToolUse, &base);
+        assert_eq!(
+            diag.response_text.as_deref(),
+            Some(r#"get_weather({"city":"Paris"})"#)
+        );
+
+        let body = r#"{"candidates":[{"content":{"parts":[{"text":"Hi there"}]}}]}"#;
+        let (base, requests) = serve_recording(vec![(200, body.to_string())]);
+        let diag = endpoint(WireProtocol::Gemini, TestMode::Inference, &base);
+        assert_eq!(diag.response_text.as_deref(), Some("Hi there"));
+        let req = requests.lock().unwrap()[0].clone();
+        assert!(req.starts_with("POST /v1beta/models/m:generateContent "));
+        assert!(req.contains(r#""maxOutputTokens":32"#));
+    }
+
+    #[test]
+    fn retry_policy_helpers() {
+        assert!(!is_retryable(Some(401)));
+        assert!(!is_retryable(Some(404)));
+        assert!(is_retryable(Some(408)));
+        assert!(is_retryable(Some(429)));
+        assert!(is_retryable(Some(502)));
+        assert!(is_retryable(None));
+
+        let config = fast_config(5);
+        assert_eq!(retry_delay(&config, 1), Duration::from_millis(1));
+        assert_eq!(retry_delay(&config, 3), Duration::from_millis(4));
+        let slow = TestConfig {
+            retry_backoff_ms: 20_000,
+            ..TestConfig::default()
+        };
+        assert_eq!(retry_delay(&slow, 4), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_config_round_trips_through_settings() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            get_test_config_for_home(dir.path())
+                .unwrap()
+                .max_concurrency,
+            4
+        );
+        save_test_config_for_home(dir.path(), &fast_config(1)).unwrap();
+        let loaded = get_test_config_for_home(dir.path()).unwrap();
+        assert_eq!(loaded.max_retries, 1);
+        assert_eq!(loaded.retry_backoff_ms, 1);
+    }
+
     #[test]
     fn test_provider_string_conversion() {
         assert_eq!(provider_to_string(&Provider::Anthropic), "anthropic");
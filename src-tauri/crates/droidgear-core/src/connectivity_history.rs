@@ -0,0 +1,479 @@
+//! Persistent connectivity history (core).
+//!
+//! Every connectivity test result is appended to
+//! `~/.droidgear/connectivity/history.jsonl` (one JSON record per line). The
+//! query APIs below aggregate that log into per-model availability trends:
+//! uptime, latency percentiles and the most recent failure.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::connectivity::{ConnectionDiagnostics, ModelTestResult, TestMode};
+use crate::paths;
+use crate::storage::atomic_write;
+
+/// Once the log grows past this size it is compacted to the newest records.
+const MAX_HISTORY_BYTES: u64 = 8 * 1024 * 1024;
+const COMPACT_KEEP_RECORDS: usize = 20_000;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One persisted test result
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRecord {
+    pub timestamp: String,
+    pub model_id: String,
+    pub model_name: String,
+    pub provider: String,
+    /// Endpoint the model was tested against (identifies the relay channel)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    pub test_mode: TestMode,
+    pub success: bool,
+    pub latency_ms: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of attempts made (including retries)
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+}
+
+fn default_attempts() -> u32 {
+    1
+}
+
+/// Availability statistics of one model over a time window
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelAvailabilityStats {
+    pub model_id: String,
+    pub model_name: String,
+    pub provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    pub total_tests: u32,
+    pub successful_tests: u32,
+    /// Successful tests / total tests, 0-100
+    pub uptime_percent: f64,
+    /// Latency percentiles of successful tests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p50_latency_ms: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p95_latency_ms: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_failure_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_failure_at: Option<String>,
+    pub last_tested_at: String,
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+pub fn history_path_for_home(home_dir: &Path) -> PathBuf {
+    paths::droidgear_dir_from_home(home_dir)
+        .join("connectivity")
+        .join("history.jsonl")
+}
+
+impl HistoryRecord {
+    pub fn from_result(result: &ModelTestResult, base_url: Option<&str>) -> Self {
+        let mut record = Self::from_diagnostics(&result.diagnostics, &result.model_name, base_url);
+        record.model_id = result.model_id.clone();
+        record
+    }
+
+    /// Record for a test that is not tied to a Factory custom model (direct
+    /// provider/endpoint tests and profile endpoints).
+    pub fn from_diagnostics(
+        d: &ConnectionDiagnostics,
+        model_name: &str,
+        base_url: Option<&str>,
+    ) -> Self {
+        Self {
+            timestamp: d.timestamp.clone(),
+            model_id: d.model_id.clone(),
+            model_name: model_name.to_string(),
+            provider: d.provider.clone(),
+            base_url: base_url.map(str::to_string),
+            test_mode: d.test_mode.clone(),
+            success: d.success,
+            latency_ms: d.latency_ms,
+            error: d.error.clone(),
+            attempts: (d.attempts.len() as u32).max(1),
+        }
+    }
+}
+
+/// Appends records to the history log, compacting it when it grows too large.
+pub fn append_records_for_home(home_dir: &Path, records: &[HistoryRecord]) -> Result<(), String> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let path = history_path_for_home(home_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create connectivity history directory: {e}"))?;
+    }
+
+    let mut buf = String::new();
+    for record in records {
+        let line = serde_json::to_string(record)
+            .map_err(|e| format!("Failed to serialize history record: {e}"))?;
+        buf.push_str(&line);
+        buf.push('\n');
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open connectivity history: {e}"))?;
+    file.write_all(buf.as_bytes())
+        .map_err(|e| format!("Failed to write connectivity history: {e}"))?;
+
+    let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    if size > MAX_HISTORY_BYTES {
+        compact(&path)?;
+    }
+    Ok(())
+}
+
+fn compact(path: &Path) -> Result<(), String> {
+    let records = read_records(path)?;
+    let start = records.len().saturating_sub(COMPACT_KEEP_RECORDS);
+    let mut out = String::new();
+    for record in &records[start..] {
+        let line = serde_json::to_string(record)
+            .map_err(|e| format!("Failed to serialize history record: {e}"))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    atomic_write(path, out.as_bytes())
+}
+
+/// Reads all records; malformed lines (e.g. from an interrupted write) are skipped.
+fn read_records(path: &Path) -> Result<Vec<HistoryRecord>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read connectivity history: {e}")),
+    };
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Records within the last `window_hours` (all records when `None`), oldest first.
+fn records_in_window(
+    home_dir: &Path,
+    window_hours: Option<u32>,
+) -> Result<Vec<HistoryRecord>, String> {
+    let records = read_records(&history_path_for_home(home_dir))?;
+    let Some(hours) = window_hours else {
+        return Ok(records);
+    };
+    let cutoff = Utc::now() - Duration::hours(i64::from(hours));
+    Ok(records
+        .into_iter()
+        .filter(|r| parse_timestamp(&r.timestamp).is_some_and(|t| t >= cutoff))
+        .collect())
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+/// History of one model within the window, newest first.
+pub fn get_model_history_for_home(
+    home_dir: &Path,
+    model_id: &str,
+    window_hours: Option<u32>,
+) -> Result<Vec<HistoryRecord>, String> {
+    let mut records: Vec<HistoryRecord> = records_in_window(home_dir, window_hours)?
+        .into_iter()
+        .filter(|r| r.model_id == model_id)
+        .collect();
+    records.reverse();
+    Ok(records)
+}
+
+/// Availability statistics for every model and endpoint seen within the
+/// window, sorted by uptime (worst first) so flaky channels surface at the top.
+///
+/// The same model served by two channels yields two entries.
+pub fn get_availability_stats_for_home(
+    home_dir: &Path,
+    window_hours: Option<u32>,
+) -> Result<Vec<ModelAvailabilityStats>, String> {
+    let records = records_in_window(home_dir, window_hours)?;
+
+    let mut order: Vec<(&str, Option<&str>)> = Vec::new();
+    let mut grouped: std::collections::HashMap<(&str, Option<&str>), Vec<&HistoryRecord>> =
+        std::collections::HashMap::new();
+    for r in &records {
+        let key = (r.model_id.as_str(), r.base_url.as_deref());
+        let entry = grouped.entry(key).or_default();
+        if entry.is_empty() {
+            order.push(key);
+        }
+        entry.push(r);
+    }
+
+    let mut stats: Vec<ModelAvailabilityStats> = order
+        .iter()
+        .filter_map(|key| grouped.get(key).map(|group| compute_stats(group)))
+        .collect();
+    stats.sort_by(|a, b| {
+        a.uptime_percent
+            .partial_cmp(&b.uptime_percent)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.model_name.cmp(&b.model_name))
+    });
+    Ok(stats)
+}
+
+/// Availability statistics for one model (None if it has no records in the window).
+pub fn get_model_availability_for_home(
+    home_dir: &Path,
+    model_id: &str,
+    window_hours: Option<u32>,
+) -> Result<Option<ModelAvailabilityStats>, String> {
+    let records: Vec<HistoryRecord> = records_in_window(home_dir, window_hours)?
+        .into_iter()
+        .filter(|r| r.model_id == model_id)
+        .collect();
+    if records.is_empty() {
+        return Ok(None);
+    }
+    let refs: Vec<&HistoryRecord> = records.iter().collect();
+    Ok(Some(compute_stats(&refs)))
+}
+
+pub fn clear_history_for_home(home_dir: &Path) -> Result<(), String> {
+    let path = history_path_for_home(home_dir);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to clear connectivity history: {e}")),
+    }
+}
+
+/// `records` must be non-empty and in chronological order.
+fn compute_stats(records: &[&HistoryRecord]) -> ModelAvailabilityStats {
+    let latest = records[records.len() - 1];
+    let total = records.len() as u32;
+    let successful = records.iter().filter(|r| r.success).count() as u32;
+
+    let mut latencies: Vec<u32> = records
+        .iter()
+        .filter(|r| r.success)
+        .map(|r| r.latency_ms)
+        .collect();
+    latencies.sort_unstable();
+
+    let last_failure = records.iter().rev().find(|r| !r.success);
+
+    ModelAvailabilityStats {
+        model_id: latest.model_id.clone(),
+        model_name: latest.model_name.clone(),
+        provider: latest.provider.clone(),
+        base_url: latest.base_url.clone(),
+        total_tests: total,
+        successful_tests: successful,
+        uptime_percent: f64::from(successful) * 100.0 / f64::from(total),
+        p50_latency_ms: percentile(&latencies, 50),
+        p95_latency_ms: percentile(&latencies, 95),
+        last_failure_reason: last_failure.map(|r| {
+            r.error
+                .clone()
+                .unwrap_or_else(|| "Unknown error".to_string())
+        }),
+        last_failure_at: last_failure.map(|r| r.timestamp.clone()),
+        last_tested_at: latest.timestamp.clone(),
+    }
+}
+
+/// Nearest-rank percentile of a sorted slice.
+fn percentile(sorted: &[u32], pct: u32) -> Option<u32> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct as usize * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+// ============================================================================
+// System wrappers
+// ============================================================================
+
+pub fn get_model_history(
+    model_id: &str,
+    window_hours: Option<u32>,
+) -> Result<Vec<HistoryRecord>, String> {
+    get_model_history_for_home(&paths::get_home_dir()?, model_id, window_hours)
+}
+
+pub fn get_availability_stats(
+    window_hours: Option<u32>,
+) -> Result<Vec<ModelAvailabilityStats>, String> {
+    get_availability_stats_for_home(&paths::get_home_dir()?, window_hours)
+}
+
+pub fn get_model_availability(
+    model_id: &str,
+    window_hours: Option<u32>,
+) -> Result<Option<ModelAvailabilityStats>, String> {
+    get_model_availability_for_home(&paths::get_home_dir()?, model_id, window_hours)
+}
+
+pub fn clear_history() -> Result<(), String> {
+    clear_history_for_home(&paths::get_home_dir()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn record(model_id: &str, hours_ago: i64, success: bool, latency_ms: u32) -> HistoryRecord {
+        HistoryRecord {
+            timestamp: (Utc::now() - Duration::hours(hours_ago)).to_rfc3339(),
+            model_id: model_id.to_string(),
+            model_name: model_id.to_uppercase(),
+            provider: "openai".to_string(),
+            base_url: Some("https://relay.example.com".to_string()),
+            test_mode: TestMode::Ping,
+            success,
+            latency_ms,
+            error: (!success).then(|| format!("HTTP 502 at {hours_ago}h")),
+            attempts: 1,
+        }
+    }
+
+    #[test]
+    fn stats_report_uptime_percentiles_and_last_failure() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let mut records = Vec::new();
+        for (i, latency) in [100, 200, 300, 400, 500, 600, 700, 800, 900, 1000]
+            .into_iter()
+            .enumerate()
+        {
+            records.push(record("a", 20 - i as i64, true, latency));
+        }
+        records.push(record("a", 5, false, 0));
+        records.push(record("a", 3, false, 0));
+        records.push(record("b", 2, true, 50));
+        append_records_for_home(home, &records).unwrap();
+
+        let stats = get_availability_stats_for_home(home, None).unwrap();
+        assert_eq!(stats.len(), 2);
+        // Worst uptime first
+        let a = &stats[0];
+        assert_eq!(a.model_id, "a");
+        assert_eq!(a.total_tests, 12);
+        assert_eq!(a.successful_tests, 10);
+        assert!((a.uptime_percent - 83.333).abs() < 0.01);
+        assert_eq!(a.p50_latency_ms, Some(500));
+        assert_eq!(a.p95_latency_ms, Some(1000));
+        assert_eq!(a.last_failure_reason.as_deref(), Some("HTTP 502 at 3h"));
+        assert_eq!(stats[1].uptime_percent, 100.0);
+        assert!(stats[1].last_failure_reason.is_none());
+    }
+
+    #[test]
+    fn stats_separate_the_same_model_on_different_channels() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let mut other = record("a", 1, false, 0);
+        other.base_url = Some("https://backup.example.com".to_string());
+        append_records_for_home(home, &[record("a", 2, true, 100), other]).unwrap();
+
+        let stats = get_availability_stats_for_home(home, None).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats[0].base_url.as_deref(),
+            Some("https://backup.example.com")
+        );
+        assert_eq!(stats[0].uptime_percent, 0.0);
+        assert_eq!(
+            stats[1].base_url.as_deref(),
+            Some("https://relay.example.com")
+        );
+        assert_eq!(stats[1].uptime_percent, 100.0);
+    }
+
+    #[test]
+    fn window_filters_old_records_and_history_is_newest_first() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        append_records_for_home(
+            home,
+            &[
+                record("a", 48, false, 0),
+                record("a", 10, true, 120),
+                record("a", 1, true, 80),
+            ],
+        )
+        .unwrap();
+
+        let day = get_model_availability_for_home(home, "a", Some(24))
+            .unwrap()
+            .unwrap();
+        assert_eq!(day.total_tests, 2);
+        assert_eq!(day.uptime_percent, 100.0);
+
+        let history = get_model_history_for_home(home, "a", None).unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].latency_ms, 80);
+        assert!(get_model_availability_for_home(home, "missing", None)
+            .unwrap()
+            .is_none());
+
+        clear_history_for_home(home).unwrap();
+        assert!(get_model_history_for_home(home, "a", None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        append_records_for_home(home, &[record("a", 1, true, 10)]).unwrap();
+        let path = history_path_for_home(home);
+        let mut content = fs::read_to_string(&path).unwrap();
+        content.push_str("{\"truncated\n");
+        fs::write(&path, content).unwrap();
+        append_records_for_home(home, &[record("a", 0, true, 20)]).unwrap();
+
+        assert_eq!(
+            get_model_history_for_home(home, "a", None).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn percentile_nearest_rank() {
+        assert_eq!(percentile(&[], 50), None);
+        assert_eq!(percentile(&[7], 95), Some(7));
+        assert_eq!(percentile(&[1, 2, 3, 4], 50), Some(2));
+        assert_eq!(percentile(&[1, 2, 3, 4], 95), Some(4));
+    }
+}
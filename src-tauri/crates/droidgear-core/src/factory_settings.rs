@@ -8,7 +8,9 @@ use specta::Type;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use crate::paths;
+use crate::apply_plan::{file_plan, ApplyPlan, PlanFormat};
+use crate::channel::ChannelRef;
+use crate::{backup, paths};
 
 // ============================================================================
 // Config Read Result
@@ -73,13 +75,95 @@ pub struct CustomModel {
     /// Additional HTTP headers
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra_headers: Option<HashMap<String, String>>,
+    /// Channel this model was provisioned from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ref: Option<ChannelRef>,
 }
 
 /// Model info returned from API
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
 pub struct ModelInfo {
     pub id: String,
     pub name: Option<String>,
+    /// Context window in tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u32>,
+    /// Maximum output tokens per response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    /// Accepted input modalities (e.g. "text", "image")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input_modalities: Vec<String>,
+    /// Whether the model supports extended thinking / reasoning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_reasoning: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
+    /// Release timestamp as reported by the provider (e.g. Anthropic's `created_at`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+
+impl ModelInfo {
+    /// Whether the model is known to accept image input (`None` if unknown).
+    pub fn supports_images(&self) -> Option<bool> {
+        (!self.input_modalities.is_empty())
+            .then(|| self.input_modalities.iter().any(|m| m == "image"))
+    }
+}
+
+/// Model prices in USD per million tokens
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPricing {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+}
+
+impl ModelPricing {
+    /// Converts a USD-per-token price (as reported by OpenRouter and LiteLLM)
+    /// to USD per million tokens.
+    pub fn per_million(per_token: f64) -> f64 {
+        (per_token * 1_000_000.0 * 1e6).round() / 1e6
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input.is_none()
+            && self.output.is_none()
+            && self.cache_read.is_none()
+            && self.cache_write.is_none()
+    }
+}
+
+/// Builds a custom model from channel metadata (display name, output limit
+/// and image support).
+pub fn custom_model_from_model_info(
+    info: &ModelInfo,
+    base_url: &str,
+    api_key: &str,
+    provider: Provider,
+) -> CustomModel {
+    CustomModel {
+        model: info.id.clone(),
+        id: None,
+        index: None,
+        display_name: info.name.clone(),
+        base_url: base_url.to_string(),
+        api_key: api_key.to_string(),
+        provider,
+        max_output_tokens: info.max_output_tokens,
+        no_image_support: (info.supports_images() == Some(false)).then_some(true),
+        extra_args: None,
+        extra_headers: None,
+        channel_ref: None,
+    }
 }
 
 // ============================================================================
@@ -133,16 +217,18 @@ fn factory_config_path_for_home(home_dir: &Path) -> Result<PathBuf, String> {
 }
 
 fn read_config_file_for_home(home_dir: &Path) -> ConfigReadResult {
-    let config_path = match factory_config_path_for_home(home_dir) {
-        Ok(path) => path,
-        Err(_) => return ConfigReadResult::NotFound,
-    };
+    match factory_config_path_for_home(home_dir) {
+        Ok(path) => read_config_file_at(&path),
+        Err(_) => ConfigReadResult::NotFound,
+    }
+}
 
+fn read_config_file_at(config_path: &Path) -> ConfigReadResult {
     if !config_path.exists() {
         return ConfigReadResult::NotFound;
     }
 
-    let contents = match std::fs::read_to_string(&config_path) {
+    let contents = match std::fs::read_to_string(config_path) {
         Ok(c) => c,
         Err(e) => return ConfigReadResult::ParseError(format!("Failed to read config file: {e}")),
     };
@@ -158,7 +244,21 @@ fn read_config_file_for_home(home_dir: &Path) -> ConfigReadResult {
 }
 
 fn write_config_file_for_home(home_dir: &Path, config: &Value) -> Result<(), String> {
-    let config_path = factory_config_path_for_home(home_dir)?;
+    write_config_file_at_for_home(home_dir, factory_config_path_for_home(home_dir)?, config)
+}
+
+fn write_config_file_at_for_home(
+    home_dir: &Path,
+    config_path: PathBuf,
+    config: &Value,
+) -> Result<(), String> {
+    // Ensure parent directory exists
+    if let Some(parent) = config_path.parent() {
+        if !parent.exists() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {e}"))?;
+        }
+    }
 
     // Resolve symlink to get the actual file path
     let actual_path = if config_path.is_symlink() {
@@ -171,6 +271,7 @@ fn write_config_file_for_home(home_dir: &Path, config: &Value) -> Result<(), Str
     let temp_path = actual_path.with_extension("tmp");
     let json_content = serde_json::to_string_pretty(config)
         .map_err(|e| format!("Failed to serialize config: {e}"))?;
+    backup::snapshot_file_for_home(home_dir, &actual_path, "factory")?;
 
     std::fs::write(&temp_path, json_content)
         .map_err(|e| format!("Failed to write config file: {e}"))?;
@@ -189,62 +290,16 @@ fn write_config_file_for_home(home_dir: &Path, config: &Value) -> Result<(), Str
 
 /// Reads the config from the active settings file (respects user's file selection)
 fn read_active_config_file() -> ConfigReadResult {
-    let config_path = match crate::droid_settings_files::get_active_settings_path() {
-        Ok(path) => path,
-        Err(_) => return ConfigReadResult::NotFound,
-    };
-
-    if !config_path.exists() {
-        return ConfigReadResult::NotFound;
-    }
-
-    let contents = match std::fs::read_to_string(&config_path) {
-        Ok(c) => c,
-        Err(e) => return ConfigReadResult::ParseError(format!("Failed to read config file: {e}")),
-    };
-
-    if contents.trim().is_empty() {
-        return ConfigReadResult::NotFound;
-    }
-
-    match serde_json::from_str(&contents) {
-        Ok(value) => ConfigReadResult::Ok(value),
-        Err(e) => ConfigReadResult::ParseError(format!("Failed to parse config JSON: {e}")),
+    match crate::droid_settings_files::get_active_settings_path() {
+        Ok(path) => read_config_file_at(&path),
+        Err(_) => ConfigReadResult::NotFound,
     }
 }
 
 /// Writes the config to the active settings file
 fn write_active_config_file(config: &Value) -> Result<(), String> {
     let config_path = crate::droid_settings_files::get_active_settings_path()?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = config_path.parent() {
-        if !parent.exists() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create directory: {e}"))?;
-        }
-    }
-
-    let actual_path = if config_path.is_symlink() {
-        std::fs::canonicalize(&config_path)
-            .map_err(|e| format!("Failed to resolve symlink: {e}"))?
-    } else {
-        config_path
-    };
-
-    let temp_path = actual_path.with_extension("tmp");
-    let json_content = serde_json::to_string_pretty(config)
-        .map_err(|e| format!("Failed to serialize config: {e}"))?;
-
-    std::fs::write(&temp_path, json_content)
-        .map_err(|e| format!("Failed to write config file: {e}"))?;
-
-    std::fs::rename(&temp_path, &actual_path).map_err(|e| {
-        let _ = std::fs::remove_file(&temp_path);
-        format!("Failed to finalize config file: {e}")
-    })?;
-
-    Ok(())
+    write_config_file_at_for_home(&system_home_dir()?, config_path, config)
 }
 
 fn system_home_dir() -> Result<PathBuf, String> {
@@ -274,33 +329,33 @@ pub fn reset_config_file() -> Result<(), String> {
 }
 
 pub fn load_custom_models_for_home(home_dir: &Path) -> Result<Vec<CustomModel>, String> {
-    let config = match read_config_file_for_home(home_dir) {
-        ConfigReadResult::Ok(value) => value,
-        ConfigReadResult::NotFound => return Ok(vec![]),
-        ConfigReadResult::ParseError(_) => return Ok(vec![]),
-    };
+    Ok(custom_models_from_config(read_config_file_for_home(
+        home_dir,
+    )))
+}
 
-    let models: Vec<CustomModel> = config
-        .get("customModels")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| serde_json::from_value(v.clone()).ok())
-                .collect()
-        })
-        .unwrap_or_default();
+pub fn load_custom_models() -> Result<Vec<CustomModel>, String> {
+    Ok(custom_models_from_config(read_active_config_file()))
+}
 
-    Ok(models)
+/// Loads custom models from a specific settings file (global or a Droid settings file)
+pub fn load_custom_models_from_file(config_path: &Path) -> Result<Vec<CustomModel>, String> {
+    Ok(custom_models_from_config(read_config_file_at(config_path)))
 }
 
-pub fn load_custom_models() -> Result<Vec<CustomModel>, String> {
-    let config = match read_active_config_file() {
-        ConfigReadResult::Ok(value) => value,
-        ConfigReadResult::NotFound => return Ok(vec![]),
-        ConfigReadResult::ParseError(_) => return Ok(vec![]),
-    };
+/// Loads custom models from the active Droid settings file under `home_dir`
+/// (the file the desktop app edits).
+pub fn load_active_custom_models_for_home(home_dir: &Path) -> Result<Vec<CustomModel>, String> {
+    load_custom_models_from_file(
+        &crate::droid_settings_files::get_active_settings_path_for_home(home_dir)?,
+    )
+}
 
-    let models: Vec<CustomModel> = config
+fn custom_models_from_config(config: ConfigReadResult) -> Vec<CustomModel> {
+    let ConfigReadResult::Ok(config) = config else {
+        return vec![];
+    };
+    config
         .get("customModels")
         .and_then(|v| v.as_array())
         .map(|arr| {
@@ -308,37 +363,67 @@ pub fn load_custom_models() -> Result<Vec<CustomModel>, String> {
                 .filter_map(|v| serde_json::from_value(v.clone()).ok())
                 .collect()
         })
-        .unwrap_or_default();
-
-    Ok(models)
+        .unwrap_or_default()
 }
 
 pub fn save_custom_models_for_home(
     home_dir: &Path,
     models: Vec<CustomModel>,
 ) -> Result<(), String> {
-    let mut config = match read_config_file_for_home(home_dir) {
-        ConfigReadResult::Ok(value) => value,
-        ConfigReadResult::NotFound => serde_json::json!({}),
-        ConfigReadResult::ParseError(e) => {
-            return Err(format!("{CONFIG_PARSE_ERROR_PREFIX} {e}"));
-        }
-    };
+    let config = config_with_custom_models(read_config_file_for_home(home_dir), &models)?;
+    write_config_file_for_home(home_dir, &config)
+}
 
-    let models_value =
-        serde_json::to_value(&models).map_err(|e| format!("Failed to serialize models: {e}"))?;
+/// Dry-run of [`save_custom_models_for_home`]: returns the planned write without touching disk.
+pub fn plan_save_custom_models_for_home(
+    home_dir: &Path,
+    models: Vec<CustomModel>,
+) -> Result<ApplyPlan, String> {
+    let config = config_with_custom_models(read_config_file_for_home(home_dir), &models)?;
+    plan_config_write(&factory_config_path_for_home(home_dir)?, &config)
+}
 
-    if let Some(obj) = config.as_object_mut() {
-        obj.insert("customModels".to_string(), models_value);
-    } else {
-        config = serde_json::json!({ "customModels": models_value });
-    }
+pub fn save_custom_models(models: Vec<CustomModel>) -> Result<(), String> {
+    let config = config_with_custom_models(read_active_config_file(), &models)?;
+    write_active_config_file(&config)
+}
 
-    write_config_file_for_home(home_dir, &config)
+/// Saves custom models into a specific settings file, preserving its other fields
+pub fn save_custom_models_to_file_for_home(
+    home_dir: &Path,
+    config_path: &Path,
+    models: Vec<CustomModel>,
+) -> Result<(), String> {
+    let config = config_with_custom_models(read_config_file_at(config_path), &models)?;
+    write_config_file_at_for_home(home_dir, config_path.to_path_buf(), &config)
 }
 
-pub fn save_custom_models(models: Vec<CustomModel>) -> Result<(), String> {
-    let mut config = match read_active_config_file() {
+/// Saves custom models into the active Droid settings file under `home_dir`
+pub fn save_active_custom_models_for_home(
+    home_dir: &Path,
+    models: Vec<CustomModel>,
+) -> Result<(), String> {
+    save_custom_models_to_file_for_home(
+        home_dir,
+        &crate::droid_settings_files::get_active_settings_path_for_home(home_dir)?,
+        models,
+    )
+}
+
+pub fn plan_save_custom_models(models: Vec<CustomModel>) -> Result<ApplyPlan, String> {
+    let config = config_with_custom_models(read_active_config_file(), &models)?;
+    plan_config_write(
+        &crate::droid_settings_files::get_active_settings_path()?,
+        &config,
+    )
+}
+
+/// Internal: merge `customModels` into the current config, preserving other fields.
+fn config_with_custom_models(
+    current: ConfigReadResult,
+    models: &[CustomModel],
+) -> Result<Value, String> {
+    let mut config = match current {
         ConfigReadResult::Ok(value) => value,
         ConfigReadResult::NotFound => serde_json::json!({}),
         ConfigReadResult::ParseError(e) => {
@@ -347,15 +432,24 @@ pub fn save_custom_models(models: Vec<CustomModel>) -> Result<(), String> {
     };
 
     let models_value =
-        serde_json::to_value(&models).map_err(|e| format!("Failed to serialize models: {e}"))?;
+        serde_json::to_value(models).map_err(|e| format!("Failed to serialize models: {e}"))?;
 
     if let Some(obj) = config.as_object_mut() {
         obj.insert("customModels".to_string(), models_value);
     } else {
         config = serde_json::json!({ "customModels": models_value });
     }
+    Ok(config)
+}
 
-    write_active_config_file(&config)
+fn plan_config_write(config_path: &Path, config: &Value) -> Result<ApplyPlan, String> {
+    let json_content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {e}"))?;
+    Ok(ApplyPlan {
+        tool: "factory".to_string(),
+        profile_id: None,
+        files: vec![file_plan(config_path, json_content, PlanFormat::Json)],
+    })
 }
 
 pub fn check_legacy_config_for_home(home_dir: &Path) -> Result<bool, String> {
@@ -477,11 +571,13 @@ async fn fetch_anthropic_models(
             arr.iter()
                 .filter_map(|m| {
                     let id = m.get("id")?.as_str()?.to_string();
-                    let name = m
-                        .get("display_name")
-                        .and_then(|n| n.as_str())
-                        .map(String::from);
-                    Some(ModelInfo { id, name })
+                    let text = |key: &str| m.get(key).and_then(|v| v.as_str()).map(String::from);
+                    Some(ModelInfo {
+                        id,
+                        name: text("display_name"),
+                        created_at: text("created_at"),
+                        ..Default::default()
+                    })
                 })
                 .collect()
         })
@@ -527,20 +623,7 @@ async fn fetch_openai_models(
         format!("Failed to parse response: {e}")
     })?;
 
-    let models = data
-        .get("data")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|m| {
-                    let id = m.get("id")?.as_str()?.to_string();
-                    Some(ModelInfo { id, name: None })
-                })
-                .collect()
-        })
-        .unwrap_or_default();
-
-    Ok(models)
+    Ok(crate::channel_provider::parse_openai_models(&data))
 }
 
 pub fn get_default_model_for_home(home_dir: &Path) -> Result<Option<String>, String> {
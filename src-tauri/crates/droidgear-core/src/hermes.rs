@@ -11,7 +11,9 @@ use specta::Type;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{paths, storage};
+use crate::apply_plan::{file_plan, ApplyPlan, PlanFormat};
+use crate::channel::ChannelRef;
+use crate::{backup, paths, secrets, storage};
 
 // ============================================================================
 // Types
@@ -29,6 +31,9 @@ pub struct HermesModelConfig {
     pub base_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
+    /// Channel this entry was provisioned from (DroidGear-only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ref: Option<ChannelRef>,
 }
 
 /// Hermes Profile（用于在 DroidGear 内部保存并切换）
@@ -257,6 +262,7 @@ pub fn create_default_hermes_profile_for_home(home_dir: &Path) -> Result<HermesP
             provider: Some(String::new()),
             base_url: Some(String::new()),
             api_key: Some(String::new()),
+            channel_ref: None,
         },
     };
 
@@ -292,66 +298,95 @@ fn set_active_profile_id_for_home(home_dir: &Path, id: &str) -> Result<(), Strin
 // Apply + status
 // ============================================================================
 
-/// Internal: write a profile's model config to the given config.yaml path.
-///
-/// 采用读取-修改-写入模式：只替换 config.yaml 中的 model 节，保留其他所有配置。
-fn apply_profile_to_config_path(profile: &HermesProfile, config_path: &Path) -> Result<(), String> {
-    // Read existing YAML as a generic Value to preserve all non-model sections.
-    let mut config: Value = if config_path.exists() {
-        let s = std::fs::read_to_string(config_path)
-            .map_err(|e| format!("Failed to read config.yaml: {e}"))?;
-        if s.trim().is_empty() {
-            Value::Mapping(serde_yaml::Mapping::new())
-        } else {
-            serde_yaml::from_str(&s).map_err(|e| format!("Failed to parse config.yaml: {e}"))?
-        }
-    } else {
-        Value::Mapping(serde_yaml::Mapping::new())
-    };
-
-    // Ensure root is a mapping.
-    let root = config
-        .as_mapping_mut()
-        .ok_or("config.yaml root must be a YAML mapping")?;
-
-    // Build the new model section from the profile's model config.
+/// Build the `model` section of config.yaml from a model config.
+pub(crate) fn model_section(model: &HermesModelConfig) -> serde_yaml::Mapping {
     let mut model_map = serde_yaml::Mapping::new();
-    if let Some(ref default) = profile.model.default {
+    if let Some(ref default) = model.default {
         model_map.insert(
             Value::String("default".to_string()),
             Value::String(default.clone()),
         );
     }
-    if let Some(ref provider) = profile.model.provider {
+    if let Some(ref provider) = model.provider {
         model_map.insert(
             Value::String("provider".to_string()),
             Value::String(provider.clone()),
         );
     }
-    if let Some(ref base_url) = profile.model.base_url {
+    if let Some(ref base_url) = model.base_url {
         model_map.insert(
             Value::String("base_url".to_string()),
             Value::String(base_url.clone()),
         );
     }
-    if let Some(ref api_key) = profile.model.api_key {
+    if let Some(ref api_key) = model.api_key {
         model_map.insert(
             Value::String("api_key".to_string()),
             Value::String(api_key.clone()),
         );
     }
+    model_map
+}
+
+/// Internal: write a profile's model config to the given config.yaml path.
+///
+/// 采用读取-修改-写入模式：只替换 config.yaml 中的 model 节，保留其他所有配置。
+fn render_profile_config(profile: &HermesProfile, config_path: &Path) -> Result<String, String> {
+    // Read existing YAML as a generic Value to preserve all non-model sections.
+    let mut config: Value = if config_path.exists() {
+        let s = std::fs::read_to_string(config_path)
+            .map_err(|e| format!("Failed to read config.yaml: {e}"))?;
+        if s.trim().is_empty() {
+            Value::Mapping(serde_yaml::Mapping::new())
+        } else {
+            serde_yaml::from_str(&s).map_err(|e| format!("Failed to parse config.yaml: {e}"))?
+        }
+    } else {
+        Value::Mapping(serde_yaml::Mapping::new())
+    };
+
+    // Ensure root is a mapping.
+    let root = config
+        .as_mapping_mut()
+        .ok_or("config.yaml root must be a YAML mapping")?;
 
     // Replace the model section (preserving all other sections).
     root.insert(
         Value::String("model".to_string()),
-        Value::Mapping(model_map),
+        Value::Mapping(model_section(&profile.model)),
     );
 
-    let yaml_str = serde_yaml::to_string(&config)
-        .map_err(|e| format!("Failed to serialize config.yaml: {e}"))?;
+    serde_yaml::to_string(&config).map_err(|e| format!("Failed to serialize config.yaml: {e}"))
+}
+
+fn apply_profile_to_config_path(profile: &HermesProfile, config_path: &Path) -> Result<(), String> {
+    let yaml_str = render_profile_config(profile, config_path)?;
     storage::atomic_write(config_path, yaml_str.as_bytes())
 }
 
+fn plan_profile_for_config_path(
+    home_dir: &Path,
+    id: &str,
+    config_path: &Path,
+) -> Result<ApplyPlan, String> {
+    let profile =
+        secrets::resolve_secret_refs_for_home(home_dir, &load_profile_by_id(home_dir, id)?)?;
+    let yaml_str = render_profile_config(&profile, config_path)?;
+
+    Ok(ApplyPlan {
+        tool: "hermes".to_string(),
+        profile_id: Some(id.to_string()),
+        files: vec![
+            file_plan(config_path, yaml_str, PlanFormat::Yaml),
+            file_plan(
+                &active_profile_path_for_home(home_dir)?,
+                id.to_string(),
+                PlanFormat::Text,
+            ),
+        ],
+    })
+}
+
 /// Internal: read current Hermes config from a specific config.yaml path.
 fn read_current_config_from_path(config_path: &Path) -> Result<HermesCurrentConfig, String> {
     let model = if config_path.exists() {
@@ -363,6 +398,7 @@ fn read_current_config_from_path(config_path: &Path) -> Result<HermesCurrentConf
                 provider: None,
                 base_url: None,
                 api_key: None,
+                channel_ref: None,
             }
         } else {
             let parsed: Value = serde_yaml::from_str(&s)
@@ -382,6 +418,7 @@ fn read_current_config_from_path(config_path: &Path) -> Result<HermesCurrentConf
                 provider: get_str("provider"),
                 base_url: get_str("base_url"),
                 api_key: get_str("api_key"),
+                channel_ref: None,
             }
         }
     } else {
@@ -390,6 +427,7 @@ fn read_current_config_from_path(config_path: &Path) -> Result<HermesCurrentConf
             provider: None,
             base_url: None,
             api_key: None,
+            channel_ref: None,
         }
     };
 
@@ -398,13 +436,21 @@ fn read_current_config_from_path(config_path: &Path) -> Result<HermesCurrentConf
 
 /// 应用指定 Profile 到 `~/.hermes/config.yaml`（for_home variant, NOT WSL-aware）
 pub fn apply_hermes_profile_for_home(home_dir: &Path, id: &str) -> Result<(), String> {
-    let profile = load_profile_by_id(home_dir, id)?;
+    let profile =
+        secrets::resolve_secret_refs_for_home(home_dir, &load_profile_by_id(home_dir, id)?)?;
     let config_path = hermes_config_path_for_home(home_dir)?;
+    backup::snapshot_file_for_home(home_dir, &config_path, "hermes")?;
     apply_profile_to_config_path(&profile, &config_path)?;
     set_active_profile_id_for_home(home_dir, id)?;
     Ok(())
 }
 
+/// Dry-run of [`apply_hermes_profile_for_home`]: returns the planned writes without touching disk.
+pub fn plan_apply_hermes_profile_for_home(home_dir: &Path, id: &str) -> Result<ApplyPlan, String> {
+    let config_path = hermes_config_path_for_home(home_dir)?;
+    plan_profile_for_config_path(home_dir, id, &config_path)
+}
+
 pub fn get_hermes_config_status_for_home(home_dir: &Path) -> Result<HermesConfigStatus, String> {
     let config_path = hermes_config_path_for_home(home_dir)?;
     Ok(HermesConfigStatus {
@@ -456,13 +502,20 @@ pub fn get_active_hermes_profile_id() -> Result<Option<String>, String> {
 
 pub fn apply_hermes_profile(id: &str) -> Result<(), String> {
     let home = system_home_dir()?;
-    let profile = load_profile_by_id(&home, id)?;
+    let profile = secrets::resolve_secret_refs_for_home(&home, &load_profile_by_id(&home, id)?)?;
     let config_path = hermes_config_path()?;
+    backup::snapshot_file_for_home(&home, &config_path, "hermes")?;
     apply_profile_to_config_path(&profile, &config_path)?;
     set_active_profile_id_for_home(&home, id)?;
     Ok(())
 }
 
+pub fn plan_apply_hermes_profile(id: &str) -> Result<ApplyPlan, String> {
+    let home = system_home_dir()?;
+    let config_path = hermes_config_path()?;
+    plan_profile_for_config_path(&home, id, &config_path)
+}
+
 pub fn get_hermes_config_status() -> Result<HermesConfigStatus, String> {
     let config_path = hermes_config_path()?;
     Ok(HermesConfigStatus {
@@ -508,6 +561,7 @@ mod tests {
                 provider: Some("openai".to_string()),
                 base_url: Some("https://api.openai.com/v1".to_string()),
                 api_key: Some("sk-test".to_string()),
+                channel_ref: None,
             },
         }
     }
@@ -519,6 +573,7 @@ mod tests {
             provider: Some("openai".to_string()),
             base_url: Some("https://api.openai.com/v1".to_string()),
             api_key: Some("sk-test".to_string()),
+            channel_ref: None,
         };
 
         let profile = HermesProfile {
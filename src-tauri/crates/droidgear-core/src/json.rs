@@ -1,5 +1,7 @@
+use json_comments::StripComments;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::io::Read;
 use std::path::Path;
 
 use crate::storage;
@@ -39,3 +41,12 @@ pub fn read_json_value_file_or_empty_object(path: &Path) -> Value {
     }
     serde_json::from_str(&s).unwrap_or(serde_json::json!({}))
 }
+
+/// Parses JSON that may contain comments (JSONC).
+pub fn parse_jsonc_value(content: &str) -> Result<Value, String> {
+    let mut buf = String::new();
+    StripComments::new(content.as_bytes())
+        .read_to_string(&mut buf)
+        .map_err(|e| format!("Failed to strip JSON comments: {e}"))?;
+    serde_json::from_str(&buf).map_err(|e| format!("Invalid JSON: {e}"))
+}
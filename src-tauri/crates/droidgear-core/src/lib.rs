@@ -1,11 +1,17 @@
+pub mod apply_plan;
+pub mod backup;
 pub mod channel;
 pub mod channel_export;
+pub mod channel_provider;
+pub mod channel_provisioning;
+pub mod channel_sync;
 pub mod claude;
 pub mod claude_runtime;
 pub mod claude_settings_files;
 pub mod codex;
 pub mod codex_runtime;
 pub mod connectivity;
+pub mod connectivity_history;
 pub mod droid_runtime;
 pub mod droid_settings_files;
 pub mod factory_auth_profiles;
@@ -13,13 +19,26 @@ pub mod factory_settings;
 pub mod hermes;
 pub mod json;
 pub mod mcp;
+pub mod model_autofill;
 pub mod openclaw;
 pub mod opencode;
 pub mod paths;
 pub mod pi;
+pub mod profile_connectivity;
+pub mod profiles;
+pub mod quota_monitor;
+pub mod secrets;
+pub mod session_analytics;
+pub mod session_export;
+pub mod session_search;
+pub mod session_source;
 pub mod sessions;
 pub mod specs;
 pub mod storage;
+pub mod token_rotation;
+
+#[cfg(test)]
+mod test_support;
 
 pub fn core_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
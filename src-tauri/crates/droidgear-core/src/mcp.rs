@@ -8,7 +8,7 @@ use specta::Type;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use crate::paths;
+use crate::{backup, paths};
 
 // ============================================================================
 // Types
@@ -105,6 +105,7 @@ fn write_mcp_file_for_home(home_dir: &Path, config: &Value) -> Result<(), String
     let temp_path = actual_path.with_extension("tmp");
     let json_content = serde_json::to_string_pretty(config)
         .map_err(|e| format!("Failed to serialize MCP config: {e}"))?;
+    backup::snapshot_file_for_home(home_dir, &actual_path, "mcp")?;
 
     std::fs::write(&temp_path, json_content)
         .map_err(|e| format!("Failed to write MCP config file: {e}"))?;
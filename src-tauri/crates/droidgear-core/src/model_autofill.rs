@@ -0,0 +1,599 @@
+//! Capability auto-fill for Factory custom models (core).
+//!
+//! Runs connectivity probes against a [`CustomModel`] and proposes concrete
+//! updates to its capability fields (`noImageSupport`, `maxOutputTokens`,
+//! `extraHeaders`) as a reviewable [`ModelPatch`]. Nothing is written until the
+//! user accepts the patch, which then goes through `save_custom_models`.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::apply_plan::{self, ApplyPlan};
+use crate::connectivity::{
+    truncate_body, ConnectionDiagnostics, ModelCapabilities, ModelTester, TestConfig, TestMode,
+    WireProtocol,
+};
+use crate::factory_settings::{self, CustomModel, Provider};
+use crate::{paths, secrets};
+
+/// Smallest `max_output_tokens` the step-down search will propose.
+const MIN_OUTPUT_TOKENS: u32 = 256;
+/// Maximum number of step-down requests when the provider's limit is not stated.
+const MAX_STEP_DOWN_PROBES: u32 = 4;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One proposed field update, for review (secrets masked)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelFieldChange {
+    /// Field name as stored in settings.json (e.g. `noImageSupport`)
+    pub field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Why the change is proposed (probe evidence)
+    pub reason: String,
+}
+
+/// Proposed updates to one custom model
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPatch {
+    /// `CustomModel.id`, or `model` when the entry has no id
+    pub model_id: String,
+    pub model_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_image_support: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    /// Headers merged into the model's `extraHeaders`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_headers: Option<HashMap<String, String>>,
+    pub changes: Vec<ModelFieldChange>,
+    pub capabilities: ModelCapabilities,
+    pub probes: Vec<ConnectionDiagnostics>,
+}
+
+impl ModelPatch {
+    fn empty(model: &CustomModel) -> Self {
+        Self {
+            model_id: model_key(model).to_string(),
+            model_name: model
+                .display_name
+                .clone()
+                .unwrap_or_else(|| model.model.clone()),
+            no_image_support: None,
+            max_output_tokens: None,
+            extra_headers: None,
+            changes: Vec::new(),
+            capabilities: ModelCapabilities::default(),
+            probes: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.no_image_support.is_none()
+            && self.max_output_tokens.is_none()
+            && self.extra_headers.is_none()
+    }
+
+    fn set_no_image_support(&mut self, model: &CustomModel, value: bool, reason: String) {
+        self.changes.push(ModelFieldChange {
+            field: "noImageSupport".to_string(),
+            before: model.no_image_support.map(|v| v.to_string()),
+            after: Some(value.to_string()),
+            reason,
+        });
+        self.no_image_support = Some(value);
+    }
+
+    /// Applies the proposed values to `model`.
+    pub fn apply_to(&self, model: &mut CustomModel) {
+        if let Some(v) = self.no_image_support {
+            model.no_image_support = Some(v);
+        }
+        if let Some(v) = self.max_output_tokens {
+            model.max_output_tokens = Some(v);
+        }
+        if let Some(headers) = &self.extra_headers {
+            model
+                .extra_headers
+                .get_or_insert_with(HashMap::new)
+                .extend(headers.clone());
+        }
+    }
+}
+
+fn model_key(model: &CustomModel) -> &str {
+    model.id.as_deref().unwrap_or(&model.model)
+}
+
+// ============================================================================
+// Proposing
+// ============================================================================
+
+/// Probes `model` and proposes updates to its capability fields.
+///
+/// `secret://` references in the model are resolved for the probes only; the
+/// patch is built against the stored entry.
+pub async fn propose_model_patch_for_home(
+    home_dir: &Path,
+    model: &CustomModel,
+    config: TestConfig,
+) -> Result<ModelPatch, String> {
+    let resolved = secrets::resolve_secret_refs_for_home(home_dir, model)?;
+    Ok(propose_model_patch(model, &resolved, config).await)
+}
+
+async fn propose_model_patch(
+    stored: &CustomModel,
+    model: &CustomModel,
+    config: TestConfig,
+) -> ModelPatch {
+    let mut patch = ModelPatch::empty(stored);
+    let base_headers = model.extra_headers.clone().unwrap_or_default();
+    let mut tester = ModelTester::with_config(config.clone()).with_extra_headers(&base_headers);
+
+    // 1. Auth: some Anthropic relays only accept `Authorization: Bearer`
+    let has_auth_header = base_headers
+        .keys()
+        .any(|k| k.eq_ignore_ascii_case("authorization"));
+    if matches!(model.provider, Provider::Anthropic) && !has_auth_header {
+        if let Some(reason) = needs_bearer_auth(&tester, &config, model).await {
+            let headers = HashMap::from([(
+                "Authorization".to_string(),
+                format!("Bearer {}", model.api_key),
+            )]);
+            tester = ModelTester::with_config(config.clone())
+                .with_extra_headers(&base_headers)
+                .with_extra_headers(&headers);
+            // A header value is never resolved, so a vault-backed key can't
+            // be copied into it
+            if !secrets::is_secret_ref(&stored.api_key) {
+                patch.changes.push(ModelFieldChange {
+                    field: "extraHeaders.Authorization".to_string(),
+                    before: None,
+                    after: Some(format!(
+                        "Bearer {}",
+                        apply_plan::mask_secret(&model.api_key)
+                    )),
+                    reason,
+                });
+                patch.extra_headers = Some(headers);
+            }
+        }
+    }
+
+    // 2. Capabilities (vision drives noImageSupport)
+    let report = tester.probe_custom_model(model).await;
+    patch.capabilities = report.capabilities;
+    patch.probes = report.probes;
+    let vision_error = patch
+        .probes
+        .iter()
+        .find(|d| matches!(d.test_mode, TestMode::Vision))
+        .and_then(|d| d.error.clone());
+    match patch.capabilities.vision {
+        Some(false) if model.no_image_support != Some(true) => {
+            patch.set_no_image_support(
+                model,
+                true,
+                format!(
+                    "Vision probe was rejected: {}",
+                    vision_error.unwrap_or_else(|| "unsupported".to_string())
+                ),
+            );
+        }
+        Some(true) if model.no_image_support == Some(true) => {
+            patch.set_no_image_support(model, false, "Vision probe succeeded".to_string());
+        }
+        _ => {}
+    }
+
+    // 3. Output token limit
+    if let Some(configured) = model.max_output_tokens {
+        if let Some((limit, reason)) = find_output_token_limit(&tester, model, configured).await {
+            patch.changes.push(ModelFieldChange {
+                field: "maxOutputTokens".to_string(),
+                before: Some(configured.to_string()),
+                after: Some(limit.to_string()),
+                reason,
+            });
+            patch.max_output_tokens = Some(limit);
+        }
+    }
+
+    patch
+}
+
+fn minimal_request(model: &CustomModel, max_tokens: u32) -> serde_json::Value {
+    serde_json::json!({
+        "model": model.model,
+        "max_tokens": max_tokens,
+        "messages": [{"role": "user", "content": "Hi"}]
+    })
+}
+
+/// Returns a reason when the native `x-api-key` auth is rejected but a retry
+/// with `Authorization: Bearer` succeeds.
+async fn needs_bearer_auth(
+    tester: &ModelTester,
+    config: &TestConfig,
+    model: &CustomModel,
+) -> Option<String> {
+    let body = minimal_request(model, 16);
+    let (status, _) = tester
+        .send_message(
+            WireProtocol::from_provider(&model.provider),
+            &model.base_url,
+            &model.api_key,
+            &model.model,
+            &body,
+        )
+        .await
+        .ok()?;
+    if !matches!(status.as_u16(), 401 | 403) {
+        return None;
+    }
+
+    let bearer = HashMap::from([(
+        "Authorization".to_string(),
+        format!("Bearer {}", model.api_key),
+    )]);
+    let retry = ModelTester::with_config(config.clone())
+        .with_extra_headers(model.extra_headers.as_ref().unwrap_or(&HashMap::new()))
+        .with_extra_headers(&bearer);
+    let (retry_status, _) = retry
+        .send_message(
+            WireProtocol::from_provider(&model.provider),
+            &model.base_url,
+            &model.api_key,
+            &model.model,
+            &body,
+        )
+        .await
+        .ok()?;
+    retry_status
+        .is_success()
+        .then(|| format!("x-api-key auth was rejected (HTTP {status}); Bearer auth succeeded"))
+}
+
+/// When the provider rejects the configured `max_tokens`, returns the limit it
+/// accepts: parsed from the error message, or found by halving the value.
+async fn find_output_token_limit(
+    tester: &ModelTester,
+    model: &CustomModel,
+    configured: u32,
+) -> Option<(u32, String)> {
+    let (status, body) = tester
+        .send_message(
+            WireProtocol::from_provider(&model.provider),
+            &model.base_url,
+            &model.api_key,
+            &model.model,
+            &minimal_request(model, configured),
+        )
+        .await
+        .ok()?;
+    if status.is_success() || !matches!(status.as_u16(), 400 | 422) || !mentions_token_limit(&body)
+    {
+        return None;
+    }
+    let rejection = format!(
+        "Provider rejected max_tokens={configured}: {}",
+        truncate_body(&body)
+    );
+
+    if let Some(limit) = parse_token_limit(&body, configured) {
+        return Some((limit, rejection));
+    }
+
+    let mut candidate = configured / 2;
+    for _ in 0..MAX_STEP_DOWN_PROBES {
+        if candidate < MIN_OUTPUT_TOKENS {
+            break;
+        }
+        let accepted = tester
+            .send_message(
+                WireProtocol::from_provider(&model.provider),
+                &model.base_url,
+                &model.api_key,
+                &model.model,
+                &minimal_request(model, candidate),
+            )
+            .await
+            .is_ok_and(|(status, _)| status.is_success());
+        if accepted {
+            return Some((
+                candidate,
+                format!("{rejection} (max_tokens={candidate} was accepted)"),
+            ));
+        }
+        candidate /= 2;
+    }
+    None
+}
+
+fn mentions_token_limit(body: &str) -> bool {
+    let lower = body.to_ascii_lowercase();
+    ["max_tokens", "max_output_tokens", "max_completion_tokens"]
+        .iter()
+        .any(|k| lower.contains(k))
+        || (lower.contains("token")
+            && ["maximum", "at most", "limit", "exceed"]
+                .iter()
+                .any(|k| lower.contains(k)))
+}
+
+/// Picks the largest number below `configured` mentioned in the error message,
+/// e.g. `max_tokens: 100000 > 64000, which is the maximum allowed`.
+fn parse_token_limit(body: &str, configured: u32) -> Option<u32> {
+    body.split(|c: char| !c.is_ascii_digit())
+        .filter_map(|n| n.parse::<u32>().ok())
+        .filter(|n| (MIN_OUTPUT_TOKENS..configured).contains(n))
+        .max()
+}
+
+/// Blocking variant of [`propose_model_patch_for_home`] for sync callers (TUI).
+pub fn propose_model_patch_blocking(
+    home_dir: &Path,
+    model: &CustomModel,
+    config: TestConfig,
+) -> Result<ModelPatch, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create async runtime: {e}"))?;
+    runtime.block_on(propose_model_patch_for_home(home_dir, model, config))
+}
+
+/// Probes the custom model with the given id in the active settings file.
+pub async fn propose_model_patch_for_id(
+    model_id: &str,
+    config: TestConfig,
+) -> Result<ModelPatch, String> {
+    let models = factory_settings::load_custom_models()?;
+    let model = models
+        .iter()
+        .find(|m| model_key(m) == model_id)
+        .ok_or_else(|| format!("Model {model_id} not found"))?;
+    propose_model_patch_for_home(&paths::get_home_dir()?, model, config).await
+}
+
+// ============================================================================
+// Accepting
+// ============================================================================
+
+/// Applies `patch` to the matching entry of `models`.
+pub fn apply_model_patch(models: &mut [CustomModel], patch: &ModelPatch) -> Result<(), String> {
+    let model = models
+        .iter_mut()
+        .find(|m| model_key(m) == patch.model_id)
+        .ok_or_else(|| format!("Model {} not found", patch.model_id))?;
+    patch.apply_to(model);
+    Ok(())
+}
+
+/// Dry-run of [`accept_model_patch_for_home`].
+pub fn plan_model_patch_for_home(home_dir: &Path, patch: &ModelPatch) -> Result<ApplyPlan, String> {
+    let mut models = factory_settings::load_custom_models_for_home(home_dir)?;
+    apply_model_patch(&mut models, patch)?;
+    factory_settings::plan_save_custom_models_for_home(home_dir, models)
+}
+
+pub fn accept_model_patch_for_home(home_dir: &Path, patch: &ModelPatch) -> Result<(), String> {
+    let mut models = factory_settings::load_custom_models_for_home(home_dir)?;
+    apply_model_patch(&mut models, patch)?;
+    factory_settings::save_custom_models_for_home(home_dir, models)
+}
+
+pub fn plan_model_patch(patch: &ModelPatch) -> Result<ApplyPlan, String> {
+    let mut models = factory_settings::load_custom_models()?;
+    apply_model_patch(&mut models, patch)?;
+    factory_settings::plan_save_custom_models(models)
+}
+
+pub fn accept_model_patch(patch: &ModelPatch) -> Result<(), String> {
+    let mut models = factory_settings::load_custom_models()?;
+    apply_model_patch(&mut models, patch)?;
+    factory_settings::save_custom_models(models)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, serve_recording};
+    use tempfile::TempDir;
+
+    fn model(base_url: &str, provider: Provider, max_output_tokens: Option<u32>) -> CustomModel {
+        CustomModel {
+            model: "claude-x".to_string(),
+            id: Some("custom:claude-x-0".to_string()),
+            index: Some(0),
+            display_name: Some("Claude X".to_string()),
+            base_url: base_url.to_string(),
+            api_key: "sk-test-key-123456".to_string(),
+            provider,
+            max_output_tokens,
+            no_image_support: None,
+            extra_args: None,
+            extra_headers: None,
+            channel_ref: None,
+        }
+    }
+
+    fn config() -> TestConfig {
+        TestConfig {
+            timeout_seconds: 5,
+            retry_on_failure: false,
+            max_retries: 0,
+            retry_backoff_ms: 1,
+            max_concurrency: 1,
+        }
+    }
+
+    #[test]
+    fn proposes_bearer_header_vision_flag_and_token_limit() {
+        let text = r#"{"content":[{"type":"text","text":"Hi"}]}"#;
+        let sse = concat!(
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"text\":\"Hi\"}}\n\n",
+            "data: {\"type\":\"message_stop\"}\n\n"
+        );
+        let tool =
+            r#"{"content":[{"type":"tool_use","name":"get_weather","input":{"city":"Paris"}}]}"#;
+        let (base, requests) = serve_recording(vec![
+            (401, r#"{"error":"invalid x-api-key"}"#.to_string()),
+            (200, text.to_string()),
+            (200, sse.to_string()),
+            (200, tool.to_string()),
+            (400, r#"{"error":"image input is not supported"}"#.to_string()),
+            (
+                400,
+                r#"{"error":{"message":"max_tokens: 100000 > 64000, which is the maximum allowed"}}"#
+                    .to_string(),
+            ),
+        ]);
+        let m = model(&base, Provider::Anthropic, Some(100_000));
+
+        let patch = block_on(propose_model_patch(&m, &m, config()));
+        assert_eq!(patch.model_id, "custom:claude-x-0");
+        assert_eq!(
+            patch
+                .extra_headers
+                .as_ref()
+                .and_then(|h| h.get("Authorization"))
+                .map(String::as_str),
+            Some("Bearer sk-test-key-123456")
+        );
+        assert_eq!(patch.no_image_support, Some(true));
+        assert_eq!(patch.max_output_tokens, Some(64_000));
+        assert_eq!(patch.capabilities.streaming, Some(true));
+        assert_eq!(patch.capabilities.tool_use, Some(true));
+        let fields: Vec<&str> = patch.changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "extraHeaders.Authorization",
+                "noImageSupport",
+                "maxOutputTokens"
+            ]
+        );
+        // Review output never shows the full key
+        assert!(!serde_json::to_string(&patch.changes)
+            .unwrap()
+            .contains("sk-test-key-123456"));
+
+        // Probes after the auth check carry the Bearer header
+        let requests = requests.lock().unwrap();
+        assert!(!requests[0].to_lowercase().contains("authorization: bearer"));
+        assert!(requests[2..].iter().all(|r| r
+            .to_lowercase()
+            .contains("authorization: bearer sk-test-key-123456")));
+    }
+
+    #[test]
+    fn vault_key_is_resolved_for_probes_but_not_copied_into_headers() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        secrets::init_vault_for_home(home, "correct horse").unwrap();
+        let reference = secrets::set_secret_for_home(home, "factory", "sk-vault-key").unwrap();
+        let text = r#"{"content":[{"type":"text","text":"Hi"}]}"#;
+        let (base, requests) = serve_recording(vec![
+            (401, r#"{"error":"invalid x-api-key"}"#.to_string()),
+            (200, text.to_string()),
+            (200, "data: {\"type\":\"message_stop\"}\n\n".to_string()),
+            (200, text.to_string()),
+            (200, text.to_string()),
+        ]);
+        let m = CustomModel {
+            api_key: reference,
+            ..model(&base, Provider::Anthropic, None)
+        };
+
+        let patch = block_on(propose_model_patch_for_home(home, &m, config())).unwrap();
+        assert!(patch.extra_headers.is_none());
+        assert!(patch
+            .changes
+            .iter()
+            .all(|c| c.field != "extraHeaders.Authorization"));
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].contains("sk-vault-key"));
+        assert!(requests[2..].iter().all(|r| r
+            .to_lowercase()
+            .contains("authorization: bearer sk-vault-key")));
+    }
+
+    #[test]
+    fn healthy_model_yields_empty_patch() {
+        let text = r#"{"choices":[{"message":{"content":"Red"}}]}"#;
+        let sse = "data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"},\"finish_reason\":\"stop\"}]}\n\ndata: [DONE]\n\n";
+        let tool = r#"{"choices":[{"message":{"tool_calls":[{"function":{"name":"get_weather","arguments":"{}"}}]}}]}"#;
+        let (base, _) = serve_recording(vec![
+            (200, sse.to_string()),
+            (200, tool.to_string()),
+            (200, text.to_string()),
+            (200, text.to_string()),
+        ]);
+        let m = model(&base, Provider::Openai, Some(8192));
+        let patch = block_on(propose_model_patch(&m, &m, config()));
+        assert!(patch.is_empty(), "{:?}", patch.changes);
+        assert_eq!(patch.capabilities.vision, Some(true));
+    }
+
+    #[test]
+    fn accepted_patch_is_saved_through_custom_models() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let m = model(
+            "https://relay.example.com",
+            Provider::Anthropic,
+            Some(100_000),
+        );
+        factory_settings::save_custom_models_for_home(home, vec![m.clone()]).unwrap();
+
+        let mut patch = ModelPatch::empty(&m);
+        patch.max_output_tokens = Some(64_000);
+        patch.no_image_support = Some(true);
+
+        let plan = plan_model_patch_for_home(home, &patch).unwrap();
+        assert!(plan.has_changes());
+        assert_eq!(
+            factory_settings::load_custom_models_for_home(home).unwrap()[0].max_output_tokens,
+            Some(100_000)
+        );
+
+        accept_model_patch_for_home(home, &patch).unwrap();
+        let saved = &factory_settings::load_custom_models_for_home(home).unwrap()[0];
+        assert_eq!(saved.max_output_tokens, Some(64_000));
+        assert_eq!(saved.no_image_support, Some(true));
+
+        patch.model_id = "missing".to_string();
+        assert!(accept_model_patch_for_home(home, &patch).is_err());
+    }
+
+    #[test]
+    fn token_limit_parsing() {
+        assert!(mentions_token_limit(
+            "max_tokens is too large: 100000. This model supports at most 16384 completion tokens"
+        ));
+        assert!(!mentions_token_limit("invalid api key"));
+        assert_eq!(
+            parse_token_limit(
+                "max_tokens is too large: 100000. This model supports at most 16384 completion tokens",
+                100_000
+            ),
+            Some(16384)
+        );
+        assert_eq!(
+            parse_token_limit("claude-3-5 max_tokens too large", 8192),
+            None
+        );
+    }
+}
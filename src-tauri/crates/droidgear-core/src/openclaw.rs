@@ -10,7 +10,10 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{paths, storage};
+use crate::apply_plan::{file_plan, ApplyPlan, PlanFormat};
+use crate::channel::ChannelRef;
+use crate::factory_settings::ModelInfo;
+use crate::{backup, paths, secrets, storage};
 
 // ============================================================================
 // Types
@@ -45,6 +48,9 @@ pub struct OpenClawProviderConfig {
     pub api: Option<String>,
     #[serde(default)]
     pub models: Vec<OpenClawModel>,
+    /// Channel this entry was provisioned from (DroidGear-only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ref: Option<ChannelRef>,
 }
 
 /// Block streaming chunk configuration
@@ -186,6 +192,33 @@ pub struct OpenClawSubAgent {
     pub subagents: Option<OpenClawSubAgentSubagentsConfig>,
 }
 
+// ============================================================================
+// Channel import
+// ============================================================================
+
+/// Builds an OpenClaw model from channel metadata; limits the channel does
+/// not report are left to OpenClaw's defaults.
+pub fn openclaw_model_from_model_info(info: &ModelInfo) -> OpenClawModel {
+    let input: Vec<String> = info
+        .input_modalities
+        .iter()
+        .filter(|m| matches!(m.as_str(), "text" | "image"))
+        .cloned()
+        .collect();
+    OpenClawModel {
+        id: info.id.clone(),
+        name: info.name.clone(),
+        reasoning: info.supports_reasoning.unwrap_or(false),
+        input: if input.is_empty() {
+            vec!["text".to_string()]
+        } else {
+            input
+        },
+        context_window: info.context_window,
+        max_tokens: info.max_output_tokens,
+    }
+}
+
 // ============================================================================
 // Path Helpers
 // ============================================================================
@@ -361,6 +394,7 @@ fn parse_openclaw_config(
                             .and_then(|v| v.as_str())
                             .map(|s| s.to_string()),
                         models: Vec::new(),
+                        channel_ref: None,
                     };
 
                     if let Some(models_arr) = provider_obj.get("models").and_then(|v| v.as_array())
@@ -423,12 +457,11 @@ fn read_openclaw_config_raw_for_home(home_dir: &Path) -> Result<Value, String> {
     serde_json::from_str(&s).map_err(|e| format!("Invalid config JSON: {e}"))
 }
 
-fn write_openclaw_config_for_home(
+/// Internal: render the openclaw.json content an apply would write.
+fn render_openclaw_config_for_home(
     home_dir: &Path,
     profile: &OpenClawProfile,
-) -> Result<(), String> {
-    let config_path = openclaw_config_path_for_home(home_dir)?;
-
+) -> Result<String, String> {
     // Surgically update DroidGear-owned fields on top of the existing config.
     // Everything else — unknown keys, key order, user-added settings — is
     // preserved.
@@ -438,8 +471,16 @@ fn write_openclaw_config_for_home(
     }
     apply_profile_in_place(&mut config, profile);
 
-    let s = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize config: {e}"))?;
+    serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize config: {e}"))
+}
+
+fn write_openclaw_config_for_home(
+    home_dir: &Path,
+    profile: &OpenClawProfile,
+) -> Result<(), String> {
+    let config_path = openclaw_config_path_for_home(home_dir)?;
+    let s = render_openclaw_config_for_home(home_dir, profile)?;
+    backup::snapshot_file_for_home(home_dir, &config_path, "openclaw")?;
     storage::atomic_write(&config_path, s.as_bytes())
 }
 
@@ -744,6 +785,18 @@ fn apply_models_providers(
     }
 }
 
+/// Render providers as a standalone `models` block (`mode` + `providers`), the
+/// same shape an apply writes into openclaw.json.
+pub(crate) fn render_models_block(providers: &HashMap<String, OpenClawProviderConfig>) -> Value {
+    let mut providers_map = serde_json::Map::new();
+    for (id, provider) in providers {
+        let mut provider_obj = serde_json::Map::new();
+        update_provider_in_place(&mut provider_obj, provider);
+        providers_map.insert(id.clone(), Value::Object(provider_obj));
+    }
+    serde_json::json!({ "mode": "merge", "providers": providers_map })
+}
+
 /// Final pass: drop top-level blocks DroidGear created but didn't populate.
 /// Existing user blocks (even if empty) are left alone.
 fn cleanup_empty_dg_blocks(config_obj: &mut serde_json::Map<String, Value>) {
@@ -1029,11 +1082,38 @@ pub fn apply_openclaw_profile_for_home(
     home_dir: &Path,
     profile: &OpenClawProfile,
 ) -> Result<(), String> {
+    let profile = &secrets::resolve_secret_refs_for_home(home_dir, profile)?;
     write_openclaw_config_for_home(home_dir, profile)?;
     set_active_profile_id_for_home(home_dir, &profile.id)?;
     Ok(())
 }
 
+/// Dry-run of [`apply_openclaw_profile_for_home`]: returns the planned writes without touching disk.
+pub fn plan_apply_openclaw_profile_for_home(
+    home_dir: &Path,
+    profile: &OpenClawProfile,
+) -> Result<ApplyPlan, String> {
+    let profile = &secrets::resolve_secret_refs_for_home(home_dir, profile)?;
+    let config = render_openclaw_config_for_home(home_dir, profile)?;
+
+    Ok(ApplyPlan {
+        tool: "openclaw".to_string(),
+        profile_id: Some(profile.id.clone()),
+        files: vec![
+            file_plan(
+                &openclaw_config_path_for_home(home_dir)?,
+                config,
+                PlanFormat::Json,
+            ),
+            file_plan(
+                &active_profile_path_for_home(home_dir)?,
+                profile.id.clone(),
+                PlanFormat::Text,
+            ),
+        ],
+    })
+}
+
 pub fn get_openclaw_config_status_for_home(
     home_dir: &Path,
 ) -> Result<OpenClawConfigStatus, String> {
@@ -1107,6 +1187,10 @@ pub fn apply_openclaw_profile(profile: &OpenClawProfile) -> Result<(), String> {
     apply_openclaw_profile_for_home(&system_home_dir()?, profile)
 }
 
+pub fn plan_apply_openclaw_profile(profile: &OpenClawProfile) -> Result<ApplyPlan, String> {
+    plan_apply_openclaw_profile_for_home(&system_home_dir()?, profile)
+}
+
 pub fn get_openclaw_config_status() -> Result<OpenClawConfigStatus, String> {
     get_openclaw_config_status_for_home(&system_home_dir()?)
 }
@@ -1220,6 +1304,7 @@ pub fn save_openclaw_subagents_for_home(
 
     let s = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {e}"))?;
+    backup::snapshot_file_for_home(home_dir, &config_path, "openclaw")?;
     storage::atomic_write(&config_path, s.as_bytes())
 }
 
@@ -1278,6 +1363,21 @@ mod tests {
     // Roundtrip tests (build → parse → verify)
     // ------------------------------------------------------------------
 
+    #[test]
+    fn builds_model_from_channel_metadata() {
+        let info = ModelInfo {
+            id: "gpt-4o".to_string(),
+            context_window: Some(128000),
+            input_modalities: vec!["image".into(), "text".into()],
+            ..Default::default()
+        };
+        let model = openclaw_model_from_model_info(&info);
+        assert!(!model.reasoning);
+        assert_eq!(model.input, vec!["image", "text"]);
+        assert_eq!(model.context_window, Some(128000));
+        assert_eq!(model.max_tokens, None);
+    }
+
     #[test]
     fn test_roundtrip_empty_profile() {
         let profile = empty_profile();
@@ -1383,6 +1483,7 @@ mod tests {
                         max_tokens: Some(4096),
                     },
                 ],
+                channel_ref: None,
             },
         );
         profile.providers = providers;
@@ -1430,6 +1531,7 @@ mod tests {
                 api_key: None,
                 api: None,
                 models: vec![],
+                channel_ref: None,
             },
         );
         profile.providers = providers;
@@ -1564,6 +1666,7 @@ mod tests {
                     context_window: Some(1000000),
                     max_tokens: Some(32000),
                 }],
+                channel_ref: None,
             },
         );
         profile.providers = providers;
@@ -2048,6 +2151,7 @@ mod tests {
                     context_window: Some(1000),
                     max_tokens: Some(500),
                 }],
+                channel_ref: None,
             },
         );
         profile.providers = providers;
@@ -2150,7 +2254,7 @@ mod tests {
         // Create a temp directory and write an existing openclaw.json
         let temp = tempfile::TempDir::new().unwrap();
         let home = temp.path().join("home");
-        std::fs::create_dir_all(&home.join(".openclaw")).unwrap();
+        std::fs::create_dir_all(home.join(".openclaw")).unwrap();
 
         let existing_config = r#"
         {
@@ -11,7 +11,10 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{paths, storage};
+use crate::apply_plan::{file_plan, ApplyPlan, PlanFormat};
+use crate::channel::ChannelRef;
+use crate::factory_settings::ModelInfo;
+use crate::{backup, paths, secrets, storage};
 
 // ============================================================================
 // Types
@@ -64,6 +67,9 @@ pub struct OpenCodeProviderConfig {
     pub options: Option<OpenCodeProviderOptions>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub models: Option<HashMap<String, OpenCodeModelConfig>>,
+    /// Channel this entry was provisioned from (DroidGear-only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ref: Option<ChannelRef>,
 }
 
 /// OpenCode Profile
@@ -111,6 +117,22 @@ pub struct OpenCodeCurrentConfig {
     pub auth: HashMap<String, Value>,
 }
 
+// ============================================================================
+// Channel import
+// ============================================================================
+
+/// Builds an OpenCode model entry (name and limits) from channel metadata.
+pub fn opencode_model_from_model_info(info: &ModelInfo) -> OpenCodeModelConfig {
+    let limit = OpenCodeModelLimit {
+        context: info.context_window,
+        output: info.max_output_tokens,
+    };
+    OpenCodeModelConfig {
+        name: info.name.clone(),
+        limit: (limit.context.is_some() || limit.output.is_some()).then_some(limit),
+    }
+}
+
 // ============================================================================
 // Path Helpers
 // ============================================================================
@@ -365,15 +387,28 @@ pub fn get_active_opencode_profile_id_for_home(home_dir: &Path) -> Result<Option
     }
 }
 
-pub fn apply_opencode_profile_for_home(home_dir: &Path, id: &str) -> Result<(), String> {
-    let profile = get_opencode_profile_for_home(home_dir, id)?;
+/// Serialize providers as they appear under `provider` in opencode.json.
+pub(crate) fn providers_to_value(
+    providers: &HashMap<String, OpenCodeProviderConfig>,
+) -> Result<Value, String> {
+    // Channel links are DroidGear-only and never reach OpenCode's config.
+    let mut providers = providers.clone();
+    for provider in providers.values_mut() {
+        provider.channel_ref = None;
+    }
+    serde_json::to_value(&providers).map_err(|e| format!("Failed to serialize providers: {e}"))
+}
 
+/// Internal: render the config and auth file contents an apply would write.
+fn render_apply_files(
+    home_dir: &Path,
+    profile: &OpenCodeProfile,
+) -> Result<(String, String), String> {
     let config_path = opencode_config_path_for_home(home_dir)?;
     let mut config = read_json_file(&config_path);
 
     if !profile.providers.is_empty() {
-        let providers_value = serde_json::to_value(&profile.providers)
-            .map_err(|e| format!("Failed to serialize providers: {e}"))?;
+        let providers_value = providers_to_value(&profile.providers)?;
 
         if let Some(obj) = config.as_object_mut() {
             let existing = obj
@@ -391,7 +426,6 @@ pub fn apply_opencode_profile_for_home(home_dir: &Path, id: &str) -> Result<(),
 
     let config_content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {e}"))?;
-    storage::atomic_write(&config_path, config_content.as_bytes())?;
 
     let auth_path = opencode_auth_path_for_home(home_dir)?;
     let mut auth = read_json_file(&auth_path);
@@ -406,6 +440,23 @@ pub fn apply_opencode_profile_for_home(home_dir: &Path, id: &str) -> Result<(),
 
     let auth_content = serde_json::to_string_pretty(&auth)
         .map_err(|e| format!("Failed to serialize auth: {e}"))?;
+
+    Ok((config_content, auth_content))
+}
+
+pub fn apply_opencode_profile_for_home(home_dir: &Path, id: &str) -> Result<(), String> {
+    let profile = secrets::resolve_secret_refs_for_home(
+        home_dir,
+        &get_opencode_profile_for_home(home_dir, id)?,
+    )?;
+    let (config_content, auth_content) = render_apply_files(home_dir, &profile)?;
+
+    let config_path = opencode_config_path_for_home(home_dir)?;
+    backup::snapshot_file_for_home(home_dir, &config_path, "opencode")?;
+    storage::atomic_write(&config_path, config_content.as_bytes())?;
+
+    let auth_path = opencode_auth_path_for_home(home_dir)?;
+    backup::snapshot_file_for_home(home_dir, &auth_path, "opencode")?;
     storage::atomic_write(&auth_path, auth_content.as_bytes())?;
 
     let active_path = active_profile_path_for_home(home_dir)?;
@@ -414,6 +465,40 @@ pub fn apply_opencode_profile_for_home(home_dir: &Path, id: &str) -> Result<(),
     Ok(())
 }
 
+/// Dry-run of [`apply_opencode_profile_for_home`]: returns the planned writes without touching disk.
+pub fn plan_apply_opencode_profile_for_home(
+    home_dir: &Path,
+    id: &str,
+) -> Result<ApplyPlan, String> {
+    let profile = secrets::resolve_secret_refs_for_home(
+        home_dir,
+        &get_opencode_profile_for_home(home_dir, id)?,
+    )?;
+    let (config_content, auth_content) = render_apply_files(home_dir, &profile)?;
+
+    Ok(ApplyPlan {
+        tool: "opencode".to_string(),
+        profile_id: Some(id.to_string()),
+        files: vec![
+            file_plan(
+                &opencode_config_path_for_home(home_dir)?,
+                config_content,
+                PlanFormat::Json,
+            ),
+            file_plan(
+                &opencode_auth_path_for_home(home_dir)?,
+                auth_content,
+                PlanFormat::Json,
+            ),
+            file_plan(
+                &active_profile_path_for_home(home_dir)?,
+                id.to_string(),
+                PlanFormat::Text,
+            ),
+        ],
+    })
+}
+
 pub fn get_opencode_config_status_for_home(
     home_dir: &Path,
 ) -> Result<OpenCodeConfigStatus, String> {
@@ -592,6 +677,10 @@ pub fn apply_opencode_profile(id: &str) -> Result<(), String> {
     apply_opencode_profile_for_home(&system_home_dir()?, id)
 }
 
+pub fn plan_apply_opencode_profile(id: &str) -> Result<ApplyPlan, String> {
+    plan_apply_opencode_profile_for_home(&system_home_dir()?, id)
+}
+
 pub fn get_opencode_config_status() -> Result<OpenCodeConfigStatus, String> {
     get_opencode_config_status_for_home(&system_home_dir()?)
 }
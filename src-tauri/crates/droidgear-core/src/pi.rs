@@ -10,7 +10,10 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{paths, storage};
+use crate::apply_plan::{file_plan, ApplyPlan, PlanFormat};
+use crate::channel::ChannelRef;
+use crate::factory_settings::ModelInfo;
+use crate::{backup, paths, secrets, storage};
 
 // ============================================================================
 // Types
@@ -135,6 +138,9 @@ pub struct PiProviderConfig {
     pub model_overrides: Option<HashMap<String, PiModelOverride>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compat: Option<PiCompatConfig>,
+    /// Channel this entry was provisioned from (DroidGear-only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ref: Option<ChannelRef>,
 }
 
 /// Pi profile (stored in DroidGear)
@@ -183,6 +189,41 @@ fn default_max_tokens() -> u32 {
     16384
 }
 
+// ============================================================================
+// Channel import
+// ============================================================================
+
+/// Builds a Pi model from channel metadata, falling back to the defaults
+/// used for hand-added models where the channel reports nothing.
+pub fn pi_model_from_model_info(info: &ModelInfo) -> PiModel {
+    let input: Vec<String> = info
+        .input_modalities
+        .iter()
+        .filter(|m| matches!(m.as_str(), "text" | "image"))
+        .cloned()
+        .collect();
+    PiModel {
+        id: info.id.clone(),
+        name: info.name.clone(),
+        api: None,
+        reasoning: info.supports_reasoning.unwrap_or(false),
+        input: if input.is_empty() {
+            default_input()
+        } else {
+            input
+        },
+        context_window: info.context_window.unwrap_or_else(default_context_window),
+        max_tokens: info.max_output_tokens.unwrap_or_else(default_max_tokens),
+        cost: info.pricing.as_ref().map(|p| PiModelCost {
+            input: p.input.unwrap_or(0.0),
+            output: p.output.unwrap_or(0.0),
+            cache_read: p.cache_read.unwrap_or(0.0),
+            cache_write: p.cache_write.unwrap_or(0.0),
+        }),
+        compat: None,
+    }
+}
+
 // ============================================================================
 // Path Helpers
 // ============================================================================
@@ -429,19 +470,53 @@ pub fn set_active_pi_profile_id_for_home(home_dir: &Path, id: &str) -> Result<()
 /// `{ "providers": {...} }` to Pi's models.json. Also sets the active profile
 /// ID to the applied profile.
 pub fn apply_pi_profile_for_home(home_dir: &Path, id: &str) -> Result<(), String> {
-    let profile = load_profile_by_id(home_dir, id)?;
+    let profile =
+        secrets::resolve_secret_refs_for_home(home_dir, &load_profile_by_id(home_dir, id)?)?;
     let config_path = pi_config_path_for_home(home_dir)?;
-
-    let current = PiCurrentConfig {
-        providers: profile.providers,
-    };
-    let s = serde_json::to_string_pretty(&current)
-        .map_err(|e| format!("Failed to serialize Pi config: {e}"))?;
+    let s = render_models_json(profile)?;
+    backup::snapshot_file_for_home(home_dir, &config_path, "pi")?;
     storage::atomic_write(&config_path, s.as_bytes())?;
     set_active_pi_profile_id_for_home(home_dir, id)?;
     Ok(())
 }
 
+fn render_models_json(profile: PiProfile) -> Result<String, String> {
+    render_providers_json(profile.providers)
+}
+
+/// Serialize providers as Pi's `models.json` document.
+pub(crate) fn render_providers_json(
+    mut providers: HashMap<String, PiProviderConfig>,
+) -> Result<String, String> {
+    // Channel links are DroidGear-only and never reach Pi's models.json.
+    for provider in providers.values_mut() {
+        provider.channel_ref = None;
+    }
+    let current = PiCurrentConfig { providers };
+    serde_json::to_string_pretty(&current)
+        .map_err(|e| format!("Failed to serialize Pi config: {e}"))
+}
+
+/// Dry-run of [`apply_pi_profile_for_home`]: returns the planned writes without touching disk.
+pub fn plan_apply_pi_profile_for_home(home_dir: &Path, id: &str) -> Result<ApplyPlan, String> {
+    let profile =
+        secrets::resolve_secret_refs_for_home(home_dir, &load_profile_by_id(home_dir, id)?)?;
+    let s = render_models_json(profile)?;
+
+    Ok(ApplyPlan {
+        tool: "pi".to_string(),
+        profile_id: Some(id.to_string()),
+        files: vec![
+            file_plan(&pi_config_path_for_home(home_dir)?, s, PlanFormat::Json),
+            file_plan(
+                &active_profile_path_for_home(home_dir)?,
+                id.to_string(),
+                PlanFormat::Text,
+            ),
+        ],
+    })
+}
+
 /// Get the status of `~/.pi/agent/models.json`.
 pub fn get_pi_config_status_for_home(home_dir: &Path) -> Result<PiConfigStatus, String> {
     let config_path = pi_config_path_for_home(home_dir)?;
@@ -509,6 +584,10 @@ pub fn apply_pi_profile(id: &str) -> Result<(), String> {
     apply_pi_profile_for_home(&system_home_dir()?, id)
 }
 
+pub fn plan_apply_pi_profile(id: &str) -> Result<ApplyPlan, String> {
+    plan_apply_pi_profile_for_home(&system_home_dir()?, id)
+}
+
 pub fn get_pi_config_status() -> Result<PiConfigStatus, String> {
     get_pi_config_status_for_home(&system_home_dir()?)
 }
@@ -564,6 +643,7 @@ mod tests {
                 }],
                 model_overrides: None,
                 compat: None,
+                channel_ref: None,
             },
         );
         PiProfile {
@@ -576,6 +656,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn builds_model_from_channel_metadata() {
+        let info = ModelInfo {
+            id: "anthropic/claude-sonnet-4".to_string(),
+            name: Some("Claude Sonnet 4".to_string()),
+            context_window: Some(200000),
+            max_output_tokens: Some(64000),
+            input_modalities: vec!["text".into(), "image".into(), "file".into()],
+            supports_reasoning: Some(true),
+            pricing: Some(crate::factory_settings::ModelPricing {
+                input: Some(3.0),
+                output: Some(15.0),
+                cache_read: Some(0.3),
+                cache_write: None,
+            }),
+            ..Default::default()
+        };
+        let model = pi_model_from_model_info(&info);
+        assert!(model.reasoning);
+        assert_eq!(model.input, vec!["text", "image"]);
+        assert_eq!(model.context_window, 200000);
+        assert_eq!(model.max_tokens, 64000);
+        let cost = model.cost.unwrap();
+        assert_eq!(cost.output, 15.0);
+        assert_eq!(cost.cache_write, 0.0);
+
+        let bare = pi_model_from_model_info(&ModelInfo {
+            id: "llama3".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(bare.input, vec!["text"]);
+        assert_eq!(bare.context_window, 128000);
+        assert_eq!(bare.max_tokens, 16384);
+        assert!(bare.cost.is_none());
+    }
+
     #[test]
     fn test_type_serialization() {
         let model = PiModel {
@@ -612,6 +728,7 @@ mod tests {
             models: vec![],
             model_overrides: None,
             compat: None,
+            channel_ref: None,
         };
 
         let json = serde_json::to_string_pretty(&provider).unwrap();
@@ -956,6 +1073,7 @@ mod tests {
                 models: vec![],
                 model_overrides: None,
                 compat: None,
+                channel_ref: None,
             },
         );
         save_pi_profile_for_home(home, updated).unwrap();
@@ -1087,6 +1205,7 @@ mod tests {
                 }],
                 model_overrides: None,
                 compat: None,
+                channel_ref: None,
             },
         );
         providers.insert(
@@ -1100,6 +1219,7 @@ mod tests {
                 models: vec![],
                 model_overrides: None,
                 compat: None,
+                channel_ref: None,
             },
         );
 
@@ -0,0 +1,795 @@
+//! Connectivity testing for tool profiles (core).
+//!
+//! Enumerates the model endpoints configured in Codex, Claude Code, OpenCode,
+//! OpenClaw, Pi and Hermes profiles and tests them with the matching
+//! [`WireProtocol`], so a profile can be checked before it is applied.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::claude::ClaudeCodeProfile;
+use crate::codex::CodexProfile;
+use crate::connectivity::{
+    self, ConnectionDiagnostics, ModelTester, TestConfig, TestMode, WireProtocol,
+    DEFAULT_INFERENCE_PROMPT,
+};
+use crate::connectivity_history::HistoryRecord;
+use crate::hermes::HermesProfile;
+use crate::openclaw::OpenClawProfile;
+use crate::opencode::OpenCodeProfile;
+use crate::pi::PiProfile;
+use crate::profiles::ProfileTool;
+use crate::{claude, codex, hermes, openclaw, opencode, paths, pi, secrets};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One model endpoint configured in a profile
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileEndpoint {
+    pub tool: ProfileTool,
+    pub profile_id: String,
+    pub profile_name: String,
+    /// Provider key inside the profile (None for single-endpoint profiles)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    pub model_id: String,
+    pub protocol: WireProtocol,
+    pub base_url: String,
+    pub has_api_key: bool,
+    /// Resolved key (never serialized)
+    #[serde(skip)]
+    pub api_key: String,
+    /// Extra headers sent with every request (never serialized, may hold credentials)
+    #[serde(skip)]
+    pub headers: HashMap<String, String>,
+}
+
+/// Connectivity result for one profile endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileTestResult {
+    pub endpoint: ProfileEndpoint,
+    pub diagnostics: ConnectionDiagnostics,
+    pub is_available: bool,
+}
+
+// ============================================================================
+// Endpoint enumeration
+// ============================================================================
+
+const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+
+/// Official endpoint for protocols that have one, used when a provider omits its base URL.
+fn default_base_url(protocol: WireProtocol) -> &'static str {
+    match protocol {
+        WireProtocol::AnthropicMessages => ANTHROPIC_BASE_URL,
+        WireProtocol::Responses => OPENAI_BASE_URL,
+        WireProtocol::Gemini => GEMINI_BASE_URL,
+        WireProtocol::ChatCompletions => "",
+    }
+}
+
+fn base_url_or_default(base_url: Option<&str>, protocol: WireProtocol) -> String {
+    base_url
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| default_base_url(protocol))
+        .to_string()
+}
+
+/// Resolves a key that may name an environment variable (e.g. `LITELLM_KEY`).
+fn resolve_key(value: Option<&str>) -> String {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return String::new();
+    };
+    if secrets::is_indirect_key(value) {
+        if let Ok(resolved) = std::env::var(value) {
+            return resolved;
+        }
+    }
+    value.to_string()
+}
+
+struct EndpointBuilder<'a> {
+    tool: ProfileTool,
+    profile_id: &'a str,
+    profile_name: &'a str,
+}
+
+impl EndpointBuilder<'_> {
+    fn endpoint(
+        &self,
+        provider_id: Option<&str>,
+        model_id: &str,
+        protocol: WireProtocol,
+        base_url: String,
+        api_key: String,
+        headers: HashMap<String, String>,
+    ) -> ProfileEndpoint {
+        ProfileEndpoint {
+            tool: self.tool,
+            profile_id: self.profile_id.to_string(),
+            profile_name: self.profile_name.to_string(),
+            provider_id: provider_id.map(str::to_string),
+            model_id: model_id.to_string(),
+            protocol,
+            base_url,
+            has_api_key: !api_key.is_empty(),
+            api_key,
+            headers,
+        }
+    }
+}
+
+fn sorted<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Codex: one endpoint per provider, protocol from `wire_api`.
+pub fn codex_endpoints(profile: &CodexProfile) -> Vec<ProfileEndpoint> {
+    let b = EndpointBuilder {
+        tool: ProfileTool::Codex,
+        profile_id: &profile.id,
+        profile_name: &profile.name,
+    };
+    sorted(&profile.providers)
+        .into_iter()
+        .map(|(id, provider)| {
+            let protocol = match provider.wire_api.as_deref() {
+                Some(wire_api) => WireProtocol::from_name(wire_api),
+                None if id == "openai" => WireProtocol::Responses,
+                None => WireProtocol::ChatCompletions,
+            };
+            let model = provider
+                .model
+                .as_deref()
+                .filter(|m| !m.is_empty())
+                .unwrap_or(&profile.model);
+            let mut api_key = codex::resolved_api_key(profile, Some(provider)).unwrap_or_default();
+            if api_key.is_empty() {
+                if let Some(env_key) = provider.env_key.as_deref() {
+                    api_key = std::env::var(env_key).unwrap_or_default();
+                }
+            }
+            b.endpoint(
+                Some(id),
+                model,
+                protocol,
+                base_url_or_default(provider.base_url.as_deref(), protocol),
+                api_key,
+                provider.http_headers.clone().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+/// Claude Code: the main model and, when configured separately, the small model.
+///
+/// `ANTHROPIC_AUTH_TOKEN` is sent as `Authorization: Bearer`; the header is
+/// added by [`resolve_endpoint_secrets`] once the token is resolved.
+pub fn claude_endpoints(profile: &ClaudeCodeProfile) -> Vec<ProfileEndpoint> {
+    let b = EndpointBuilder {
+        tool: ProfileTool::Claude,
+        profile_id: &profile.id,
+        profile_name: &profile.name,
+    };
+    let protocol = WireProtocol::AnthropicMessages;
+    let base_url = base_url_or_default(profile.base_url.as_deref(), protocol);
+    let api_key = resolve_key(profile.bearer_token.as_deref());
+
+    let mut models = vec![profile.model.clone().unwrap_or_default()];
+    if !profile.small_model_uses_main_model {
+        if let Some(small) = profile.small_model.as_deref().filter(|s| !s.is_empty()) {
+            if !models.iter().any(|m| m == small) {
+                models.push(small.to_string());
+            }
+        }
+    }
+    models
+        .iter()
+        .map(|model| {
+            b.endpoint(
+                None,
+                model,
+                protocol,
+                base_url.clone(),
+                api_key.clone(),
+                HashMap::new(),
+            )
+        })
+        .collect()
+}
+
+/// Protocol of an OpenCode provider, from its AI SDK package.
+fn opencode_protocol(npm: Option<&str>) -> WireProtocol {
+    match npm.unwrap_or_default() {
+        "@ai-sdk/anthropic" => WireProtocol::AnthropicMessages,
+        "@ai-sdk/openai" => WireProtocol::Responses,
+        "@ai-sdk/google" => WireProtocol::Gemini,
+        _ => WireProtocol::ChatCompletions,
+    }
+}
+
+/// OpenCode: one endpoint per provider model; keys come from `options.apiKey`
+/// or the profile's auth entries.
+pub fn opencode_endpoints(profile: &OpenCodeProfile) -> Vec<ProfileEndpoint> {
+    let b = EndpointBuilder {
+        tool: ProfileTool::OpenCode,
+        profile_id: &profile.id,
+        profile_name: &profile.name,
+    };
+    let mut out = Vec::new();
+    for (id, provider) in sorted(&profile.providers) {
+        let protocol = opencode_protocol(provider.npm.as_deref());
+        let options = provider.options.clone().unwrap_or_default();
+        let auth_key = profile
+            .auth
+            .get(id)
+            .and_then(|a| a.get("key"))
+            .and_then(|k| k.as_str());
+        let api_key = resolve_key(options.api_key.as_deref().or(auth_key));
+        let base_url = base_url_or_default(options.base_url.as_deref(), protocol);
+        let headers = options.headers.unwrap_or_default();
+        let mut models: Vec<&String> = provider
+            .models
+            .as_ref()
+            .map(|m| m.keys().collect())
+            .unwrap_or_default();
+        models.sort();
+        if models.is_empty() {
+            out.push(b.endpoint(Some(id), "", protocol, base_url, api_key, headers));
+            continue;
+        }
+        for model in models {
+            out.push(b.endpoint(
+                Some(id),
+                model,
+                protocol,
+                base_url.clone(),
+                api_key.clone(),
+                headers.clone(),
+            ));
+        }
+    }
+    out
+}
+
+/// OpenClaw: one endpoint per provider model, protocol from the provider `api`.
+pub fn openclaw_endpoints(profile: &OpenClawProfile) -> Vec<ProfileEndpoint> {
+    let b = EndpointBuilder {
+        tool: ProfileTool::OpenClaw,
+        profile_id: &profile.id,
+        profile_name: &profile.name,
+    };
+    let mut out = Vec::new();
+    for (id, provider) in sorted(&profile.providers) {
+        let protocol = WireProtocol::from_name(provider.api.as_deref().unwrap_or_default());
+        let base_url = base_url_or_default(provider.base_url.as_deref(), protocol);
+        let api_key = resolve_key(provider.api_key.as_deref());
+        let models: Vec<&str> = if provider.models.is_empty() {
+            vec![""]
+        } else {
+            provider.models.iter().map(|m| m.id.as_str()).collect()
+        };
+        for model in models {
+            out.push(b.endpoint(
+                Some(id),
+                model,
+                protocol,
+                base_url.clone(),
+                api_key.clone(),
+                HashMap::new(),
+            ));
+        }
+    }
+    out
+}
+
+/// Pi: one endpoint per provider model; a model's `api` overrides the provider's.
+pub fn pi_endpoints(profile: &PiProfile) -> Vec<ProfileEndpoint> {
+    let b = EndpointBuilder {
+        tool: ProfileTool::Pi,
+        profile_id: &profile.id,
+        profile_name: &profile.name,
+    };
+    let mut out = Vec::new();
+    for (id, provider) in sorted(&profile.providers) {
+        let api_key = resolve_key(provider.api_key.as_deref());
+        let headers = provider.headers.clone().unwrap_or_default();
+        let models: Vec<(&str, Option<&str>)> = if provider.models.is_empty() {
+            vec![("", None)]
+        } else {
+            provider
+                .models
+                .iter()
+                .map(|m| (m.id.as_str(), m.api.as_deref()))
+                .collect()
+        };
+        for (model, model_api) in models {
+            let api = model_api.or(provider.api.as_deref()).unwrap_or_default();
+            let protocol = WireProtocol::from_name(api);
+            out.push(b.endpoint(
+                Some(id),
+                model,
+                protocol,
+                base_url_or_default(provider.base_url.as_deref(), protocol),
+                api_key.clone(),
+                headers.clone(),
+            ));
+        }
+    }
+    out
+}
+
+/// Hermes: the single `model` section.
+pub fn hermes_endpoints(profile: &HermesProfile) -> Vec<ProfileEndpoint> {
+    let b = EndpointBuilder {
+        tool: ProfileTool::Hermes,
+        profile_id: &profile.id,
+        profile_name: &profile.name,
+    };
+    let model = &profile.model;
+    let protocol = WireProtocol::from_name(model.provider.as_deref().unwrap_or_default());
+    vec![b.endpoint(
+        model.provider.as_deref().filter(|p| !p.is_empty()),
+        model.default.as_deref().unwrap_or_default(),
+        protocol,
+        base_url_or_default(model.base_url.as_deref(), protocol),
+        resolve_key(model.api_key.as_deref()),
+        HashMap::new(),
+    )]
+}
+
+/// Lists the endpoints of one profile.
+pub fn list_profile_endpoints_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+    profile_id: &str,
+) -> Result<Vec<ProfileEndpoint>, String> {
+    Ok(match tool {
+        ProfileTool::Codex => {
+            codex_endpoints(&codex::get_codex_profile_for_home(home_dir, profile_id)?)
+        }
+        ProfileTool::Claude => {
+            claude_endpoints(&claude::get_claude_profile_for_home(home_dir, profile_id)?)
+        }
+        ProfileTool::OpenCode => opencode_endpoints(&opencode::get_opencode_profile_for_home(
+            home_dir, profile_id,
+        )?),
+        ProfileTool::OpenClaw => openclaw_endpoints(&openclaw::get_openclaw_profile_for_home(
+            home_dir, profile_id,
+        )?),
+        ProfileTool::Pi => pi_endpoints(&pi::get_pi_profile_for_home(home_dir, profile_id)?),
+        ProfileTool::Hermes => {
+            hermes_endpoints(&hermes::get_hermes_profile_for_home(home_dir, profile_id)?)
+        }
+    })
+}
+
+/// Lists the endpoints of every profile of one tool.
+pub fn list_tool_endpoints_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+) -> Result<Vec<ProfileEndpoint>, String> {
+    Ok(match tool {
+        ProfileTool::Codex => codex::list_codex_profiles_for_home(home_dir)?
+            .iter()
+            .flat_map(codex_endpoints)
+            .collect(),
+        ProfileTool::Claude => claude::list_claude_profiles_for_home(home_dir)?
+            .iter()
+            .flat_map(claude_endpoints)
+            .collect(),
+        ProfileTool::OpenCode => opencode::list_opencode_profiles_for_home(home_dir)?
+            .iter()
+            .flat_map(opencode_endpoints)
+            .collect(),
+        ProfileTool::OpenClaw => openclaw::list_openclaw_profiles_for_home(home_dir)?
+            .iter()
+            .flat_map(openclaw_endpoints)
+            .collect(),
+        ProfileTool::Pi => pi::list_pi_profiles_for_home(home_dir)?
+            .iter()
+            .flat_map(pi_endpoints)
+            .collect(),
+        ProfileTool::Hermes => hermes::list_hermes_profiles_for_home(home_dir)?
+            .iter()
+            .flat_map(hermes_endpoints)
+            .collect(),
+    })
+}
+
+/// Lists the endpoints of every profile of every tool. A tool whose profiles
+/// cannot be read is skipped.
+pub fn list_all_profile_endpoints_for_home(home_dir: &Path) -> Vec<ProfileEndpoint> {
+    let mut out = Vec::new();
+    for tool in ProfileTool::ALL {
+        match list_tool_endpoints_for_home(home_dir, tool) {
+            Ok(endpoints) => out.extend(endpoints),
+            Err(e) => log::warn!(
+                "Connectivity: failed to list {} profiles: {e}",
+                tool.as_str()
+            ),
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Testing
+// ============================================================================
+
+/// Tests endpoints concurrently (bounded by [`TestConfig::max_concurrency`]),
+/// keeping input order.
+pub async fn test_endpoints(
+    endpoints: Vec<ProfileEndpoint>,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
+) -> Vec<ProfileTestResult> {
+    let limit = config.max_concurrency.max(1) as usize;
+    let config = Arc::new(config);
+    let prompt: Arc<str> =
+        Arc::from(prompt.unwrap_or_else(|| DEFAULT_INFERENCE_PROMPT.to_string()));
+
+    let failed_mode = mode.clone();
+
+    connectivity::run_bounded(
+        endpoints,
+        limit,
+        move |endpoint| {
+            let config = Arc::clone(&config);
+            let prompt = Arc::clone(&prompt);
+            let mode = mode.clone();
+            async move {
+                let tester = ModelTester::with_config((*config).clone())
+                    .with_extra_headers(&endpoint.headers);
+                let diagnostics = tester
+                    .test_endpoint(
+                        endpoint.protocol,
+                        &mode,
+                        &endpoint.base_url,
+                        &endpoint.api_key,
+                        &endpoint.model_id,
+                        &prompt,
+                    )
+                    .await;
+                ProfileTestResult {
+                    is_available: diagnostics.success,
+                    endpoint,
+                    diagnostics,
+                }
+            }
+        },
+        move |endpoint, error| ProfileTestResult {
+            diagnostics: ConnectionDiagnostics::failed(
+                endpoint.protocol.name(),
+                &endpoint.model_id,
+                &failed_mode,
+                error,
+            ),
+            is_available: false,
+            endpoint,
+        },
+    )
+    .await
+}
+
+/// Replaces `secret://` keys and header values with their vault values.
+fn resolve_endpoint_secrets(
+    home_dir: &Path,
+    mut endpoints: Vec<ProfileEndpoint>,
+) -> Result<Vec<ProfileEndpoint>, String> {
+    for endpoint in &mut endpoints {
+        endpoint.api_key = secrets::resolve_secret_value_for_home(home_dir, &endpoint.api_key)?;
+        for value in endpoint.headers.values_mut() {
+            *value = secrets::resolve_secret_value_for_home(home_dir, value)?;
+        }
+        if endpoint.tool == ProfileTool::Claude && !endpoint.api_key.is_empty() {
+            endpoint.headers.insert(
+                "Authorization".to_string(),
+                format!("Bearer {}", endpoint.api_key),
+            );
+        }
+    }
+    Ok(endpoints)
+}
+
+/// Tests every endpoint of one profile.
+pub async fn test_profile_connections_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+    profile_id: &str,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
+) -> Result<Vec<ProfileTestResult>, String> {
+    let endpoints = list_profile_endpoints_for_home(home_dir, tool, profile_id)?;
+    let endpoints = resolve_endpoint_secrets(home_dir, endpoints)?;
+    let results = test_endpoints(endpoints, mode, prompt, config).await;
+    record_history(home_dir, &results);
+    Ok(results)
+}
+
+/// Tests every endpoint of every profile (optionally limited to one tool).
+pub async fn test_all_profile_connections_for_home(
+    home_dir: &Path,
+    tool: Option<ProfileTool>,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
+) -> Result<Vec<ProfileTestResult>, String> {
+    let endpoints = match tool {
+        Some(tool) => list_tool_endpoints_for_home(home_dir, tool)?,
+        None => list_all_profile_endpoints_for_home(home_dir),
+    };
+    let endpoints = resolve_endpoint_secrets(home_dir, endpoints)?;
+    let results = test_endpoints(endpoints, mode, prompt, config).await;
+    record_history(home_dir, &results);
+    Ok(results)
+}
+
+/// Appends profile results to the connectivity history, keyed by model and
+/// base URL so they share trends with Factory models on the same channel.
+fn record_history(home_dir: &Path, results: &[ProfileTestResult]) {
+    let records = results
+        .iter()
+        .map(|r| {
+            let name = format!("{} ({})", r.endpoint.model_id, r.endpoint.profile_name);
+            HistoryRecord::from_diagnostics(&r.diagnostics, &name, Some(&r.endpoint.base_url))
+        })
+        .collect();
+    connectivity::append_history(home_dir, records);
+}
+
+/// Blocking variant of [`test_profile_connections_for_home`] (for the TUI).
+pub fn test_profile_connections_blocking_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+    profile_id: &str,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: TestConfig,
+) -> Result<Vec<ProfileTestResult>, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create async runtime: {e}"))?;
+    runtime.block_on(test_profile_connections_for_home(
+        home_dir, tool, profile_id, mode, prompt, config,
+    ))
+}
+
+// ============================================================================
+// Public API (system home)
+// ============================================================================
+
+pub fn list_profile_endpoints(
+    tool: ProfileTool,
+    profile_id: &str,
+) -> Result<Vec<ProfileEndpoint>, String> {
+    list_profile_endpoints_for_home(&paths::get_home_dir()?, tool, profile_id)
+}
+
+/// Tests one profile using the saved [`TestConfig`].
+pub async fn test_profile_connections(
+    tool: ProfileTool,
+    profile_id: &str,
+    mode: TestMode,
+    prompt: Option<String>,
+) -> Result<Vec<ProfileTestResult>, String> {
+    let home_dir = paths::get_home_dir()?;
+    let config = connectivity::get_test_config_for_home(&home_dir)?;
+    test_profile_connections_for_home(&home_dir, tool, profile_id, mode, prompt, config).await
+}
+
+/// Tests all profiles (optionally of one tool) using the saved [`TestConfig`].
+pub async fn test_all_profile_connections(
+    tool: Option<ProfileTool>,
+    mode: TestMode,
+    prompt: Option<String>,
+) -> Result<Vec<ProfileTestResult>, String> {
+    let home_dir = paths::get_home_dir()?;
+    let config = connectivity::get_test_config_for_home(&home_dir)?;
+    test_all_profile_connections_for_home(&home_dir, tool, mode, prompt, config).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::CodexProviderConfig;
+    use crate::pi::PiProviderConfig;
+    use crate::test_support::{block_on, serve_recording};
+
+    fn codex_profile(base_url: &str) -> CodexProfile {
+        let provider = CodexProviderConfig {
+            name: None,
+            base_url: Some(format!("{base_url}/v1")),
+            wire_api: Some("responses".to_string()),
+            requires_openai_auth: None,
+            env_key: None,
+            env_key_instructions: None,
+            http_headers: Some(HashMap::from([("X-Team".to_string(), "core".to_string())])),
+            query_params: None,
+            model: None,
+            model_reasoning_effort: None,
+            api_key: Some("sk-codex".to_string()),
+            channel_ref: None,
+        };
+        CodexProfile {
+            id: "p1".to_string(),
+            name: "Relay".to_string(),
+            description: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            providers: HashMap::from([("relay".to_string(), provider)]),
+            model_provider: "relay".to_string(),
+            model: "gpt-5".to_string(),
+            model_reasoning_effort: None,
+            api_key: None,
+        }
+    }
+
+    #[test]
+    fn enumerates_codex_and_pi_endpoints() {
+        let endpoints = codex_endpoints(&codex_profile("https://relay.example"));
+        assert_eq!(endpoints.len(), 1);
+        let ep = &endpoints[0];
+        assert_eq!(ep.tool, ProfileTool::Codex);
+        assert_eq!(ep.provider_id.as_deref(), Some("relay"));
+        assert_eq!(ep.model_id, "gpt-5");
+        assert_eq!(ep.protocol, WireProtocol::Responses);
+        assert!(ep.has_api_key);
+        let json = serde_json::to_string(ep).unwrap();
+        assert!(!json.contains("sk-codex"));
+
+        let pi_model = |id: &str, api: Option<&str>| {
+            serde_json::from_value::<crate::pi::PiModel>(serde_json::json!({
+                "id": id,
+                "api": api,
+            }))
+            .unwrap()
+        };
+        let provider = PiProviderConfig {
+            base_url: None,
+            api: Some("anthropic-messages".to_string()),
+            models: vec![
+                pi_model("claude-sonnet-4-5", None),
+                pi_model("gemini-2.5-pro", Some("google-generative-ai")),
+            ],
+            ..Default::default()
+        };
+        let profile = PiProfile {
+            id: "pi1".to_string(),
+            name: "Pi".to_string(),
+            description: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            providers: HashMap::from([("mixed".to_string(), provider)]),
+        };
+        let endpoints = pi_endpoints(&profile);
+        assert_eq!(endpoints[0].protocol, WireProtocol::AnthropicMessages);
+        assert_eq!(endpoints[0].base_url, ANTHROPIC_BASE_URL);
+        assert_eq!(endpoints[1].protocol, WireProtocol::Gemini);
+        assert_eq!(endpoints[1].base_url, GEMINI_BASE_URL);
+        assert!(!endpoints[0].has_api_key);
+    }
+
+    #[test]
+    fn tests_profile_endpoints_with_their_protocol_and_headers() {
+        let (base, requests) = serve_recording(vec![(200, r#"{"data":[]}"#.to_string())]);
+        let dir = tempfile::TempDir::new().unwrap();
+        codex::save_codex_profile_for_home(dir.path(), codex_profile(&base)).unwrap();
+        let profile_id = codex::list_codex_profiles_for_home(dir.path())
+            .unwrap()
+            .into_iter()
+            .find(|p| p.name == "Relay")
+            .unwrap()
+            .id;
+
+        let config = TestConfig {
+            retry_on_failure: false,
+            ..TestConfig::default()
+        };
+        let results = block_on(test_profile_connections_for_home(
+            dir.path(),
+            ProfileTool::Codex,
+            &profile_id,
+            TestMode::Ping,
+            None,
+            config,
+        ))
+        .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(
+            results[0].is_available,
+            "{:?}",
+            results[0].diagnostics.error
+        );
+        assert_eq!(results[0].endpoint.profile_id, profile_id);
+        assert_eq!(results[0].diagnostics.provider, "responses");
+
+        let history =
+            crate::connectivity_history::get_model_history_for_home(dir.path(), "gpt-5", None)
+                .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].model_name, "gpt-5 (Relay)");
+        assert_eq!(
+            history[0].base_url.as_deref(),
+            Some(format!("{base}/v1").as_str())
+        );
+
+        let req = requests.lock().unwrap()[0].to_lowercase();
+        assert!(req.starts_with("get /v1/models "));
+        assert!(req.contains("authorization: bearer sk-codex"));
+        assert!(req.contains("x-team: core"));
+    }
+
+    #[test]
+    fn claude_bearer_header_uses_the_resolved_token() {
+        let dir = tempfile::TempDir::new().unwrap();
+        secrets::init_vault_for_home(dir.path(), "passphrase").unwrap();
+        let token_ref =
+            secrets::set_secret_for_home(dir.path(), "claude/work", "sk-ant-live").unwrap();
+        let profile: ClaudeCodeProfile = serde_json::from_value(serde_json::json!({
+            "id": "c1",
+            "name": "Work",
+            "bearerToken": token_ref,
+            "model": "claude-sonnet-4-5",
+            "createdAt": "",
+            "updatedAt": "",
+        }))
+        .unwrap();
+
+        let endpoints = claude_endpoints(&profile);
+        assert!(endpoints[0].headers.is_empty());
+        let endpoints = resolve_endpoint_secrets(dir.path(), endpoints).unwrap();
+        assert_eq!(endpoints[0].api_key, "sk-ant-live");
+        assert_eq!(
+            endpoints[0]
+                .headers
+                .get("Authorization")
+                .map(String::as_str),
+            Some("Bearer sk-ant-live")
+        );
+    }
+
+    #[test]
+    fn missing_base_url_fails_without_network() {
+        let profile = HermesProfile {
+            id: "h1".to_string(),
+            name: "Hermes".to_string(),
+            description: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            model: hermes::HermesModelConfig {
+                default: Some("some-model".to_string()),
+                provider: Some("openrouter".to_string()),
+                base_url: None,
+                api_key: Some("sk-or".to_string()),
+                channel_ref: None,
+            },
+        };
+        let results = block_on(test_endpoints(
+            hermes_endpoints(&profile),
+            TestMode::Ping,
+            None,
+            TestConfig::default(),
+        ));
+        assert_eq!(
+            results[0].diagnostics.error.as_deref(),
+            Some("No base URL configured")
+        );
+        assert_eq!(results[0].diagnostics.attempts.len(), 1);
+    }
+}
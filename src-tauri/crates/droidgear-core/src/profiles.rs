@@ -0,0 +1,400 @@
+//! Tool-agnostic profile operations (core).
+//!
+//! Dispatches list/show/apply/delete/duplicate to the per-tool `*_for_home`
+//! functions so headless callers (the `droidgear-tui profile` CLI) can treat
+//! Codex, Claude Code, OpenCode, OpenClaw, Pi and Hermes profiles alike.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use std::path::Path;
+
+use crate::apply_plan::ApplyPlan;
+use crate::{claude, codex, hermes, openclaw, opencode, pi};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Tool whose profiles DroidGear manages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileTool {
+    Codex,
+    Claude,
+    OpenCode,
+    OpenClaw,
+    Pi,
+    Hermes,
+}
+
+impl ProfileTool {
+    pub const ALL: [ProfileTool; 6] = [
+        Self::Codex,
+        Self::Claude,
+        Self::OpenCode,
+        Self::OpenClaw,
+        Self::Pi,
+        Self::Hermes,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Codex => "codex",
+            Self::Claude => "claude",
+            Self::OpenCode => "opencode",
+            Self::OpenClaw => "openclaw",
+            Self::Pi => "pi",
+            Self::Hermes => "hermes",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                format!("Unknown tool '{name}' (expected codex, claude, opencode, openclaw, pi or hermes)")
+            })
+    }
+}
+
+/// Common fields of a profile of any tool
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSummary {
+    pub tool: ProfileTool,
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub updated_at: String,
+    /// Whether this is the profile last applied to the tool's live config
+    pub active: bool,
+}
+
+// ============================================================================
+// Operations
+// ============================================================================
+
+/// Reads a profile as JSON (the stored representation).
+pub fn get_profile_value_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+    id: &str,
+) -> Result<Value, String> {
+    let value = match tool {
+        ProfileTool::Codex => {
+            serde_json::to_value(codex::get_codex_profile_for_home(home_dir, id)?)
+        }
+        ProfileTool::Claude => {
+            serde_json::to_value(claude::get_claude_profile_for_home(home_dir, id)?)
+        }
+        ProfileTool::OpenCode => {
+            serde_json::to_value(opencode::get_opencode_profile_for_home(home_dir, id)?)
+        }
+        ProfileTool::OpenClaw => {
+            serde_json::to_value(openclaw::get_openclaw_profile_for_home(home_dir, id)?)
+        }
+        ProfileTool::Pi => serde_json::to_value(pi::get_pi_profile_for_home(home_dir, id)?),
+        ProfileTool::Hermes => {
+            serde_json::to_value(hermes::get_hermes_profile_for_home(home_dir, id)?)
+        }
+    };
+    value.map_err(|e| format!("Failed to serialize profile: {e}"))
+}
+
+/// Saves a profile from its JSON representation (see [`get_profile_value_for_home`]).
+pub fn save_profile_value_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+    value: Value,
+) -> Result<(), String> {
+    fn parse<T: serde::de::DeserializeOwned>(value: Value) -> Result<T, String> {
+        serde_json::from_value(value).map_err(|e| format!("Invalid profile: {e}"))
+    }
+    match tool {
+        ProfileTool::Codex => codex::save_codex_profile_for_home(home_dir, parse(value)?),
+        ProfileTool::Claude => claude::save_claude_profile_for_home(home_dir, parse(value)?),
+        ProfileTool::OpenCode => opencode::save_opencode_profile_for_home(home_dir, parse(value)?),
+        ProfileTool::OpenClaw => openclaw::save_openclaw_profile_for_home(home_dir, parse(value)?),
+        ProfileTool::Pi => pi::save_pi_profile_for_home(home_dir, parse(value)?),
+        ProfileTool::Hermes => hermes::save_hermes_profile_for_home(home_dir, parse(value)?),
+    }
+}
+
+fn summary_from_value(tool: ProfileTool, value: &Value, active: Option<&str>) -> ProfileSummary {
+    let text = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let id = text("id");
+    ProfileSummary {
+        tool,
+        active: active == Some(id.as_str()),
+        name: text("name"),
+        description: value
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        updated_at: text("updatedAt"),
+        id,
+    }
+}
+
+pub fn get_active_profile_id_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+) -> Result<Option<String>, String> {
+    match tool {
+        ProfileTool::Codex => codex::get_active_codex_profile_id_for_home(home_dir),
+        ProfileTool::Claude => claude::get_active_claude_profile_id_for_home(home_dir),
+        ProfileTool::OpenCode => opencode::get_active_opencode_profile_id_for_home(home_dir),
+        ProfileTool::OpenClaw => openclaw::get_active_openclaw_profile_id_for_home(home_dir),
+        ProfileTool::Pi => pi::get_active_pi_profile_id_for_home(home_dir),
+        ProfileTool::Hermes => hermes::get_active_hermes_profile_id_for_home(home_dir),
+    }
+}
+
+/// Lists the profiles of a tool in the order the tool module returns them.
+pub fn list_profiles_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+) -> Result<Vec<ProfileSummary>, String> {
+    let values: Vec<Value> = match tool {
+        ProfileTool::Codex => to_values(codex::list_codex_profiles_for_home(home_dir)?),
+        ProfileTool::Claude => to_values(claude::list_claude_profiles_for_home(home_dir)?),
+        ProfileTool::OpenCode => to_values(opencode::list_opencode_profiles_for_home(home_dir)?),
+        ProfileTool::OpenClaw => to_values(openclaw::list_openclaw_profiles_for_home(home_dir)?),
+        ProfileTool::Pi => to_values(pi::list_pi_profiles_for_home(home_dir)?),
+        ProfileTool::Hermes => to_values(hermes::list_hermes_profiles_for_home(home_dir)?),
+    }?;
+    let active = get_active_profile_id_for_home(home_dir, tool)
+        .ok()
+        .flatten();
+    Ok(values
+        .iter()
+        .map(|v| summary_from_value(tool, v, active.as_deref()))
+        .collect())
+}
+
+fn to_values<T: Serialize>(profiles: Vec<T>) -> Result<Vec<Value>, String> {
+    profiles
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to serialize profile: {e}"))
+}
+
+/// Resolves a profile by id, exact name, case-insensitive name or 1-based
+/// index (as shown by `profile list`).
+pub fn resolve_profile_selector_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+    selector: &str,
+) -> Result<ProfileSummary, String> {
+    let selector = selector.trim();
+    if selector.is_empty() {
+        return Err("Profile selector cannot be empty".to_string());
+    }
+    let profiles = list_profiles_for_home(home_dir, tool)?;
+    if let Some(p) = profiles.iter().find(|p| p.id == selector) {
+        return Ok(p.clone());
+    }
+
+    let folded = selector.to_lowercase();
+    for matches in [
+        profiles
+            .iter()
+            .filter(|p| p.name == selector)
+            .collect::<Vec<_>>(),
+        profiles
+            .iter()
+            .filter(|p| p.name.to_lowercase() == folded)
+            .collect(),
+    ] {
+        match matches.as_slice() {
+            [] => {}
+            [p] => return Ok((*p).clone()),
+            _ => {
+                return Err(format!(
+                    "Multiple {} profiles share the name '{selector}'. Use the profile index or id instead.",
+                    tool.as_str()
+                ))
+            }
+        }
+    }
+
+    if let Some(p) = selector
+        .parse::<usize>()
+        .ok()
+        .and_then(|i| i.checked_sub(1))
+        .and_then(|i| profiles.get(i))
+    {
+        return Ok(p.clone());
+    }
+
+    Err(format!(
+        "No {tool} profile matches '{selector}'. Use `droidgear-tui profile list {tool}` to inspect available profiles.",
+        tool = tool.as_str()
+    ))
+}
+
+/// Writes the profile to the tool's live config and marks it active.
+pub fn apply_profile_for_home(home_dir: &Path, tool: ProfileTool, id: &str) -> Result<(), String> {
+    match tool {
+        ProfileTool::Codex => codex::apply_codex_profile_for_home(home_dir, id),
+        ProfileTool::Claude => claude::apply_claude_profile_for_home(home_dir, id),
+        ProfileTool::OpenCode => opencode::apply_opencode_profile_for_home(home_dir, id),
+        ProfileTool::OpenClaw => {
+            let profile = openclaw::get_openclaw_profile_for_home(home_dir, id)?;
+            openclaw::apply_openclaw_profile_for_home(home_dir, &profile)
+        }
+        ProfileTool::Pi => pi::apply_pi_profile_for_home(home_dir, id),
+        ProfileTool::Hermes => hermes::apply_hermes_profile_for_home(home_dir, id),
+    }
+}
+
+/// Dry-run of [`apply_profile_for_home`].
+pub fn plan_apply_profile_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+    id: &str,
+) -> Result<ApplyPlan, String> {
+    match tool {
+        ProfileTool::Codex => codex::plan_apply_codex_profile_for_home(home_dir, id),
+        ProfileTool::Claude => claude::plan_apply_claude_profile_for_home(home_dir, id),
+        ProfileTool::OpenCode => opencode::plan_apply_opencode_profile_for_home(home_dir, id),
+        ProfileTool::OpenClaw => {
+            let profile = openclaw::get_openclaw_profile_for_home(home_dir, id)?;
+            openclaw::plan_apply_openclaw_profile_for_home(home_dir, &profile)
+        }
+        ProfileTool::Pi => pi::plan_apply_pi_profile_for_home(home_dir, id),
+        ProfileTool::Hermes => hermes::plan_apply_hermes_profile_for_home(home_dir, id),
+    }
+}
+
+pub fn delete_profile_for_home(home_dir: &Path, tool: ProfileTool, id: &str) -> Result<(), String> {
+    match tool {
+        ProfileTool::Codex => codex::delete_codex_profile_for_home(home_dir, id),
+        ProfileTool::Claude => claude::delete_claude_profile_for_home(home_dir, id),
+        ProfileTool::OpenCode => opencode::delete_opencode_profile_for_home(home_dir, id),
+        ProfileTool::OpenClaw => openclaw::delete_openclaw_profile_for_home(home_dir, id),
+        ProfileTool::Pi => pi::delete_pi_profile_for_home(home_dir, id),
+        ProfileTool::Hermes => hermes::delete_hermes_profile_for_home(home_dir, id),
+    }
+}
+
+/// Copies a profile under a new name and returns the copy.
+pub fn duplicate_profile_for_home(
+    home_dir: &Path,
+    tool: ProfileTool,
+    id: &str,
+    new_name: &str,
+) -> Result<ProfileSummary, String> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    let value = match tool {
+        ProfileTool::Codex => serde_json::to_value(codex::duplicate_codex_profile_for_home(
+            home_dir, id, new_name,
+        )?),
+        ProfileTool::Claude => serde_json::to_value(claude::duplicate_claude_profile_for_home(
+            home_dir, id, new_name,
+        )?),
+        ProfileTool::OpenCode => serde_json::to_value(
+            opencode::duplicate_opencode_profile_for_home(home_dir, id, new_name)?,
+        ),
+        ProfileTool::OpenClaw => serde_json::to_value(
+            openclaw::duplicate_openclaw_profile_for_home(home_dir, id, new_name)?,
+        ),
+        ProfileTool::Pi => {
+            serde_json::to_value(pi::duplicate_pi_profile_for_home(home_dir, id, new_name)?)
+        }
+        ProfileTool::Hermes => serde_json::to_value(hermes::duplicate_hermes_profile_for_home(
+            home_dir, id, new_name,
+        )?),
+    }
+    .map_err(|e| format!("Failed to serialize profile: {e}"))?;
+    Ok(summary_from_value(tool, &value, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pi::PiProfile;
+
+    fn save_pi(home: &Path, name: &str) -> String {
+        pi::save_pi_profile_for_home(
+            home,
+            PiProfile {
+                id: String::new(),
+                name: name.to_string(),
+                description: None,
+                created_at: String::new(),
+                updated_at: String::new(),
+                providers: Default::default(),
+            },
+        )
+        .unwrap();
+        list_profiles_for_home(home, ProfileTool::Pi)
+            .unwrap()
+            .into_iter()
+            .find(|p| p.name == name)
+            .unwrap()
+            .id
+    }
+
+    #[test]
+    fn resolves_selectors_by_id_name_and_index() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let alpha = save_pi(dir.path(), "Alpha");
+        let beta = save_pi(dir.path(), "Beta");
+        let resolve = |s: &str| {
+            resolve_profile_selector_for_home(dir.path(), ProfileTool::Pi, s).map(|p| p.id)
+        };
+
+        assert_eq!(resolve(&beta).unwrap(), beta);
+        assert_eq!(resolve("alpha").unwrap(), alpha);
+        let first = list_profiles_for_home(dir.path(), ProfileTool::Pi).unwrap()[0]
+            .id
+            .clone();
+        assert_eq!(resolve("1").unwrap(), first);
+        assert!(resolve("9").unwrap_err().contains("profile list pi"));
+
+        save_pi(dir.path(), "alpha");
+        assert_eq!(resolve("Alpha").unwrap(), alpha);
+        assert!(resolve("ALPHA")
+            .unwrap_err()
+            .contains("Multiple pi profiles"));
+    }
+
+    #[test]
+    fn apply_marks_profile_active_and_duplicate_copies() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let id = save_pi(dir.path(), "Alpha");
+        apply_profile_for_home(dir.path(), ProfileTool::Pi, &id).unwrap();
+        let listed = list_profiles_for_home(dir.path(), ProfileTool::Pi).unwrap();
+        assert!(listed[0].active);
+
+        let copy = duplicate_profile_for_home(dir.path(), ProfileTool::Pi, &id, "Copy").unwrap();
+        assert_ne!(copy.id, id);
+        assert!(!copy.active);
+        delete_profile_for_home(dir.path(), ProfileTool::Pi, &copy.id).unwrap();
+        assert_eq!(
+            list_profiles_for_home(dir.path(), ProfileTool::Pi)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            ProfileTool::parse("OpenCode").unwrap(),
+            ProfileTool::OpenCode
+        );
+        assert!(ProfileTool::parse("droid").is_err());
+    }
+}
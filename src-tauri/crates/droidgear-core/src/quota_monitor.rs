@@ -0,0 +1,719 @@
+//! Channel quota monitoring (core).
+//!
+//! A refresh polls the balance of every enabled channel and appends one
+//! snapshot per channel to `~/.droidgear/quota/snapshots.jsonl`. Burn rate,
+//! estimated days remaining and low-balance alerts are derived from that log.
+//! Scheduling is left to the frontends: the desktop app and the TUI poll
+//! [`is_refresh_due_for_home`] and call [`refresh_all_for_home`] when it is.
+//! Monitoring is off until the user enables it in settings.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::channel::{self, Channel, ChannelQuota};
+use crate::paths;
+use crate::storage::atomic_write;
+
+const SETTINGS_KEY: &str = "quotaMonitor";
+
+/// Burn rate is computed over this many days of snapshots.
+const BURN_WINDOW_DAYS: i64 = 7;
+/// Snapshots closer together than this are too noisy for a burn rate.
+const MIN_BURN_SPAN_MINUTES: i64 = 60;
+
+/// Once the log grows past this size it is compacted to the newest records.
+const MAX_SNAPSHOT_BYTES: u64 = 4 * 1024 * 1024;
+const COMPACT_KEEP_RECORDS: usize = 20_000;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Quota monitor settings (stored under `quotaMonitor` in DroidGear settings)
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuotaMonitorSettings {
+    pub enabled: bool,
+    /// Minutes between automatic refreshes
+    pub interval_minutes: u32,
+    /// Alert when the remaining balance drops below this amount
+    pub low_balance_threshold: Option<f64>,
+    /// Alert when the estimated days remaining drop below this
+    pub days_remaining_threshold: Option<f64>,
+    /// Per-channel overrides of `low_balance_threshold`, keyed by channel id
+    pub channel_thresholds: HashMap<String, f64>,
+}
+
+impl Default for QuotaMonitorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: 30,
+            low_balance_threshold: Some(5.0),
+            days_remaining_threshold: Some(3.0),
+            channel_thresholds: HashMap::new(),
+        }
+    }
+}
+
+/// One recorded balance of a channel
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaSnapshot {
+    pub timestamp: String,
+    pub channel_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<f64>,
+    #[serde(default)]
+    pub unlimited: bool,
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum QuotaAlertKind {
+    /// Balance is used up
+    Exhausted,
+    /// Balance is below the low-balance threshold
+    LowBalance,
+    /// At the current burn rate the balance runs out soon
+    RunningOut,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaAlert {
+    pub channel_id: String,
+    pub channel_name: String,
+    pub kind: QuotaAlertKind,
+    pub message: String,
+}
+
+/// Latest balance and trend of one channel
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelQuotaStatus {
+    pub channel_id: String,
+    pub channel_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest: Option<QuotaSnapshot>,
+    /// Average spend per day over the last week, in the snapshot unit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burn_rate_per_day: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_remaining: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alert: Option<QuotaAlert>,
+    /// Error of the last refresh, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of a refresh
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaRefreshReport {
+    pub checked_at: String,
+    pub statuses: Vec<ChannelQuotaStatus>,
+    /// Alerts that were not active before this refresh (to notify about)
+    pub new_alerts: Vec<QuotaAlert>,
+}
+
+/// Bookkeeping between refreshes (`~/.droidgear/quota/state.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct MonitorState {
+    last_refresh_at: Option<String>,
+    /// Active alert per channel id, so each one is only notified once
+    active_alerts: HashMap<String, QuotaAlertKind>,
+}
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+pub fn get_settings_for_home(home_dir: &Path) -> Result<QuotaMonitorSettings, String> {
+    let settings_path = paths::get_droidgear_settings_path_for_home(home_dir);
+    let settings = paths::read_droidgear_settings_from_path_internal(&settings_path)?;
+    match settings.get(SETTINGS_KEY) {
+        Some(v) => serde_json::from_value(v.clone())
+            .map_err(|e| format!("Failed to parse quota monitor settings: {e}")),
+        None => Ok(QuotaMonitorSettings::default()),
+    }
+}
+
+pub fn save_settings_for_home(
+    home_dir: &Path,
+    config: &QuotaMonitorSettings,
+) -> Result<(), String> {
+    let settings_path = paths::get_droidgear_settings_path_for_home(home_dir);
+    let mut settings = paths::read_droidgear_settings_from_path_internal(&settings_path)?;
+    if !settings.is_object() {
+        settings = serde_json::json!({});
+    }
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize quota monitor settings: {e}"))?;
+    if let Some(obj) = settings.as_object_mut() {
+        obj.insert(SETTINGS_KEY.to_string(), value);
+    }
+    paths::write_droidgear_settings_to_path_internal(&settings_path, &settings)
+}
+
+pub fn get_settings() -> Result<QuotaMonitorSettings, String> {
+    get_settings_for_home(&paths::get_home_dir()?)
+}
+
+pub fn save_settings(config: QuotaMonitorSettings) -> Result<(), String> {
+    save_settings_for_home(&paths::get_home_dir()?, &config)
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn quota_dir_for_home(home_dir: &Path) -> PathBuf {
+    paths::droidgear_dir_from_home(home_dir).join("quota")
+}
+
+pub fn snapshots_path_for_home(home_dir: &Path) -> PathBuf {
+    quota_dir_for_home(home_dir).join("snapshots.jsonl")
+}
+
+fn state_path_for_home(home_dir: &Path) -> PathBuf {
+    quota_dir_for_home(home_dir).join("state.json")
+}
+
+/// Appends snapshots to the log, compacting it when it grows too large.
+pub fn append_snapshots_for_home(
+    home_dir: &Path,
+    snapshots: &[QuotaSnapshot],
+) -> Result<(), String> {
+    if snapshots.is_empty() {
+        return Ok(());
+    }
+    let path = snapshots_path_for_home(home_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create quota directory: {e}"))?;
+    }
+
+    let mut buf = String::new();
+    for snapshot in snapshots {
+        let line = serde_json::to_string(snapshot)
+            .map_err(|e| format!("Failed to serialize quota snapshot: {e}"))?;
+        buf.push_str(&line);
+        buf.push('\n');
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open quota snapshots: {e}"))?;
+    file.write_all(buf.as_bytes())
+        .map_err(|e| format!("Failed to write quota snapshots: {e}"))?;
+
+    let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    if size > MAX_SNAPSHOT_BYTES {
+        let snapshots = read_snapshots(&path)?;
+        let start = snapshots.len().saturating_sub(COMPACT_KEEP_RECORDS);
+        let mut out = String::new();
+        for snapshot in &snapshots[start..] {
+            let line = serde_json::to_string(snapshot)
+                .map_err(|e| format!("Failed to serialize quota snapshot: {e}"))?;
+            out.push_str(&line);
+            out.push('\n');
+        }
+        atomic_write(&path, out.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Reads all snapshots, oldest first; malformed lines are skipped.
+fn read_snapshots(path: &Path) -> Result<Vec<QuotaSnapshot>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read quota snapshots: {e}")),
+    };
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+fn read_state(home_dir: &Path) -> MonitorState {
+    fs::read_to_string(state_path_for_home(home_dir))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn write_state(home_dir: &Path, state: &MonitorState) -> Result<(), String> {
+    let path = state_path_for_home(home_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create quota directory: {e}"))?;
+    }
+    let content = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize quota monitor state: {e}"))?;
+    atomic_write(&path, content.as_bytes())
+}
+
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+// ============================================================================
+// Analysis
+// ============================================================================
+
+/// Average spend per day over the last week. Uses the reported `used` amount
+/// when available, otherwise drops in `remaining` (top-ups are ignored).
+/// `None` until the snapshots span at least an hour.
+pub fn burn_rate_per_day(snapshots: &[QuotaSnapshot], now: DateTime<Utc>) -> Option<f64> {
+    let cutoff = now - Duration::days(BURN_WINDOW_DAYS);
+    let points: Vec<(DateTime<Utc>, &QuotaSnapshot)> = snapshots
+        .iter()
+        .filter_map(|s| parse_timestamp(&s.timestamp).map(|t| (t, s)))
+        .filter(|(t, s)| *t >= cutoff && !s.unlimited)
+        .collect();
+    let (first, last) = (points.first()?.0, points.last()?.0);
+    if last - first < Duration::minutes(MIN_BURN_SPAN_MINUTES) {
+        return None;
+    }
+
+    let spent: f64 = points
+        .windows(2)
+        .filter_map(|w| {
+            let (a, b) = (w[0].1, w[1].1);
+            match (a.used, b.used, a.remaining, b.remaining) {
+                (Some(u0), Some(u1), _, _) => Some((u1 - u0).max(0.0)),
+                (_, _, Some(r0), Some(r1)) => Some((r0 - r1).max(0.0)),
+                _ => None,
+            }
+        })
+        .sum();
+    let days = (last - first).num_seconds() as f64 / 86_400.0;
+    Some(spent / days)
+}
+
+fn evaluate_alert(
+    channel: &Channel,
+    latest: &QuotaSnapshot,
+    days_remaining: Option<f64>,
+    settings: &QuotaMonitorSettings,
+) -> Option<QuotaAlert> {
+    if latest.unlimited {
+        return None;
+    }
+    let remaining = latest.remaining?;
+    let unit = &latest.unit;
+    let threshold = settings
+        .channel_thresholds
+        .get(&channel.id)
+        .copied()
+        .or(settings.low_balance_threshold);
+
+    let (kind, message) = if remaining <= 0.0 {
+        (
+            QuotaAlertKind::Exhausted,
+            format!("{} has no balance left", channel.name),
+        )
+    } else if threshold.is_some_and(|t| remaining < t) {
+        (
+            QuotaAlertKind::LowBalance,
+            format!("{} balance is low: {remaining:.2} {unit}", channel.name),
+        )
+    } else if let Some(days) =
+        days_remaining.filter(|d| settings.days_remaining_threshold.is_some_and(|t| *d < t))
+    {
+        (
+            QuotaAlertKind::RunningOut,
+            format!(
+                "{} runs out in about {days:.1} days ({remaining:.2} {unit} left)",
+                channel.name
+            ),
+        )
+    } else {
+        return None;
+    };
+    Some(QuotaAlert {
+        channel_id: channel.id.clone(),
+        channel_name: channel.name.clone(),
+        kind,
+        message,
+    })
+}
+
+fn build_status(
+    channel: &Channel,
+    history: &[QuotaSnapshot],
+    error: Option<String>,
+    settings: &QuotaMonitorSettings,
+    now: DateTime<Utc>,
+) -> ChannelQuotaStatus {
+    let latest = history.last().cloned();
+    let burn_rate = burn_rate_per_day(history, now);
+    let days_remaining = match (&latest, burn_rate) {
+        (Some(s), Some(rate)) if !s.unlimited && rate > 0.0 => {
+            s.remaining.map(|r| (r.max(0.0)) / rate)
+        }
+        _ => None,
+    };
+    let alert = latest
+        .as_ref()
+        .and_then(|s| evaluate_alert(channel, s, days_remaining, settings));
+    ChannelQuotaStatus {
+        channel_id: channel.id.clone(),
+        channel_name: channel.name.clone(),
+        latest,
+        burn_rate_per_day: burn_rate,
+        days_remaining,
+        alert,
+        error,
+    }
+}
+
+fn statuses_for_channels(
+    channels: &[Channel],
+    snapshots: &[QuotaSnapshot],
+    errors: &HashMap<String, String>,
+    settings: &QuotaMonitorSettings,
+    now: DateTime<Utc>,
+) -> Vec<ChannelQuotaStatus> {
+    channels
+        .iter()
+        .map(|c| {
+            let history: Vec<QuotaSnapshot> = snapshots
+                .iter()
+                .filter(|s| s.channel_id == c.id)
+                .cloned()
+                .collect();
+            build_status(c, &history, errors.get(&c.id).cloned(), settings, now)
+        })
+        .collect()
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Whether the configured interval has passed since the last refresh.
+pub fn is_refresh_due_for_home(home_dir: &Path) -> bool {
+    let Ok(settings) = get_settings_for_home(home_dir) else {
+        return false;
+    };
+    if !settings.enabled {
+        return false;
+    }
+    let interval = Duration::minutes(i64::from(settings.interval_minutes.max(1)));
+    read_state(home_dir)
+        .last_refresh_at
+        .and_then(|t| parse_timestamp(&t))
+        .is_none_or(|last| Utc::now() - last >= interval)
+}
+
+/// Current status of every enabled channel from the recorded snapshots
+/// (no network access).
+pub fn get_quota_status_for_home(home_dir: &Path) -> Result<Vec<ChannelQuotaStatus>, String> {
+    let settings = get_settings_for_home(home_dir)?;
+    let channels: Vec<Channel> = channel::load_channels_for_home(home_dir)?
+        .into_iter()
+        .filter(|c| c.enabled)
+        .collect();
+    let snapshots = read_snapshots(&snapshots_path_for_home(home_dir))?;
+    Ok(statuses_for_channels(
+        &channels,
+        &snapshots,
+        &HashMap::new(),
+        &settings,
+        Utc::now(),
+    ))
+}
+
+/// Snapshots of one channel within the last `window_days` (all when `None`), oldest first.
+pub fn get_quota_history_for_home(
+    home_dir: &Path,
+    channel_id: &str,
+    window_days: Option<u32>,
+) -> Result<Vec<QuotaSnapshot>, String> {
+    let cutoff = window_days.map(|d| Utc::now() - Duration::days(i64::from(d)));
+    Ok(read_snapshots(&snapshots_path_for_home(home_dir))?
+        .into_iter()
+        .filter(|s| s.channel_id == channel_id)
+        .filter(|s| match cutoff {
+            Some(cutoff) => parse_timestamp(&s.timestamp).is_some_and(|t| t >= cutoff),
+            None => true,
+        })
+        .collect())
+}
+
+async fn fetch_quota_for_home(
+    home_dir: &Path,
+    channel: &Channel,
+) -> Result<Option<ChannelQuota>, String> {
+    let (username, secret) = if channel.channel_type.uses_login() {
+        channel::get_channel_credentials_for_home(home_dir, &channel.id)?
+            .ok_or_else(|| "No credentials stored".to_string())?
+    } else {
+        let key = channel::get_channel_api_key_for_home(home_dir, &channel.id)?
+            .ok_or_else(|| "No API key stored".to_string())?;
+        (String::new(), key)
+    };
+    channel::fetch_channel_quota(
+        channel.channel_type.clone(),
+        &channel.base_url,
+        &username,
+        &secret,
+    )
+    .await
+}
+
+/// Polls every enabled channel, records the balances and returns the
+/// resulting statuses. Alerts that became active since the previous refresh
+/// are listed in `new_alerts`; they are reported again only after the
+/// channel recovers or the alert changes kind.
+pub async fn refresh_all_for_home(home_dir: &Path) -> Result<QuotaRefreshReport, String> {
+    let settings = get_settings_for_home(home_dir)?;
+    let channels: Vec<Channel> = channel::load_channels_for_home(home_dir)?
+        .into_iter()
+        .filter(|c| c.enabled)
+        .collect();
+    let now = Utc::now();
+    let checked_at = now.to_rfc3339();
+
+    let mut fresh = Vec::new();
+    let mut errors = HashMap::new();
+    for c in &channels {
+        match fetch_quota_for_home(home_dir, c).await {
+            Ok(Some(quota)) => fresh.push(QuotaSnapshot {
+                timestamp: checked_at.clone(),
+                channel_id: c.id.clone(),
+                remaining: quota.remaining,
+                used: quota.used,
+                unlimited: quota.unlimited,
+                unit: quota.unit,
+            }),
+            Ok(None) => {}
+            Err(e) => {
+                log::warn!("Quota refresh failed for channel {}: {e}", c.name);
+                errors.insert(c.id.clone(), e);
+            }
+        }
+    }
+    append_snapshots_for_home(home_dir, &fresh)?;
+
+    let snapshots = read_snapshots(&snapshots_path_for_home(home_dir))?;
+    let statuses = statuses_for_channels(&channels, &snapshots, &errors, &settings, now);
+
+    let mut state = read_state(home_dir);
+    let mut new_alerts = Vec::new();
+    let mut active = HashMap::new();
+    for status in &statuses {
+        let Some(alert) = &status.alert else {
+            continue;
+        };
+        if state.active_alerts.get(&status.channel_id) != Some(&alert.kind) {
+            new_alerts.push(alert.clone());
+        }
+        active.insert(status.channel_id.clone(), alert.kind);
+    }
+    state.active_alerts = active;
+    state.last_refresh_at = Some(checked_at.clone());
+    write_state(home_dir, &state)?;
+
+    Ok(QuotaRefreshReport {
+        checked_at,
+        statuses,
+        new_alerts,
+    })
+}
+
+/// Blocking version of `refresh_all_for_home`.
+pub fn refresh_all_for_home_blocking(home_dir: &Path) -> Result<QuotaRefreshReport, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create tokio runtime: {e}"))?;
+    runtime.block_on(refresh_all_for_home(home_dir))
+}
+
+pub fn is_refresh_due() -> bool {
+    paths::get_home_dir().is_ok_and(|home| is_refresh_due_for_home(&home))
+}
+
+pub fn get_quota_status() -> Result<Vec<ChannelQuotaStatus>, String> {
+    get_quota_status_for_home(&paths::get_home_dir()?)
+}
+
+pub fn get_quota_history(
+    channel_id: &str,
+    window_days: Option<u32>,
+) -> Result<Vec<QuotaSnapshot>, String> {
+    get_quota_history_for_home(&paths::get_home_dir()?, channel_id, window_days)
+}
+
+pub async fn refresh_all() -> Result<QuotaRefreshReport, String> {
+    refresh_all_for_home(&paths::get_home_dir()?).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::ChannelType;
+    use crate::test_support::serve_responses;
+    use tempfile::TempDir;
+
+    fn snapshot(hours_ago: i64, remaining: f64, used: Option<f64>) -> QuotaSnapshot {
+        QuotaSnapshot {
+            timestamp: (Utc::now() - Duration::hours(hours_ago)).to_rfc3339(),
+            channel_id: "c1".to_string(),
+            remaining: Some(remaining),
+            used,
+            unlimited: false,
+            unit: "USD".to_string(),
+        }
+    }
+
+    fn deepseek_channel(base_url: &str) -> Channel {
+        Channel {
+            id: "c1".to_string(),
+            name: "DeepSeek".to_string(),
+            channel_type: ChannelType::DeepSeek,
+            base_url: base_url.to_string(),
+            enabled: true,
+            created_at: 0.0,
+        }
+    }
+
+    fn balance(amount: &str) -> (u16, String) {
+        (
+            200,
+            format!(
+                r#"{{"is_available":true,"balance_infos":[{{"currency":"USD","total_balance":"{amount}"}}]}}"#
+            ),
+        )
+    }
+
+    #[test]
+    fn burn_rate_ignores_top_ups_and_prefers_used() {
+        let now = Utc::now();
+        // 10 spent over 48h, with a top-up of 50 in between
+        let by_remaining = vec![
+            snapshot(48, 20.0, None),
+            snapshot(24, 14.0, None),
+            snapshot(12, 64.0, None),
+            snapshot(0, 60.0, None),
+        ];
+        let rate = burn_rate_per_day(&by_remaining, now).unwrap();
+        assert!((rate - 5.0).abs() < 0.01);
+
+        let by_used = vec![
+            snapshot(24, 100.0, Some(1.0)),
+            snapshot(0, 100.0, Some(4.0)),
+        ];
+        let rate = burn_rate_per_day(&by_used, now).unwrap();
+        assert!((rate - 3.0).abs() < 0.01);
+
+        // Too short a span, or snapshots older than the window
+        assert!(burn_rate_per_day(&[snapshot(0, 1.0, None)], now).is_none());
+        assert!(
+            burn_rate_per_day(&[snapshot(400, 9.0, None), snapshot(300, 1.0, None)], now).is_none()
+        );
+    }
+
+    #[test]
+    fn status_reports_days_remaining_and_alert_kind() {
+        let channel = deepseek_channel("http://unused");
+        let settings = QuotaMonitorSettings::default();
+        let history = vec![snapshot(48, 30.0, None), snapshot(0, 10.0, None)];
+
+        let status = build_status(&channel, &history, None, &settings, Utc::now());
+        assert!((status.burn_rate_per_day.unwrap() - 10.0).abs() < 0.01);
+        assert!((status.days_remaining.unwrap() - 1.0).abs() < 0.01);
+        assert_eq!(status.alert.unwrap().kind, QuotaAlertKind::RunningOut);
+
+        let mut low = settings.clone();
+        low.channel_thresholds.insert("c1".to_string(), 20.0);
+        let status = build_status(&channel, &history, None, &low, Utc::now());
+        assert_eq!(status.alert.unwrap().kind, QuotaAlertKind::LowBalance);
+
+        let empty = vec![snapshot(0, 0.0, None)];
+        let status = build_status(&channel, &empty, None, &settings, Utc::now());
+        assert_eq!(status.alert.unwrap().kind, QuotaAlertKind::Exhausted);
+    }
+
+    #[test]
+    fn refresh_records_snapshots_and_alerts_once() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let base = serve_responses(vec![balance("2.50"), balance("2.40"), balance("80")]);
+        channel::save_channels_for_home(home, vec![deepseek_channel(&base)]).unwrap();
+        channel::save_channel_api_key_for_home(home, "c1", "sk-ds").unwrap();
+
+        // Monitoring is opt-in
+        assert!(!is_refresh_due_for_home(home));
+        let settings = QuotaMonitorSettings {
+            enabled: true,
+            ..QuotaMonitorSettings::default()
+        };
+        save_settings_for_home(home, &settings).unwrap();
+        assert!(is_refresh_due_for_home(home));
+        let report = refresh_all_for_home_blocking(home).unwrap();
+        assert!(!is_refresh_due_for_home(home));
+        assert_eq!(report.statuses.len(), 1);
+        assert_eq!(
+            report.statuses[0].latest.as_ref().unwrap().remaining,
+            Some(2.5)
+        );
+        assert_eq!(report.new_alerts.len(), 1);
+        assert_eq!(report.new_alerts[0].kind, QuotaAlertKind::LowBalance);
+
+        // Still low: the alert stays active but is not reported again
+        let report = refresh_all_for_home_blocking(home).unwrap();
+        assert!(report.statuses[0].alert.is_some());
+        assert!(report.new_alerts.is_empty());
+
+        // Topped up: alert clears
+        let report = refresh_all_for_home_blocking(home).unwrap();
+        assert!(report.statuses[0].alert.is_none());
+        assert_eq!(
+            get_quota_history_for_home(home, "c1", Some(1))
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(
+            get_quota_status_for_home(home).unwrap()[0]
+                .latest
+                .as_ref()
+                .unwrap()
+                .remaining,
+            Some(80.0)
+        );
+    }
+
+    #[test]
+    fn refresh_reports_missing_credentials_as_errors() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        channel::save_channels_for_home(home, vec![deepseek_channel("http://127.0.0.1:9")])
+            .unwrap();
+
+        let report = refresh_all_for_home_blocking(home).unwrap();
+        assert_eq!(
+            report.statuses[0].error.as_deref(),
+            Some("No API key stored")
+        );
+        assert!(report.statuses[0].latest.is_none());
+        assert!(report.new_alerts.is_empty());
+    }
+}
@@ -0,0 +1,824 @@
+//! Encrypted secret vault (core).
+//!
+//! API keys and tokens are stored in `~/.droidgear/vault.json`, each value
+//! encrypted with AES-256-GCM under a key derived from the user's passphrase
+//! (Argon2id). Profiles and channel auth files hold `secret://name` references
+//! instead of raw values; references are resolved only when a profile is
+//! applied, launched for a temporary run, or tested.
+//!
+//! The derived key lives in process memory once the vault is unlocked. Headless
+//! callers (TUI / CLI) can instead provide the passphrase through the
+//! `DROIDGEAR_VAULT_PASSPHRASE` environment variable.
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::paths;
+use crate::profiles::{self, ProfileTool};
+use crate::storage::atomic_write;
+
+/// Prefix of a secret reference stored in place of a raw value.
+pub const SECRET_REF_PREFIX: &str = "secret://";
+/// Environment variable consulted when the vault is locked.
+pub const VAULT_PASSPHRASE_ENV: &str = "DROIDGEAR_VAULT_PASSPHRASE";
+
+const VAULT_VERSION: u32 = 1;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const MIN_PASSPHRASE_LEN: usize = 8;
+/// Plaintext of the verifier entry, used to check a passphrase.
+const VERIFIER_PLAINTEXT: &[u8] = b"droidgear-vault";
+const VERIFIER_AAD: &[u8] = b"droidgear-vault-verifier";
+
+/// Argon2id defaults (OWASP minimum); tests use a cheaper setting.
+#[cfg(not(test))]
+const KDF_MEMORY_KIB: u32 = 19 * 1024;
+#[cfg(test)]
+const KDF_MEMORY_KIB: u32 = 256;
+const KDF_ITERATIONS: u32 = 2;
+const KDF_PARALLELISM: u32 = 1;
+
+/// Derived keys of unlocked vaults, keyed by vault path.
+static UNLOCKED_KEYS: Mutex<BTreeMap<PathBuf, [u8; KEY_LEN]>> = Mutex::new(BTreeMap::new());
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Vault state as shown to the user
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultStatus {
+    pub initialized: bool,
+    pub unlocked: bool,
+    pub secret_count: u32,
+    pub path: String,
+}
+
+/// A stored secret (the value itself is never listed)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretInfo {
+    pub name: String,
+    /// Reference to put in a profile field (`secret://name`)
+    pub reference: String,
+    pub updated_at: String,
+}
+
+/// Result of moving plaintext keys into the vault
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretMigrationReport {
+    /// Names of the secrets that were created
+    pub migrated: Vec<String>,
+    /// Items that could not be migrated, with the reason
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KdfParams {
+    algorithm: String,
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedValue {
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultEntry {
+    #[serde(flatten)]
+    value: EncryptedValue,
+    updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultFile {
+    version: u32,
+    kdf: KdfParams,
+    verifier: EncryptedValue,
+    #[serde(default)]
+    secrets: BTreeMap<String, VaultEntry>,
+}
+
+// ============================================================================
+// References
+// ============================================================================
+
+/// Returns the secret name if `value` is a `secret://name` reference.
+pub fn parse_secret_ref(value: &str) -> Option<&str> {
+    value
+        .trim()
+        .strip_prefix(SECRET_REF_PREFIX)
+        .filter(|name| !name.is_empty())
+}
+
+pub fn is_secret_ref(value: &str) -> bool {
+    parse_secret_ref(value).is_some()
+}
+
+pub fn secret_ref(name: &str) -> String {
+    format!("{SECRET_REF_PREFIX}{name}")
+}
+
+fn validate_secret_name(name: &str) -> Result<(), String> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+    if name.is_empty()
+        || name.len() > 200
+        || !valid_chars
+        || name.starts_with('/')
+        || name.ends_with('/')
+        || name.contains("..")
+    {
+        return Err(format!(
+            "Invalid secret name '{name}': use letters, digits, '-', '_', '.' and '/'"
+        ));
+    }
+    Ok(())
+}
+
+/// Replaces characters not allowed in secret names (e.g. in provider ids).
+fn sanitize_name_part(part: &str) -> String {
+    let cleaned: String = part
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if cleaned.is_empty() {
+        "_".to_string()
+    } else {
+        cleaned
+    }
+}
+
+// ============================================================================
+// Crypto helpers
+// ============================================================================
+
+fn derive_key(passphrase: &str, kdf: &KdfParams) -> Result<[u8; KEY_LEN], String> {
+    if kdf.algorithm != "argon2id" {
+        return Err(format!("Unsupported vault KDF: {}", kdf.algorithm));
+    }
+    let salt = BASE64
+        .decode(&kdf.salt)
+        .map_err(|e| format!("Failed to decode vault salt: {e}"))?;
+    let params = Params::new(
+        kdf.memory_kib,
+        kdf.iterations,
+        kdf.parallelism,
+        Some(KEY_LEN),
+    )
+    .map_err(|e| format!("Invalid vault KDF parameters: {e}"))?;
+    let mut key = [0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| format!("Failed to derive vault key: {e}"))?;
+    Ok(key)
+}
+
+fn encrypt(key: &[u8; KEY_LEN], plaintext: &[u8], aad: &[u8]) -> Result<EncryptedValue, String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|e| format!("Failed to encrypt secret: {e}"))?;
+    Ok(EncryptedValue {
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn decrypt(key: &[u8; KEY_LEN], value: &EncryptedValue, aad: &[u8]) -> Option<Vec<u8>> {
+    let nonce = BASE64.decode(&value.nonce).ok()?;
+    if nonce.len() != 12 {
+        return None;
+    }
+    let ciphertext = BASE64.decode(&value.ciphertext).ok()?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad,
+            },
+        )
+        .ok()
+}
+
+fn verify_key(vault: &VaultFile, key: &[u8; KEY_LEN]) -> bool {
+    decrypt(key, &vault.verifier, VERIFIER_AAD).as_deref() == Some(VERIFIER_PLAINTEXT)
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn vault_path_for_home(home_dir: &Path) -> PathBuf {
+    paths::droidgear_dir_from_home(home_dir).join("vault.json")
+}
+
+fn read_vault_for_home(home_dir: &Path) -> Result<Option<VaultFile>, String> {
+    let path = vault_path_for_home(home_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read vault: {e}"))?;
+    let vault: VaultFile =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse vault: {e}"))?;
+    if vault.version > VAULT_VERSION {
+        return Err(format!(
+            "Vault version {} is newer than this DroidGear supports",
+            vault.version
+        ));
+    }
+    Ok(Some(vault))
+}
+
+fn load_vault_for_home(home_dir: &Path) -> Result<VaultFile, String> {
+    read_vault_for_home(home_dir)?.ok_or_else(|| {
+        "Secret vault is not initialized. Create it with a passphrase first.".to_string()
+    })
+}
+
+fn write_vault_for_home(home_dir: &Path, vault: &VaultFile) -> Result<(), String> {
+    let path = vault_path_for_home(home_dir);
+    let content = serde_json::to_string_pretty(vault)
+        .map_err(|e| format!("Failed to serialize vault: {e}"))?;
+    atomic_write(&path, content.as_bytes())?;
+    restrict_permissions(&path);
+    Ok(())
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) {}
+
+fn cached_key(home_dir: &Path) -> Option<[u8; KEY_LEN]> {
+    let keys = UNLOCKED_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    keys.get(&vault_path_for_home(home_dir)).copied()
+}
+
+fn cache_key(home_dir: &Path, key: [u8; KEY_LEN]) {
+    let mut keys = UNLOCKED_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    keys.insert(vault_path_for_home(home_dir), key);
+}
+
+/// Returns the key of an unlocked vault, unlocking it from
+/// [`VAULT_PASSPHRASE_ENV`] when possible.
+fn unlocked_key(home_dir: &Path, vault: &VaultFile) -> Result<[u8; KEY_LEN], String> {
+    if let Some(key) = cached_key(home_dir) {
+        return Ok(key);
+    }
+    match std::env::var(VAULT_PASSPHRASE_ENV) {
+        Ok(passphrase) if !passphrase.is_empty() => {
+            let key = derive_key(&passphrase, &vault.kdf)?;
+            if !verify_key(vault, &key) {
+                return Err(format!(
+                    "Incorrect vault passphrase in {VAULT_PASSPHRASE_ENV}"
+                ));
+            }
+            cache_key(home_dir, key);
+            Ok(key)
+        }
+        _ => Err(format!(
+            "Secret vault is locked. Unlock it in DroidGear or set {VAULT_PASSPHRASE_ENV}."
+        )),
+    }
+}
+
+// ============================================================================
+// Public API (vault)
+// ============================================================================
+
+pub fn is_vault_initialized_for_home(home_dir: &Path) -> bool {
+    vault_path_for_home(home_dir).exists()
+}
+
+pub fn get_vault_status_for_home(home_dir: &Path) -> Result<VaultStatus, String> {
+    let path = vault_path_for_home(home_dir);
+    let vault = read_vault_for_home(home_dir)?;
+    Ok(VaultStatus {
+        initialized: vault.is_some(),
+        unlocked: vault.is_some() && cached_key(home_dir).is_some(),
+        secret_count: vault.map(|v| v.secrets.len() as u32).unwrap_or(0),
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+/// Creates an empty vault protected by `passphrase` and leaves it unlocked.
+pub fn init_vault_for_home(home_dir: &Path, passphrase: &str) -> Result<(), String> {
+    if is_vault_initialized_for_home(home_dir) {
+        return Err("Secret vault already exists".to_string());
+    }
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "Vault passphrase must be at least {MIN_PASSPHRASE_LEN} characters"
+        ));
+    }
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let kdf = KdfParams {
+        algorithm: "argon2id".to_string(),
+        salt: BASE64.encode(salt),
+        memory_kib: KDF_MEMORY_KIB,
+        iterations: KDF_ITERATIONS,
+        parallelism: KDF_PARALLELISM,
+    };
+    let key = derive_key(passphrase, &kdf)?;
+    let vault = VaultFile {
+        version: VAULT_VERSION,
+        verifier: encrypt(&key, VERIFIER_PLAINTEXT, VERIFIER_AAD)?,
+        kdf,
+        secrets: BTreeMap::new(),
+    };
+    write_vault_for_home(home_dir, &vault)?;
+    cache_key(home_dir, key);
+    Ok(())
+}
+
+pub fn unlock_vault_for_home(home_dir: &Path, passphrase: &str) -> Result<(), String> {
+    let vault = load_vault_for_home(home_dir)?;
+    let key = derive_key(passphrase, &vault.kdf)?;
+    if !verify_key(&vault, &key) {
+        return Err("Incorrect vault passphrase".to_string());
+    }
+    cache_key(home_dir, key);
+    Ok(())
+}
+
+/// Forgets the derived key; later resolutions need the passphrase again.
+pub fn lock_vault_for_home(home_dir: &Path) {
+    let mut keys = UNLOCKED_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    keys.remove(&vault_path_for_home(home_dir));
+}
+
+/// Re-encrypts every secret under a key derived from `new_passphrase`.
+pub fn change_vault_passphrase_for_home(
+    home_dir: &Path,
+    old_passphrase: &str,
+    new_passphrase: &str,
+) -> Result<(), String> {
+    if new_passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "Vault passphrase must be at least {MIN_PASSPHRASE_LEN} characters"
+        ));
+    }
+    let mut vault = load_vault_for_home(home_dir)?;
+    let old_key = derive_key(old_passphrase, &vault.kdf)?;
+    if !verify_key(&vault, &old_key) {
+        return Err("Incorrect vault passphrase".to_string());
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    vault.kdf.salt = BASE64.encode(salt);
+    let new_key = derive_key(new_passphrase, &vault.kdf)?;
+
+    for (name, entry) in vault.secrets.iter_mut() {
+        let plaintext = decrypt(&old_key, &entry.value, name.as_bytes())
+            .ok_or_else(|| format!("Failed to decrypt secret '{name}'"))?;
+        entry.value = encrypt(&new_key, &plaintext, name.as_bytes())?;
+    }
+    vault.verifier = encrypt(&new_key, VERIFIER_PLAINTEXT, VERIFIER_AAD)?;
+    write_vault_for_home(home_dir, &vault)?;
+    cache_key(home_dir, new_key);
+    Ok(())
+}
+
+/// Lists stored secret names; does not require the vault to be unlocked.
+pub fn list_secrets_for_home(home_dir: &Path) -> Result<Vec<SecretInfo>, String> {
+    let Some(vault) = read_vault_for_home(home_dir)? else {
+        return Ok(Vec::new());
+    };
+    Ok(vault
+        .secrets
+        .into_iter()
+        .map(|(name, entry)| SecretInfo {
+            reference: secret_ref(&name),
+            name,
+            updated_at: entry.updated_at,
+        })
+        .collect())
+}
+
+/// Stores (or replaces) a secret and returns its `secret://` reference.
+pub fn set_secret_for_home(home_dir: &Path, name: &str, value: &str) -> Result<String, String> {
+    let name = name.trim();
+    validate_secret_name(name)?;
+    if is_secret_ref(value) {
+        return Err("A secret value cannot be another secret reference".to_string());
+    }
+    let mut vault = load_vault_for_home(home_dir)?;
+    let key = unlocked_key(home_dir, &vault)?;
+    let entry = VaultEntry {
+        value: encrypt(&key, value.as_bytes(), name.as_bytes())?,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    vault.secrets.insert(name.to_string(), entry);
+    write_vault_for_home(home_dir, &vault)?;
+    Ok(secret_ref(name))
+}
+
+pub fn get_secret_for_home(home_dir: &Path, name: &str) -> Result<String, String> {
+    let vault = load_vault_for_home(home_dir)?;
+    let entry = vault
+        .secrets
+        .get(name)
+        .ok_or_else(|| format!("Secret '{name}' not found in vault"))?;
+    let key = unlocked_key(home_dir, &vault)?;
+    let plaintext = decrypt(&key, &entry.value, name.as_bytes())
+        .ok_or_else(|| format!("Failed to decrypt secret '{name}'"))?;
+    String::from_utf8(plaintext).map_err(|e| format!("Secret '{name}' is not valid UTF-8: {e}"))
+}
+
+/// Removes a secret. Does not require the vault to be unlocked.
+pub fn delete_secret_for_home(home_dir: &Path, name: &str) -> Result<(), String> {
+    let Some(mut vault) = read_vault_for_home(home_dir)? else {
+        return Ok(());
+    };
+    if vault.secrets.remove(name).is_some() {
+        write_vault_for_home(home_dir, &vault)?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// Resolves `value` if it is a `secret://` reference, otherwise returns it as is.
+pub fn resolve_secret_value_for_home(home_dir: &Path, value: &str) -> Result<String, String> {
+    match parse_secret_ref(value) {
+        Some(name) => get_secret_for_home(home_dir, name),
+        None => Ok(value.to_string()),
+    }
+}
+
+fn contains_secret_ref(value: &Value) -> bool {
+    match value {
+        Value::String(s) => is_secret_ref(s),
+        Value::Array(items) => items.iter().any(contains_secret_ref),
+        Value::Object(map) => map.values().any(contains_secret_ref),
+        _ => false,
+    }
+}
+
+fn resolve_value(home_dir: &Path, value: &mut Value) -> Result<(), String> {
+    match value {
+        Value::String(s) => {
+            if let Some(name) = parse_secret_ref(s) {
+                *s = get_secret_for_home(home_dir, name)?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                resolve_value(home_dir, item)?;
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                resolve_value(home_dir, item)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Returns a copy of `item` with every `secret://` string replaced by its value.
+///
+/// Items without references are returned unchanged, so a locked (or missing)
+/// vault only matters for profiles that actually use it.
+pub fn resolve_secret_refs_for_home<T>(home_dir: &Path, item: &T) -> Result<T, String>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    let mut value =
+        serde_json::to_value(item).map_err(|e| format!("Failed to serialize profile: {e}"))?;
+    if !contains_secret_ref(&value) {
+        return Ok(item.clone());
+    }
+    resolve_value(home_dir, &mut value)?;
+    serde_json::from_value(value).map_err(|e| format!("Failed to deserialize profile: {e}"))
+}
+
+// ============================================================================
+// Migration
+// ============================================================================
+
+/// Values that tools interpret as something other than a literal key:
+/// environment variable names (`OPENAI_API_KEY`) and shell commands (`!cmd`).
+pub(crate) fn is_indirect_key(value: &str) -> bool {
+    let env_name = value.chars().any(|c| c.is_ascii_uppercase())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    env_name || value.starts_with('!')
+}
+
+/// Whether the field at `path` (JSON keys from the profile root) holds a credential.
+fn is_credential_field(tool: ProfileTool, path: &[String]) -> bool {
+    let Some(key) = path.last().map(String::as_str) else {
+        return false;
+    };
+    match key {
+        "apiKey" | "bearerToken" => true,
+        "key" | "access" | "refresh" => {
+            tool == ProfileTool::OpenCode && path.first().map(String::as_str) == Some("auth")
+        }
+        _ => false,
+    }
+}
+
+fn collect_profile_secrets(
+    tool: ProfileTool,
+    value: &mut Value,
+    path: &mut Vec<String>,
+    found: &mut Vec<(Vec<String>, String)>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                path.push(key.clone());
+                collect_profile_secrets(tool, item, path, found);
+                path.pop();
+            }
+        }
+        Value::String(s) => {
+            let trimmed = s.trim();
+            if is_credential_field(tool, path)
+                && !trimmed.is_empty()
+                && !is_secret_ref(trimmed)
+                && !is_indirect_key(trimmed)
+            {
+                found.push((path.clone(), trimmed.to_string()));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn set_at_path(value: &mut Value, path: &[String], new_value: String) {
+    let mut current = value;
+    for key in path {
+        match current.get_mut(key) {
+            Some(next) => current = next,
+            None => return,
+        }
+    }
+    *current = Value::String(new_value);
+}
+
+fn migrate_profile(home_dir: &Path, tool: ProfileTool, id: &str) -> Result<Vec<String>, String> {
+    let mut value = profiles::get_profile_value_for_home(home_dir, tool, id)?;
+    let mut found = Vec::new();
+    collect_profile_secrets(tool, &mut value, &mut Vec::new(), &mut found);
+    if found.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut created = Vec::new();
+    for (path, raw) in found {
+        let parts: Vec<String> = path.iter().map(|p| sanitize_name_part(p)).collect();
+        let name = format!(
+            "{}/{}/{}",
+            tool.as_str(),
+            sanitize_name_part(id),
+            parts.join(".")
+        );
+        let reference = set_secret_for_home(home_dir, &name, &raw)?;
+        set_at_path(&mut value, &path, reference);
+        created.push(name);
+    }
+    profiles::save_profile_value_for_home(home_dir, tool, value)?;
+    Ok(created)
+}
+
+/// Moves plaintext keys from channel auth files and all tool profiles into
+/// the vault, replacing them with `secret://` references.
+pub fn migrate_plaintext_secrets_for_home(
+    home_dir: &Path,
+) -> Result<SecretMigrationReport, String> {
+    let vault = load_vault_for_home(home_dir)?;
+    unlocked_key(home_dir, &vault)?;
+
+    let mut report = SecretMigrationReport::default();
+    match crate::channel::migrate_channel_auth_to_vault_for_home(home_dir) {
+        Ok(names) => report.migrated.extend(names),
+        Err(e) => report.errors.push(format!("channels: {e}")),
+    }
+
+    for tool in ProfileTool::ALL {
+        let summaries = match profiles::list_profiles_for_home(home_dir, tool) {
+            Ok(summaries) => summaries,
+            Err(e) => {
+                report.errors.push(format!("{}: {e}", tool.as_str()));
+                continue;
+            }
+        };
+        for summary in summaries {
+            match migrate_profile(home_dir, tool, &summary.id) {
+                Ok(names) => report.migrated.extend(names),
+                Err(e) => {
+                    report
+                        .errors
+                        .push(format!("{} profile '{}': {e}", tool.as_str(), summary.name))
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+// ============================================================================
+// Public API (system home)
+// ============================================================================
+
+pub fn get_vault_status() -> Result<VaultStatus, String> {
+    get_vault_status_for_home(&paths::get_home_dir()?)
+}
+
+pub fn init_vault(passphrase: &str) -> Result<(), String> {
+    init_vault_for_home(&paths::get_home_dir()?, passphrase)
+}
+
+pub fn unlock_vault(passphrase: &str) -> Result<(), String> {
+    unlock_vault_for_home(&paths::get_home_dir()?, passphrase)
+}
+
+pub fn lock_vault() -> Result<(), String> {
+    lock_vault_for_home(&paths::get_home_dir()?);
+    Ok(())
+}
+
+pub fn change_vault_passphrase(old_passphrase: &str, new_passphrase: &str) -> Result<(), String> {
+    change_vault_passphrase_for_home(&paths::get_home_dir()?, old_passphrase, new_passphrase)
+}
+
+pub fn list_secrets() -> Result<Vec<SecretInfo>, String> {
+    list_secrets_for_home(&paths::get_home_dir()?)
+}
+
+pub fn set_secret(name: &str, value: &str) -> Result<String, String> {
+    set_secret_for_home(&paths::get_home_dir()?, name, value)
+}
+
+pub fn delete_secret(name: &str) -> Result<(), String> {
+    delete_secret_for_home(&paths::get_home_dir()?, name)
+}
+
+pub fn migrate_plaintext_secrets() -> Result<SecretMigrationReport, String> {
+    migrate_plaintext_secrets_for_home(&paths::get_home_dir()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hermes::{self, HermesModelConfig, HermesProfile};
+    use tempfile::TempDir;
+
+    const PASSPHRASE: &str = "correct horse battery";
+
+    #[test]
+    fn stores_secrets_encrypted_and_checks_the_passphrase() {
+        let dir = TempDir::new().unwrap();
+        let home = dir.path();
+        init_vault_for_home(home, PASSPHRASE).unwrap();
+
+        let reference = set_secret_for_home(home, "relay/main", "sk-live-123456").unwrap();
+        assert_eq!(reference, "secret://relay/main");
+        let raw = std::fs::read_to_string(vault_path_for_home(home)).unwrap();
+        assert!(!raw.contains("sk-live-123456"));
+        assert_eq!(
+            resolve_secret_value_for_home(home, &reference).unwrap(),
+            "sk-live-123456"
+        );
+
+        lock_vault_for_home(home);
+        let err = get_secret_for_home(home, "relay/main").unwrap_err();
+        assert!(err.contains("locked"), "{err}");
+        assert_eq!(
+            unlock_vault_for_home(home, "wrong passphrase").unwrap_err(),
+            "Incorrect vault passphrase"
+        );
+
+        change_vault_passphrase_for_home(home, PASSPHRASE, "another passphrase").unwrap();
+        lock_vault_for_home(home);
+        unlock_vault_for_home(home, "another passphrase").unwrap();
+        assert_eq!(
+            get_secret_for_home(home, "relay/main").unwrap(),
+            "sk-live-123456"
+        );
+        assert!(set_secret_for_home(home, "../escape", "x").is_err());
+    }
+
+    #[test]
+    fn migrates_profile_keys_and_resolves_them_on_apply() {
+        let dir = TempDir::new().unwrap();
+        let home = dir.path();
+        hermes::save_hermes_profile_for_home(
+            home,
+            HermesProfile {
+                id: "work".to_string(),
+                name: "Work".to_string(),
+                description: None,
+                created_at: String::new(),
+                updated_at: String::new(),
+                model: HermesModelConfig {
+                    default: Some("gpt-5".to_string()),
+                    provider: Some("custom".to_string()),
+                    base_url: Some("https://relay.example/v1".to_string()),
+                    api_key: Some("sk-hermes-plain".to_string()),
+                    channel_ref: None,
+                },
+            },
+        )
+        .unwrap();
+        crate::channel::save_channel_api_key_for_home(home, "ch1", "sk-channel-plain").unwrap();
+
+        assert!(migrate_plaintext_secrets_for_home(home).is_err());
+        init_vault_for_home(home, PASSPHRASE).unwrap();
+        let report = migrate_plaintext_secrets_for_home(home).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(
+            report.migrated,
+            vec![
+                "channel/ch1/api-key".to_string(),
+                "hermes/work/model.apiKey".to_string()
+            ]
+        );
+
+        let stored = hermes::get_hermes_profile_for_home(home, "work").unwrap();
+        assert_eq!(
+            stored.model.api_key.as_deref(),
+            Some("secret://hermes/work/model.apiKey")
+        );
+        let auth_file = std::fs::read_to_string(home.join(".droidgear/auth/ch1.json")).unwrap();
+        assert!(!auth_file.contains("sk-channel-plain"));
+        assert_eq!(
+            crate::channel::get_channel_api_key_for_home(home, "ch1").unwrap(),
+            Some("sk-channel-plain".to_string())
+        );
+
+        let plan = hermes::plan_apply_hermes_profile_for_home(home, "work").unwrap();
+        let planned = &plan.files[0].after;
+        assert!(planned.contains("sk-****lain"), "{planned}");
+        assert!(!planned.contains("secret://"), "{planned}");
+
+        hermes::apply_hermes_profile_for_home(home, "work").unwrap();
+        let live = std::fs::read_to_string(home.join(".hermes/config.yaml")).unwrap();
+        assert!(live.contains("sk-hermes-plain"), "{live}");
+
+        // Running the migration again finds nothing new.
+        let again = migrate_plaintext_secrets_for_home(home).unwrap();
+        assert!(again.migrated.is_empty());
+    }
+
+    #[test]
+    fn leaves_env_names_and_commands_in_place() {
+        assert!(is_indirect_key("OPENAI_API_KEY"));
+        assert!(is_indirect_key("!op read op://vault/key"));
+        assert!(!is_indirect_key("sk-abc"));
+        assert!(!is_indirect_key("ollama"));
+    }
+}
@@ -0,0 +1,609 @@
+//! Token usage and cost analytics over agent sessions (core).
+//!
+//! Rolls the `TokenUsage` of every session up by day, ISO week, project,
+//! model and agent, and estimates spend from per-model prices. Prices come
+//! from `~/.droidgear/model-prices.json` (user-configured, USD per million
+//! tokens) and fall back to the `cost` of Pi models, both in the live Pi
+//! config and in saved Pi profiles.
+//!
+//! A session is attributed to the day it was last modified, in the
+//! requested UTC offset.
+
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::paths;
+use crate::pi::{self, PiModelCost, PiProviderConfig};
+use crate::sessions::{self, SessionSource, SessionSummary, TokenUsage};
+use crate::storage::atomic_write;
+
+const PRICES_FILE: &str = "model-prices.json";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Which sessions a usage report covers
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageQuery {
+    /// Only sessions of this agent
+    #[serde(default)]
+    pub source: Option<SessionSource>,
+    /// Only sessions of this project
+    #[serde(default)]
+    pub project: Option<String>,
+    /// First day included (`YYYY-MM-DD`)
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Last day included (`YYYY-MM-DD`)
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Offset used to assign sessions to days; defaults to the local offset
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+/// Token counts and estimated cost of a group of sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotals {
+    pub sessions: u32,
+    pub input_tokens: f64,
+    pub output_tokens: f64,
+    pub cache_creation_tokens: f64,
+    pub cache_read_tokens: f64,
+    pub thinking_tokens: f64,
+    /// Estimated cost in USD of the priced sessions
+    pub cost: f64,
+    /// Sessions whose model has no known price (not included in `cost`)
+    pub unpriced_sessions: u32,
+    /// Cache reads / all prompt tokens (input + cache reads + cache writes), 0-1
+    pub cache_hit_ratio: f64,
+}
+
+/// Totals of one day, week, project, model or agent
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageBucket {
+    pub key: String,
+    pub totals: UsageTotals,
+}
+
+/// Usage rolled up along every dimension.
+///
+/// Sessions only record their total usage, so a session's whole usage lands
+/// on the day (and week) it was last modified, even if it spanned several
+/// days. The `from`/`to` filters apply to that day as well.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    pub totals: UsageTotals,
+    /// Oldest day first (`YYYY-MM-DD`, the day each session was last modified)
+    pub by_day: Vec<UsageBucket>,
+    /// Oldest week first (ISO week, `2026-W03`, of that same day)
+    pub by_week: Vec<UsageBucket>,
+    /// Most expensive first
+    pub by_project: Vec<UsageBucket>,
+    pub by_model: Vec<UsageBucket>,
+    pub by_source: Vec<UsageBucket>,
+    /// Models seen without a price
+    pub unpriced_models: Vec<String>,
+}
+
+/// Dimension a report is broken down by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UsageDimension {
+    Day,
+    Week,
+    #[default]
+    Project,
+    Model,
+    Source,
+}
+
+impl UsageDimension {
+    pub const ALL: [UsageDimension; 5] = [
+        UsageDimension::Day,
+        UsageDimension::Week,
+        UsageDimension::Project,
+        UsageDimension::Model,
+        UsageDimension::Source,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UsageDimension::Day => "Day",
+            UsageDimension::Week => "Week",
+            UsageDimension::Project => "Project",
+            UsageDimension::Model => "Model",
+            UsageDimension::Source => "Agent",
+        }
+    }
+}
+
+impl UsageReport {
+    pub fn buckets(&self, dimension: UsageDimension) -> &[UsageBucket] {
+        match dimension {
+            UsageDimension::Day => &self.by_day,
+            UsageDimension::Week => &self.by_week,
+            UsageDimension::Project => &self.by_project,
+            UsageDimension::Model => &self.by_model,
+            UsageDimension::Source => &self.by_source,
+        }
+    }
+}
+
+// ============================================================================
+// Prices
+// ============================================================================
+
+pub fn prices_path_for_home(home_dir: &Path) -> PathBuf {
+    paths::droidgear_dir_from_home(home_dir).join(PRICES_FILE)
+}
+
+/// User-configured prices, keyed by model ID.
+pub fn load_model_prices_for_home(home_dir: &Path) -> Result<HashMap<String, PiModelCost>, String> {
+    let path = prices_path_for_home(home_dir);
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read model prices: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid model prices JSON: {e}"))
+}
+
+pub fn save_model_prices_for_home(
+    home_dir: &Path,
+    prices: &HashMap<String, PiModelCost>,
+) -> Result<(), String> {
+    let path = prices_path_for_home(home_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create DroidGear directory: {e}"))?;
+    }
+    let sorted: BTreeMap<&String, &PiModelCost> = prices.iter().collect();
+    let content = serde_json::to_string_pretty(&sorted)
+        .map_err(|e| format!("Failed to serialize model prices: {e}"))?;
+    atomic_write(&path, content.as_bytes())
+}
+
+/// Model ID as used for price lookups: lowercase, without a `provider/` or
+/// `custom:` prefix.
+fn normalize_model(model: &str) -> String {
+    let model = model.trim().to_lowercase();
+    let model = model.rsplit('/').next().unwrap_or("");
+    model.strip_prefix("custom:").unwrap_or(model).to_string()
+}
+
+/// Price table used to estimate cost.
+#[derive(Debug, Clone, Default)]
+pub struct PriceBook {
+    prices: HashMap<String, PiModelCost>,
+}
+
+impl PriceBook {
+    /// Adds a price unless the model already has one (earlier sources win).
+    pub fn insert(&mut self, model: &str, cost: PiModelCost) {
+        let key = normalize_model(model);
+        if !key.is_empty() {
+            self.prices.entry(key).or_insert(cost);
+        }
+    }
+
+    fn insert_pi_providers(&mut self, providers: &HashMap<String, PiProviderConfig>) {
+        for provider in providers.values() {
+            for model in &provider.models {
+                if let Some(cost) = &model.cost {
+                    self.insert(&model.id, cost.clone());
+                }
+            }
+            for (id, model_override) in provider.model_overrides.iter().flatten() {
+                if let Some(cost) = &model_override.cost {
+                    self.insert(id, cost.clone());
+                }
+            }
+        }
+    }
+
+    /// Price of `model`: an exact match, else a priced ID followed only by a
+    /// date or version suffix (so `claude-sonnet-4-5` prices
+    /// `claude-sonnet-4-5-20250929`, but `gpt-5` does not price `gpt-5-mini`).
+    pub fn lookup(&self, model: &str) -> Option<&PiModelCost> {
+        let model = normalize_model(model);
+        if model.is_empty() {
+            return None;
+        }
+        self.prices.get(&model).or_else(|| {
+            self.prices
+                .iter()
+                .filter(|(key, _)| {
+                    model
+                        .strip_prefix(key.as_str())
+                        .is_some_and(is_version_suffix)
+                })
+                .max_by_key(|(key, _)| key.len())
+                .map(|(_, cost)| cost)
+        })
+    }
+}
+
+/// Whether `rest` only names a snapshot of a model: `-20250929`,
+/// `-2025-08-07`, `-0613`, `-001`, `@20250929`, `-v2` or `-latest`.
+fn is_version_suffix(rest: &str) -> bool {
+    let Some(rest) = rest.strip_prefix('-').or_else(|| rest.strip_prefix('@')) else {
+        return false;
+    };
+    let digits = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+    match rest.split('-').collect::<Vec<_>>().as_slice() {
+        ["latest"] => true,
+        [part] => {
+            digits(part, 8)
+                || digits(part, 4)
+                || digits(part, 3)
+                || part
+                    .strip_prefix('v')
+                    .is_some_and(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()))
+        }
+        [year, month, day] => digits(year, 4) && digits(month, 2) && digits(day, 2),
+        _ => false,
+    }
+}
+
+/// User prices first, then Pi model costs (live config, then profiles).
+pub fn price_book_for_home(home_dir: &Path) -> Result<PriceBook, String> {
+    let mut book = PriceBook::default();
+    for (model, cost) in load_model_prices_for_home(home_dir)? {
+        book.insert(&model, cost);
+    }
+    match pi::read_pi_current_config_for_home(home_dir) {
+        Ok(config) => book.insert_pi_providers(&config.providers),
+        Err(e) => log::warn!("Skipping Pi model costs: {e}"),
+    }
+    for profile in pi::list_pi_profiles_for_home(home_dir).unwrap_or_default() {
+        book.insert_pi_providers(&profile.providers);
+    }
+    Ok(book)
+}
+
+/// Estimated cost in USD. Thinking tokens are billed as output.
+pub fn estimate_cost(usage: &TokenUsage, price: &PiModelCost) -> f64 {
+    (usage.input_tokens * price.input
+        + (usage.output_tokens + usage.thinking_tokens) * price.output
+        + usage.cache_read_tokens * price.cache_read
+        + usage.cache_creation_tokens * price.cache_write)
+        / 1_000_000.0
+}
+
+// ============================================================================
+// Aggregation
+// ============================================================================
+
+impl UsageTotals {
+    fn add(&mut self, usage: &TokenUsage, cost: Option<f64>) {
+        self.sessions += 1;
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.cache_creation_tokens += usage.cache_creation_tokens;
+        self.cache_read_tokens += usage.cache_read_tokens;
+        self.thinking_tokens += usage.thinking_tokens;
+        match cost {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced_sessions += 1,
+        }
+        let prompt = self.input_tokens + self.cache_read_tokens + self.cache_creation_tokens;
+        self.cache_hit_ratio = if prompt > 0.0 {
+            self.cache_read_tokens / prompt
+        } else {
+            0.0
+        };
+    }
+
+    pub fn total_tokens(&self) -> f64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_tokens
+            + self.cache_read_tokens
+            + self.thinking_tokens
+    }
+}
+
+fn parse_day(value: Option<&str>, name: &str) -> Result<Option<NaiveDate>, String> {
+    value
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| {
+            NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("Invalid '{name}' date '{v}' (expected YYYY-MM-DD)"))
+        })
+        .transpose()
+}
+
+fn utc_offset(minutes: Option<i32>) -> Result<FixedOffset, String> {
+    match minutes {
+        Some(minutes) => FixedOffset::east_opt(minutes * 60)
+            .ok_or_else(|| format!("Invalid UTC offset: {minutes} minutes")),
+        None => Ok(*Local::now().offset()),
+    }
+}
+
+fn into_buckets(map: BTreeMap<String, UsageTotals>) -> Vec<UsageBucket> {
+    map.into_iter()
+        .map(|(key, totals)| UsageBucket { key, totals })
+        .collect()
+}
+
+/// Most expensive first; ties (e.g. unpriced) by token volume.
+fn into_ranked_buckets(map: BTreeMap<String, UsageTotals>) -> Vec<UsageBucket> {
+    let mut buckets = into_buckets(map);
+    buckets.sort_by(|a, b| {
+        b.totals
+            .cost
+            .total_cmp(&a.totals.cost)
+            .then(b.totals.total_tokens().total_cmp(&a.totals.total_tokens()))
+    });
+    buckets
+}
+
+/// Rolls `sessions` up into a report. Sessions outside the query's date
+/// range are skipped; `source` and `project` filters are applied when the
+/// sessions are listed.
+pub fn build_usage_report(
+    sessions: &[SessionSummary],
+    prices: &PriceBook,
+    query: &UsageQuery,
+) -> Result<UsageReport, String> {
+    let from = parse_day(query.from.as_deref(), "from")?;
+    let to = parse_day(query.to.as_deref(), "to")?;
+    let offset = utc_offset(query.utc_offset_minutes)?;
+
+    let mut report = UsageReport::default();
+    let mut by_day = BTreeMap::new();
+    let mut by_week = BTreeMap::new();
+    let mut by_project = BTreeMap::new();
+    let mut by_model = BTreeMap::new();
+    let mut by_source = BTreeMap::new();
+    let mut unpriced = BTreeSet::new();
+
+    for session in sessions {
+        let Some(modified) = DateTime::<Utc>::from_timestamp_millis(session.modified_at as i64)
+        else {
+            continue;
+        };
+        let day = modified.with_timezone(&offset).date_naive();
+        if from.is_some_and(|from| day < from) || to.is_some_and(|to| day > to) {
+            continue;
+        }
+
+        let model = if session.model.is_empty() {
+            "unknown"
+        } else {
+            session.model.as_str()
+        };
+        let cost = prices
+            .lookup(model)
+            .map(|price| estimate_cost(&session.token_usage, price));
+        if cost.is_none() {
+            unpriced.insert(model.to_string());
+        }
+
+        let week = day.iso_week();
+        let usage = &session.token_usage;
+        report.totals.add(usage, cost);
+        for (map, key) in [
+            (&mut by_day, day.format("%Y-%m-%d").to_string()),
+            (&mut by_week, format!("{}-W{:02}", week.year(), week.week())),
+            (&mut by_project, session.project.clone()),
+            (&mut by_model, model.to_string()),
+            (&mut by_source, session.source.label().to_string()),
+        ] {
+            map.entry(key)
+                .or_insert_with(UsageTotals::default)
+                .add(usage, cost);
+        }
+    }
+
+    report.by_day = into_buckets(by_day);
+    report.by_week = into_buckets(by_week);
+    report.by_project = into_ranked_buckets(by_project);
+    report.by_model = into_ranked_buckets(by_model);
+    report.by_source = into_ranked_buckets(by_source);
+    report.unpriced_models = unpriced.into_iter().collect();
+    Ok(report)
+}
+
+pub fn usage_report_for_home(home_dir: &Path, query: &UsageQuery) -> Result<UsageReport, String> {
+    let project = query.project.as_deref().filter(|p| !p.is_empty());
+    let sessions = sessions::list_agent_sessions_for_home(home_dir, query.source, project)?;
+    build_usage_report(&sessions, &price_book_for_home(home_dir)?, query)
+}
+
+// ============================================================================
+// System wrappers
+// ============================================================================
+
+pub fn usage_report(query: &UsageQuery) -> Result<UsageReport, String> {
+    usage_report_for_home(&paths::get_home_dir()?, query)
+}
+
+pub fn load_model_prices() -> Result<HashMap<String, PiModelCost>, String> {
+    load_model_prices_for_home(&paths::get_home_dir()?)
+}
+
+pub fn save_model_prices(prices: &HashMap<String, PiModelCost>) -> Result<(), String> {
+    save_model_prices_for_home(&paths::get_home_dir()?, prices)
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn cost(input: f64, output: f64, cache_read: f64, cache_write: f64) -> PiModelCost {
+        PiModelCost {
+            input,
+            output,
+            cache_read,
+            cache_write,
+        }
+    }
+
+    fn session(
+        source: SessionSource,
+        project: &str,
+        model: &str,
+        day: &str,
+        usage: [f64; 5],
+    ) -> SessionSummary {
+        let modified_at = NaiveDate::parse_from_str(day, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis() as f64;
+        SessionSummary {
+            source,
+            id: format!("{project}-{day}"),
+            title: String::new(),
+            project: project.to_string(),
+            model: model.to_string(),
+            modified_at,
+            token_usage: TokenUsage {
+                input_tokens: usage[0],
+                output_tokens: usage[1],
+                cache_creation_tokens: usage[2],
+                cache_read_tokens: usage[3],
+                thinking_tokens: usage[4],
+            },
+            path: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_price_lookup() {
+        let mut book = PriceBook::default();
+        book.insert("claude-sonnet-4-5", cost(3.0, 15.0, 0.3, 3.75));
+        book.insert("gpt-5", cost(1.25, 10.0, 0.125, 0.0));
+        book.insert("gpt-5-mini", cost(0.25, 2.0, 0.025, 0.0));
+        book.insert("GPT-5", cost(99.0, 99.0, 0.0, 0.0));
+
+        let price = |model: &str| book.lookup(model).map(|p| p.input);
+        assert_eq!(price("claude-sonnet-4-5-20250929"), Some(3.0));
+        assert_eq!(price("openai/GPT-5"), Some(1.25));
+        assert_eq!(price("gpt-5-mini-2025-08-07"), Some(0.25));
+        assert_eq!(price("claude-sonnet-4-5@20250929"), Some(3.0));
+        assert_eq!(price("gpt-5-v2"), Some(1.25));
+        // A different model sharing a prefix is not priced as its base
+        assert_eq!(price("gpt-5-nano"), None);
+        assert_eq!(price("custom:gpt-5-codex-0"), None);
+        assert_eq!(price("glm-4.6"), None);
+        assert_eq!(price(""), None);
+    }
+
+    #[test]
+    fn test_builds_usage_report() {
+        let mut book = PriceBook::default();
+        book.insert("claude-sonnet-4-5", cost(3.0, 15.0, 0.3, 3.75));
+        let sessions = vec![
+            session(
+                SessionSource::Factory,
+                "-work-api",
+                "claude-sonnet-4-5-20250929",
+                "2026-01-05",
+                [1_000_000.0, 100_000.0, 0.0, 3_000_000.0, 100_000.0],
+            ),
+            session(
+                SessionSource::Claude,
+                "-work-api",
+                "claude-sonnet-4-5",
+                "2026-01-11",
+                [0.0, 0.0, 1_000_000.0, 0.0, 0.0],
+            ),
+            session(
+                SessionSource::Pi,
+                "-work-web",
+                "glm-4.6",
+                "2026-01-12",
+                [500.0, 50.0, 0.0, 0.0, 0.0],
+            ),
+            session(
+                SessionSource::Codex,
+                "-work-old",
+                "gpt-5",
+                "2025-12-01",
+                [1.0, 1.0, 0.0, 0.0, 0.0],
+            ),
+        ];
+        let query = UsageQuery {
+            from: Some("2026-01-01".to_string()),
+            utc_offset_minutes: Some(0),
+            ..Default::default()
+        };
+
+        let report = build_usage_report(&sessions, &book, &query).unwrap();
+        // 3 + 0.2 * 15 + 0.9 (cache reads), then 3.75 (cache writes)
+        assert!((report.totals.cost - 10.65).abs() < 1e-9);
+        assert_eq!(report.totals.sessions, 3);
+        assert_eq!(report.totals.unpriced_sessions, 1);
+        assert_eq!(report.unpriced_models, vec!["glm-4.6"]);
+        assert!((report.totals.cache_hit_ratio - 3.0 / 5.0).abs() < 1e-4);
+
+        let keys = |buckets: &[UsageBucket]| -> Vec<String> {
+            buckets.iter().map(|b| b.key.clone()).collect()
+        };
+        assert_eq!(
+            keys(&report.by_day),
+            vec!["2026-01-05", "2026-01-11", "2026-01-12"]
+        );
+        assert_eq!(keys(&report.by_week), vec!["2026-W02", "2026-W03"]);
+        assert_eq!(keys(&report.by_project), vec!["-work-api", "-work-web"]);
+        assert_eq!(report.by_project[0].totals.sessions, 2);
+        assert_eq!(keys(&report.by_source), vec!["Droid", "Claude Code", "Pi"]);
+        assert_eq!(report.by_model.len(), 3);
+
+        let late = UsageQuery {
+            utc_offset_minutes: Some(14 * 60),
+            from: Some("2026-01-13".to_string()),
+            ..Default::default()
+        };
+        let report = build_usage_report(&sessions, &book, &late).unwrap();
+        assert_eq!(keys(&report.by_day), vec!["2026-01-13"]);
+
+        let bad = UsageQuery {
+            to: Some("last week".to_string()),
+            ..Default::default()
+        };
+        assert!(build_usage_report(&sessions, &book, &bad).is_err());
+    }
+
+    #[test]
+    fn test_price_book_prefers_user_prices() {
+        let home = TempDir::new().unwrap();
+        let mut prices = HashMap::new();
+        prices.insert("kimi-k2".to_string(), cost(0.6, 2.5, 0.15, 0.0));
+        save_model_prices_for_home(home.path(), &prices).unwrap();
+        assert_eq!(load_model_prices_for_home(home.path()).unwrap().len(), 1);
+
+        let pi_dir = home.path().join(".pi/agent");
+        std::fs::create_dir_all(&pi_dir).unwrap();
+        std::fs::write(
+            pi_dir.join("models.json"),
+            serde_json::json!({ "providers": { "moonshot": { "models": [
+                { "id": "kimi-k2", "cost": { "input": 9.0, "output": 9.0 } },
+                { "id": "glm-4.6", "cost": { "input": 0.5, "output": 2.0 } }
+            ] } } })
+            .to_string(),
+        )
+        .unwrap();
+
+        let book = price_book_for_home(home.path()).unwrap();
+        assert_eq!(book.lookup("kimi-k2").unwrap().input, 0.6);
+        assert_eq!(book.lookup("glm-4.6").unwrap().output, 2.0);
+    }
+}
@@ -0,0 +1,766 @@
+//! Full-text search across agent sessions (core).
+//!
+//! Session transcripts of every [`crate::session_source`] reader are tokenized
+//! into `~/.droidgear/session-index.json`: one entry per message text,
+//! thinking block, tool call and tool result, plus the session title. The
+//! index is refreshed incrementally before every search — only sessions whose
+//! transcript size or mtime changed are parsed again, and deleted sessions are
+//! dropped — but the file itself is rewritten in full whenever anything
+//! changed.
+//!
+//! Each entry keeps up to `MAX_ENTRY_CHARS` of its text for snippets, so the
+//! index is a plaintext copy of transcript content (tool output included),
+//! readable by anyone who can read the transcripts themselves.
+//!
+//! Matching is word-prefix based: every query term must start a word of the
+//! same entry (`migrat` finds "migration"). CJK characters are indexed one per
+//! term so queries work without whitespace.
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::paths;
+use crate::session_source::{readers, SessionReader};
+use crate::sessions::{modified_at_ms, SessionDetail, SessionSource};
+use crate::storage::atomic_write;
+
+/// Bump when the on-disk index layout or tokenization changes.
+const INDEX_VERSION: u32 = 2;
+/// Stored text per entry is capped; terms still cover the full text.
+const MAX_ENTRY_CHARS: usize = 20_000;
+const MAX_SNIPPETS_PER_SESSION: usize = 3;
+const DEFAULT_RESULT_LIMIT: usize = 50;
+/// Characters of context kept before the first highlight in a snippet.
+const SNIPPET_LEAD: usize = 60;
+const SNIPPET_CHARS: usize = 200;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Search filters (all optional)
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSearchFilters {
+    /// Agent the session belongs to
+    #[serde(default)]
+    pub source: Option<SessionSource>,
+    /// Project directory name
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Case-insensitive substring of the session model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Only match entries at or after this timestamp (milliseconds)
+    #[serde(default)]
+    pub from: Option<f64>,
+    /// Only match entries at or before this timestamp (milliseconds)
+    #[serde(default)]
+    pub to: Option<f64>,
+    /// Message role ("user" or "assistant")
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Maximum number of sessions returned (default 50)
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Highlighted range within a snippet, in UTF-16 code units (end exclusive)
+/// so it indexes JavaScript strings directly
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// One matching piece of a session
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSnippet {
+    /// Message ID (empty for title matches)
+    pub message_id: String,
+    /// Message role ("session" for title matches)
+    pub role: String,
+    /// "title", "text", "thinking", "tool_use" or "tool_result"
+    pub kind: String,
+    pub timestamp: String,
+    /// Excerpt around the first match, newlines flattened to spaces
+    pub text: String,
+    pub highlights: Vec<HighlightRange>,
+}
+
+/// A session matching a search
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSearchHit {
+    pub source: SessionSource,
+    pub id: String,
+    pub title: String,
+    pub project: String,
+    pub model: String,
+    /// Last modified timestamp in milliseconds
+    pub modified_at: f64,
+    /// Full path to the session files (without extension)
+    pub path: String,
+    /// Number of matching entries
+    pub match_count: u32,
+    /// The first few matching entries
+    pub snippets: Vec<SearchSnippet>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SessionIndex {
+    version: u32,
+    /// Keyed by session path
+    files: HashMap<String, IndexedSession>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedSession {
+    source: SessionSource,
+    /// Transcript size and mtime when indexed
+    size: u64,
+    file_modified_at: f64,
+    modified_at: f64,
+    id: String,
+    title: String,
+    project: String,
+    model: String,
+    entries: Vec<IndexedEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedEntry {
+    message_id: String,
+    role: String,
+    kind: String,
+    timestamp: String,
+    text: String,
+    /// Sorted, deduplicated terms
+    terms: Vec<String>,
+}
+
+// ============================================================================
+// Tokenization
+// ============================================================================
+
+/// Single-char lowercase fold, so folded text keeps the original char offsets.
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF // Hiragana, Katakana
+        | 0x3400..=0x4DBF // CJK Extension A
+        | 0x4E00..=0x9FFF // CJK Unified Ideographs
+        | 0xAC00..=0xD7AF // Hangul syllables
+        | 0xF900..=0xFAFF) // CJK Compatibility Ideographs
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() && !is_cjk(c)
+}
+
+/// Split text into lowercase terms: runs of letters/digits, and single CJK characters.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        if is_word_char(c) {
+            current.push(fold(c));
+            continue;
+        }
+        if !current.is_empty() {
+            terms.push(std::mem::take(&mut current));
+        }
+        if is_cjk(c) {
+            terms.push(c.to_string());
+        }
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    terms
+}
+
+fn index_terms(text: &str) -> Vec<String> {
+    let mut terms = tokenize(text);
+    terms.sort();
+    terms.dedup();
+    terms
+}
+
+/// Whether some term in the sorted `terms` starts with `prefix`.
+fn has_prefix(terms: &[String], prefix: &str) -> bool {
+    let i = terms.partition_point(|t| t.as_str() < prefix);
+    terms.get(i).is_some_and(|t| t.starts_with(prefix))
+}
+
+// ============================================================================
+// Indexing
+// ============================================================================
+
+fn index_path_for_home(home_dir: &Path) -> PathBuf {
+    paths::droidgear_dir_from_home(home_dir).join("session-index.json")
+}
+
+fn load_index(path: &Path) -> SessionIndex {
+    let index: SessionIndex = fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default();
+    if index.version == INDEX_VERSION {
+        index
+    } else {
+        SessionIndex {
+            version: INDEX_VERSION,
+            files: HashMap::new(),
+        }
+    }
+}
+
+fn new_entry(
+    message_id: &str,
+    role: &str,
+    kind: &str,
+    timestamp: &str,
+    text: &str,
+) -> IndexedEntry {
+    IndexedEntry {
+        message_id: message_id.to_string(),
+        role: role.to_string(),
+        kind: kind.to_string(),
+        timestamp: timestamp.to_string(),
+        text: text.chars().take(MAX_ENTRY_CHARS).collect(),
+        terms: index_terms(text),
+    }
+}
+
+/// Turn a parsed session into index entries.
+fn index_session(detail: SessionDetail, size: u64, file_modified_at: f64) -> IndexedSession {
+    let mut session = IndexedSession {
+        source: detail.source,
+        size,
+        file_modified_at,
+        modified_at: detail.modified_at,
+        id: detail.id,
+        title: detail.title,
+        project: detail.project,
+        model: detail.model,
+        entries: Vec::new(),
+    };
+
+    for message in &detail.messages {
+        for block in &message.content {
+            let text = match block.content_type.as_str() {
+                "thinking" => block.thinking.clone().unwrap_or_default(),
+                "tool_use" => format!(
+                    "{} {}",
+                    block.tool_name.as_deref().unwrap_or(""),
+                    block.tool_input.as_ref().unwrap_or(&Value::Null)
+                ),
+                "tool_result" => block.tool_result.clone().unwrap_or_default(),
+                _ => block.text.clone().unwrap_or_default(),
+            };
+            if !text.trim().is_empty() {
+                session.entries.push(new_entry(
+                    &message.id,
+                    &message.role,
+                    &block.content_type,
+                    &message.timestamp,
+                    &text,
+                ));
+            }
+        }
+    }
+
+    // The title is dated like the session's first message for date filters
+    let title = session.title.clone();
+    let started_at = session
+        .entries
+        .first()
+        .map(|e| e.timestamp.clone())
+        .unwrap_or_default();
+    session
+        .entries
+        .insert(0, new_entry("", "session", "title", &started_at, &title));
+    session
+}
+
+/// Bring the index up to date with every agent's sessions. Returns the index
+/// and the number of sessions (re)parsed.
+fn refresh_index_for_home(home_dir: &Path) -> Result<(SessionIndex, usize), String> {
+    let index_path = index_path_for_home(home_dir);
+    let mut index = load_index(&index_path);
+
+    let sessions: Vec<(&dyn SessionReader, String)> = readers()
+        .iter()
+        .flat_map(|reader| {
+            reader
+                .session_paths(home_dir)
+                .into_iter()
+                .map(move |path| (*reader, path))
+        })
+        .collect();
+
+    let mut changed = false;
+    let live: HashSet<&str> = sessions.iter().map(|(_, path)| path.as_str()).collect();
+    let before = index.files.len();
+    index.files.retain(|path, _| live.contains(path.as_str()));
+    changed |= index.files.len() != before;
+
+    let mut reindexed = 0;
+    for (reader, path) in sessions {
+        let transcript = reader.transcript_path(&path);
+        let size = fs::metadata(&transcript).map(|m| m.len()).unwrap_or(0);
+        let file_modified_at = modified_at_ms(&transcript);
+        let fresh = index
+            .files
+            .get(&path)
+            .is_some_and(|s| s.size == size && s.file_modified_at == file_modified_at);
+        if fresh {
+            continue;
+        }
+        match reader.read_detail(&path) {
+            Ok(detail) => {
+                let session = index_session(detail, size, file_modified_at);
+                index.files.insert(path, session);
+            }
+            Err(e) => {
+                log::warn!("Skipping session {path}: {e}");
+                index.files.remove(&path);
+            }
+        }
+        reindexed += 1;
+        changed = true;
+    }
+
+    if changed {
+        let bytes = serde_json::to_vec(&index)
+            .map_err(|e| format!("Failed to serialize session index: {e}"))?;
+        atomic_write(&index_path, &bytes)?;
+    }
+    Ok((index, reindexed))
+}
+
+// ============================================================================
+// Search
+// ============================================================================
+
+fn entry_time_ms(entry: &IndexedEntry, session: &IndexedSession) -> f64 {
+    DateTime::parse_from_rfc3339(&entry.timestamp)
+        .map(|t| t.timestamp_millis() as f64)
+        .unwrap_or(session.modified_at)
+}
+
+/// Build a snippet around the first occurrence of any query term.
+fn build_snippet(entry: &IndexedEntry, query_terms: &[String]) -> SearchSnippet {
+    let chars: Vec<char> = entry.text.chars().collect();
+    let folded: Vec<char> = chars.iter().map(|c| fold(*c)).collect();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for term in query_terms {
+        let term: Vec<char> = term.chars().collect();
+        let word_start_only = !term.first().copied().is_some_and(is_cjk);
+        for i in 0..folded.len() {
+            if folded[i..].starts_with(&term)
+                && (!word_start_only || i == 0 || !is_word_char(folded[i - 1]))
+            {
+                ranges.push((i, i + term.len()));
+            }
+        }
+    }
+    ranges.sort();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let window_start = merged
+        .first()
+        .map_or(0, |(start, _)| start.saturating_sub(SNIPPET_LEAD));
+    let window_end = (window_start + SNIPPET_CHARS).min(chars.len());
+    let text: String = chars[window_start..window_end]
+        .iter()
+        .map(|c| if c.is_whitespace() { ' ' } else { *c })
+        .collect();
+    // UTF-16 offset of every char boundary in the window
+    let utf16_offsets: Vec<u32> = std::iter::once(0)
+        .chain(chars[window_start..window_end].iter().scan(0, |offset, c| {
+            *offset += c.len_utf16() as u32;
+            Some(*offset)
+        }))
+        .collect();
+    let highlights = merged
+        .into_iter()
+        .filter(|(start, _)| *start < window_end)
+        .map(|(start, end)| HighlightRange {
+            start: utf16_offsets[start - window_start],
+            end: utf16_offsets[end.min(window_end) - window_start],
+        })
+        .collect();
+
+    SearchSnippet {
+        message_id: entry.message_id.clone(),
+        role: entry.role.clone(),
+        kind: entry.kind.clone(),
+        timestamp: entry.timestamp.clone(),
+        text,
+        highlights,
+    }
+}
+
+fn search_index(
+    index: &SessionIndex,
+    query: &str,
+    filters: &SessionSearchFilters,
+) -> Vec<SessionSearchHit> {
+    let mut query_terms = tokenize(query);
+    query_terms.sort();
+    query_terms.dedup();
+    if query_terms.is_empty() {
+        return Vec::new();
+    }
+    let model_filter = filters.model.as_ref().map(|m| m.to_lowercase());
+
+    let mut hits: Vec<SessionSearchHit> = Vec::new();
+    for (path, session) in &index.files {
+        if filters.source.is_some_and(|s| s != session.source)
+            || filters
+                .project
+                .as_ref()
+                .is_some_and(|p| *p != session.project)
+        {
+            continue;
+        }
+        if model_filter
+            .as_ref()
+            .is_some_and(|m| !session.model.to_lowercase().contains(m))
+        {
+            continue;
+        }
+
+        let mut match_count = 0u32;
+        let mut snippets = Vec::new();
+        for entry in &session.entries {
+            if filters.role.as_ref().is_some_and(|r| *r != entry.role) {
+                continue;
+            }
+            if filters.from.is_some() || filters.to.is_some() {
+                let at = entry_time_ms(entry, session);
+                if filters.from.is_some_and(|from| at < from)
+                    || filters.to.is_some_and(|to| at > to)
+                {
+                    continue;
+                }
+            }
+            if !query_terms.iter().all(|q| has_prefix(&entry.terms, q)) {
+                continue;
+            }
+            match_count += 1;
+            if snippets.len() < MAX_SNIPPETS_PER_SESSION {
+                snippets.push(build_snippet(entry, &query_terms));
+            }
+        }
+
+        if match_count > 0 {
+            hits.push(SessionSearchHit {
+                source: session.source,
+                id: session.id.clone(),
+                title: session.title.clone(),
+                project: session.project.clone(),
+                model: session.model.clone(),
+                modified_at: session.modified_at,
+                path: path.clone(),
+                match_count,
+                snippets,
+            });
+        }
+    }
+
+    hits.sort_by(|a, b| {
+        b.match_count.cmp(&a.match_count).then(
+            b.modified_at
+                .partial_cmp(&a.modified_at)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
+    });
+    hits.truncate(
+        filters
+            .limit
+            .map_or(DEFAULT_RESULT_LIMIT, |limit| limit as usize),
+    );
+    hits
+}
+
+/// Search session transcripts, refreshing the index first.
+pub fn search_sessions_for_home(
+    home_dir: &Path,
+    query: &str,
+    filters: &SessionSearchFilters,
+) -> Result<Vec<SessionSearchHit>, String> {
+    let (index, _) = refresh_index_for_home(home_dir)?;
+    Ok(search_index(&index, query, filters))
+}
+
+pub fn search_sessions(
+    query: &str,
+    filters: &SessionSearchFilters,
+) -> Result<Vec<SessionSearchHit>, String> {
+    search_sessions_for_home(&paths::get_home_dir()?, query, filters)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_session(home: &Path, project: &str, id: &str, model: &str, lines: &[Value]) {
+        let dir = home.join(".factory").join("sessions").join(project);
+        fs::create_dir_all(&dir).unwrap();
+        let body: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        fs::write(dir.join(format!("{id}.jsonl")), body.join("\n")).unwrap();
+        fs::write(
+            dir.join(format!("{id}.settings.json")),
+            serde_json::json!({ "model": model }).to_string(),
+        )
+        .unwrap();
+    }
+
+    fn message(id: &str, role: &str, timestamp: &str, content: Value) -> Value {
+        serde_json::json!({
+            "type": "message",
+            "id": id,
+            "timestamp": timestamp,
+            "message": { "role": role, "content": content }
+        })
+    }
+
+    fn sample_home() -> TempDir {
+        let home = TempDir::new().unwrap();
+        write_session(
+            home.path(),
+            "-work-api",
+            "s1",
+            "claude-sonnet-4",
+            &[
+                serde_json::json!({ "type": "session_start", "id": "s1", "sessionTitle": "Fix the Migration bug" }),
+                message(
+                    "m1",
+                    "user",
+                    "2026-01-10T10:00:00Z",
+                    serde_json::json!([{ "type": "text", "text": "The users migration fails on Postgres" }]),
+                ),
+                message(
+                    "m2",
+                    "assistant",
+                    "2026-01-10T10:01:00Z",
+                    serde_json::json!([
+                        { "type": "thinking", "thinking": "Probably the migration order" },
+                        { "type": "tool_use", "name": "Execute", "input": { "command": "cargo sqlx migrate run" } }
+                    ]),
+                ),
+                message(
+                    "m3",
+                    "user",
+                    "2026-01-10T10:02:00Z",
+                    serde_json::json!([{ "type": "tool_result", "content": [{ "type": "text", "text": "error: 数据库迁移失败" }] }]),
+                ),
+            ],
+        );
+        write_session(
+            home.path(),
+            "-work-web",
+            "s2",
+            "gpt-5",
+            &[
+                serde_json::json!({ "type": "session_start", "id": "s2", "sessionTitle": "Landing page" }),
+                message(
+                    "m1",
+                    "user",
+                    "2026-02-01T09:00:00Z",
+                    serde_json::json!([{ "type": "text", "text": "Tweak the hero copy" }]),
+                ),
+            ],
+        );
+        home
+    }
+
+    #[test]
+    fn test_tokenize_folds_case_and_splits_cjk() {
+        assert_eq!(
+            tokenize("Fix run_Migration, v2!"),
+            vec!["fix", "run", "migration", "v2"]
+        );
+        assert_eq!(tokenize("数据库 ok"), vec!["数", "据", "库", "ok"]);
+    }
+
+    #[test]
+    fn test_search_matches_text_thinking_tools_and_title() {
+        let home = sample_home();
+        let filters = SessionSearchFilters::default();
+
+        let hits = search_sessions_for_home(home.path(), "migrat", &filters).unwrap();
+        assert_eq!(hits.len(), 1);
+        let hit = &hits[0];
+        assert_eq!(hit.id, "s1");
+        assert_eq!(hit.model, "claude-sonnet-4");
+        assert!(hit.path.ends_with("s1"));
+        // title, user text, thinking, tool_use ("migrate")
+        assert_eq!(hit.match_count, 4);
+        assert_eq!(hit.snippets.len(), MAX_SNIPPETS_PER_SESSION);
+        let text_snippet = &hit.snippets[1];
+        assert_eq!(text_snippet.kind, "text");
+        assert_eq!(
+            text_snippet.highlights,
+            vec![HighlightRange { start: 10, end: 16 }]
+        );
+        assert_eq!(&text_snippet.text[10..16], "migrat");
+
+        let hits = search_sessions_for_home(home.path(), "sqlx migrate", &filters).unwrap();
+        assert_eq!(hits[0].snippets[0].kind, "tool_use");
+
+        let hits = search_sessions_for_home(home.path(), "迁移", &filters).unwrap();
+        assert_eq!(hits[0].snippets[0].kind, "tool_result");
+        assert_eq!(
+            hits[0].snippets[0].highlights,
+            vec![HighlightRange { start: 10, end: 12 }]
+        );
+    }
+
+    #[test]
+    fn test_snippet_highlights_use_utf16_offsets() {
+        let entry = IndexedEntry {
+            message_id: "m1".to_string(),
+            role: "user".to_string(),
+            kind: "text".to_string(),
+            timestamp: String::new(),
+            text: "🚀 迁移 migrate".to_string(),
+            terms: Vec::new(),
+        };
+        let snippet = build_snippet(&entry, &["migrat".to_string()]);
+        // The rocket is two UTF-16 units
+        assert_eq!(
+            snippet.highlights,
+            vec![HighlightRange { start: 6, end: 12 }]
+        );
+        let utf16: Vec<u16> = snippet.text.encode_utf16().collect();
+        assert_eq!(String::from_utf16(&utf16[6..12]).unwrap(), "migrat");
+    }
+
+    #[test]
+    fn test_search_filters() {
+        let home = sample_home();
+        let search = |filters: SessionSearchFilters| {
+            search_sessions_for_home(home.path(), "the", &filters).unwrap()
+        };
+
+        assert_eq!(search(SessionSearchFilters::default()).len(), 2);
+        let by_project = search(SessionSearchFilters {
+            project: Some("-work-web".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_project.len(), 1);
+        assert_eq!(by_project[0].id, "s2");
+
+        let by_model = search(SessionSearchFilters {
+            model: Some("SONNET".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_model.len(), 1);
+        assert_eq!(by_model[0].id, "s1");
+
+        let by_role = search(SessionSearchFilters {
+            role: Some("assistant".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_role.len(), 1);
+        assert_eq!(by_role[0].snippets[0].kind, "thinking");
+
+        let from = DateTime::parse_from_rfc3339("2026-01-15T00:00:00Z")
+            .unwrap()
+            .timestamp_millis() as f64;
+        let by_date = search(SessionSearchFilters {
+            from: Some(from),
+            ..Default::default()
+        });
+        assert_eq!(by_date.len(), 1);
+        assert_eq!(by_date[0].id, "s2");
+    }
+
+    #[test]
+    fn test_index_refreshes_only_changed_files() {
+        let home = sample_home();
+
+        let (index, reindexed) = refresh_index_for_home(home.path()).unwrap();
+        assert_eq!((index.files.len(), reindexed), (2, 2));
+        assert!(index_path_for_home(home.path()).exists());
+
+        let (_, reindexed) = refresh_index_for_home(home.path()).unwrap();
+        assert_eq!(reindexed, 0);
+
+        let dir = home.path().join(".factory/sessions/-work-web");
+        let mut content = fs::read_to_string(dir.join("s2.jsonl")).unwrap();
+        content.push('\n');
+        content.push_str(
+            &message(
+                "m2",
+                "assistant",
+                "2026-02-01T09:05:00Z",
+                serde_json::json!([{ "type": "text", "text": "Updated the headline" }]),
+            )
+            .to_string(),
+        );
+        fs::write(dir.join("s2.jsonl"), content).unwrap();
+        fs::remove_file(home.path().join(".factory/sessions/-work-api/s1.jsonl")).unwrap();
+
+        let (index, reindexed) = refresh_index_for_home(home.path()).unwrap();
+        assert_eq!((index.files.len(), reindexed), (1, 1));
+        let hits = search_index(&index, "headline", &SessionSearchFilters::default());
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn test_search_covers_other_agents() {
+        let home = sample_home();
+        let dir = home.path().join(".claude/projects/-work-api");
+        fs::create_dir_all(&dir).unwrap();
+        let line = serde_json::json!({
+            "type": "user", "uuid": "u1", "sessionId": "c1", "timestamp": "2026-01-12T10:00:00Z",
+            "message": { "role": "user", "content": "Squash the migration files" }
+        });
+        fs::write(dir.join("c1.jsonl"), line.to_string()).unwrap();
+
+        let hits =
+            search_sessions_for_home(home.path(), "migration", &SessionSearchFilters::default())
+                .unwrap();
+        assert_eq!(hits.len(), 2);
+
+        let claude_only = SessionSearchFilters {
+            source: Some(SessionSource::Claude),
+            ..Default::default()
+        };
+        let hits = search_sessions_for_home(home.path(), "migration", &claude_only).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "c1");
+        assert_eq!(hits[0].title, "Squash the migration files");
+        assert_eq!(hits[0].path, dir.join("c1").to_string_lossy());
+    }
+}
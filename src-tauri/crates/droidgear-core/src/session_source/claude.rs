@@ -0,0 +1,205 @@
+//! Claude Code sessions: `~/.claude/projects/<project>/<id>.jsonl`.
+//!
+//! Each line is a `user` or `assistant` record wrapping an Anthropic message,
+//! or a `summary` record naming the conversation. Assistant messages are
+//! streamed as several records sharing one message ID, so usage is counted
+//! once per ID.
+
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::{
+    anthropic_blocks, collect_session_paths, new_detail, parent_dir_name, read_lines,
+    summary_from_detail, title_from_text, SessionReader,
+};
+use crate::paths;
+use crate::sessions::{
+    modified_at_ms, SessionDetail, SessionMessage, SessionSource, SessionSummary,
+};
+
+pub struct ClaudeReader;
+
+/// Sub-agent sidechains (`agent-<id>.jsonl`) belong to their parent session.
+fn is_subagent(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .is_some_and(|name| name.starts_with("agent-"))
+}
+
+impl SessionReader for ClaudeReader {
+    fn source(&self) -> SessionSource {
+        SessionSource::Claude
+    }
+
+    fn root_dir(&self, home_dir: &Path) -> Result<PathBuf, String> {
+        let config_paths = paths::load_config_paths_for_home(home_dir);
+        Ok(paths::get_claude_home_for_home(home_dir, &config_paths)?.join("projects"))
+    }
+
+    fn session_paths(&self, home_dir: &Path) -> Vec<String> {
+        self.root_dir(home_dir)
+            .map(|root| collect_session_paths(&root, "jsonl", &|path| !is_subagent(path)))
+            .unwrap_or_default()
+    }
+
+    fn read_detail(&self, session_path: &str) -> Result<SessionDetail, String> {
+        self.read(session_path, true)
+    }
+
+    fn read_summary(&self, session_path: &str) -> Result<SessionSummary, String> {
+        self.read(session_path, false)
+            .map(|detail| summary_from_detail(detail, session_path.to_string()))
+    }
+}
+
+impl ClaudeReader {
+    /// Parses a transcript. Without `messages`, lines past the first prompt
+    /// are only parsed when they may hold usage or a summary.
+    fn read(&self, session_path: &str, messages: bool) -> Result<SessionDetail, String> {
+        let jsonl_path = self.transcript_path(session_path);
+        let mut detail = new_detail(SessionSource::Claude, session_path);
+        detail.project = parent_dir_name(&jsonl_path);
+        detail.modified_at = modified_at_ms(&jsonl_path);
+
+        let mut summary: Option<String> = None;
+        let mut first_prompt: Option<String> = None;
+        let mut counted: HashSet<String> = HashSet::new();
+
+        for line in read_lines(&jsonl_path)? {
+            if !messages
+                && first_prompt.is_some()
+                && !line.contains("\"usage\"")
+                && !line.contains("\"summary\"")
+            {
+                continue;
+            }
+            let Ok(json) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            let record_type = json["type"].as_str().unwrap_or("");
+            if record_type == "summary" {
+                summary = json["summary"].as_str().map(|s| s.to_string());
+                continue;
+            }
+            if !matches!(record_type, "user" | "assistant")
+                || json["isMeta"].as_bool() == Some(true)
+            {
+                continue;
+            }
+            if let Some(id) = json["sessionId"].as_str().filter(|id| !id.is_empty()) {
+                detail.id = id.to_string();
+            }
+            if detail.cwd.is_empty() {
+                detail.cwd = json["cwd"].as_str().unwrap_or("").to_string();
+            }
+
+            let message = &json["message"];
+            if let Some(model) = message["model"].as_str().filter(|m| *m != "<synthetic>") {
+                detail.model = model.to_string();
+            }
+            let usage = &message["usage"];
+            let message_id = message["id"].as_str().unwrap_or("");
+            if usage.is_object() && (message_id.is_empty() || counted.insert(message_id.into())) {
+                let tu = &mut detail.token_usage;
+                tu.input_tokens += usage["input_tokens"].as_f64().unwrap_or(0.0);
+                tu.output_tokens += usage["output_tokens"].as_f64().unwrap_or(0.0);
+                tu.cache_creation_tokens +=
+                    usage["cache_creation_input_tokens"].as_f64().unwrap_or(0.0);
+                tu.cache_read_tokens += usage["cache_read_input_tokens"].as_f64().unwrap_or(0.0);
+            }
+
+            if !messages && (record_type != "user" || first_prompt.is_some()) {
+                continue;
+            }
+            let content = anthropic_blocks(&message["content"]);
+            if record_type == "user" && first_prompt.is_none() {
+                first_prompt = content
+                    .iter()
+                    .filter(|b| b.content_type == "text")
+                    .find_map(|b| b.text.as_deref().and_then(title_from_text));
+            }
+            if messages {
+                detail.messages.push(SessionMessage {
+                    id: json["uuid"].as_str().unwrap_or("").to_string(),
+                    role: message["role"].as_str().unwrap_or(record_type).to_string(),
+                    content,
+                    timestamp: json["timestamp"].as_str().unwrap_or("").to_string(),
+                });
+            }
+        }
+
+        if let Some(title) = summary.or(first_prompt) {
+            detail.title = title;
+        }
+        Ok(detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_source::finalize_detail;
+    use crate::sessions::SessionDetailOptions;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reads_claude_transcript() {
+        let home = TempDir::new().unwrap();
+        let dir = home.path().join(".claude/projects/-work-api");
+        fs::create_dir_all(&dir).unwrap();
+        let assistant_usage = serde_json::json!({
+            "input_tokens": 10, "output_tokens": 5,
+            "cache_creation_input_tokens": 100, "cache_read_input_tokens": 200
+        });
+        let lines = [
+            serde_json::json!({ "type": "user", "isMeta": true, "sessionId": "c1",
+                "message": { "role": "user", "content": "<local-command-caveat>" } }),
+            serde_json::json!({ "type": "user", "uuid": "u1", "sessionId": "c1", "cwd": "/work/api",
+                "timestamp": "2026-03-01T10:00:00Z",
+                "message": { "role": "user", "content": "Add a health check\nwith details" } }),
+            serde_json::json!({ "type": "assistant", "uuid": "a1", "timestamp": "2026-03-01T10:00:02Z",
+                "message": { "id": "msg_1", "role": "assistant", "model": "claude-sonnet-4-5",
+                    "content": [{ "type": "thinking", "thinking": "Look at routes" }],
+                    "usage": assistant_usage } }),
+            serde_json::json!({ "type": "assistant", "uuid": "a2", "timestamp": "2026-03-01T10:00:03Z",
+                "message": { "id": "msg_1", "role": "assistant", "model": "claude-sonnet-4-5",
+                    "content": [{ "type": "tool_use", "id": "toolu_1", "name": "Read", "input": { "file_path": "src/routes.rs" } }],
+                    "usage": assistant_usage } }),
+            serde_json::json!({ "type": "user", "uuid": "u2", "timestamp": "2026-03-01T10:00:04Z",
+                "message": { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": "toolu_1", "content": "fn routes() {}" }
+                ] } }),
+        ];
+        let body: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        fs::write(dir.join("c1.jsonl"), body.join("\n")).unwrap();
+
+        let paths = ClaudeReader.session_paths(home.path());
+        assert_eq!(paths.len(), 1);
+        let detail = ClaudeReader.read_detail(&paths[0]).unwrap();
+
+        assert_eq!(detail.source, SessionSource::Claude);
+        assert_eq!(detail.id, "c1");
+        assert_eq!(detail.title, "Add a health check");
+        assert_eq!(detail.project, "-work-api");
+        assert_eq!(detail.cwd, "/work/api");
+        assert_eq!(detail.model, "claude-sonnet-4-5");
+        assert_eq!(detail.token_usage.input_tokens, 10.0);
+        assert_eq!(detail.token_usage.cache_read_tokens, 200.0);
+        assert_eq!(detail.messages.len(), 4);
+
+        let summary = ClaudeReader.read_summary(&paths[0]).unwrap();
+        assert_eq!(summary.title, "Add a health check");
+        assert_eq!(summary.model, "claude-sonnet-4-5");
+        assert_eq!(summary.token_usage.input_tokens, 10.0);
+
+        let options = SessionDetailOptions {
+            include_tools: true,
+        };
+        let detail = finalize_detail(detail, &options);
+        let result = &detail.messages[3].content[0];
+        assert_eq!(result.tool_name.as_deref(), Some("Read"));
+        assert_eq!(result.tool_result.as_deref(), Some("fn routes() {}"));
+    }
+}
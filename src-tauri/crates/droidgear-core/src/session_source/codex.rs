@@ -0,0 +1,319 @@
+//! Codex sessions: `~/.codex/sessions/YYYY/MM/DD/rollout-<time>-<id>.jsonl`.
+//!
+//! A rollout starts with `session_meta` (ID, cwd) and records every model
+//! item as `response_item`: messages, reasoning summaries, function / custom
+//! tool calls and their outputs. `turn_context` carries the model, and
+//! `event_msg` `token_count` events carry running token totals.
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::{
+    collect_session_paths, new_detail, project_key, read_lines, summary_from_detail, text_block,
+    title_from_text, tool_result_block, tool_use_block, SessionReader,
+};
+use crate::paths;
+use crate::sessions::{
+    modified_at_ms, ContentBlock, SessionDetail, SessionMessage, SessionSource, SessionSummary,
+    TokenUsage,
+};
+
+pub struct CodexReader;
+
+/// User messages Codex injects on its own (not typed by the user)
+const INJECTED_PREFIXES: &[&str] = &["<environment_context>", "<user_instructions>"];
+
+fn is_rollout(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .is_some_and(|name| name.starts_with("rollout-"))
+}
+
+/// Function call arguments are a JSON string; keep the raw text when it is not.
+fn parse_arguments(arguments: &Value) -> Value {
+    arguments
+        .as_str()
+        .and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_else(|| arguments.clone())
+}
+
+/// Output text and error flag of a call output. Shell calls wrap the output
+/// as `{"output": ..., "metadata": {"exit_code": ...}}`.
+fn call_output(output: &Value) -> (String, bool) {
+    let raw = match output {
+        Value::String(s) => s.clone(),
+        Value::Object(_) => output["content"].as_str().unwrap_or("").to_string(),
+        _ => String::new(),
+    };
+    match serde_json::from_str::<Value>(&raw) {
+        Ok(wrapped) if wrapped["output"].is_string() => {
+            let exit_code = wrapped["metadata"]["exit_code"].as_i64().unwrap_or(0);
+            (
+                wrapped["output"].as_str().unwrap_or("").to_string(),
+                exit_code != 0,
+            )
+        }
+        _ => (raw, output["success"].as_bool() == Some(false)),
+    }
+}
+
+/// Codex counts cached input within `input_tokens` and reasoning within
+/// `output_tokens`; split them out so no token is counted twice.
+fn token_usage(total: &Value) -> TokenUsage {
+    let input = total["input_tokens"].as_f64().unwrap_or(0.0);
+    let cached = total["cached_input_tokens"].as_f64().unwrap_or(0.0);
+    let output = total["output_tokens"].as_f64().unwrap_or(0.0);
+    let reasoning = total["reasoning_output_tokens"].as_f64().unwrap_or(0.0);
+    TokenUsage {
+        input_tokens: (input - cached).max(0.0),
+        output_tokens: (output - reasoning).max(0.0),
+        cache_creation_tokens: 0.0,
+        cache_read_tokens: cached,
+        thinking_tokens: reasoning,
+    }
+}
+
+/// Content of a `response_item`, with its role (`None` when the item is not
+/// shown).
+fn item_content(item: &Value) -> Option<(String, Vec<ContentBlock>)> {
+    match item["type"].as_str().unwrap_or("") {
+        "message" => {
+            let message_role = item["role"].as_str().unwrap_or("");
+            if !matches!(message_role, "user" | "assistant") {
+                return None;
+            }
+            let blocks: Vec<ContentBlock> = item["content"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|c| c["text"].as_str())
+                .filter(|text| {
+                    !INJECTED_PREFIXES
+                        .iter()
+                        .any(|prefix| text.trim_start().starts_with(prefix))
+                })
+                .map(|text| text_block("text", text))
+                .collect();
+            Some((message_role.to_string(), blocks))
+        }
+        "reasoning" => {
+            let blocks = item["summary"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|s| s["text"].as_str())
+                .map(|text| text_block("thinking", text))
+                .collect();
+            Some(("assistant".to_string(), blocks))
+        }
+        "function_call" => Some((
+            "assistant".to_string(),
+            vec![tool_use_block(
+                item["call_id"].as_str().unwrap_or(""),
+                item["name"].as_str().unwrap_or(""),
+                parse_arguments(&item["arguments"]),
+            )],
+        )),
+        "custom_tool_call" => Some((
+            "assistant".to_string(),
+            vec![tool_use_block(
+                item["call_id"].as_str().unwrap_or(""),
+                item["name"].as_str().unwrap_or(""),
+                item["input"].clone(),
+            )],
+        )),
+        "function_call_output" | "custom_tool_call_output" => {
+            let (output, is_error) = call_output(&item["output"]);
+            Some((
+                "user".to_string(),
+                vec![tool_result_block(
+                    item["call_id"].as_str().unwrap_or(""),
+                    None,
+                    output,
+                    is_error,
+                )],
+            ))
+        }
+        _ => None,
+    }
+}
+
+impl SessionReader for CodexReader {
+    fn source(&self) -> SessionSource {
+        SessionSource::Codex
+    }
+
+    fn root_dir(&self, home_dir: &Path) -> Result<PathBuf, String> {
+        let config_paths = paths::load_config_paths_for_home(home_dir);
+        Ok(paths::get_codex_home_for_home(home_dir, &config_paths)?.join("sessions"))
+    }
+
+    fn session_paths(&self, home_dir: &Path) -> Vec<String> {
+        self.root_dir(home_dir)
+            .map(|root| collect_session_paths(&root, "jsonl", &is_rollout))
+            .unwrap_or_default()
+    }
+
+    fn read_detail(&self, session_path: &str) -> Result<SessionDetail, String> {
+        self.read(session_path, true)
+    }
+
+    fn read_summary(&self, session_path: &str) -> Result<SessionSummary, String> {
+        self.read(session_path, false)
+            .map(|detail| summary_from_detail(detail, session_path.to_string()))
+    }
+}
+
+impl CodexReader {
+    /// Parses a rollout. Without `messages`, lines past the first prompt are
+    /// only parsed when they may carry the model or token totals.
+    fn read(&self, session_path: &str, messages: bool) -> Result<SessionDetail, String> {
+        let jsonl_path = self.transcript_path(session_path);
+        let mut detail = new_detail(SessionSource::Codex, session_path);
+        detail.modified_at = modified_at_ms(&jsonl_path);
+        let mut title: Option<String> = None;
+
+        for (index, line) in read_lines(&jsonl_path)?.enumerate() {
+            if !messages
+                && title.is_some()
+                && !line.contains("\"turn_context\"")
+                && !line.contains("\"token_count\"")
+            {
+                continue;
+            }
+            let Ok(json) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            let payload = &json["payload"];
+            match json["type"].as_str().unwrap_or("") {
+                "session_meta" => {
+                    if let Some(id) = payload["id"].as_str().filter(|id| !id.is_empty()) {
+                        detail.id = id.to_string();
+                    }
+                    detail.cwd = payload["cwd"].as_str().unwrap_or("").to_string();
+                }
+                "turn_context" => {
+                    if let Some(model) = payload["model"].as_str() {
+                        detail.model = model.to_string();
+                    }
+                }
+                "event_msg" if payload["type"] == "token_count" => {
+                    let total = &payload["info"]["total_token_usage"];
+                    if total.is_object() {
+                        detail.token_usage = token_usage(total);
+                    }
+                }
+                "response_item" => {
+                    let Some((role, content)) = item_content(payload) else {
+                        continue;
+                    };
+                    if role == "user" && title.is_none() {
+                        title = content
+                            .iter()
+                            .find_map(|b| b.text.as_deref().and_then(title_from_text));
+                    }
+                    if messages {
+                        detail.messages.push(SessionMessage {
+                            id: payload["id"]
+                                .as_str()
+                                .map_or_else(|| index.to_string(), |id| id.to_string()),
+                            role,
+                            content,
+                            timestamp: json["timestamp"].as_str().unwrap_or("").to_string(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        detail.project = project_key(&detail.cwd);
+        if let Some(title) = title {
+            detail.title = title;
+        }
+        Ok(detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_source::finalize_detail;
+    use crate::sessions::SessionDetailOptions;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reads_codex_rollout() {
+        let home = TempDir::new().unwrap();
+        let dir = home.path().join(".codex/sessions/2026/03/02");
+        fs::create_dir_all(&dir).unwrap();
+        let item = |payload: Value| serde_json::json!({ "timestamp": "2026-03-02T08:00:00Z", "type": "response_item", "payload": payload });
+        let lines = [
+            serde_json::json!({ "type": "session_meta", "payload": { "id": "x1", "cwd": "/work/cli" } }),
+            serde_json::json!({ "type": "turn_context", "payload": { "cwd": "/work/cli", "model": "gpt-5-codex" } }),
+            item(serde_json::json!({ "type": "message", "role": "developer",
+                "content": [{ "type": "input_text", "text": "sandbox rules" }] })),
+            item(serde_json::json!({ "type": "message", "role": "user",
+                "content": [{ "type": "input_text", "text": "<environment_context>cwd</environment_context>" }] })),
+            item(serde_json::json!({ "type": "message", "role": "user",
+                "content": [{ "type": "input_text", "text": "List the crates" }] })),
+            item(serde_json::json!({ "type": "reasoning",
+                "summary": [{ "type": "summary_text", "text": "Check the workspace" }] })),
+            item(
+                serde_json::json!({ "type": "function_call", "name": "shell", "call_id": "call_1",
+                "arguments": "{\"command\":[\"ls\",\"crates\"]}" }),
+            ),
+            item(
+                serde_json::json!({ "type": "function_call_output", "call_id": "call_1",
+                "output": "{\"output\":\"ls: crates: No such file\",\"metadata\":{\"exit_code\":1}}" }),
+            ),
+            serde_json::json!({ "type": "event_msg", "payload": { "type": "token_count", "info": {
+                "total_token_usage": { "input_tokens": 1000, "cached_input_tokens": 600,
+                    "output_tokens": 50, "reasoning_output_tokens": 20 } } } }),
+        ];
+        let body: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        fs::write(
+            dir.join("rollout-2026-03-02T08-00-00-x1.jsonl"),
+            body.join("\n"),
+        )
+        .unwrap();
+        fs::write(dir.join("notes.jsonl"), "").unwrap();
+
+        let paths = CodexReader.session_paths(home.path());
+        assert_eq!(paths.len(), 1);
+        let detail = CodexReader.read_detail(&paths[0]).unwrap();
+
+        assert_eq!(detail.source, SessionSource::Codex);
+        assert_eq!(detail.id, "x1");
+        assert_eq!(detail.title, "List the crates");
+        assert_eq!(detail.project, "-work-cli");
+        assert_eq!(detail.model, "gpt-5-codex");
+        assert_eq!(detail.token_usage.input_tokens, 400.0);
+        assert_eq!(detail.token_usage.cache_read_tokens, 600.0);
+        assert_eq!(detail.token_usage.output_tokens, 30.0);
+        assert_eq!(detail.token_usage.thinking_tokens, 20.0);
+
+        let summary = CodexReader.read_summary(&paths[0]).unwrap();
+        assert_eq!(summary.title, "List the crates");
+        assert_eq!(summary.model, "gpt-5-codex");
+        assert_eq!(summary.token_usage.input_tokens, 400.0);
+
+        let options = SessionDetailOptions {
+            include_tools: true,
+        };
+        let detail = finalize_detail(detail, &options);
+        // user prompt, reasoning, call, output (injected context dropped)
+        assert_eq!(detail.messages.len(), 4);
+        assert_eq!(detail.messages[1].content[0].content_type, "thinking");
+        let call = &detail.messages[2].content[0];
+        assert_eq!(call.tool_input.as_ref().unwrap()["command"][0], "ls");
+        let output = &detail.messages[3].content[0];
+        assert_eq!(output.tool_name.as_deref(), Some("shell"));
+        assert_eq!(
+            output.tool_result.as_deref(),
+            Some("ls: crates: No such file")
+        );
+        assert_eq!(output.is_error, Some(true));
+    }
+}
@@ -0,0 +1,215 @@
+//! Factory (Droid) sessions: `~/.factory/sessions/<project>/<id>.jsonl`, with
+//! model and token usage in a sibling `<id>.settings.json`.
+
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use super::{anthropic_blocks, new_detail, parent_dir_name, read_jsonl, SessionReader};
+use crate::sessions::{
+    modified_at_ms, read_session_settings, sessions_dir_for_home, SessionDetail, SessionMessage,
+    SessionProject, SessionSource, SessionSummary,
+};
+
+pub struct FactoryReader;
+
+fn jsonl_files(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("jsonl"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn project_dirs(sessions_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    Ok(fs::read_dir(sessions_dir)
+        .map_err(|e| format!("Failed to read sessions directory: {e}"))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect())
+}
+
+fn session_title(json: &Value) -> String {
+    json["sessionTitle"]
+        .as_str()
+        .or_else(|| json["title"].as_str())
+        .unwrap_or("Untitled")
+        .to_string()
+}
+
+impl SessionReader for FactoryReader {
+    fn source(&self) -> SessionSource {
+        SessionSource::Factory
+    }
+
+    fn root_dir(&self, home_dir: &Path) -> Result<PathBuf, String> {
+        sessions_dir_for_home(home_dir)
+    }
+
+    fn session_paths(&self, home_dir: &Path) -> Vec<String> {
+        let Ok(sessions_dir) = self.root_dir(home_dir) else {
+            return Vec::new();
+        };
+        project_dirs(&sessions_dir)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|dir| jsonl_files(dir))
+            .map(|path| path.with_extension("").to_string_lossy().to_string())
+            .collect()
+    }
+
+    fn read_detail(&self, session_path: &str) -> Result<SessionDetail, String> {
+        let jsonl_path = self.transcript_path(session_path);
+        let settings_path = PathBuf::from(format!("{session_path}.settings.json"));
+
+        let mut detail = new_detail(SessionSource::Factory, session_path);
+        detail.project = parent_dir_name(&jsonl_path);
+        (detail.model, detail.token_usage) = read_session_settings(&settings_path);
+        detail.modified_at = modified_at_ms(&jsonl_path);
+
+        for json in read_jsonl(&jsonl_path)? {
+            match json["type"].as_str().unwrap_or("") {
+                "session_start" => {
+                    if let Some(id) = json["id"].as_str().filter(|id| !id.is_empty()) {
+                        detail.id = id.to_string();
+                    }
+                    detail.title = session_title(&json);
+                    detail.cwd = json["cwd"].as_str().unwrap_or("").to_string();
+                }
+                "message" => detail.messages.push(SessionMessage {
+                    id: json["id"].as_str().unwrap_or("").to_string(),
+                    role: json["message"]["role"].as_str().unwrap_or("").to_string(),
+                    content: anthropic_blocks(&json["message"]["content"]),
+                    timestamp: json["timestamp"].as_str().unwrap_or("").to_string(),
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(detail)
+    }
+
+    /// Reads only the first transcript line (for the title) and the settings
+    /// file, so listing stays cheap for long sessions.
+    fn list_sessions(
+        &self,
+        home_dir: &Path,
+        project: Option<&str>,
+    ) -> Result<Vec<SessionSummary>, String> {
+        let sessions_dir = self.root_dir(home_dir)?;
+
+        if !sessions_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let project_dirs = match project {
+            Some(proj) => vec![sessions_dir.join(proj)],
+            None => project_dirs(&sessions_dir)?,
+        };
+
+        let mut sessions: Vec<SessionSummary> = Vec::new();
+        for project_dir in project_dirs {
+            if !project_dir.is_dir() {
+                continue;
+            }
+
+            let project_name = project_dir
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("")
+                .to_string();
+
+            for path in jsonl_files(&project_dir) {
+                let session_id = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("")
+                    .to_string();
+
+                let settings_path = project_dir.join(format!("{session_id}.settings.json"));
+                let (model, token_usage) = read_session_settings(&settings_path);
+
+                // Read first line of jsonl for session title
+                let title = fs::File::open(&path)
+                    .ok()
+                    .and_then(|file| BufReader::new(file).lines().next())
+                    .and_then(Result::ok)
+                    .map(|line| session_title(&serde_json::from_str(&line).unwrap_or_default()))
+                    .unwrap_or_else(|| "Untitled".to_string());
+
+                sessions.push(SessionSummary {
+                    source: SessionSource::Factory,
+                    id: session_id,
+                    title,
+                    project: project_name.clone(),
+                    model,
+                    modified_at: modified_at_ms(&path),
+                    token_usage,
+                    path: path.with_extension("").to_string_lossy().to_string(),
+                });
+            }
+        }
+
+        sessions.sort_by(|a, b| {
+            b.modified_at
+                .partial_cmp(&a.modified_at)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Ok(sessions)
+    }
+
+    fn list_projects(&self, home_dir: &Path) -> Result<Vec<SessionProject>, String> {
+        let sessions_dir = self.root_dir(home_dir)?;
+
+        if !sessions_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut projects: Vec<SessionProject> = Vec::new();
+        for path in project_dirs(&sessions_dir)? {
+            let session_count = jsonl_files(&path).len() as u32;
+            if session_count == 0 {
+                continue;
+            }
+
+            projects.push(SessionProject {
+                source: SessionSource::Factory,
+                name: path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("")
+                    .to_string(),
+                path: path.to_string_lossy().to_string(),
+                session_count,
+                modified_at: modified_at_ms(&path),
+            });
+        }
+
+        projects.sort_by(|a, b| {
+            b.modified_at
+                .partial_cmp(&a.modified_at)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Ok(projects)
+    }
+
+    fn delete(&self, session_path: &str) -> Result<(), String> {
+        let settings_path = PathBuf::from(format!("{session_path}.settings.json"));
+
+        fs::remove_file(self.transcript_path(session_path))
+            .map_err(|e| format!("Failed to delete session: {e}"))?;
+        if settings_path.exists() {
+            let _ = fs::remove_file(&settings_path);
+        }
+
+        Ok(())
+    }
+}
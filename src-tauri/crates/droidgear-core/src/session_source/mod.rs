@@ -0,0 +1,382 @@
+//! Session sources (core).
+//!
+//! Each agent whose history DroidGear can browse (Droid, Claude Code, Codex,
+//! OpenCode, Pi) implements [`SessionReader`] in its own module: where its
+//! transcripts live, how to enumerate them and how to parse one into a
+//! [`SessionDetail`]. The functions in `sessions` and `session_search`
+//! dispatch through [`readers`] and [`reader_for_path`], so supporting a new
+//! agent means adding a `SessionSource` variant, a module here and one entry
+//! in [`READERS`].
+//!
+//! A session is addressed by its path without the transcript extension, like
+//! Factory's `<project>/<id>` (see [`SessionReader::transcript_path`]).
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::sessions::{
+    tool_result_text, ContentBlock, SessionDetail, SessionDetailOptions, SessionMessage,
+    SessionProject, SessionSource, SessionSummary, TokenUsage,
+};
+
+mod claude;
+mod codex;
+mod factory;
+mod opencode;
+mod pi;
+
+pub use claude::ClaudeReader;
+pub use codex::CodexReader;
+pub use factory::FactoryReader;
+pub use opencode::OpencodeReader;
+pub use pi::PiReader;
+
+/// Registered readers, in the order their sources are offered to the user.
+static READERS: &[&dyn SessionReader] = &[
+    &FactoryReader,
+    &ClaudeReader,
+    &CodexReader,
+    &OpencodeReader,
+    &PiReader,
+];
+
+// ============================================================================
+// Trait
+// ============================================================================
+
+/// An agent whose session history can be listed and read.
+pub trait SessionReader: Send + Sync {
+    fn source(&self) -> SessionSource;
+
+    /// Directory holding the agent's transcripts
+    fn root_dir(&self, home_dir: &Path) -> Result<PathBuf, String>;
+
+    /// Transcript file of a session path
+    fn transcript_path(&self, session_path: &str) -> PathBuf {
+        PathBuf::from(format!("{session_path}.jsonl"))
+    }
+
+    /// Paths (without extension) of every session under the root
+    fn session_paths(&self, home_dir: &Path) -> Vec<String>;
+
+    /// Parses a whole transcript, tool blocks included. Callers shape the
+    /// result with [`finalize_detail`].
+    fn read_detail(&self, session_path: &str) -> Result<SessionDetail, String>;
+
+    /// Title, model and token usage of a session. The default parses the
+    /// whole transcript; readers override it with a pass that skips message
+    /// content.
+    fn read_summary(&self, session_path: &str) -> Result<SessionSummary, String> {
+        self.read_detail(session_path)
+            .map(|detail| summary_from_detail(detail, session_path.to_string()))
+    }
+
+    /// Lists sessions, optionally only those of `project`.
+    fn list_sessions(
+        &self,
+        home_dir: &Path,
+        project: Option<&str>,
+    ) -> Result<Vec<SessionSummary>, String> {
+        let mut sessions = Vec::new();
+        for path in self.session_paths(home_dir) {
+            match self.read_summary(&path) {
+                Ok(summary) if project.is_none_or(|p| p == summary.project) => {
+                    sessions.push(summary);
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Skipping session {path}: {e}"),
+            }
+        }
+        Ok(sessions)
+    }
+
+    /// Groups the sessions by project.
+    fn list_projects(&self, home_dir: &Path) -> Result<Vec<SessionProject>, String> {
+        let root = self.root_dir(home_dir)?;
+        let mut projects: BTreeMap<String, SessionProject> = BTreeMap::new();
+        for session in self.list_sessions(home_dir, None)? {
+            let project = projects.entry(session.project.clone()).or_insert_with(|| {
+                let dir = root.join(&session.project);
+                SessionProject {
+                    source: self.source(),
+                    name: session.project.clone(),
+                    path: if dir.is_dir() {
+                        dir.to_string_lossy().to_string()
+                    } else {
+                        String::new()
+                    },
+                    session_count: 0,
+                    modified_at: 0.0,
+                }
+            });
+            project.session_count += 1;
+            project.modified_at = project.modified_at.max(session.modified_at);
+        }
+        Ok(projects.into_values().collect())
+    }
+
+    /// Deletes the session's files.
+    fn delete(&self, session_path: &str) -> Result<(), String> {
+        fs::remove_file(self.transcript_path(session_path))
+            .map_err(|e| format!("Failed to delete session: {e}"))
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// All registered readers.
+pub fn readers() -> &'static [&'static dyn SessionReader] {
+    READERS
+}
+
+pub fn reader_for(source: SessionSource) -> &'static dyn SessionReader {
+    READERS
+        .iter()
+        .copied()
+        .find(|r| r.source() == source)
+        .unwrap_or(&FactoryReader)
+}
+
+/// Reader whose root contains `session_path` (Factory when none does).
+pub fn reader_for_path(home_dir: &Path, session_path: &str) -> &'static dyn SessionReader {
+    let path = Path::new(session_path);
+    READERS
+        .iter()
+        .copied()
+        .find(|r| {
+            r.root_dir(home_dir)
+                .is_ok_and(|root| path.starts_with(root))
+        })
+        .unwrap_or(&FactoryReader)
+}
+
+// ============================================================================
+// Shared helpers
+// ============================================================================
+
+fn summary_from_detail(detail: SessionDetail, path: String) -> SessionSummary {
+    SessionSummary {
+        source: detail.source,
+        id: detail.id,
+        title: detail.title,
+        project: detail.project,
+        model: detail.model,
+        modified_at: detail.modified_at,
+        token_usage: detail.token_usage,
+        path,
+    }
+}
+
+/// Empty detail for `session_path`, with the file stem as ID.
+fn new_detail(source: SessionSource, session_path: &str) -> SessionDetail {
+    SessionDetail {
+        source,
+        id: Path::new(session_path)
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string(),
+        title: "Untitled".to_string(),
+        project: String::new(),
+        model: "unknown".to_string(),
+        cwd: String::new(),
+        modified_at: 0.0,
+        token_usage: TokenUsage::default(),
+        messages: Vec::new(),
+    }
+}
+
+/// Project key for a working directory, in Factory's directory-name form
+/// (`/Users/me/api` -> `-Users-me-api`).
+pub(crate) fn project_key(cwd: &str) -> String {
+    if cwd.is_empty() {
+        return "unknown".to_string();
+    }
+    cwd.chars()
+        .map(|c| {
+            if matches!(c, '/' | '\\' | ':') {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Name of the directory containing `path`.
+fn parent_dir_name(path: &Path) -> String {
+    path.parent()
+        .and_then(|p| p.file_name())
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Title from the first line of a prompt.
+fn title_from_text(text: &str) -> Option<String> {
+    const MAX_TITLE_CHARS: usize = 80;
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty())?;
+    Some(line.chars().take(MAX_TITLE_CHARS).collect())
+}
+
+fn empty_block(content_type: &str) -> ContentBlock {
+    ContentBlock {
+        content_type: content_type.to_string(),
+        text: None,
+        thinking: None,
+        tool_use_id: None,
+        tool_name: None,
+        tool_input: None,
+        tool_result: None,
+        is_error: None,
+    }
+}
+
+/// A `text` block, or a `thinking` block holding `text`.
+fn text_block(content_type: &str, text: &str) -> ContentBlock {
+    if content_type == "thinking" {
+        ContentBlock {
+            thinking: Some(text.to_string()),
+            ..empty_block(content_type)
+        }
+    } else {
+        ContentBlock {
+            text: Some(text.to_string()),
+            ..empty_block(content_type)
+        }
+    }
+}
+
+fn tool_use_block(id: &str, name: &str, input: Value) -> ContentBlock {
+    ContentBlock {
+        tool_use_id: Some(id.to_string()),
+        tool_name: Some(name.to_string()),
+        tool_input: Some(input),
+        ..empty_block("tool_use")
+    }
+}
+
+fn tool_result_block(id: &str, name: Option<&str>, result: String, is_error: bool) -> ContentBlock {
+    ContentBlock {
+        tool_use_id: Some(id.to_string()),
+        tool_name: name.map(str::to_string),
+        tool_result: Some(result),
+        is_error: Some(is_error),
+        ..empty_block("tool_result")
+    }
+}
+
+/// Content blocks of an Anthropic-style message (`content` is a string or a
+/// list of text / thinking / tool_use / tool_result blocks).
+fn anthropic_blocks(content: &Value) -> Vec<ContentBlock> {
+    if let Some(text) = content.as_str() {
+        return vec![text_block("text", text)];
+    }
+    let mut blocks = Vec::new();
+    for item in content.as_array().into_iter().flatten() {
+        let block = match item["type"].as_str().unwrap_or("text") {
+            "thinking" => text_block("thinking", item["thinking"].as_str().unwrap_or("")),
+            "tool_use" => tool_use_block(
+                item["id"].as_str().unwrap_or(""),
+                item["name"].as_str().unwrap_or(""),
+                item["input"].clone(),
+            ),
+            "tool_result" => tool_result_block(
+                item["tool_use_id"].as_str().unwrap_or(""),
+                None,
+                tool_result_text(&item["content"]),
+                item["is_error"].as_bool().unwrap_or(false),
+            ),
+            other => ContentBlock {
+                text: item["text"].as_str().map(|s| s.to_string()),
+                ..empty_block(other)
+            },
+        };
+        blocks.push(block);
+    }
+    blocks
+}
+
+/// Raw lines of a transcript, for passes that skip lines without parsing them.
+fn read_lines(path: &Path) -> Result<impl Iterator<Item = String>, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open session file: {e}"))?;
+    Ok(BufReader::new(file).lines().map_while(Result::ok))
+}
+
+/// Parsed JSON lines of a transcript (unreadable lines are skipped).
+fn read_jsonl(path: &Path) -> Result<impl Iterator<Item = Value>, String> {
+    Ok(read_lines(path)?.filter_map(|line| serde_json::from_str::<Value>(&line).ok()))
+}
+
+/// Files under `dir` (recursively) accepted by `keep`, as session paths
+/// without `extension`.
+fn collect_session_paths(dir: &Path, extension: &str, keep: &dyn Fn(&Path) -> bool) -> Vec<String> {
+    fn walk(dir: &Path, extension: &str, keep: &dyn Fn(&Path) -> bool, out: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(&path, extension, keep, out);
+            } else if path.extension().and_then(|s| s.to_str()) == Some(extension) && keep(&path) {
+                out.push(path.with_extension("").to_string_lossy().to_string());
+            }
+        }
+    }
+    let mut out = Vec::new();
+    walk(dir, extension, keep, &mut out);
+    out
+}
+
+/// RFC 3339 timestamp for epoch milliseconds.
+fn timestamp_from_ms(ms: f64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(ms as i64)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Links tool results to their calls' names, drops tool blocks unless
+/// requested and removes messages left without content.
+pub fn finalize_detail(mut detail: SessionDetail, options: &SessionDetailOptions) -> SessionDetail {
+    let mut tool_names: HashMap<String, String> = HashMap::new();
+    for block in detail.messages.iter().flat_map(|m| &m.content) {
+        if let (Some(id), Some(name)) = (&block.tool_use_id, &block.tool_name) {
+            tool_names.entry(id.clone()).or_insert_with(|| name.clone());
+        }
+    }
+    for message in &mut detail.messages {
+        message.content.retain_mut(|block| {
+            let Some(id) = &block.tool_use_id else {
+                return true;
+            };
+            if block.tool_name.is_none() {
+                block.tool_name = tool_names.get(id).cloned();
+            }
+            // Tool blocks are skipped for cleaner display unless requested
+            options.include_tools
+        });
+    }
+    detail
+        .messages
+        .retain(|m: &SessionMessage| !m.content.is_empty());
+    detail
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_key_matches_factory_directory_names() {
+        assert_eq!(project_key("/Users/me/api"), "-Users-me-api");
+        assert_eq!(project_key("C:\\work\\web"), "C--work-web");
+        assert_eq!(project_key(""), "unknown");
+    }
+}
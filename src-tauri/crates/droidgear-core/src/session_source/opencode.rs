@@ -0,0 +1,274 @@
+//! OpenCode sessions, stored as one JSON file per record under
+//! `~/.local/share/opencode/storage`:
+//!
+//! - `session/<project>/<session>.json`: title, directory and timestamps
+//! - `message/<session>/<message>.json`: role, model and token counts
+//! - `part/<message>/<part>.json`: text, reasoning and tool parts
+//!
+//! A tool part holds both the call and its result, so it becomes a
+//! `tool_use` block followed by a `tool_result` block.
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{
+    collect_session_paths, new_detail, project_key, summary_from_detail, text_block,
+    timestamp_from_ms, tool_result_block, tool_use_block, SessionReader,
+};
+use crate::paths;
+use crate::sessions::{ContentBlock, SessionDetail, SessionMessage, SessionSource, SessionSummary};
+
+pub struct OpencodeReader;
+
+fn read_json(path: &Path) -> Option<Value> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// JSON records in `dir`, ordered by `sort_key` (given the record and its
+/// file stem).
+fn read_records<K: PartialOrd>(dir: &Path, sort_key: impl Fn(&Value, &str) -> K) -> Vec<Value> {
+    let mut records: Vec<(K, Value)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("json"))
+                .filter_map(|p| {
+                    let record = read_json(&p)?;
+                    let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+                    Some((sort_key(&record, stem), record))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    records.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    records.into_iter().map(|(_, record)| record).collect()
+}
+
+/// `storage` directory from a session file (`storage/session/<project>/<id>.json`).
+fn storage_dir(session_file: &Path) -> Option<&Path> {
+    session_file.parent()?.parent()?.parent()
+}
+
+fn part_blocks(part: &Value) -> Vec<ContentBlock> {
+    match part["type"].as_str().unwrap_or("") {
+        "text" => vec![text_block("text", part["text"].as_str().unwrap_or(""))],
+        "reasoning" => vec![text_block("thinking", part["text"].as_str().unwrap_or(""))],
+        "tool" => {
+            let call_id = part["callID"].as_str().unwrap_or("");
+            let name = part["tool"].as_str().unwrap_or("");
+            let state = &part["state"];
+            let mut blocks = vec![tool_use_block(call_id, name, state["input"].clone())];
+            match state["status"].as_str() {
+                Some("completed") => blocks.push(tool_result_block(
+                    call_id,
+                    Some(name),
+                    state["output"].as_str().unwrap_or("").to_string(),
+                    false,
+                )),
+                Some("error") => blocks.push(tool_result_block(
+                    call_id,
+                    Some(name),
+                    state["error"].as_str().unwrap_or("").to_string(),
+                    true,
+                )),
+                _ => {}
+            }
+            blocks
+        }
+        _ => Vec::new(),
+    }
+}
+
+impl SessionReader for OpencodeReader {
+    fn source(&self) -> SessionSource {
+        SessionSource::Opencode
+    }
+
+    fn root_dir(&self, home_dir: &Path) -> Result<PathBuf, String> {
+        let config_paths = paths::load_config_paths_for_home(home_dir);
+        Ok(
+            paths::get_opencode_auth_dir_for_home(home_dir, &config_paths)?
+                .join("storage")
+                .join("session"),
+        )
+    }
+
+    fn transcript_path(&self, session_path: &str) -> PathBuf {
+        PathBuf::from(format!("{session_path}.json"))
+    }
+
+    fn session_paths(&self, home_dir: &Path) -> Vec<String> {
+        self.root_dir(home_dir)
+            .map(|root| collect_session_paths(&root, "json", &|_| true))
+            .unwrap_or_default()
+    }
+
+    fn read_detail(&self, session_path: &str) -> Result<SessionDetail, String> {
+        self.read(session_path, true)
+    }
+
+    /// Reads the session and message records only; parts hold the content.
+    fn read_summary(&self, session_path: &str) -> Result<SessionSummary, String> {
+        self.read(session_path, false)
+            .map(|detail| summary_from_detail(detail, session_path.to_string()))
+    }
+
+    /// Removes the session file with its message and part records.
+    fn delete(&self, session_path: &str) -> Result<(), String> {
+        let session_file = self.transcript_path(session_path);
+        let session = read_json(&session_file).unwrap_or_default();
+        if let (Some(storage), Some(id)) = (storage_dir(&session_file), session["id"].as_str()) {
+            let message_dir = storage.join("message").join(id);
+            for message in read_records(&message_dir, |_, _| 0.0) {
+                if let Some(message_id) = message["id"].as_str() {
+                    let _ = fs::remove_dir_all(storage.join("part").join(message_id));
+                }
+            }
+            let _ = fs::remove_dir_all(message_dir);
+        }
+        fs::remove_file(&session_file).map_err(|e| format!("Failed to delete session: {e}"))
+    }
+}
+
+impl OpencodeReader {
+    /// Parses a session. Without `messages`, part records are not read.
+    fn read(&self, session_path: &str, messages: bool) -> Result<SessionDetail, String> {
+        let session_file = self.transcript_path(session_path);
+        let session = read_json(&session_file)
+            .ok_or_else(|| "Failed to read OpenCode session file".to_string())?;
+        let storage = storage_dir(&session_file).unwrap_or(Path::new(""));
+
+        let mut detail = new_detail(SessionSource::Opencode, session_path);
+        if let Some(id) = session["id"].as_str() {
+            detail.id = id.to_string();
+        }
+        if let Some(title) = session["title"].as_str().filter(|t| !t.is_empty()) {
+            detail.title = title.to_string();
+        }
+        detail.cwd = session["directory"].as_str().unwrap_or("").to_string();
+        detail.project = project_key(&detail.cwd);
+        detail.modified_at = session["time"]["updated"].as_f64().unwrap_or(0.0);
+
+        let records = read_records(&storage.join("message").join(&detail.id), |m, _| {
+            m["time"]["created"].as_f64().unwrap_or(0.0)
+        });
+        for message in records {
+            let message_id = message["id"].as_str().unwrap_or("");
+            if let Some(model) = message["modelID"].as_str() {
+                detail.model = model.to_string();
+            }
+            let tokens = &message["tokens"];
+            let tu = &mut detail.token_usage;
+            tu.input_tokens += tokens["input"].as_f64().unwrap_or(0.0);
+            tu.output_tokens += tokens["output"].as_f64().unwrap_or(0.0);
+            tu.thinking_tokens += tokens["reasoning"].as_f64().unwrap_or(0.0);
+            tu.cache_read_tokens += tokens["cache"]["read"].as_f64().unwrap_or(0.0);
+            tu.cache_creation_tokens += tokens["cache"]["write"].as_f64().unwrap_or(0.0);
+
+            if !messages {
+                continue;
+            }
+            // Part IDs are ascending; text parts usually have no `time.start`
+            let parts = read_records(&storage.join("part").join(message_id), |p, stem| {
+                p["id"].as_str().unwrap_or(stem).to_string()
+            });
+            detail.messages.push(SessionMessage {
+                id: message_id.to_string(),
+                role: message["role"].as_str().unwrap_or("").to_string(),
+                content: parts.iter().flat_map(part_blocks).collect(),
+                timestamp: timestamp_from_ms(message["time"]["created"].as_f64().unwrap_or(0.0)),
+            });
+        }
+
+        Ok(detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_source::finalize_detail;
+    use crate::sessions::SessionDetailOptions;
+    use tempfile::TempDir;
+
+    fn write(path: PathBuf, value: Value) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, value.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_reads_and_deletes_opencode_session() {
+        let home = TempDir::new().unwrap();
+        let storage = home.path().join(".local/share/opencode/storage");
+        write(
+            storage.join("session/proj1/ses_1.json"),
+            serde_json::json!({ "id": "ses_1", "title": "Fix flaky test", "directory": "/work/web",
+                "time": { "created": 1_767_000_000_000.0, "updated": 1_767_000_100_000.0 } }),
+        );
+        write(
+            storage.join("message/ses_1/msg_1.json"),
+            serde_json::json!({ "id": "msg_1", "sessionID": "ses_1", "role": "user",
+                "time": { "created": 1_767_000_000_000.0 } }),
+        );
+        write(
+            storage.join("message/ses_1/msg_2.json"),
+            serde_json::json!({ "id": "msg_2", "sessionID": "ses_1", "role": "assistant",
+                "modelID": "kimi-k2", "time": { "created": 1_767_000_001_000.0 },
+                "tokens": { "input": 300, "output": 40, "reasoning": 5, "cache": { "read": 100, "write": 0 } } }),
+        );
+        write(
+            storage.join("part/msg_1/prt_1.json"),
+            serde_json::json!({ "id": "prt_1", "type": "text", "text": "The login test is flaky" }),
+        );
+        write(
+            storage.join("part/msg_2/prt_2.json"),
+            serde_json::json!({ "id": "prt_2", "type": "tool", "callID": "t1", "tool": "bash",
+                "state": { "status": "error", "input": { "command": "npm test" }, "error": "exit 1" },
+                "time": { "start": 1_767_000_002_000.0 } }),
+        );
+        // Text parts carry no start time; the part ID keeps them in order
+        write(
+            storage.join("part/msg_2/prt_3.json"),
+            serde_json::json!({ "id": "prt_3", "type": "text", "text": "The test needs a retry" }),
+        );
+
+        let paths = OpencodeReader.session_paths(home.path());
+        assert_eq!(paths.len(), 1);
+        let detail = OpencodeReader.read_detail(&paths[0]).unwrap();
+
+        assert_eq!(detail.source, SessionSource::Opencode);
+        assert_eq!(detail.id, "ses_1");
+        assert_eq!(detail.title, "Fix flaky test");
+        assert_eq!(detail.project, "-work-web");
+        assert_eq!(detail.model, "kimi-k2");
+        assert_eq!(detail.modified_at, 1_767_000_100_000.0);
+        assert_eq!(detail.token_usage.input_tokens, 300.0);
+        assert_eq!(detail.token_usage.cache_read_tokens, 100.0);
+
+        let options = SessionDetailOptions {
+            include_tools: true,
+        };
+        let detail = finalize_detail(detail, &options);
+        assert_eq!(detail.messages.len(), 2);
+        assert!(detail.messages[0].timestamp.starts_with("2025-12-29"));
+        let blocks = &detail.messages[1].content;
+        assert_eq!(blocks[0].content_type, "tool_use");
+        assert_eq!(blocks[1].tool_result.as_deref(), Some("exit 1"));
+        assert_eq!(blocks[1].is_error, Some(true));
+        assert_eq!(blocks[2].text.as_deref(), Some("The test needs a retry"));
+
+        let summary = OpencodeReader.read_summary(&paths[0]).unwrap();
+        assert_eq!(summary.title, "Fix flaky test");
+        assert_eq!(summary.model, "kimi-k2");
+        assert_eq!(summary.token_usage.output_tokens, 40.0);
+
+        OpencodeReader.delete(&paths[0]).unwrap();
+        assert!(!storage.join("session/proj1/ses_1.json").exists());
+        assert!(!storage.join("message/ses_1").exists());
+        assert!(!storage.join("part/msg_2").exists());
+    }
+}
@@ -0,0 +1,213 @@
+//! Pi sessions: `~/.pi/agent/sessions/<cwd>/<time>_<id>.jsonl`.
+//!
+//! The first line is a `session` header (ID, cwd); `message` lines carry
+//! `user`, `assistant` and `toolResult` messages. Assistant content uses
+//! `toolCall` blocks, and tool results are their own messages, shown here as
+//! user-side `tool_result` blocks like the other agents.
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::{
+    collect_session_paths, new_detail, project_key, read_lines, summary_from_detail, text_block,
+    title_from_text, tool_result_block, tool_use_block, SessionReader,
+};
+use crate::paths;
+use crate::sessions::{
+    modified_at_ms, tool_result_text, ContentBlock, SessionDetail, SessionMessage, SessionSource,
+    SessionSummary,
+};
+
+pub struct PiReader;
+
+fn message_blocks(content: &Value) -> Vec<ContentBlock> {
+    if let Some(text) = content.as_str() {
+        return vec![text_block("text", text)];
+    }
+    content
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| match item["type"].as_str().unwrap_or("") {
+            "text" => Some(text_block("text", item["text"].as_str().unwrap_or(""))),
+            "thinking" => Some(text_block(
+                "thinking",
+                item["thinking"].as_str().unwrap_or(""),
+            )),
+            "toolCall" => Some(tool_use_block(
+                item["id"].as_str().unwrap_or(""),
+                item["name"].as_str().unwrap_or(""),
+                item["arguments"].clone(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+impl SessionReader for PiReader {
+    fn source(&self) -> SessionSource {
+        SessionSource::Pi
+    }
+
+    fn root_dir(&self, home_dir: &Path) -> Result<PathBuf, String> {
+        let config_paths = paths::load_config_paths_for_home(home_dir);
+        Ok(paths::get_pi_home_for_home(home_dir, &config_paths)?.join("sessions"))
+    }
+
+    fn session_paths(&self, home_dir: &Path) -> Vec<String> {
+        self.root_dir(home_dir)
+            .map(|root| collect_session_paths(&root, "jsonl", &|_| true))
+            .unwrap_or_default()
+    }
+
+    fn read_detail(&self, session_path: &str) -> Result<SessionDetail, String> {
+        self.read(session_path, true)
+    }
+
+    fn read_summary(&self, session_path: &str) -> Result<SessionSummary, String> {
+        self.read(session_path, false)
+            .map(|detail| summary_from_detail(detail, session_path.to_string()))
+    }
+}
+
+impl PiReader {
+    /// Parses a session. Without `messages`, lines past the first prompt are
+    /// only parsed when they may carry the model or usage.
+    fn read(&self, session_path: &str, messages: bool) -> Result<SessionDetail, String> {
+        let jsonl_path = self.transcript_path(session_path);
+        let mut detail = new_detail(SessionSource::Pi, session_path);
+        detail.modified_at = modified_at_ms(&jsonl_path);
+        let mut title: Option<String> = None;
+
+        for (index, line) in read_lines(&jsonl_path)?.enumerate() {
+            if !messages
+                && title.is_some()
+                && !line.contains("\"usage\"")
+                && !line.contains("\"model_change\"")
+            {
+                continue;
+            }
+            let Ok(json) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            match json["type"].as_str().unwrap_or("") {
+                "session" => {
+                    if let Some(id) = json["id"].as_str().filter(|id| !id.is_empty()) {
+                        detail.id = id.to_string();
+                    }
+                    detail.cwd = json["cwd"].as_str().unwrap_or("").to_string();
+                }
+                "model_change" => {
+                    if let Some(model) = json["modelId"].as_str() {
+                        detail.model = model.to_string();
+                    }
+                }
+                "message" => {
+                    let message = &json["message"];
+                    let (role, content) = match message["role"].as_str().unwrap_or("") {
+                        "toolResult" => (
+                            "user",
+                            vec![tool_result_block(
+                                message["toolCallId"].as_str().unwrap_or(""),
+                                message["toolName"].as_str(),
+                                tool_result_text(&message["content"]),
+                                message["isError"].as_bool().unwrap_or(false),
+                            )],
+                        ),
+                        role => (role, message_blocks(&message["content"])),
+                    };
+                    if role == "assistant" {
+                        if let Some(model) = message["model"].as_str() {
+                            detail.model = model.to_string();
+                        }
+                        let usage = &message["usage"];
+                        let tu = &mut detail.token_usage;
+                        tu.input_tokens += usage["input"].as_f64().unwrap_or(0.0);
+                        tu.output_tokens += usage["output"].as_f64().unwrap_or(0.0);
+                        tu.cache_read_tokens += usage["cacheRead"].as_f64().unwrap_or(0.0);
+                        tu.cache_creation_tokens += usage["cacheWrite"].as_f64().unwrap_or(0.0);
+                    }
+                    if role == "user" && title.is_none() {
+                        title = content
+                            .iter()
+                            .find_map(|b| b.text.as_deref().and_then(title_from_text));
+                    }
+                    if messages {
+                        detail.messages.push(SessionMessage {
+                            id: json["id"]
+                                .as_str()
+                                .map_or_else(|| index.to_string(), |id| id.to_string()),
+                            role: role.to_string(),
+                            content,
+                            timestamp: json["timestamp"].as_str().unwrap_or("").to_string(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        detail.project = project_key(&detail.cwd);
+        if let Some(title) = title {
+            detail.title = title;
+        }
+        Ok(detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_source::finalize_detail;
+    use crate::sessions::SessionDetailOptions;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reads_pi_session() {
+        let home = TempDir::new().unwrap();
+        let dir = home.path().join(".pi/agent/sessions/--work-docs--");
+        fs::create_dir_all(&dir).unwrap();
+        let lines = [
+            serde_json::json!({ "type": "session", "id": "p1", "cwd": "/work/docs",
+                "timestamp": "2026-04-01T12:00:00Z" }),
+            serde_json::json!({ "type": "message", "id": "e1", "timestamp": "2026-04-01T12:00:01Z",
+                "message": { "role": "user", "content": [{ "type": "text", "text": "Spell-check the README" }] } }),
+            serde_json::json!({ "type": "message", "id": "e2", "timestamp": "2026-04-01T12:00:02Z",
+                "message": { "role": "assistant", "model": "glm-4.6",
+                    "content": [
+                        { "type": "thinking", "thinking": "Read it first" },
+                        { "type": "toolCall", "id": "tc1", "name": "read", "arguments": { "path": "README.md" } }
+                    ],
+                    "usage": { "input": 120, "output": 30, "cacheRead": 50, "cacheWrite": 10 } } }),
+            serde_json::json!({ "type": "message", "id": "e3", "timestamp": "2026-04-01T12:00:03Z",
+                "message": { "role": "toolResult", "toolCallId": "tc1", "toolName": "read",
+                    "content": [{ "type": "text", "text": "# Docs" }], "isError": false } }),
+        ];
+        let body: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        fs::write(dir.join("2026-04-01T12-00-00_p1.jsonl"), body.join("\n")).unwrap();
+
+        let paths = PiReader.session_paths(home.path());
+        assert_eq!(paths.len(), 1);
+        let detail = PiReader.read_detail(&paths[0]).unwrap();
+
+        assert_eq!(detail.source, SessionSource::Pi);
+        assert_eq!(detail.id, "p1");
+        assert_eq!(detail.title, "Spell-check the README");
+        assert_eq!(detail.project, "-work-docs");
+        assert_eq!(detail.model, "glm-4.6");
+        assert_eq!(detail.token_usage.input_tokens, 120.0);
+        assert_eq!(detail.token_usage.cache_creation_tokens, 10.0);
+
+        let summary = PiReader.read_summary(&paths[0]).unwrap();
+        assert_eq!(summary.title, "Spell-check the README");
+        assert_eq!(summary.model, "glm-4.6");
+        assert_eq!(summary.token_usage.input_tokens, 120.0);
+
+        let detail = finalize_detail(detail, &SessionDetailOptions::default());
+        // The tool result message has nothing left to show without tools
+        assert_eq!(detail.messages.len(), 2);
+        assert_eq!(detail.messages[1].content.len(), 1);
+        assert_eq!(detail.messages[1].content[0].content_type, "thinking");
+    }
+}
@@ -1,23 +1,58 @@
 //! Sessions management (core).
 //!
-//! Handles reading session files from Factory sessions directory.
+//! Lists, reads and deletes agent session transcripts. Factory (Droid)
+//! sessions live in `~/.factory/sessions`; Claude Code, Codex, OpenCode and Pi
+//! histories are read through the per-tool parsers in
+//! [`crate::session_source`], which normalize into the same summary and detail
+//! types tagged with their [`SessionSource`].
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use specta::Type;
 use std::fs;
-use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use crate::paths;
+use crate::session_source::{
+    finalize_detail, reader_for_path, readers, FactoryReader, SessionReader,
+};
+
+/// Agent whose history a session belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionSource {
+    #[default]
+    Factory,
+    Claude,
+    Codex,
+    Opencode,
+    Pi,
+}
+
+impl SessionSource {
+    /// Display name of the agent
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionSource::Factory => "Droid",
+            SessionSource::Claude => "Claude Code",
+            SessionSource::Codex => "Codex",
+            SessionSource::Opencode => "OpenCode",
+            SessionSource::Pi => "Pi",
+        }
+    }
+}
 
 /// Session project (directory containing sessions)
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionProject {
-    /// Directory name (e.g., "-Users-sunshow-GIT-sunshow-quickcast-api")
+    #[serde(default)]
+    pub source: SessionSource,
+    /// Directory name (e.g., "-Users-sunshow-GIT-sunshow-quickcast-api"); for
+    /// sources without per-project directories, the working directory in the
+    /// same form
     pub name: String,
-    /// Full path to the directory
+    /// Full path to the directory (empty when the source has none)
     pub path: String,
     /// Number of sessions in this project
     pub session_count: u32,
@@ -40,6 +75,8 @@ pub struct TokenUsage {
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionSummary {
+    #[serde(default)]
+    pub source: SessionSource,
     /// Session UUID
     pub id: String,
     /// Session title
@@ -66,6 +103,21 @@ pub struct ContentBlock {
     pub text: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thinking: Option<String>,
+    /// Tool call ID (`tool_use`), or the call a `tool_result` answers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_use_id: Option<String>,
+    /// Tool name (`tool_use`; copied onto the linked `tool_result`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+    /// Tool input (`tool_use`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_input: Option<Value>,
+    /// Result text (`tool_result`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_result: Option<String>,
+    /// Whether the tool reported an error (`tool_result`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_error: Option<bool>,
 }
 
 /// Session message
@@ -82,6 +134,8 @@ pub struct SessionMessage {
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionDetail {
+    #[serde(default)]
+    pub source: SessionSource,
     pub id: String,
     pub title: String,
     pub project: String,
@@ -92,81 +146,84 @@ pub struct SessionDetail {
     pub messages: Vec<SessionMessage>,
 }
 
-fn sessions_dir_for_home(home_dir: &Path) -> Result<PathBuf, String> {
+/// Options for [`get_session_detail_with_options_for_home`]
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDetailOptions {
+    /// Include `tool_use` / `tool_result` blocks (skipped by default)
+    #[serde(default)]
+    pub include_tools: bool,
+}
+
+pub(crate) fn sessions_dir_for_home(home_dir: &Path) -> Result<PathBuf, String> {
     let config_paths = paths::load_config_paths_for_home(home_dir);
     let factory_dir = paths::get_factory_home_for_home(home_dir, &config_paths)?;
     Ok(factory_dir.join("sessions"))
 }
 
-fn system_home_dir() -> Result<PathBuf, String> {
-    dirs::home_dir().ok_or_else(|| "Failed to get home directory".to_string())
+/// Model and token usage from a session's `.settings.json` ("unknown" when missing).
+pub(crate) fn read_session_settings(settings_path: &Path) -> (String, TokenUsage) {
+    let content = match fs::read_to_string(settings_path) {
+        Ok(content) => content,
+        Err(_) => return ("unknown".to_string(), TokenUsage::default()),
+    };
+    let json: Value = serde_json::from_str(&content).unwrap_or_default();
+    let model = json["model"].as_str().unwrap_or("unknown").to_string();
+    let tu = TokenUsage {
+        input_tokens: json["tokenUsage"]["inputTokens"].as_f64().unwrap_or(0.0),
+        output_tokens: json["tokenUsage"]["outputTokens"].as_f64().unwrap_or(0.0),
+        cache_creation_tokens: json["tokenUsage"]["cacheCreationTokens"]
+            .as_f64()
+            .unwrap_or(0.0),
+        cache_read_tokens: json["tokenUsage"]["cacheReadTokens"]
+            .as_f64()
+            .unwrap_or(0.0),
+        thinking_tokens: json["tokenUsage"]["thinkingTokens"].as_f64().unwrap_or(0.0),
+    };
+    (model, tu)
 }
 
-pub fn list_session_projects_for_home(home_dir: &Path) -> Result<Vec<SessionProject>, String> {
-    let sessions_dir = sessions_dir_for_home(home_dir)?;
-
-    if !sessions_dir.exists() {
-        return Ok(Vec::new());
+/// Text of a `tool_result` content value (a string or a list of text blocks).
+pub(crate) fn tool_result_text(content: &Value) -> String {
+    match content {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item["text"].as_str().or_else(|| item.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
     }
+}
 
-    let mut projects: Vec<SessionProject> = Vec::new();
-
-    let entries = fs::read_dir(&sessions_dir)
-        .map_err(|e| format!("Failed to read sessions directory: {e}"))?;
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
-
-        let name = path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_string();
-
-        // Count .jsonl files (sessions)
-        let session_count = fs::read_dir(&path)
-            .map(|entries| {
-                entries
-                    .flatten()
-                    .filter(|e| {
-                        e.path()
-                            .extension()
-                            .and_then(|s| s.to_str())
-                            .is_some_and(|s| s == "jsonl")
-                    })
-                    .count() as u32
-            })
-            .unwrap_or(0);
-
-        if session_count == 0 {
-            continue;
-        }
+/// Last modified timestamp of a path in milliseconds (0 when unavailable).
+pub(crate) fn modified_at_ms(path: &Path) -> f64 {
+    fs::metadata(path)
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as f64)
+        .unwrap_or(0.0)
+}
 
-        let modified_at = fs::metadata(&path)
-            .ok()
-            .and_then(|m| m.modified().ok())
-            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d| d.as_millis() as f64)
-            .unwrap_or(0.0);
-
-        projects.push(SessionProject {
-            name,
-            path: path.to_string_lossy().to_string(),
-            session_count,
-            modified_at,
-        });
-    }
+fn system_home_dir() -> Result<PathBuf, String> {
+    dirs::home_dir().ok_or_else(|| "Failed to get home directory".to_string())
+}
 
-    projects.sort_by(|a, b| {
-        b.modified_at
-            .partial_cmp(&a.modified_at)
+fn sort_by_modified_desc<T>(items: &mut [T], modified_at: impl Fn(&T) -> f64) {
+    items.sort_by(|a, b| {
+        modified_at(b)
+            .partial_cmp(&modified_at(a))
             .unwrap_or(std::cmp::Ordering::Equal)
     });
+}
 
-    Ok(projects)
+// ============================================================================
+// Factory sessions
+// ============================================================================
+
+pub fn list_session_projects_for_home(home_dir: &Path) -> Result<Vec<SessionProject>, String> {
+    FactoryReader.list_projects(home_dir)
 }
 
 pub fn list_session_projects() -> Result<Vec<SessionProject>, String> {
@@ -177,283 +234,185 @@ pub fn list_sessions_for_home(
     home_dir: &Path,
     project: Option<&str>,
 ) -> Result<Vec<SessionSummary>, String> {
-    let sessions_dir = sessions_dir_for_home(home_dir)?;
+    FactoryReader.list_sessions(home_dir, project)
+}
 
-    if !sessions_dir.exists() {
-        return Ok(Vec::new());
-    }
+pub fn list_sessions(project: Option<&str>) -> Result<Vec<SessionSummary>, String> {
+    list_sessions_for_home(&system_home_dir()?, project)
+}
 
-    let mut sessions: Vec<SessionSummary> = Vec::new();
-
-    let project_dirs: Vec<PathBuf> = if let Some(proj) = project {
-        vec![sessions_dir.join(proj)]
-    } else {
-        fs::read_dir(&sessions_dir)
-            .map_err(|e| format!("Failed to read sessions directory: {e}"))?
-            .flatten()
-            .map(|e| e.path())
-            .filter(|p| p.is_dir())
-            .collect()
-    };
+// ============================================================================
+// All agents
+// ============================================================================
 
-    for project_dir in project_dirs {
-        if !project_dir.exists() || !project_dir.is_dir() {
+/// Projects of one agent, or of every agent when `source` is `None`.
+pub fn list_agent_session_projects_for_home(
+    home_dir: &Path,
+    source: Option<SessionSource>,
+) -> Result<Vec<SessionProject>, String> {
+    let mut projects = Vec::new();
+    for reader in readers() {
+        if source.is_some_and(|s| s != reader.source()) {
             continue;
         }
-
-        let project_name = project_dir
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_string();
-
-        let entries = match fs::read_dir(&project_dir) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
-                continue;
-            }
-
-            let session_id = path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("")
-                .to_string();
-
-            let settings_path = project_dir.join(format!("{session_id}.settings.json"));
-
-            let (model, token_usage) = if settings_path.exists() {
-                match fs::read_to_string(&settings_path) {
-                    Ok(content) => {
-                        let json: Value = serde_json::from_str(&content).unwrap_or_default();
-                        let model = json["model"].as_str().unwrap_or("unknown").to_string();
-                        let tu = TokenUsage {
-                            input_tokens: json["tokenUsage"]["inputTokens"].as_f64().unwrap_or(0.0),
-                            output_tokens: json["tokenUsage"]["outputTokens"]
-                                .as_f64()
-                                .unwrap_or(0.0),
-                            cache_creation_tokens: json["tokenUsage"]["cacheCreationTokens"]
-                                .as_f64()
-                                .unwrap_or(0.0),
-                            cache_read_tokens: json["tokenUsage"]["cacheReadTokens"]
-                                .as_f64()
-                                .unwrap_or(0.0),
-                            thinking_tokens: json["tokenUsage"]["thinkingTokens"]
-                                .as_f64()
-                                .unwrap_or(0.0),
-                        };
-                        (model, tu)
-                    }
-                    Err(_) => ("unknown".to_string(), TokenUsage::default()),
-                }
-            } else {
-                ("unknown".to_string(), TokenUsage::default())
-            };
-
-            // Read first line of jsonl for session title
-            let title = match fs::File::open(&path) {
-                Ok(file) => {
-                    let reader = BufReader::new(file);
-                    if let Some(Ok(line)) = reader.lines().next() {
-                        let json: Value = serde_json::from_str(&line).unwrap_or_default();
-                        json["sessionTitle"]
-                            .as_str()
-                            .or_else(|| json["title"].as_str())
-                            .unwrap_or("Untitled")
-                            .to_string()
-                    } else {
-                        "Untitled".to_string()
-                    }
-                }
-                Err(_) => "Untitled".to_string(),
-            };
-
-            let modified_at = fs::metadata(&path)
-                .ok()
-                .and_then(|m| m.modified().ok())
-                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                .map(|d| d.as_millis() as f64)
-                .unwrap_or(0.0);
-
-            sessions.push(SessionSummary {
-                id: session_id.clone(),
-                title,
-                project: project_name.clone(),
-                model,
-                modified_at,
-                token_usage,
-                path: path.with_extension("").to_string_lossy().to_string(),
-            });
+        match reader.list_projects(home_dir) {
+            Ok(found) => projects.extend(found),
+            Err(e) => log::warn!("Failed to list {} projects: {e}", reader.source().label()),
         }
     }
+    sort_by_modified_desc(&mut projects, |p| p.modified_at);
+    Ok(projects)
+}
 
-    sessions.sort_by(|a, b| {
-        b.modified_at
-            .partial_cmp(&a.modified_at)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+pub fn list_agent_session_projects(
+    source: Option<SessionSource>,
+) -> Result<Vec<SessionProject>, String> {
+    list_agent_session_projects_for_home(&system_home_dir()?, source)
+}
 
+/// Sessions of one agent, or of every agent when `source` is `None`,
+/// newest first.
+pub fn list_agent_sessions_for_home(
+    home_dir: &Path,
+    source: Option<SessionSource>,
+    project: Option<&str>,
+) -> Result<Vec<SessionSummary>, String> {
+    let mut sessions = Vec::new();
+    for reader in readers() {
+        if source.is_some_and(|s| s != reader.source()) {
+            continue;
+        }
+        match reader.list_sessions(home_dir, project) {
+            Ok(found) => sessions.extend(found),
+            Err(e) => log::warn!("Failed to list {} sessions: {e}", reader.source().label()),
+        }
+    }
+    sort_by_modified_desc(&mut sessions, |s| s.modified_at);
     Ok(sessions)
 }
 
-pub fn list_sessions(project: Option<&str>) -> Result<Vec<SessionSummary>, String> {
-    list_sessions_for_home(&system_home_dir()?, project)
+pub fn list_agent_sessions(
+    source: Option<SessionSource>,
+    project: Option<&str>,
+) -> Result<Vec<SessionSummary>, String> {
+    list_agent_sessions_for_home(&system_home_dir()?, source, project)
 }
 
+// ============================================================================
+// Detail / delete (any agent, resolved from the session path)
+// ============================================================================
+
 pub fn get_session_detail_for_home(
-    _home_dir: &Path,
+    home_dir: &Path,
     session_path: &str,
 ) -> Result<SessionDetail, String> {
-    let jsonl_path = PathBuf::from(format!("{session_path}.jsonl"));
-    let settings_path = PathBuf::from(format!("{session_path}.settings.json"));
+    get_session_detail_with_options_for_home(
+        home_dir,
+        session_path,
+        &SessionDetailOptions::default(),
+    )
+}
 
-    if !jsonl_path.exists() {
+pub fn get_session_detail_with_options_for_home(
+    home_dir: &Path,
+    session_path: &str,
+    options: &SessionDetailOptions,
+) -> Result<SessionDetail, String> {
+    let reader = reader_for_path(home_dir, session_path);
+    if !reader.transcript_path(session_path).exists() {
         return Err("Session file not found".to_string());
     }
+    let detail = reader.read_detail(session_path)?;
+    Ok(finalize_detail(detail, options))
+}
 
-    let project = jsonl_path
-        .parent()
-        .and_then(|p| p.file_name())
-        .and_then(|s| s.to_str())
-        .unwrap_or("")
-        .to_string();
-
-    let (model, token_usage) = if settings_path.exists() {
-        match fs::read_to_string(&settings_path) {
-            Ok(content) => {
-                let json: Value = serde_json::from_str(&content).unwrap_or_default();
-                let model = json["model"].as_str().unwrap_or("unknown").to_string();
-                let tu = TokenUsage {
-                    input_tokens: json["tokenUsage"]["inputTokens"].as_f64().unwrap_or(0.0),
-                    output_tokens: json["tokenUsage"]["outputTokens"].as_f64().unwrap_or(0.0),
-                    cache_creation_tokens: json["tokenUsage"]["cacheCreationTokens"]
-                        .as_f64()
-                        .unwrap_or(0.0),
-                    cache_read_tokens: json["tokenUsage"]["cacheReadTokens"]
-                        .as_f64()
-                        .unwrap_or(0.0),
-                    thinking_tokens: json["tokenUsage"]["thinkingTokens"].as_f64().unwrap_or(0.0),
-                };
-                (model, tu)
-            }
-            Err(_) => ("unknown".to_string(), TokenUsage::default()),
-        }
-    } else {
-        ("unknown".to_string(), TokenUsage::default())
-    };
-
-    let modified_at = fs::metadata(&jsonl_path)
-        .ok()
-        .and_then(|m| m.modified().ok())
-        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|d| d.as_millis() as f64)
-        .unwrap_or(0.0);
-
-    let file =
-        fs::File::open(&jsonl_path).map_err(|e| format!("Failed to open session file: {e}"))?;
-    let reader = BufReader::new(file);
-
-    let mut id = String::new();
-    let mut title = String::from("Untitled");
-    let mut cwd = String::new();
-    let mut messages: Vec<SessionMessage> = Vec::new();
-
-    for line in reader.lines() {
-        let line = match line {
-            Ok(l) => l,
-            Err(_) => continue,
-        };
-
-        let json: Value = match serde_json::from_str(&line) {
-            Ok(j) => j,
-            Err(_) => continue,
-        };
-
-        let msg_type = json["type"].as_str().unwrap_or("");
-
-        match msg_type {
-            "session_start" => {
-                id = json["id"].as_str().unwrap_or("").to_string();
-                title = json["sessionTitle"]
-                    .as_str()
-                    .or_else(|| json["title"].as_str())
-                    .unwrap_or("Untitled")
-                    .to_string();
-                cwd = json["cwd"].as_str().unwrap_or("").to_string();
-            }
-            "message" => {
-                let msg_id = json["id"].as_str().unwrap_or("").to_string();
-                let timestamp = json["timestamp"].as_str().unwrap_or("").to_string();
-                let role = json["message"]["role"].as_str().unwrap_or("").to_string();
-
-                let content_arr = json["message"]["content"].as_array();
-                let mut content_blocks: Vec<ContentBlock> = Vec::new();
-
-                if let Some(arr) = content_arr {
-                    for item in arr {
-                        let content_type = item["type"].as_str().unwrap_or("text").to_string();
-                        let text = item["text"].as_str().map(|s| s.to_string());
-                        let thinking = item["thinking"].as_str().map(|s| s.to_string());
-
-                        // Skip tool_use and tool_result for cleaner display
-                        if content_type == "tool_use" || content_type == "tool_result" {
-                            continue;
-                        }
-
-                        content_blocks.push(ContentBlock {
-                            content_type,
-                            text,
-                            thinking,
-                        });
-                    }
-                }
-
-                if !content_blocks.is_empty() {
-                    messages.push(SessionMessage {
-                        id: msg_id,
-                        role,
-                        content: content_blocks,
-                        timestamp,
-                    });
-                }
-            }
-            _ => {}
-        }
-    }
+pub fn get_session_detail(session_path: &str) -> Result<SessionDetail, String> {
+    get_session_detail_for_home(&system_home_dir()?, session_path)
+}
 
-    Ok(SessionDetail {
-        id,
-        title,
-        project,
-        model,
-        cwd,
-        modified_at,
-        token_usage,
-        messages,
-    })
+pub fn get_session_detail_with_options(
+    session_path: &str,
+    options: &SessionDetailOptions,
+) -> Result<SessionDetail, String> {
+    get_session_detail_with_options_for_home(&system_home_dir()?, session_path, options)
 }
 
-pub fn get_session_detail(session_path: &str) -> Result<SessionDetail, String> {
-    let _home_dir = system_home_dir()?;
-    get_session_detail_for_home(&_home_dir, session_path)
+pub fn delete_session_for_home(home_dir: &Path, session_path: &str) -> Result<(), String> {
+    let reader = reader_for_path(home_dir, session_path);
+    if !reader.transcript_path(session_path).exists() {
+        return Err("Session file not found".to_string());
+    }
+    reader.delete(session_path)
 }
 
 pub fn delete_session(session_path: &str) -> Result<(), String> {
-    let jsonl_path = PathBuf::from(format!("{session_path}.jsonl"));
-    let settings_path = PathBuf::from(format!("{session_path}.settings.json"));
+    delete_session_for_home(&system_home_dir()?, session_path)
+}
 
-    if !jsonl_path.exists() {
-        return Err("Session file not found".to_string());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_tool_session(home: &Path) -> String {
+        let dir = home.join(".factory/sessions/-work-api");
+        fs::create_dir_all(&dir).unwrap();
+        let lines = [
+            serde_json::json!({ "type": "session_start", "id": "s1", "sessionTitle": "Tools" }),
+            serde_json::json!({
+                "type": "message", "id": "m1", "timestamp": "2026-01-10T10:00:00Z",
+                "message": { "role": "assistant", "content": [
+                    { "type": "text", "text": "Running the tests" },
+                    { "type": "tool_use", "id": "call-1", "name": "Execute", "input": { "command": "cargo test" } }
+                ] }
+            }),
+            serde_json::json!({
+                "type": "message", "id": "m2", "timestamp": "2026-01-10T10:00:05Z",
+                "message": { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": "call-1", "is_error": true,
+                      "content": [{ "type": "text", "text": "1 test failed" }] }
+                ] }
+            }),
+        ];
+        let body: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        fs::write(dir.join("s1.jsonl"), body.join("\n")).unwrap();
+        dir.join("s1").to_string_lossy().to_string()
     }
 
-    fs::remove_file(&jsonl_path).map_err(|e| format!("Failed to delete session: {e}"))?;
-    if settings_path.exists() {
-        let _ = fs::remove_file(&settings_path);
+    #[test]
+    fn test_session_detail_skips_tool_blocks_by_default() {
+        let home = TempDir::new().unwrap();
+        let path = write_tool_session(home.path());
+
+        let detail = get_session_detail_for_home(home.path(), &path).unwrap();
+
+        assert_eq!(detail.messages.len(), 1);
+        assert_eq!(detail.messages[0].content.len(), 1);
+        assert_eq!(detail.messages[0].content[0].content_type, "text");
     }
 
-    Ok(())
+    #[test]
+    fn test_session_detail_includes_linked_tool_blocks() {
+        let home = TempDir::new().unwrap();
+        let path = write_tool_session(home.path());
+        let options = SessionDetailOptions {
+            include_tools: true,
+        };
+
+        let detail =
+            get_session_detail_with_options_for_home(home.path(), &path, &options).unwrap();
+
+        assert_eq!(detail.messages.len(), 2);
+        let call = &detail.messages[0].content[1];
+        assert_eq!(call.content_type, "tool_use");
+        assert_eq!(call.tool_use_id.as_deref(), Some("call-1"));
+        assert_eq!(call.tool_name.as_deref(), Some("Execute"));
+        assert_eq!(call.tool_input.as_ref().unwrap()["command"], "cargo test");
+        let result = &detail.messages[1].content[0];
+        assert_eq!(result.content_type, "tool_result");
+        assert_eq!(result.tool_use_id.as_deref(), Some("call-1"));
+        assert_eq!(result.tool_name.as_deref(), Some("Execute"));
+        assert_eq!(result.tool_result.as_deref(), Some("1 test failed"));
+        assert_eq!(result.is_error, Some(true));
+    }
 }
@@ -0,0 +1,75 @@
+//! Shared helpers for unit tests that talk HTTP.
+
+use std::future::Future;
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+/// Runs a future to completion on a fresh current-thread runtime.
+pub(crate) fn block_on<F: Future>(f: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(f)
+}
+
+/// Minimal HTTP server answering each connection with the next `(status, body)`.
+/// Returns the base URL (`http://127.0.0.1:<port>`).
+pub(crate) fn serve_responses(responses: Vec<(u16, String)>) -> String {
+    serve_recording(responses).0
+}
+
+/// Like [`serve_responses`], also returning the raw requests received
+/// (request line, headers and body).
+pub(crate) fn serve_recording(responses: Vec<(u16, String)>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+    std::thread::spawn(move || {
+        for (status, body) in responses {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut buf = [0u8; 4096];
+            let mut req = Vec::new();
+            let header_end = loop {
+                if let Some(pos) = req.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break req.len(),
+                    Ok(n) => req.extend_from_slice(&buf[..n]),
+                }
+            };
+            let headers = String::from_utf8_lossy(&req[..header_end]).to_lowercase();
+            let content_length = headers
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length:"))
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            while req.len() < header_end + content_length {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => req.extend_from_slice(&buf[..n]),
+                }
+            }
+            recorded
+                .lock()
+                .unwrap()
+                .push(String::from_utf8_lossy(&req).to_string());
+
+            let content_type = if body.starts_with("data:") || body.starts_with("event:") {
+                "text/event-stream"
+            } else {
+                "application/json"
+            };
+            let resp = format!(
+                "HTTP/1.1 {status} X\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(resp.as_bytes());
+        }
+    });
+    (format!("http://{addr}"), requests)
+}
@@ -0,0 +1,619 @@
+//! Relay token rotation (core).
+//!
+//! Rotating a token creates a replacement on the channel's dashboard,
+//! rewrites every place DroidGear knows that holds the old key (vault
+//! secrets, tool profiles and the custom models of every Factory settings
+//! file), re-applies the active profile of every tool whose profile changed
+//! so live configs carry the new key, and only then revokes the old token.
+//! Only whole values equal to the key (or `Bearer <key>`) are rewritten. If
+//! any reference or live config could not be rewritten, or the old key can
+//! still be found afterwards, the old token is left active so nothing that
+//! still uses it breaks.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use std::path::{Path, PathBuf};
+
+use crate::channel::{self, ChannelToken, NewChannelToken};
+use crate::channel_provider;
+use crate::droid_settings_files;
+use crate::factory_settings;
+use crate::paths;
+use crate::profiles::{self, ProfileTool};
+use crate::secrets;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// What happens to the old token once references point at the new one
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RevokeMode {
+    #[default]
+    Disable,
+    Delete,
+}
+
+/// Places whose key was replaced
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyReplacementReport {
+    /// Human-readable locations now holding the new key
+    pub updated: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRotationReport {
+    pub new_token: ChannelToken,
+    pub updated: Vec<String>,
+    /// Whether the old token was revoked (it stays active when any
+    /// reference could not be rewritten)
+    pub revoked: bool,
+    pub errors: Vec<String>,
+}
+
+// ============================================================================
+// Key replacement
+// ============================================================================
+
+/// The replacement for a whole string value: `old` itself, or a
+/// `Bearer <old>` header value. Anything else (names, URLs, model ids that
+/// merely contain the key) is left alone.
+fn replacement_for(value: &str, old: &str, new: &str) -> Option<String> {
+    if value == old {
+        Some(new.to_string())
+    } else if value.strip_prefix("Bearer ") == Some(old) {
+        Some(format!("Bearer {new}"))
+    } else {
+        None
+    }
+}
+
+/// Replaces every string of `value` that is exactly `old` (or `Bearer <old>`)
+/// with the new key; returns whether anything changed.
+fn replace_in_value(value: &mut Value, old: &str, new: &str) -> bool {
+    match value {
+        Value::String(s) => match replacement_for(s, old, new) {
+            Some(replaced) => {
+                *s = replaced;
+                true
+            }
+            None => false,
+        },
+        Value::Array(items) => {
+            let mut changed = false;
+            for item in items {
+                changed |= replace_in_value(item, old, new);
+            }
+            changed
+        }
+        Value::Object(map) => {
+            let mut changed = false;
+            for item in map.values_mut() {
+                changed |= replace_in_value(item, old, new);
+            }
+            changed
+        }
+        _ => false,
+    }
+}
+
+/// Returns the names of the secrets that changed.
+fn replace_in_vault(
+    home_dir: &Path,
+    old: &str,
+    new: &str,
+    report: &mut KeyReplacementReport,
+) -> Vec<String> {
+    let mut changed = Vec::new();
+    if !secrets::is_vault_initialized_for_home(home_dir) {
+        return changed;
+    }
+    let infos = match secrets::list_secrets_for_home(home_dir) {
+        Ok(infos) => infos,
+        Err(e) => {
+            report.errors.push(format!("vault: {e}"));
+            return changed;
+        }
+    };
+    for info in infos {
+        let result = secrets::get_secret_for_home(home_dir, &info.name).and_then(|value| {
+            let Some(replaced) = replacement_for(&value, old, new) else {
+                return Ok(false);
+            };
+            secrets::set_secret_for_home(home_dir, &info.name, &replaced).map(|_| true)
+        });
+        match result {
+            Ok(true) => {
+                report.updated.push(format!("secret '{}'", info.name));
+                changed.push(info.name);
+            }
+            Ok(false) => {}
+            Err(e) => report.errors.push(format!("secret '{}': {e}", info.name)),
+        }
+    }
+    changed
+}
+
+/// Returns the profiles that changed.
+fn replace_in_profiles(
+    home_dir: &Path,
+    old: &str,
+    new: &str,
+    report: &mut KeyReplacementReport,
+) -> Vec<(ProfileTool, String)> {
+    let mut changed = Vec::new();
+    for tool in ProfileTool::ALL {
+        let summaries = match profiles::list_profiles_for_home(home_dir, tool) {
+            Ok(summaries) => summaries,
+            Err(e) => {
+                report.errors.push(format!("{}: {e}", tool.as_str()));
+                continue;
+            }
+        };
+        for summary in summaries {
+            let result = profiles::get_profile_value_for_home(home_dir, tool, &summary.id)
+                .and_then(|mut value| {
+                    if !replace_in_value(&mut value, old, new) {
+                        return Ok(false);
+                    }
+                    profiles::save_profile_value_for_home(home_dir, tool, value).map(|_| true)
+                });
+            let label = format!("{} profile '{}'", tool.as_str(), summary.name);
+            match result {
+                Ok(true) => {
+                    report.updated.push(label);
+                    changed.push((tool, summary.id));
+                }
+                Ok(false) => {}
+                Err(e) => report.errors.push(format!("{label}: {e}")),
+            }
+        }
+    }
+    changed
+}
+
+/// Re-applies the active profile of every tool whose profile changed, either
+/// directly or through a vault secret it references.
+fn reapply_active_profiles(
+    home_dir: &Path,
+    changed_profiles: &[(ProfileTool, String)],
+    changed_secrets: &[String],
+    report: &mut KeyReplacementReport,
+) {
+    // Quoted so `secret://a` does not match `secret://ab`
+    let refs: Vec<String> = changed_secrets
+        .iter()
+        .map(|name| format!("\"{}\"", secrets::secret_ref(name)))
+        .collect();
+    for tool in ProfileTool::ALL {
+        let Ok(Some(id)) = profiles::get_active_profile_id_for_home(home_dir, tool) else {
+            continue;
+        };
+        let affected = changed_profiles.iter().any(|(t, p)| *t == tool && *p == id)
+            || (!refs.is_empty()
+                && profiles::get_profile_value_for_home(home_dir, tool, &id).is_ok_and(|value| {
+                    let text = value.to_string();
+                    refs.iter().any(|r| text.contains(r.as_str()))
+                }));
+        if !affected {
+            continue;
+        }
+        let label = format!("live {} config", tool.as_str());
+        match profiles::apply_profile_for_home(home_dir, tool, &id) {
+            Ok(()) => report.updated.push(label),
+            Err(e) => report.errors.push(format!("{label}: {e}")),
+        }
+    }
+}
+
+/// Every Factory settings file: the global one and each Droid settings file,
+/// whichever is active.
+fn factory_settings_files(home_dir: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    Ok(
+        droid_settings_files::list_settings_files_for_home(home_dir)?
+            .into_iter()
+            .filter(|f| f.exists)
+            .map(|f| (f.name, PathBuf::from(f.path)))
+            .collect(),
+    )
+}
+
+fn replace_in_custom_models(
+    home_dir: &Path,
+    old: &str,
+    new: &str,
+    report: &mut KeyReplacementReport,
+) {
+    let files = match factory_settings_files(home_dir) {
+        Ok(files) => files,
+        Err(e) => {
+            report.errors.push(format!("Factory settings files: {e}"));
+            return;
+        }
+    };
+    for (file_name, path) in files {
+        let location = if file_name == "Global" {
+            String::new()
+        } else {
+            format!(" in settings file '{file_name}'")
+        };
+        let result = factory_settings::load_custom_models_from_file(&path).and_then(|models| {
+            let mut value = serde_json::to_value(&models)
+                .map_err(|e| format!("Failed to serialize custom models: {e}"))?;
+            let mut changed = Vec::new();
+            if let Value::Array(items) = &mut value {
+                for (model, item) in models.iter().zip(items.iter_mut()) {
+                    if replace_in_value(item, old, new) {
+                        let name = model.display_name.as_deref().unwrap_or(&model.model);
+                        changed.push(format!("Factory custom model '{name}'{location}"));
+                    }
+                }
+            }
+            if !changed.is_empty() {
+                let models = serde_json::from_value(value)
+                    .map_err(|e| format!("Failed to parse custom models: {e}"))?;
+                factory_settings::save_custom_models_to_file_for_home(home_dir, &path, models)?;
+            }
+            Ok(changed)
+        });
+        match result {
+            Ok(changed) => report.updated.extend(changed),
+            Err(e) => report
+                .errors
+                .push(format!("Factory custom models{location}: {e}")),
+        }
+    }
+}
+
+/// Places that still contain `old` anywhere (including inside longer
+/// strings), so the old token must not be revoked yet.
+fn remaining_references(home_dir: &Path, old: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    if secrets::is_vault_initialized_for_home(home_dir) {
+        for info in secrets::list_secrets_for_home(home_dir).unwrap_or_default() {
+            if secrets::get_secret_for_home(home_dir, &info.name).is_ok_and(|v| v.contains(old)) {
+                found.push(format!("secret '{}'", info.name));
+            }
+        }
+    }
+    for tool in ProfileTool::ALL {
+        for summary in profiles::list_profiles_for_home(home_dir, tool).unwrap_or_default() {
+            if profiles::get_profile_value_for_home(home_dir, tool, &summary.id)
+                .is_ok_and(|value| value.to_string().contains(old))
+            {
+                found.push(format!("{} profile '{}'", tool.as_str(), summary.name));
+            }
+        }
+    }
+    for (file_name, path) in factory_settings_files(home_dir).unwrap_or_default() {
+        if std::fs::read_to_string(&path).is_ok_and(|text| text.contains(old)) {
+            found.push(format!("Factory settings file '{file_name}'"));
+        }
+    }
+    found
+}
+
+/// Replaces `old_key` with `new_key` in vault secrets, all tool profiles and
+/// the custom models of every Factory settings file, then re-applies affected
+/// active profiles. Any place still containing `old_key` afterwards is
+/// reported as an error.
+pub fn replace_api_key_for_home(
+    home_dir: &Path,
+    old_key: &str,
+    new_key: &str,
+) -> Result<KeyReplacementReport, String> {
+    if old_key.trim().is_empty() {
+        return Err("Old key is empty".to_string());
+    }
+    let mut report = KeyReplacementReport::default();
+    let changed_secrets = replace_in_vault(home_dir, old_key, new_key, &mut report);
+    let changed_profiles = replace_in_profiles(home_dir, old_key, new_key, &mut report);
+    replace_in_custom_models(home_dir, old_key, new_key, &mut report);
+    reapply_active_profiles(home_dir, &changed_profiles, &changed_secrets, &mut report);
+    for place in remaining_references(home_dir, old_key) {
+        report
+            .errors
+            .push(format!("{place} still contains the old key"));
+    }
+    Ok(report)
+}
+
+// ============================================================================
+// Rotation
+// ============================================================================
+
+/// Creates a replacement for token `token_id` of a channel (same name, quota
+/// and group), points every reference at it and revokes the old token.
+pub async fn rotate_channel_token_for_home(
+    home_dir: &Path,
+    channel_id: &str,
+    token_id: f64,
+    revoke: RevokeMode,
+) -> Result<TokenRotationReport, String> {
+    let channel = channel::load_channels_for_home(home_dir)?
+        .into_iter()
+        .find(|c| c.id == channel_id)
+        .ok_or_else(|| format!("Channel '{channel_id}' not found"))?;
+    let provider = channel_provider::provider_for(&channel.channel_type);
+    if !provider.manages_tokens() {
+        return Err(format!(
+            "Channel type '{}' does not support managing tokens",
+            channel.channel_type.id()
+        ));
+    }
+    let (username, password) = channel::get_channel_credentials_for_home(home_dir, channel_id)?
+        .ok_or_else(|| "No credentials stored for this channel".to_string())?;
+
+    let base_url = channel.base_url.as_str();
+    let session = provider.login(base_url, &username, &password).await?;
+    let old = provider
+        .list_tokens(base_url, &session)
+        .await?
+        .into_iter()
+        .find(|t| t.id == token_id)
+        .ok_or_else(|| "Token not found".to_string())?;
+    if old.key.contains('*') {
+        return Err("The full key of this token is not available".to_string());
+    }
+
+    let spec = NewChannelToken {
+        name: old.name.clone(),
+        remain_quota: (!old.unlimited_quota).then_some(old.remain_quota),
+        group: old.group_name.clone(),
+        platform: old.platform.clone(),
+    };
+    let new_token = provider.create_token(base_url, &session, &spec).await?;
+
+    let replaced = replace_api_key_for_home(home_dir, &old.key, &new_token.key)?;
+    let mut errors = replaced.errors;
+    let revoked = if errors.is_empty() {
+        let result = match revoke {
+            RevokeMode::Disable => {
+                provider
+                    .set_token_enabled(base_url, &session, old.id, false)
+                    .await
+            }
+            RevokeMode::Delete => provider.delete_token(base_url, &session, old.id).await,
+        };
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                errors.push(format!("Failed to revoke old token: {e}"));
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    Ok(TokenRotationReport {
+        new_token,
+        updated: replaced.updated,
+        revoked,
+        errors,
+    })
+}
+
+pub async fn rotate_channel_token(
+    channel_id: &str,
+    token_id: f64,
+    revoke: RevokeMode,
+) -> Result<TokenRotationReport, String> {
+    rotate_channel_token_for_home(&paths::get_home_dir()?, channel_id, token_id, revoke).await
+}
+
+pub fn replace_api_key(old_key: &str, new_key: &str) -> Result<KeyReplacementReport, String> {
+    replace_api_key_for_home(&paths::get_home_dir()?, old_key, new_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::{Channel, ChannelType};
+    use crate::claude::{self, ClaudeCodeProfile, ClaudeThinkingMode};
+    use crate::factory_settings::{CustomModel, Provider};
+    use crate::test_support::{block_on, serve_recording};
+    use tempfile::TempDir;
+
+    fn claude_profile(token: &str) -> ClaudeCodeProfile {
+        ClaudeCodeProfile {
+            id: "p1".to_string(),
+            name: "Relay".to_string(),
+            description: None,
+            base_url: Some("https://relay.example.com".to_string()),
+            bearer_token: Some(token.to_string()),
+            model: None,
+            small_model_uses_main_model: false,
+            small_model: None,
+            reasoning_effort: None,
+            thinking_mode: ClaudeThinkingMode::Inherit,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            channel_ref: None,
+        }
+    }
+
+    fn custom_model(api_key: &str) -> CustomModel {
+        CustomModel {
+            model: "gpt-4o".to_string(),
+            id: None,
+            index: None,
+            display_name: Some("GPT-4o".to_string()),
+            base_url: "https://relay.example.com/v1".to_string(),
+            api_key: api_key.to_string(),
+            provider: Provider::Openai,
+            max_output_tokens: None,
+            no_image_support: None,
+            extra_args: None,
+            extra_headers: None,
+            channel_ref: None,
+        }
+    }
+
+    #[test]
+    fn replaces_key_in_profiles_models_and_vault() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        secrets::init_vault_for_home(home, "passphrase").unwrap();
+        secrets::set_secret_for_home(home, "team/key", "sk-old").unwrap();
+        claude::save_claude_profile_for_home(home, claude_profile("sk-old")).unwrap();
+        factory_settings::save_custom_models_for_home(
+            home,
+            vec![custom_model("sk-old"), custom_model("sk-other")],
+        )
+        .unwrap();
+
+        let report = replace_api_key_for_home(home, "sk-old", "sk-new").unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.updated.len(), 3);
+
+        assert_eq!(
+            secrets::get_secret_for_home(home, "team/key").unwrap(),
+            "sk-new"
+        );
+        let profile = claude::get_claude_profile_for_home(home, "p1").unwrap();
+        assert_eq!(profile.bearer_token.as_deref(), Some("sk-new"));
+        let models = factory_settings::load_custom_models_for_home(home).unwrap();
+        assert_eq!(models[0].api_key, "sk-new");
+        assert_eq!(models[1].api_key, "sk-other");
+    }
+
+    #[test]
+    fn only_replaces_whole_key_values() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let mut model = custom_model("sk-old");
+        model.display_name = Some("Team sk-old".to_string());
+        model.extra_headers = Some(
+            [("Authorization".to_string(), "Bearer sk-old".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        factory_settings::save_custom_models_for_home(home, vec![model]).unwrap();
+
+        let report = replace_api_key_for_home(home, "sk-old", "sk-new").unwrap();
+        assert_eq!(report.updated, vec!["Factory custom model 'Team sk-old'"]);
+        assert_eq!(
+            report.errors,
+            vec!["Factory settings file 'Global' still contains the old key"]
+        );
+
+        let saved = &factory_settings::load_custom_models_for_home(home).unwrap()[0];
+        assert_eq!(saved.api_key, "sk-new");
+        assert_eq!(saved.display_name.as_deref(), Some("Team sk-old"));
+        assert_eq!(
+            saved.extra_headers.as_ref().unwrap()["Authorization"],
+            "Bearer sk-new"
+        );
+    }
+
+    #[test]
+    fn replaces_key_in_non_global_settings_files() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let team = paths::droidgear_dir_from_home(home).join("droid-settings/team.json");
+        std::fs::create_dir_all(team.parent().unwrap()).unwrap();
+        std::fs::write(&team, "{}").unwrap();
+        droid_settings_files::set_active_settings_file_for_home(home, Some("team".to_string()))
+            .unwrap();
+        factory_settings::save_active_custom_models_for_home(home, vec![custom_model("sk-old")])
+            .unwrap();
+
+        let report = replace_api_key_for_home(home, "sk-old", "sk-new").unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(
+            report.updated,
+            vec!["Factory custom model 'GPT-4o' in settings file 'team'"]
+        );
+        let models = factory_settings::load_active_custom_models_for_home(home).unwrap();
+        assert_eq!(models[0].api_key, "sk-new");
+    }
+
+    #[test]
+    fn reapplies_active_profiles_that_reference_a_changed_secret() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        secrets::init_vault_for_home(home, "passphrase").unwrap();
+        let token_ref = secrets::set_secret_for_home(home, "team/key", "sk-old").unwrap();
+        claude::save_claude_profile_for_home(home, claude_profile(&token_ref)).unwrap();
+        claude::apply_claude_profile_for_home(home, "p1").unwrap();
+
+        let report = replace_api_key_for_home(home, "sk-old", "sk-new").unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(
+            report.updated,
+            vec!["secret 'team/key'", "live claude config"]
+        );
+        let live = std::fs::read_to_string(home.join(".claude/settings.json")).unwrap();
+        assert!(
+            live.contains("sk-new") && !live.contains("sk-old"),
+            "{live}"
+        );
+    }
+
+    #[test]
+    fn rotation_creates_replacement_then_disables_old_token() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        let old_token = r#"{"id":5,"name":"team","key":"sk-old","status":1,"remain_quota":1000,"used_quota":0,"unlimited_quota":false}"#;
+        let new_token = r#"{"id":6,"name":"team","key":"sk-new","status":1,"remain_quota":1000,"used_quota":0,"unlimited_quota":false}"#;
+        let (base, requests) = serve_recording(vec![
+            (200, r#"{"success":true,"data":{"id":1}}"#.to_string()),
+            (200, format!(r#"{{"success":true,"data":[{old_token}]}}"#)),
+            (200, r#"{"success":true,"message":""}"#.to_string()),
+            (
+                200,
+                format!(r#"{{"success":true,"data":[{old_token},{new_token}]}}"#),
+            ),
+            (200, r#"{"success":true,"message":""}"#.to_string()),
+        ]);
+        channel::save_channels_for_home(
+            home,
+            vec![Channel {
+                id: "c1".to_string(),
+                name: "Relay".to_string(),
+                channel_type: ChannelType::OneApi,
+                base_url: base,
+                enabled: true,
+                created_at: 0.0,
+            }],
+        )
+        .unwrap();
+        channel::save_channel_credentials_for_home(home, "c1", "admin", "pw").unwrap();
+        claude::save_claude_profile_for_home(home, claude_profile("sk-old")).unwrap();
+        claude::apply_claude_profile_for_home(home, "p1").unwrap();
+
+        let report = block_on(rotate_channel_token_for_home(
+            home,
+            "c1",
+            5.0,
+            RevokeMode::Disable,
+        ))
+        .unwrap();
+        assert_eq!(report.new_token.key, "sk-new");
+        assert!(report.revoked);
+        assert_eq!(
+            report.updated,
+            vec!["claude profile 'Relay'", "live claude config"]
+        );
+
+        let profile = claude::get_claude_profile_for_home(home, "p1").unwrap();
+        assert_eq!(profile.bearer_token.as_deref(), Some("sk-new"));
+        let live = std::fs::read_to_string(home.join(".claude/settings.json")).unwrap();
+        assert!(
+            live.contains("sk-new") && !live.contains("sk-old"),
+            "{live}"
+        );
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[2].starts_with("POST /api/token/"));
+        assert!(requests[2].contains(r#""remain_quota":1000"#));
+        assert!(requests[4].starts_with("PUT /api/token/?status_only=true"));
+        assert!(requests[4].contains(r#""status":2"#));
+    }
+}
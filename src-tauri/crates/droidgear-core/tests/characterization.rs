@@ -1,5 +1,5 @@
 use droidgear_core::{
-    claude, claude_runtime, codex, codex_runtime, droid_runtime, droid_settings_files,
+    backup, claude, claude_runtime, codex, codex_runtime, droid_runtime, droid_settings_files,
     factory_settings, mcp, openclaw, opencode, paths,
 };
 use serde_json::Value;
@@ -76,6 +76,7 @@ base_url = "https://api.openai.com/v1"
             model: Some("gpt-5.2".to_string()),
             model_reasoning_effort: Some("high".to_string()),
             api_key: Some("sk-test".to_string()),
+            channel_ref: None,
         },
     );
 
@@ -166,6 +167,67 @@ base_url = "https://api.openai.com/v1"
     assert_eq!(active.trim(), "test_profile");
 }
 
+#[test]
+fn codex_plan_apply_matches_apply_without_writing() {
+    let temp = TempDir::new().unwrap();
+    let home = home_dir(&temp);
+
+    let config_path = home.join(".codex").join("config.toml");
+    let auth_path = home.join(".codex").join("auth.json");
+    write_file(
+        &config_path,
+        "model = \"old-model\"\nnetwork_access = \"none\"\n",
+    );
+    write_file(&auth_path, r#"{ "OPENAI_API_KEY": "sk-old-key-123456" }"#);
+
+    let profile = codex::CodexProfile {
+        id: "plan_profile".to_string(),
+        name: "Plan".to_string(),
+        description: None,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        updated_at: "2026-01-01T00:00:00Z".to_string(),
+        providers: HashMap::new(),
+        model_provider: "openai".to_string(),
+        model: "new-model".to_string(),
+        model_reasoning_effort: None,
+        api_key: Some("sk-new-key-654321".to_string()),
+    };
+    codex::save_codex_profile_for_home(home, profile).unwrap();
+
+    let plan = codex::plan_apply_codex_profile_for_home(home, "plan_profile").unwrap();
+    assert_eq!(plan.tool, "codex");
+    assert!(plan.has_changes());
+
+    // Nothing is written by the plan
+    assert_eq!(
+        read_to_string(&config_path),
+        "model = \"old-model\"\nnetwork_access = \"none\"\n"
+    );
+    assert!(backup::list_backup_files_for_home(home).unwrap().is_empty());
+
+    let config_plan = &plan.files[0];
+    assert!(config_plan
+        .changes
+        .iter()
+        .any(|c| c.key == "model" && c.after.as_deref() == Some("\"new-model\"")));
+    let key_change = plan.files[1]
+        .changes
+        .iter()
+        .find(|c| c.key == "OPENAI_API_KEY")
+        .unwrap();
+    assert_eq!(key_change.after.as_deref(), Some("\"sk-****4321\""));
+
+    codex::apply_codex_profile_for_home(home, "plan_profile").unwrap();
+    assert_eq!(read_to_string(&config_path), config_plan.after);
+    // The plan masks the key; the written file carries the real one
+    assert_eq!(
+        read_to_string(&auth_path),
+        plan.files[1]
+            .after
+            .replace("sk-****4321", "sk-new-key-654321")
+    );
+}
+
 #[test]
 fn codex_apply_can_remove_openai_api_key_without_destroying_official_auth() {
     let temp = TempDir::new().unwrap();
@@ -283,6 +345,7 @@ fn claude_apply_preserves_unmanaged_settings_and_updates_active_profile() {
         thinking_mode: claude::ClaudeThinkingMode::On,
         created_at: "2026-01-01T00:00:00Z".to_string(),
         updated_at: "2026-01-01T00:00:00Z".to_string(),
+        channel_ref: None,
     };
     claude::save_claude_profile_for_home(home, profile).unwrap();
 
@@ -350,6 +413,7 @@ fn claude_temporary_run_plan_writes_overlay_tombstones_without_mutating_live_set
         thinking_mode: claude::ClaudeThinkingMode::Off,
         created_at: "2026-01-01T00:00:00Z".to_string(),
         updated_at: "2026-01-01T00:00:00Z".to_string(),
+        channel_ref: None,
     };
 
     let before_live = read_to_string(&settings_path);
@@ -476,6 +540,7 @@ model = "existing-live-model"
             model: Some("gpt-5.5".to_string()),
             model_reasoning_effort: Some("high".to_string()),
             api_key: Some("sk-temp".to_string()),
+            channel_ref: None,
         },
     );
 
@@ -710,6 +775,7 @@ fn openclaw_apply_updates_owned_fields_in_place_and_preserves_unknown_keys() {
                 context_window: Some(200000),
                 max_tokens: Some(8192),
             }],
+            channel_ref: None,
         },
     );
 
@@ -859,6 +925,7 @@ fn factory_save_custom_models_preserves_other_fields_and_errors_on_parse() {
         no_image_support: None,
         extra_args: None,
         extra_headers: None,
+        channel_ref: None,
     };
 
     factory_settings::save_custom_models_for_home(home, vec![model]).unwrap();
@@ -882,6 +949,31 @@ fn factory_save_custom_models_preserves_other_fields_and_errors_on_parse() {
     assert!(err.starts_with(factory_settings::CONFIG_PARSE_ERROR_PREFIX));
 }
 
+#[test]
+fn factory_write_snapshots_previous_settings_and_restore_reverts() {
+    let temp = TempDir::new().unwrap();
+    let home = home_dir(&temp);
+    let settings_path = factory_settings_path(home);
+
+    let original = r#"{ "cloudSessionSync": false, "customModels": [] }"#;
+    write_file(&settings_path, original);
+
+    factory_settings::save_cloud_session_sync_for_home(home, true).unwrap();
+    let backups = backup::list_backups_for_home(home, &settings_path).unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].source, "factory");
+
+    // Unchanged content is not snapshotted twice
+    factory_settings::save_cloud_session_sync_for_home(home, true).unwrap();
+    factory_settings::save_cloud_session_sync_for_home(home, true).unwrap();
+    let backups = backup::list_backups_for_home(home, &settings_path).unwrap();
+    assert_eq!(backups.len(), 2);
+
+    let oldest = backups.last().unwrap();
+    backup::restore_backup_for_home(home, &oldest.id).unwrap();
+    assert_eq!(read_to_string(&settings_path), original);
+}
+
 #[test]
 fn mcp_toggle_missing_server_returns_error() {
     let temp = TempDir::new().unwrap();
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use droidgear_core::{
+    backup::BackupEntry,
     channel::Channel,
     claude::ClaudeCodeProfile,
     codex::CodexProfile,
@@ -13,6 +14,8 @@ use droidgear_core::{
     opencode::OpenCodeProfile,
     paths::{EffectivePath, EffectivePaths},
     pi::PiProfile,
+    quota_monitor::ChannelQuotaStatus,
+    session_analytics::{UsageDimension, UsageReport},
     sessions::SessionSummary,
     specs::SpecFile,
 };
@@ -52,7 +55,9 @@ pub enum Screen {
     HermesProfile,
     HermesProvider,
     Sessions,
+    Usage,
     Specs,
+    Backups,
     Channels,
     ChannelsEdit,
     Missions,
@@ -158,12 +163,18 @@ pub enum ConfirmAction {
     FactoryDeleteModel {
         index: usize,
     },
+    FactoryApplyModelPatch {
+        patch: Box<droidgear_core::model_autofill::ModelPatch>,
+    },
     SessionDelete {
         path: String,
     },
     SpecDelete {
         path: String,
     },
+    BackupRestore {
+        id: String,
+    },
     ChannelDelete {
         id: String,
     },
@@ -558,6 +569,8 @@ pub enum SelectAction {
         profile_id: String,
         provider_id: String,
     },
+    ChannelProvisionSetToken,
+    ChannelProvisionToggleModel,
     PiImportFromChannel {
         profile_id: String,
         provider_id: String,
@@ -717,7 +730,7 @@ pub struct App {
     pub pi_import_pending_provider_id: Option<String>,
     /// Pending models fetched from channel (for model selection in import flow)
     pub pi_import_pending_models: Option<Vec<droidgear_core::factory_settings::ModelInfo>>,
-    /// Selected indices in pending models
+    /// Selected indices in pending models (synced by the MultiSelect modal)
     pub pi_import_pending_selected: Option<Vec<bool>>,
     /// Resolved API key for pending channel import
     pub pi_import_pending_api_key: Option<String>,
@@ -725,13 +738,25 @@ pub struct App {
     pub pi_import_pending_tokens: Option<Vec<droidgear_core::channel::ChannelToken>>,
     /// Inferred API type for pending channel import
     pub pi_import_pending_api_type: Option<String>,
+    /// Temporary state used during the Channels "provision profiles" flow in TUI
+    pub channel_provision_channel: Option<droidgear_core::channel::Channel>,
+    pub channel_provision_tokens: Option<Vec<droidgear_core::channel::ChannelToken>>,
+    pub channel_provision_token: Option<droidgear_core::channel::ChannelToken>,
+    pub channel_provision_models: Option<Vec<droidgear_core::factory_settings::ModelInfo>>,
 
     pub sessions: Vec<SessionSummary>,
     pub sessions_index: usize,
 
+    pub usage_report: Option<UsageReport>,
+    pub usage_dimension: UsageDimension,
+    pub usage_index: usize,
+
     pub specs: Vec<SpecFile>,
     pub specs_index: usize,
 
+    pub backups: Vec<BackupEntry>,
+    pub backups_index: usize,
+
     pub channels: Vec<Channel>,
     pub channels_index: usize,
     pub channels_edit_draft: Option<Channel>,
@@ -740,6 +765,9 @@ pub struct App {
     pub channels_edit_password: String,
     pub channels_edit_api_key: String,
 
+    /// Latest quota status per enabled channel (updated by the background monitor)
+    pub quota_statuses: Vec<ChannelQuotaStatus>,
+
     pub mission_settings: MissionModelSettings,
     pub mission_field_index: usize,
 
@@ -855,10 +883,19 @@ impl App {
             pi_import_pending_api_key: None,
             pi_import_pending_tokens: None,
             pi_import_pending_api_type: None,
+            channel_provision_channel: None,
+            channel_provision_tokens: None,
+            channel_provision_token: None,
+            channel_provision_models: None,
             sessions: Vec::new(),
             sessions_index: 0,
+            usage_report: None,
+            usage_dimension: UsageDimension::default(),
+            usage_index: 0,
             specs: Vec::new(),
             specs_index: 0,
+            backups: Vec::new(),
+            backups_index: 0,
             channels: Vec::new(),
             channels_index: 0,
             channels_edit_draft: None,
@@ -866,6 +903,7 @@ impl App {
             channels_edit_username: String::new(),
             channels_edit_password: String::new(),
             channels_edit_api_key: String::new(),
+            quota_statuses: Vec::new(),
             mission_settings: MissionModelSettings {
                 worker_model: None,
                 worker_reasoning_effort: None,
@@ -892,7 +930,9 @@ impl App {
             ("Pi", Screen::Pi),
             ("Hermes", Screen::Hermes),
             ("Sessions", Screen::Sessions),
+            ("Usage", Screen::Usage),
             ("Specs", Screen::Specs),
+            ("Backups", Screen::Backups),
             ("Channels", Screen::Channels),
             ("Missions", Screen::Missions),
             ("Factory Auth", Screen::FactoryAuth),
@@ -910,6 +950,21 @@ impl App {
         self.toast = None;
     }
 
+    /// One-line summary of active quota alerts, most severe first.
+    pub fn quota_status_line(&self) -> Option<String> {
+        let mut alerts: Vec<_> = self
+            .quota_statuses
+            .iter()
+            .filter_map(|s| s.alert.as_ref())
+            .collect();
+        alerts.sort_by_key(|a| a.kind as u8);
+        let first = alerts.first()?;
+        Some(match alerts.len() {
+            1 => format!("Quota: {}", first.message),
+            n => format!("Quota: {} (+{} more)", first.message, n - 1),
+        })
+    }
+
     /// Get the provider ID at the current pi_provider_index.
     pub fn pi_current_provider_id(&self) -> Option<String> {
         let detail = self.pi_detail.as_ref()?;
@@ -1161,9 +1216,19 @@ impl App {
         if self.sessions_index >= self.sessions.len() {
             self.sessions_index = self.sessions.len().saturating_sub(1);
         }
+        let usage_count = self
+            .usage_report
+            .as_ref()
+            .map_or(0, |r| r.buckets(self.usage_dimension).len());
+        if self.usage_index >= usage_count {
+            self.usage_index = usage_count.saturating_sub(1);
+        }
         if self.specs_index >= self.specs.len() {
             self.specs_index = self.specs.len().saturating_sub(1);
         }
+        if self.backups_index >= self.backups.len() {
+            self.backups_index = self.backups.len().saturating_sub(1);
+        }
         if self.channels_index >= self.channels.len() {
             self.channels_index = self.channels.len().saturating_sub(1);
         }
@@ -1171,12 +1236,7 @@ impl App {
             .channels_edit_draft
             .as_ref()
             .map(|c| {
-                let uses_api_key = matches!(
-                    c.channel_type,
-                    droidgear_core::channel::ChannelType::CliProxyApi
-                        | droidgear_core::channel::ChannelType::Ollama
-                        | droidgear_core::channel::ChannelType::General
-                );
+                let uses_api_key = !c.channel_type.uses_login();
                 if uses_api_key {
                     5
                 } else {
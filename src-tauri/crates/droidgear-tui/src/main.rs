@@ -1,5 +1,7 @@
 mod app;
 mod editor;
+mod profile_cli;
+mod session_cli;
 mod tui;
 mod ui;
 
@@ -27,6 +29,16 @@ enum Command {
         #[command(subcommand)]
         target: RunTarget,
     },
+    /// Manage Codex/Claude/OpenCode/OpenClaw/Pi/Hermes profiles without the TUI
+    Profile {
+        #[command(subcommand)]
+        action: profile_cli::ProfileAction,
+    },
+    /// Export agent sessions and report their token usage without the TUI
+    Session {
+        #[command(subcommand)]
+        action: session_cli::SessionAction,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -128,6 +140,8 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         },
+        Some(Command::Profile { action }) => profile_cli::run(&home_dir, action),
+        Some(Command::Session { action }) => session_cli::run(&home_dir, action),
         None => {
             let mut app = app::App::new(home_dir);
             tui::run(&mut app)
@@ -137,8 +151,10 @@ fn main() -> anyhow::Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Cli, Command, RunTarget};
+    use super::{profile_cli::ProfileAction, session_cli::SessionAction, Cli, Command, RunTarget};
     use clap::Parser;
+    use droidgear_core::profiles::ProfileTool;
+    use droidgear_core::session_export::SessionExportFormat;
     use std::path::PathBuf;
 
     #[test]
@@ -295,4 +311,104 @@ mod tests {
             _ => panic!("expected claude preview subcommand"),
         }
     }
+
+    #[test]
+    fn cli_parses_profile_subcommands() {
+        let cli = Cli::parse_from([
+            "droidgear-tui",
+            "profile",
+            "list",
+            "openclaw",
+            "--json",
+            "--home",
+            "/tmp/demo-home",
+        ]);
+
+        assert_eq!(cli.home, Some(PathBuf::from("/tmp/demo-home")));
+        match cli.command {
+            Some(Command::Profile {
+                action: ProfileAction::List { tool, json },
+            }) => {
+                assert_eq!(tool, ProfileTool::OpenClaw);
+                assert!(json);
+            }
+            _ => panic!("expected profile list subcommand"),
+        }
+
+        let cli = Cli::parse_from([
+            "droidgear-tui",
+            "profile",
+            "duplicate",
+            "pi",
+            "2",
+            "--name",
+            "Staging",
+        ]);
+        match cli.command {
+            Some(Command::Profile {
+                action:
+                    ProfileAction::Duplicate {
+                        tool,
+                        profile,
+                        name,
+                        json,
+                    },
+            }) => {
+                assert_eq!(tool, ProfileTool::Pi);
+                assert_eq!(profile, "2");
+                assert_eq!(name.as_deref(), Some("Staging"));
+                assert!(!json);
+            }
+            _ => panic!("expected profile duplicate subcommand"),
+        }
+
+        assert!(Cli::try_parse_from(["droidgear-tui", "profile", "list", "droid"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_session_export_subcommand() {
+        let cli = Cli::parse_from([
+            "droidgear-tui",
+            "session",
+            "export",
+            "5f2c",
+            "--format",
+            "html",
+            "-o",
+            "~/exports",
+            "--tools",
+        ]);
+
+        match cli.command {
+            Some(Command::Session {
+                action:
+                    SessionAction::Export {
+                        session,
+                        format,
+                        output,
+                        thinking,
+                        tools,
+                        no_redact,
+                    },
+            }) => {
+                assert_eq!(session, "5f2c");
+                assert_eq!(format, SessionExportFormat::Html);
+                assert_eq!(output.as_deref(), Some("~/exports"));
+                assert!(!thinking);
+                assert!(tools);
+                assert!(!no_redact);
+            }
+            _ => panic!("expected session export subcommand"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "droidgear-tui",
+            "session",
+            "export",
+            "x",
+            "--format",
+            "pdf"
+        ])
+        .is_err());
+    }
 }
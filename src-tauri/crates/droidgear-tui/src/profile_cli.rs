@@ -0,0 +1,361 @@
+//! Headless `droidgear-tui profile ...` subcommands.
+
+use anyhow::Context;
+use clap::Subcommand;
+use droidgear_core::apply_plan::{self, ApplyPlan, KeyChangeKind};
+use droidgear_core::profiles::{self, ProfileSummary, ProfileTool};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Subcommand)]
+pub enum ProfileAction {
+    /// List the profiles of a tool (`*` marks the active profile)
+    List {
+        #[arg(value_parser = parse_tool)]
+        tool: ProfileTool,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a profile by index, exact name, or profile id (secrets masked)
+    Show {
+        #[arg(value_parser = parse_tool)]
+        tool: ProfileTool,
+        profile: String,
+        #[arg(long)]
+        json: bool,
+        /// Print API keys and tokens unmasked
+        #[arg(long)]
+        show_secrets: bool,
+    },
+    /// Apply a profile to the tool's live config
+    Apply {
+        #[arg(value_parser = parse_tool)]
+        tool: ProfileTool,
+        profile: String,
+        /// Print the key-level changes without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Delete a profile
+    Delete {
+        #[arg(value_parser = parse_tool)]
+        tool: ProfileTool,
+        profile: String,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Copy a profile under a new name (default: "<name> (copy)")
+    Duplicate {
+        #[arg(value_parser = parse_tool)]
+        tool: ProfileTool,
+        profile: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn parse_tool(value: &str) -> Result<ProfileTool, String> {
+    ProfileTool::parse(value)
+}
+
+/// Result of a state-changing action in `--json` mode
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ActionOutput<'a> {
+    action: &'a str,
+    profile: &'a ProfileSummary,
+}
+
+pub fn run(home_dir: &Path, action: ProfileAction) -> anyhow::Result<()> {
+    println!("{}", render(home_dir, action)?);
+    Ok(())
+}
+
+/// Runs `action` and returns what should be printed.
+pub fn render(home_dir: &Path, action: ProfileAction) -> anyhow::Result<String> {
+    match action {
+        ProfileAction::List { tool, json } => {
+            let profiles =
+                profiles::list_profiles_for_home(home_dir, tool).map_err(anyhow::Error::msg)?;
+            if json {
+                return to_json(&profiles);
+            }
+            Ok(format_profile_list(tool, &profiles))
+        }
+        ProfileAction::Show {
+            tool,
+            profile,
+            json,
+            show_secrets,
+        } => {
+            let summary = resolve(home_dir, tool, &profile)?;
+            let mut value = profiles::get_profile_value_for_home(home_dir, tool, &summary.id)
+                .map_err(anyhow::Error::msg)?;
+            if !show_secrets {
+                value = apply_plan::mask_secrets(&value);
+            }
+            let body = serde_json::to_string_pretty(&value).context("serialize profile")?;
+            if json {
+                return Ok(body);
+            }
+            Ok(format!(
+                "{} profile '{}' [id: {}]\n\n{body}",
+                tool.as_str(),
+                summary.name,
+                summary.id
+            ))
+        }
+        ProfileAction::Apply {
+            tool,
+            profile,
+            dry_run,
+            json,
+        } => {
+            let summary = resolve(home_dir, tool, &profile)?;
+            if dry_run {
+                let plan = profiles::plan_apply_profile_for_home(home_dir, tool, &summary.id)
+                    .map_err(anyhow::Error::msg)?;
+                if json {
+                    return to_json(&redact_plan(plan));
+                }
+                return Ok(format_plan(&summary, &plan));
+            }
+            profiles::apply_profile_for_home(home_dir, tool, &summary.id)
+                .map_err(anyhow::Error::msg)?;
+            let applied = ProfileSummary {
+                active: true,
+                ..summary
+            };
+            if json {
+                return to_json(&ActionOutput {
+                    action: "apply",
+                    profile: &applied,
+                });
+            }
+            Ok(format!(
+                "Applied {} profile '{}' [id: {}]",
+                tool.as_str(),
+                applied.name,
+                applied.id
+            ))
+        }
+        ProfileAction::Delete {
+            tool,
+            profile,
+            json,
+        } => {
+            let summary = resolve(home_dir, tool, &profile)?;
+            profiles::delete_profile_for_home(home_dir, tool, &summary.id)
+                .map_err(anyhow::Error::msg)?;
+            if json {
+                return to_json(&ActionOutput {
+                    action: "delete",
+                    profile: &summary,
+                });
+            }
+            Ok(format!(
+                "Deleted {} profile '{}' [id: {}]",
+                tool.as_str(),
+                summary.name,
+                summary.id
+            ))
+        }
+        ProfileAction::Duplicate {
+            tool,
+            profile,
+            name,
+            json,
+        } => {
+            let summary = resolve(home_dir, tool, &profile)?;
+            let name = name.unwrap_or_else(|| format!("{} (copy)", summary.name));
+            let copy = profiles::duplicate_profile_for_home(home_dir, tool, &summary.id, &name)
+                .map_err(anyhow::Error::msg)?;
+            if json {
+                return to_json(&ActionOutput {
+                    action: "duplicate",
+                    profile: &copy,
+                });
+            }
+            Ok(format!(
+                "Duplicated {} profile '{}' as '{}' [id: {}]",
+                tool.as_str(),
+                summary.name,
+                copy.name,
+                copy.id
+            ))
+        }
+    }
+}
+
+fn resolve(home_dir: &Path, tool: ProfileTool, selector: &str) -> anyhow::Result<ProfileSummary> {
+    profiles::resolve_profile_selector_for_home(home_dir, tool, selector)
+        .map_err(anyhow::Error::msg)
+}
+
+fn to_json<T: Serialize>(value: &T) -> anyhow::Result<String> {
+    serde_json::to_string_pretty(value).context("serialize output")
+}
+
+fn format_profile_list(tool: ProfileTool, profiles: &[ProfileSummary]) -> String {
+    let mut out = format!("{} profiles:\n", tool.as_str());
+    if profiles.is_empty() {
+        out.push_str("(none)");
+        return out;
+    }
+    for (index, profile) in profiles.iter().enumerate() {
+        let marker = if profile.active { "*" } else { " " };
+        out.push_str(&format!(
+            "{marker} {}. {} [id: {}]\n",
+            index + 1,
+            profile.name,
+            profile.id
+        ));
+    }
+    out.push_str("\n`*` marks the currently active profile.");
+    out
+}
+
+/// Drops the file contents so `--json` output carries only the (masked) key changes.
+fn redact_plan(mut plan: ApplyPlan) -> ApplyPlan {
+    for file in &mut plan.files {
+        file.before = None;
+        file.after.clear();
+    }
+    plan
+}
+
+fn format_plan(summary: &ProfileSummary, plan: &ApplyPlan) -> String {
+    let mut out = format!(
+        "Dry run: applying {} profile '{}' would write:\n",
+        summary.tool.as_str(),
+        summary.name
+    );
+    for file in &plan.files {
+        let state = if file.changed { "changed" } else { "unchanged" };
+        out.push_str(&format!("\n{} ({state})\n", file.path));
+        for c in &file.changes {
+            let none = "-";
+            let line = match c.kind {
+                KeyChangeKind::Added => {
+                    format!("  + {}: {}", c.key, c.after.as_deref().unwrap_or(none))
+                }
+                KeyChangeKind::Removed => {
+                    format!("  - {}: {}", c.key, c.before.as_deref().unwrap_or(none))
+                }
+                KeyChangeKind::Changed => format!(
+                    "  ~ {}: {} -> {}",
+                    c.key,
+                    c.before.as_deref().unwrap_or(none),
+                    c.after.as_deref().unwrap_or(none)
+                ),
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use droidgear_core::hermes::{self, HermesModelConfig, HermesProfile};
+
+    fn save_hermes(home: &Path, name: &str) {
+        hermes::save_hermes_profile_for_home(
+            home,
+            HermesProfile {
+                id: String::new(),
+                name: name.to_string(),
+                description: None,
+                created_at: String::new(),
+                updated_at: String::new(),
+                model: HermesModelConfig {
+                    default: Some("gpt-5".to_string()),
+                    provider: Some("custom".to_string()),
+                    base_url: Some("https://relay.example/v1".to_string()),
+                    api_key: Some("sk-hermes-secret-1234".to_string()),
+                    channel_ref: None,
+                },
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn manages_profiles_headlessly() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let home = dir.path();
+        save_hermes(home, "Work");
+
+        let shown = render(
+            home,
+            ProfileAction::Show {
+                tool: ProfileTool::Hermes,
+                profile: "work".to_string(),
+                json: true,
+                show_secrets: false,
+            },
+        )
+        .unwrap();
+        assert!(shown.contains("sk-****1234"));
+        assert!(!shown.contains("sk-hermes-secret-1234"));
+
+        render(
+            home,
+            ProfileAction::Duplicate {
+                tool: ProfileTool::Hermes,
+                profile: "1".to_string(),
+                name: None,
+                json: false,
+            },
+        )
+        .unwrap();
+        let listed = render(
+            home,
+            ProfileAction::List {
+                tool: ProfileTool::Hermes,
+                json: true,
+            },
+        )
+        .unwrap();
+        let listed: Vec<serde_json::Value> = serde_json::from_str(&listed).unwrap();
+        let mut names: Vec<&str> = listed.iter().map(|p| p["name"].as_str().unwrap()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Work", "Work (copy)"]);
+
+        let plan = render(
+            home,
+            ProfileAction::Apply {
+                tool: ProfileTool::Hermes,
+                profile: "Work (copy)".to_string(),
+                dry_run: true,
+                json: false,
+            },
+        )
+        .unwrap();
+        assert!(plan.starts_with("Dry run"));
+        assert!(!plan.contains("sk-hermes-secret-1234"));
+
+        let deleted = render(
+            home,
+            ProfileAction::Delete {
+                tool: ProfileTool::Hermes,
+                profile: "Work (copy)".to_string(),
+                json: true,
+            },
+        )
+        .unwrap();
+        assert!(deleted.contains("\"action\": \"delete\""));
+        assert_eq!(
+            profiles::list_profiles_for_home(home, ProfileTool::Hermes)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
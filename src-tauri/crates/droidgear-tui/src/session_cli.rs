@@ -0,0 +1,352 @@
+//! Headless `droidgear-tui session ...` subcommands.
+
+use anyhow::{bail, Context};
+use clap::Subcommand;
+use droidgear_core::session_analytics::{
+    self, UsageDimension, UsageQuery, UsageReport, UsageTotals,
+};
+use droidgear_core::session_export::{self, SessionExportFormat, SessionExportOptions};
+use droidgear_core::sessions::{self, SessionSource, SessionSummary};
+use std::path::Path;
+
+#[derive(Debug, Subcommand)]
+pub enum SessionAction {
+    /// Export a session (by path, ID or unique ID prefix) to Markdown, HTML or
+    /// a JSON bundle; prints to stdout unless `--output` is given
+    Export {
+        session: String,
+        #[arg(long, default_value = "markdown", value_parser = parse_format)]
+        format: SessionExportFormat,
+        /// Output file or directory (`~` expands to home)
+        #[arg(long, short)]
+        output: Option<String>,
+        /// Include thinking blocks
+        #[arg(long)]
+        thinking: bool,
+        /// Include tool calls and results
+        #[arg(long)]
+        tools: bool,
+        /// Keep API keys, emails and home paths unmasked
+        #[arg(long)]
+        no_redact: bool,
+    },
+    /// Show token usage and estimated cost, broken down by day, week,
+    /// project, model or agent
+    Usage {
+        #[arg(long, default_value = "project", value_parser = parse_dimension)]
+        by: UsageDimension,
+        /// Only sessions of this agent (droid, claude, codex, opencode, pi)
+        #[arg(long, value_parser = parse_source)]
+        agent: Option<SessionSource>,
+        /// Only sessions of this project (e.g. `-work-api`)
+        #[arg(long)]
+        project: Option<String>,
+        /// First day included (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<String>,
+        /// Last day included (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<String>,
+        /// Print the full report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn parse_format(value: &str) -> Result<SessionExportFormat, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "markdown" | "md" => Ok(SessionExportFormat::Markdown),
+        "html" => Ok(SessionExportFormat::Html),
+        "bundle" | "json" => Ok(SessionExportFormat::Bundle),
+        other => Err(format!(
+            "unknown format '{other}' (expected markdown, html or bundle)"
+        )),
+    }
+}
+
+fn parse_dimension(value: &str) -> Result<UsageDimension, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "day" => Ok(UsageDimension::Day),
+        "week" => Ok(UsageDimension::Week),
+        "project" => Ok(UsageDimension::Project),
+        "model" => Ok(UsageDimension::Model),
+        "agent" | "source" => Ok(UsageDimension::Source),
+        other => Err(format!(
+            "unknown breakdown '{other}' (expected day, week, project, model or agent)"
+        )),
+    }
+}
+
+fn parse_source(value: &str) -> Result<SessionSource, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "droid" | "factory" => Ok(SessionSource::Factory),
+        "claude" => Ok(SessionSource::Claude),
+        "codex" => Ok(SessionSource::Codex),
+        "opencode" => Ok(SessionSource::Opencode),
+        "pi" => Ok(SessionSource::Pi),
+        other => Err(format!(
+            "unknown agent '{other}' (expected droid, claude, codex, opencode or pi)"
+        )),
+    }
+}
+
+pub fn run(home_dir: &Path, action: SessionAction) -> anyhow::Result<()> {
+    println!("{}", render(home_dir, action)?);
+    Ok(())
+}
+
+/// Runs `action` and returns what should be printed.
+pub fn render(home_dir: &Path, action: SessionAction) -> anyhow::Result<String> {
+    match action {
+        SessionAction::Export {
+            session,
+            format,
+            output,
+            thinking,
+            tools,
+            no_redact,
+        } => {
+            let session_path = resolve(home_dir, &session)?;
+            let options = SessionExportOptions {
+                format,
+                include_thinking: thinking,
+                include_tools: tools,
+                redact: !no_redact,
+            };
+            let Some(output) = output else {
+                let export =
+                    session_export::export_session_for_home(home_dir, &session_path, &options)
+                        .map_err(anyhow::Error::msg)?;
+                return Ok(export.content.trim_end().to_string());
+            };
+            let export = session_export::export_session_to_file_for_home(
+                home_dir,
+                &session_path,
+                &output,
+                &options,
+            )
+            .map_err(anyhow::Error::msg)?;
+            Ok(format!(
+                "Exported session to {} ({} redactions)",
+                export.output_path.unwrap_or_default(),
+                export.redactions
+            ))
+        }
+        SessionAction::Usage {
+            by,
+            agent,
+            project,
+            from,
+            to,
+            json,
+        } => {
+            let query = UsageQuery {
+                source: agent,
+                project,
+                from,
+                to,
+                utc_offset_minutes: None,
+            };
+            let report = session_analytics::usage_report_for_home(home_dir, &query)
+                .map_err(anyhow::Error::msg)?;
+            if json {
+                return serde_json::to_string_pretty(&report).context("serialize output");
+            }
+            Ok(format_usage_report(&report, by))
+        }
+    }
+}
+
+/// Token count shortened to K / M.
+pub(crate) fn format_tokens(tokens: f64) -> String {
+    if tokens >= 1_000_000.0 {
+        format!("{:.1}M", tokens / 1_000_000.0)
+    } else if tokens >= 1_000.0 {
+        format!("{:.1}K", tokens / 1_000.0)
+    } else {
+        format!("{tokens:.0}")
+    }
+}
+
+/// Header of the usage table, with `key_label` as the first column.
+pub(crate) fn usage_table_header(key_label: &str) -> String {
+    format!(
+        "{key_label:<28} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8} {:>6} {:>10}",
+        "SESSIONS", "INPUT", "OUTPUT", "THINKING", "CACHE-R", "CACHE-W", "HIT", "COST"
+    )
+}
+
+/// One usage table row; unpriced sessions are flagged with `*` after the cost.
+pub(crate) fn usage_table_row(key: &str, totals: &UsageTotals) -> String {
+    let key = if key.chars().count() > 28 {
+        let tail: String = key.chars().skip(key.chars().count() - 27).collect();
+        format!("…{tail}")
+    } else {
+        key.to_string()
+    };
+    let unpriced = if totals.unpriced_sessions > 0 {
+        "*"
+    } else {
+        " "
+    };
+    format!(
+        "{key:<28} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8} {:>5.0}% {:>9}{unpriced}",
+        totals.sessions,
+        format_tokens(totals.input_tokens),
+        format_tokens(totals.output_tokens),
+        format_tokens(totals.thinking_tokens),
+        format_tokens(totals.cache_read_tokens),
+        format_tokens(totals.cache_creation_tokens),
+        totals.cache_hit_ratio * 100.0,
+        format!("${:.2}", totals.cost),
+    )
+}
+
+fn format_usage_report(report: &UsageReport, by: UsageDimension) -> String {
+    let buckets = report.buckets(by);
+    if buckets.is_empty() {
+        return "No sessions with token usage".to_string();
+    }
+    let mut lines = vec![usage_table_header(&by.label().to_uppercase())];
+    for bucket in buckets {
+        lines.push(usage_table_row(&bucket.key, &bucket.totals));
+    }
+    lines.push(usage_table_row("TOTAL", &report.totals));
+    if !report.unpriced_models.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+            "* Not included in cost, no price for: {}",
+            report.unpriced_models.join(", ")
+        ));
+        lines.push(
+            "  Set prices (USD per million tokens) in ~/.droidgear/model-prices.json".to_string(),
+        );
+    }
+    lines.join("\n")
+}
+
+/// Session path for a selector: an existing session path, an exact session
+/// ID, or a unique ID prefix.
+fn resolve(home_dir: &Path, selector: &str) -> anyhow::Result<String> {
+    if sessions::get_session_detail_for_home(home_dir, selector).is_ok() {
+        return Ok(selector.to_string());
+    }
+    let all =
+        sessions::list_agent_sessions_for_home(home_dir, None, None).map_err(anyhow::Error::msg)?;
+    if let Some(exact) = all.iter().find(|s| s.id == selector) {
+        return Ok(exact.path.clone());
+    }
+    let matches: Vec<&SessionSummary> = all.iter().filter(|s| s.id.starts_with(selector)).collect();
+    match matches.as_slice() {
+        [only] => Ok(only.path.clone()),
+        [] => bail!("No session matches '{selector}'"),
+        many => {
+            let ids: Vec<&str> = many.iter().map(|s| s.id.as_str()).collect();
+            bail!(
+                "'{selector}' matches several sessions ({}); use a longer ID prefix or the session path",
+                ids.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn exports_sessions_headlessly() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let home = dir.path();
+        let project = home.join(".factory/sessions/-work-api");
+        fs::create_dir_all(&project).unwrap();
+        let lines = [
+            serde_json::json!({ "type": "session_start", "id": "5f2c-aa", "sessionTitle": "Rotate keys" }),
+            serde_json::json!({ "type": "message", "id": "m1", "timestamp": "2026-01-10T10:00:00Z",
+                "message": { "role": "user", "content": [
+                    { "type": "text", "text": "Old key was sk-live-0123456789abcdef, ping me@corp.example" }
+                ] } }),
+        ];
+        let body: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        fs::write(project.join("5f2c-aa.jsonl"), body.join("\n")).unwrap();
+
+        let export = |session: &str, output: Option<String>, no_redact: bool| {
+            render(
+                home,
+                SessionAction::Export {
+                    session: session.to_string(),
+                    format: SessionExportFormat::Markdown,
+                    output,
+                    thinking: false,
+                    tools: false,
+                    no_redact,
+                },
+            )
+        };
+
+        let printed = export("5f2c", None, false).unwrap();
+        assert!(printed.starts_with("# Rotate keys"));
+        assert!(printed.contains("Old key was sk-****cdef, ping [redacted-email]"));
+
+        let raw = export("5f2c-aa", None, true).unwrap();
+        assert!(raw.contains("sk-live-0123456789abcdef"));
+
+        let out = home.join("report.md");
+        let written = export("5f2c", Some(out.to_string_lossy().to_string()), false).unwrap();
+        assert!(written.contains("(2 redactions)"));
+        assert!(fs::read_to_string(&out)
+            .unwrap()
+            .starts_with("# Rotate keys"));
+
+        assert!(export("nope", None, false).is_err());
+    }
+
+    #[test]
+    fn prints_usage_table() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let home = dir.path();
+        let project = home.join(".factory/sessions/-work-api");
+        fs::create_dir_all(&project).unwrap();
+        let start =
+            serde_json::json!({ "type": "session_start", "id": "s1", "sessionTitle": "Ship it" });
+        fs::write(project.join("s1.jsonl"), start.to_string()).unwrap();
+        let settings = serde_json::json!({ "model": "claude-sonnet-4-5-20250929", "tokenUsage": {
+            "inputTokens": 1_000_000, "outputTokens": 200_000, "cacheReadTokens": 1_000_000 } });
+        fs::write(project.join("s1.settings.json"), settings.to_string()).unwrap();
+        fs::create_dir_all(home.join(".droidgear")).unwrap();
+        fs::write(
+            home.join(".droidgear/model-prices.json"),
+            r#"{ "claude-sonnet-4-5": { "input": 3, "output": 15, "cacheRead": 0.3 } }"#,
+        )
+        .unwrap();
+
+        let usage = |by: UsageDimension, json: bool| {
+            render(
+                home,
+                SessionAction::Usage {
+                    by,
+                    agent: None,
+                    project: None,
+                    from: None,
+                    to: None,
+                    json,
+                },
+            )
+            .unwrap()
+        };
+
+        let table = usage(UsageDimension::Project, false);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("PROJECT"));
+        assert!(lines[1].starts_with("-work-api"));
+        assert!(lines[1].contains("1.0M"));
+        assert!(lines[1].contains("50%"));
+        assert!(lines[1].contains("$6.30"));
+        assert!(lines[2].starts_with("TOTAL"));
+
+        let report: serde_json::Value =
+            serde_json::from_str(&usage(UsageDimension::Model, true)).unwrap();
+        assert_eq!(report["byModel"][0]["key"], "claude-sonnet-4-5-20250929");
+    }
+}
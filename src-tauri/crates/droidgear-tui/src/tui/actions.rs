@@ -3,6 +3,103 @@ use super::*;
 pub(super) fn run_action(app: &mut app::App, action: Action) -> anyhow::Result<()> {
     match action {
         Action::EditFactoryModels => edit_factory_models(app),
+        Action::TestFactoryModels => {
+            let config = droidgear_core::connectivity::get_test_config_for_home(&app.home_dir)
+                .map_err(anyhow::Error::msg)?;
+            let results =
+                droidgear_core::connectivity::test_all_model_connections_blocking_for_home(
+                    &app.home_dir,
+                    droidgear_core::connectivity::TestMode::Ping,
+                    None,
+                    config,
+                )
+                .map_err(anyhow::Error::msg)?;
+            open_text_in_pager(&format_connectivity_results(&results))?;
+            Ok(())
+        }
+        Action::TestProfileConnections { tool, id } => {
+            let config = droidgear_core::connectivity::get_test_config_for_home(&app.home_dir)
+                .map_err(anyhow::Error::msg)?;
+            let results =
+                droidgear_core::profile_connectivity::test_profile_connections_blocking_for_home(
+                    &app.home_dir,
+                    tool,
+                    &id,
+                    droidgear_core::connectivity::TestMode::Ping,
+                    None,
+                    config,
+                )
+                .map_err(anyhow::Error::msg)?;
+            open_text_in_pager(&format_profile_test_results(&results))?;
+            Ok(())
+        }
+        Action::ProbeFactoryModel { index } => {
+            let model = app
+                .custom_models
+                .get(index)
+                .cloned()
+                .context("No custom model selected")?;
+            let config = droidgear_core::connectivity::get_test_config_for_home(&app.home_dir)
+                .map_err(anyhow::Error::msg)?;
+            let report = droidgear_core::connectivity::probe_custom_model_capabilities_blocking(
+                &model, config,
+            )
+            .map_err(anyhow::Error::msg)?;
+            open_text_in_pager(&format_capability_report(&report))?;
+            Ok(())
+        }
+        Action::AutofillFactoryModel { index } => {
+            let model = app
+                .custom_models
+                .get(index)
+                .cloned()
+                .context("No custom model selected")?;
+            let config = droidgear_core::connectivity::get_test_config_for_home(&app.home_dir)
+                .map_err(anyhow::Error::msg)?;
+            let patch = droidgear_core::model_autofill::propose_model_patch_blocking(
+                &app.home_dir,
+                &model,
+                config,
+            )
+            .map_err(anyhow::Error::msg)?;
+            if patch.is_empty() {
+                app.set_toast("Probes found nothing to change", false);
+                return Ok(());
+            }
+            let plan =
+                droidgear_core::model_autofill::plan_model_patch_for_home(&app.home_dir, &patch)
+                    .map_err(anyhow::Error::msg)?;
+            open_text_in_pager(&format_model_patch(&patch, &plan))?;
+            app.modal = Some(app::Modal::Confirm {
+                message: format!(
+                    "Apply {} proposed change(s) to {}?",
+                    patch.changes.len(),
+                    patch.model_name
+                ),
+                action: app::ConfirmAction::FactoryApplyModelPatch {
+                    patch: Box::new(patch),
+                },
+            });
+            Ok(())
+        }
+        Action::ViewConnectivityHistory => {
+            let stats = droidgear_core::connectivity_history::get_availability_stats_for_home(
+                &app.home_dir,
+                Some(AVAILABILITY_WINDOW_HOURS),
+            )
+            .map_err(anyhow::Error::msg)?;
+            open_text_in_pager(&format_availability_stats(&stats))?;
+            Ok(())
+        }
+        Action::EditConnectivityConfig => {
+            let config = droidgear_core::connectivity::get_test_config_for_home(&app.home_dir)
+                .map_err(anyhow::Error::msg)?;
+            let edited: droidgear_core::connectivity::TestConfig = edit_json_in_editor(&config)?;
+            droidgear_core::connectivity::save_test_config_for_home(&app.home_dir, &edited)
+                .map_err(anyhow::Error::msg)?;
+            app.set_toast("Saved", false);
+            Ok(())
+        }
         Action::EditCodexProfile { id } => {
             let profile = droidgear_core::codex::get_codex_profile_for_home(&app.home_dir, &id)
                 .map_err(anyhow::Error::msg)?;
@@ -57,6 +154,11 @@ pub(super) fn run_action(app: &mut app::App, action: Action) -> anyhow::Result<(
             open_text_in_pager(&diff)?;
             Ok(())
         }
+        Action::PreviewFactorySave { models } => {
+            let diff = preview_factory_save(&app.home_dir, models)?;
+            open_text_in_pager(&diff)?;
+            Ok(())
+        }
         Action::PreviewCodexRun { id } => {
             let preview = preview_codex_temporary_run(&app.home_dir, &id)?;
             open_text_in_pager(&preview)?;
@@ -67,6 +169,21 @@ pub(super) fn run_action(app: &mut app::App, action: Action) -> anyhow::Result<(
             app.should_quit = true;
             Ok(())
         }
+        Action::PreviewClaudeApply { id } => {
+            let diff = preview_claude_apply(&app.home_dir, &id)?;
+            open_text_in_pager(&diff)?;
+            Ok(())
+        }
+        Action::PreviewPiApply { id } => {
+            let diff = preview_pi_apply(&app.home_dir, &id)?;
+            open_text_in_pager(&diff)?;
+            Ok(())
+        }
+        Action::PreviewHermesApply { id } => {
+            let diff = preview_hermes_apply(&app.home_dir, &id)?;
+            open_text_in_pager(&diff)?;
+            Ok(())
+        }
         Action::PreviewOpenCodeApply { id } => {
             let diff = preview_opencode_apply(&app.home_dir, &id)?;
             open_text_in_pager(&diff)?;
@@ -78,9 +195,15 @@ pub(super) fn run_action(app: &mut app::App, action: Action) -> anyhow::Result<(
             Ok(())
         }
         Action::ViewSession { path } => {
-            let detail =
-                droidgear_core::sessions::get_session_detail_for_home(&app.home_dir, &path)
-                    .map_err(anyhow::Error::msg)?;
+            let options = droidgear_core::sessions::SessionDetailOptions {
+                include_tools: true,
+            };
+            let detail = droidgear_core::sessions::get_session_detail_with_options_for_home(
+                &app.home_dir,
+                &path,
+                &options,
+            )
+            .map_err(anyhow::Error::msg)?;
             let text = format_session_detail(&detail);
 
             let mut temp = NamedTempFile::new().context("create temp file")?;
@@ -89,6 +212,17 @@ pub(super) fn run_action(app: &mut app::App, action: Action) -> anyhow::Result<(
             editor::open_in_pager(temp.path())?;
             Ok(())
         }
+        Action::ViewBackupDiff { id } => {
+            let diff = droidgear_core::backup::diff_backup_for_home(&app.home_dir, &id)
+                .map_err(anyhow::Error::msg)?;
+            let text = if diff.unified_diff.trim().is_empty() {
+                format!("{}\n\n(no changes since this backup)\n", diff.entry.path)
+            } else {
+                diff.unified_diff
+            };
+            open_text_in_pager(&text)?;
+            Ok(())
+        }
         Action::EditSpec { path } => {
             let path = PathBuf::from(path);
             editor::open_in_editor(&path)?;
@@ -126,9 +260,13 @@ pub(super) fn run_action(app: &mut app::App, action: Action) -> anyhow::Result<(
     }
 }
 
+/// Tool results longer than this are truncated in the session viewer.
+const SESSION_TOOL_RESULT_LINES: usize = 20;
+
 pub(super) fn format_session_detail(detail: &droidgear_core::sessions::SessionDetail) -> String {
     let mut out = String::new();
     out.push_str(&format!("Title: {}\n", detail.title));
+    out.push_str(&format!("Agent: {}\n", detail.source.label()));
     out.push_str(&format!("Project: {}\n", detail.project));
     out.push_str(&format!("Model: {}\n", detail.model));
     out.push_str(&format!("CWD: {}\n", detail.cwd));
@@ -150,6 +288,29 @@ pub(super) fn format_session_detail(detail: &droidgear_core::sessions::SessionDe
                     out.push('\n');
                 }
             }
+            if let Some(input) = block.tool_input.as_ref() {
+                let name = block.tool_name.as_deref().unwrap_or("tool");
+                out.push_str(&format!("-> {name} {input}\n"));
+            }
+            if let Some(result) = block.tool_result.as_deref() {
+                let name = block.tool_name.as_deref().unwrap_or("tool");
+                let status = if block.is_error == Some(true) {
+                    " (error)"
+                } else {
+                    ""
+                };
+                out.push_str(&format!("<- {name}{status}\n"));
+                let lines: Vec<&str> = result.lines().collect();
+                for line in lines.iter().take(SESSION_TOOL_RESULT_LINES) {
+                    out.push_str(&format!("   {line}\n"));
+                }
+                if lines.len() > SESSION_TOOL_RESULT_LINES {
+                    out.push_str(&format!(
+                        "   ... ({} more lines)\n",
+                        lines.len() - SESSION_TOOL_RESULT_LINES
+                    ));
+                }
+            }
         }
         out.push('\n');
     }
@@ -167,6 +328,9 @@ pub(super) fn edit_factory_models(app: &mut app::App) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Window used by the availability report (7 days).
+const AVAILABILITY_WINDOW_HOURS: u32 = 24 * 7;
+
 pub(super) fn edit_json_in_editor<T>(value: &T) -> anyhow::Result<T>
 where
     T: Serialize + DeserializeOwned,
@@ -191,13 +355,3 @@ pub(super) fn open_text_in_pager(text: &str) -> anyhow::Result<()> {
     editor::open_in_pager(temp.path())?;
     Ok(())
 }
-
-pub(super) fn read_to_string_if_exists(path: &Path) -> anyhow::Result<Option<String>> {
-    if path.exists() {
-        Ok(Some(
-            std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?,
-        ))
-    } else {
-        Ok(None)
-    }
-}
@@ -0,0 +1,25 @@
+use super::*;
+
+pub(super) fn handle_backups_key(app: &mut app::App, code: KeyCode) -> Option<Action> {
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => app.screen = app::Screen::Main,
+        KeyCode::Down => app.backups_index = app.backups_index.saturating_add(1),
+        KeyCode::Up => app.backups_index = app.backups_index.saturating_sub(1),
+        KeyCode::Char('r') => refresh_backups(app),
+        KeyCode::Enter | KeyCode::Char('v') => {
+            if let Some(b) = app.backups.get(app.backups_index) {
+                return Some(Action::ViewBackupDiff { id: b.id.clone() });
+            }
+        }
+        KeyCode::Char('R') => {
+            if let Some(b) = app.backups.get(app.backups_index) {
+                app.modal = Some(app::Modal::Confirm {
+                    message: format!("Restore backup of '{}' from {}?", b.path, b.created_at),
+                    action: app::ConfirmAction::BackupRestore { id: b.id.clone() },
+                });
+            }
+        }
+        _ => {}
+    }
+    None
+}
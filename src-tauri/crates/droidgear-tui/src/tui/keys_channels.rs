@@ -59,6 +59,13 @@ pub(super) fn handle_channels_key(app: &mut app::App, code: KeyCode) -> Option<A
                 });
             }
         }
+        KeyCode::Char('p') => {
+            if let Some(c) = app.channels.get(app.channels_index).cloned() {
+                if let Err(e) = start_channel_provision(app, c) {
+                    app.set_toast(e.to_string(), true);
+                }
+            }
+        }
         KeyCode::Char('E') => return Some(Action::EditChannels),
         KeyCode::Char('A') => {
             if let Some(c) = app.channels.get(app.channels_index) {
@@ -70,16 +77,103 @@ pub(super) fn handle_channels_key(app: &mut app::App, code: KeyCode) -> Option<A
     None
 }
 
+/// Starts the "provision profiles" flow: pick a token (dashboard channels),
+/// then the models to provision every tool with.
+fn start_channel_provision(
+    app: &mut app::App,
+    channel: droidgear_core::channel::Channel,
+) -> anyhow::Result<()> {
+    if channel.channel_type.uses_login() {
+        let (username, password) =
+            droidgear_core::channel::get_channel_credentials_for_home(&app.home_dir, &channel.id)
+                .map_err(anyhow::Error::msg)?
+                .ok_or_else(|| anyhow::anyhow!("No credentials stored for this channel"))?;
+        let tokens = droidgear_core::channel::fetch_channel_tokens_blocking(
+            channel.channel_type.clone(),
+            &channel.base_url,
+            &username,
+            &password,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to fetch tokens: {e}"))?;
+        if tokens.is_empty() {
+            return Err(anyhow::anyhow!("No tokens available for this channel"));
+        }
+        let options = tokens
+            .iter()
+            .map(|t| match t.platform.as_deref() {
+                Some(platform) => format!("{} [{platform}]", t.name),
+                None => t.name.clone(),
+            })
+            .collect();
+        app.channel_provision_channel = Some(channel);
+        app.channel_provision_tokens = Some(tokens);
+        app.modal = Some(app::Modal::Select {
+            title: "Select token".to_string(),
+            options,
+            index: 0,
+            action: app::SelectAction::ChannelProvisionSetToken,
+        });
+        return Ok(());
+    }
+
+    let api_key = droidgear_core::channel::get_channel_api_key_for_home(&app.home_dir, &channel.id)
+        .map_err(anyhow::Error::msg)?
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("No API key stored for this channel"))?;
+    // API-key channels have no dashboard tokens; the stored key stands in for one.
+    let token = droidgear_core::channel::ChannelToken {
+        id: 0.0,
+        name: String::new(),
+        key: api_key,
+        status: 1,
+        remain_quota: 0.0,
+        used_quota: 0.0,
+        unlimited_quota: true,
+        platform: None,
+        group_name: None,
+    };
+    app.channel_provision_channel = Some(channel);
+    show_channel_provision_models(app, token)
+}
+
+/// Fetches the models `token` can use and opens the model MultiSelect.
+pub(super) fn show_channel_provision_models(
+    app: &mut app::App,
+    token: droidgear_core::channel::ChannelToken,
+) -> anyhow::Result<()> {
+    let base_url = app
+        .channel_provision_channel
+        .as_ref()
+        .map(|c| c.base_url.clone())
+        .unwrap_or_default();
+    let models = droidgear_core::channel::fetch_models_by_api_key_blocking(
+        &base_url,
+        &token.key,
+        token.platform.as_deref(),
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to fetch models: {e}"))?;
+    if models.is_empty() {
+        return Err(anyhow::anyhow!("No models available for this token"));
+    }
+
+    let selected = vec![true; models.len()];
+    app.pi_import_pending_selected = Some(selected.clone());
+    app.modal = Some(app::Modal::MultiSelect {
+        title: "Select models to provision (Tab/c: confirm)".to_string(),
+        options: models.iter().map(|m| m.id.clone()).collect(),
+        selected,
+        index: 0,
+        action: app::SelectAction::ChannelProvisionToggleModel,
+    });
+    app.channel_provision_token = Some(token);
+    app.channel_provision_models = Some(models);
+    Ok(())
+}
+
 pub(super) fn channel_type_uses_api_key(
     channel_type: &droidgear_core::channel::ChannelType,
 ) -> bool {
-    matches!(
-        channel_type,
-        droidgear_core::channel::ChannelType::CliProxyApi
-            | droidgear_core::channel::ChannelType::Ollama
-            | droidgear_core::channel::ChannelType::General
-            | droidgear_core::channel::ChannelType::DeepSeek
-    )
+    !channel_type.uses_login()
 }
 
 pub(super) fn load_channel_auth_into_edit_state(
@@ -207,7 +301,17 @@ pub(super) fn handle_channels_edit_key(app: &mut app::App, code: KeyCode) -> Opt
             if let Some(idx) = app.channels.iter().position(|c| c.id == channel.id) {
                 app.channels_index = idx;
             }
-            app.set_toast("Saved", false);
+            // Entries linked to this channel follow its new base URL / key
+            match droidgear_core::channel_sync::sync_channel_links_blocking(
+                &app.home_dir,
+                &channel.id,
+            ) {
+                Ok(report) if !report.errors.is_empty() => {
+                    app.set_toast(format!("Saved; link sync: {}", report.errors[0]), true)
+                }
+                Err(e) => app.set_toast(format!("Saved; link sync failed: {e}"), true),
+                Ok(_) => app.set_toast("Saved", false),
+            }
         }
         KeyCode::Enter | KeyCode::Char('e') => match app.channels_edit_field_index {
             0 => {
@@ -220,22 +324,13 @@ pub(super) fn handle_channels_edit_key(app: &mut app::App, code: KeyCode) -> Opt
                 });
             }
             1 => {
-                let options = vec![
-                    "new-api".to_string(),
-                    "sub-2-api".to_string(),
-                    "cli-proxy-api".to_string(),
-                    "ollama".to_string(),
-                    "general".to_string(),
-                    "deep-seek".to_string(),
-                ];
-                let index = match draft.channel_type {
-                    droidgear_core::channel::ChannelType::NewApi => 0,
-                    droidgear_core::channel::ChannelType::Sub2Api => 1,
-                    droidgear_core::channel::ChannelType::CliProxyApi => 2,
-                    droidgear_core::channel::ChannelType::Ollama => 3,
-                    droidgear_core::channel::ChannelType::General => 4,
-                    droidgear_core::channel::ChannelType::DeepSeek => 5,
-                };
+                let providers = droidgear_core::channel_provider::providers();
+                let options: Vec<String> =
+                    providers.iter().map(|p| p.channel_type().id()).collect();
+                let index = providers
+                    .iter()
+                    .position(|p| p.channel_type() == draft.channel_type)
+                    .unwrap_or(0);
                 app.modal = Some(app::Modal::Select {
                     title: "Channel type".to_string(),
                     options,
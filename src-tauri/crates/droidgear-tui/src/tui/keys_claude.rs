@@ -6,6 +6,21 @@ pub(super) fn handle_claude_key(app: &mut app::App, code: KeyCode) -> Option<Act
         KeyCode::Down => app.claude_index = app.claude_index.saturating_add(1),
         KeyCode::Up => app.claude_index = app.claude_index.saturating_sub(1),
         KeyCode::Char('r') => refresh_claude(app),
+        KeyCode::Char('T') => {
+            if let Some(p) = app.claude_profiles.get(app.claude_index) {
+                return Some(Action::TestProfileConnections {
+                    tool: droidgear_core::profiles::ProfileTool::Claude,
+                    id: p.id.clone(),
+                });
+            }
+        }
+        KeyCode::Char('p') => {
+            if let Some(profile) = app.claude_profiles.get(app.claude_index) {
+                return Some(Action::PreviewClaudeApply {
+                    id: profile.id.clone(),
+                });
+            }
+        }
         KeyCode::Char('n') => {
             app.modal = Some(app::Modal::Input {
                 title: "New Claude profile name".to_string(),
@@ -6,6 +6,14 @@ pub(super) fn handle_codex_key(app: &mut app::App, code: KeyCode) -> Option<Acti
         KeyCode::Down => app.codex_index = app.codex_index.saturating_add(1),
         KeyCode::Up => app.codex_index = app.codex_index.saturating_sub(1),
         KeyCode::Char('r') => refresh_codex(app),
+        KeyCode::Char('T') => {
+            if let Some(p) = app.codex_profiles.get(app.codex_index) {
+                return Some(Action::TestProfileConnections {
+                    tool: droidgear_core::profiles::ProfileTool::Codex,
+                    id: p.id.clone(),
+                });
+            }
+        }
         KeyCode::Char('p') => {
             if let Some(p) = app.codex_profiles.get(app.codex_index) {
                 return Some(Action::PreviewCodexApply { id: p.id.clone() });
@@ -7,6 +7,21 @@ pub(super) fn handle_factory_key(app: &mut app::App, code: KeyCode) -> Option<Ac
         KeyCode::Up => app.factory_models_index = app.factory_models_index.saturating_sub(1),
         KeyCode::Char('r') => refresh_factory(app),
         KeyCode::Char('E') => return Some(Action::EditFactoryModels),
+        KeyCode::Char('t') if !app.custom_models.is_empty() => {
+            return Some(Action::TestFactoryModels)
+        }
+        KeyCode::Char('T') => return Some(Action::EditConnectivityConfig),
+        KeyCode::Char('h') => return Some(Action::ViewConnectivityHistory),
+        KeyCode::Char('A') if !app.custom_models.is_empty() => {
+            return Some(Action::AutofillFactoryModel {
+                index: app.factory_models_index,
+            })
+        }
+        KeyCode::Char('P') if !app.custom_models.is_empty() => {
+            return Some(Action::ProbeFactoryModel {
+                index: app.factory_models_index,
+            })
+        }
         KeyCode::Char('n') => {
             app.factory_edit_index = None;
             app.factory_model_field_index = 0;
@@ -22,6 +37,7 @@ pub(super) fn handle_factory_key(app: &mut app::App, code: KeyCode) -> Option<Ac
                 no_image_support: None,
                 extra_args: None,
                 extra_headers: None,
+                channel_ref: None,
             });
             app.screen = app::Screen::FactoryModel;
         }
@@ -82,6 +98,27 @@ pub(super) fn normalize_factory_models(
     }
 }
 
+/// The custom model list with `draft` saved in place (or appended), normalized,
+/// plus the draft's index.
+fn factory_models_with_draft(
+    app: &app::App,
+    draft: droidgear_core::factory_settings::CustomModel,
+) -> (Vec<droidgear_core::factory_settings::CustomModel>, usize) {
+    let mut models = app.custom_models.clone();
+    let index = match app.factory_edit_index {
+        Some(edit_index) if edit_index < models.len() => {
+            models[edit_index] = draft;
+            edit_index
+        }
+        _ => {
+            models.push(draft);
+            models.len() - 1
+        }
+    };
+    normalize_factory_models(&mut models);
+    (models, index)
+}
+
 pub(super) fn handle_factory_model_key(app: &mut app::App, code: KeyCode) -> Option<Action> {
     let Some(draft) = app.factory_draft.as_ref() else {
         app.screen = app::Screen::Factory;
@@ -121,21 +158,7 @@ pub(super) fn handle_factory_model_key(app: &mut app::App, code: KeyCode) -> Opt
                 return None;
             }
 
-            let mut models = app.custom_models.clone();
-            let saved_index = if let Some(edit_index) = app.factory_edit_index {
-                if edit_index < models.len() {
-                    models[edit_index] = draft;
-                    edit_index
-                } else {
-                    models.push(draft);
-                    models.len().saturating_sub(1)
-                }
-            } else {
-                models.push(draft);
-                models.len().saturating_sub(1)
-            };
-
-            normalize_factory_models(&mut models);
+            let (models, saved_index) = factory_models_with_draft(app, draft);
 
             if let Err(e) =
                 droidgear_core::factory_settings::save_custom_models_for_home(&app.home_dir, models)
@@ -152,6 +175,11 @@ pub(super) fn handle_factory_model_key(app: &mut app::App, code: KeyCode) -> Opt
             refresh_factory(app);
             app.set_toast("Saved", false);
         }
+        KeyCode::Char('p') => {
+            let draft = draft.clone();
+            let (models, _) = factory_models_with_draft(app, draft);
+            return Some(Action::PreviewFactorySave { models });
+        }
         KeyCode::Enter | KeyCode::Char('e') => match app.factory_model_field_index {
             0 => {
                 let options = vec![
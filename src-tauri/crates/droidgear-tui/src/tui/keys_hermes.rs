@@ -6,6 +6,19 @@ pub(super) fn handle_hermes_key(app: &mut app::App, code: KeyCode) -> Option<Act
         KeyCode::Down => app.hermes_index = app.hermes_index.saturating_add(1),
         KeyCode::Up => app.hermes_index = app.hermes_index.saturating_sub(1),
         KeyCode::Char('r') => refresh_hermes(app),
+        KeyCode::Char('T') => {
+            if let Some(p) = app.hermes_profiles.get(app.hermes_index) {
+                return Some(Action::TestProfileConnections {
+                    tool: droidgear_core::profiles::ProfileTool::Hermes,
+                    id: p.id.clone(),
+                });
+            }
+        }
+        KeyCode::Char('p') => {
+            if let Some(p) = app.hermes_profiles.get(app.hermes_index) {
+                return Some(Action::PreviewHermesApply { id: p.id.clone() });
+            }
+        }
         KeyCode::Char('n') => {
             app.modal = Some(app::Modal::Input {
                 title: "New Hermes profile name".to_string(),
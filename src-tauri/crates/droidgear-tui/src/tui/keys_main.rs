@@ -40,7 +40,9 @@ pub(super) fn handle_key(app: &mut app::App, code: KeyCode) -> Option<Action> {
         app::Screen::HermesProfile => handle_hermes_profile_key(app, code),
         app::Screen::HermesProvider => handle_hermes_provider_key(app, code),
         app::Screen::Sessions => handle_sessions_key(app, code),
+        app::Screen::Usage => handle_usage_key(app, code),
         app::Screen::Specs => handle_specs_key(app, code),
+        app::Screen::Backups => handle_backups_key(app, code),
         app::Screen::Channels => handle_channels_key(app, code),
         app::Screen::ChannelsEdit => handle_channels_edit_key(app, code),
         app::Screen::Missions => handle_missions_key(app, code),
@@ -6,6 +6,14 @@ pub(super) fn handle_openclaw_key(app: &mut app::App, code: KeyCode) -> Option<A
         KeyCode::Down => app.openclaw_index = app.openclaw_index.saturating_add(1),
         KeyCode::Up => app.openclaw_index = app.openclaw_index.saturating_sub(1),
         KeyCode::Char('r') => refresh_openclaw(app),
+        KeyCode::Char('T') => {
+            if let Some(p) = app.openclaw_profiles.get(app.openclaw_index) {
+                return Some(Action::TestProfileConnections {
+                    tool: droidgear_core::profiles::ProfileTool::OpenClaw,
+                    id: p.id.clone(),
+                });
+            }
+        }
         KeyCode::Char('p') => {
             if let Some(p) = app.openclaw_profiles.get(app.openclaw_index) {
                 return Some(Action::PreviewOpenClawApply { id: p.id.clone() });
@@ -6,6 +6,14 @@ pub(super) fn handle_opencode_key(app: &mut app::App, code: KeyCode) -> Option<A
         KeyCode::Down => app.opencode_index = app.opencode_index.saturating_add(1),
         KeyCode::Up => app.opencode_index = app.opencode_index.saturating_sub(1),
         KeyCode::Char('r') => refresh_opencode(app),
+        KeyCode::Char('T') => {
+            if let Some(p) = app.opencode_profiles.get(app.opencode_index) {
+                return Some(Action::TestProfileConnections {
+                    tool: droidgear_core::profiles::ProfileTool::OpenCode,
+                    id: p.id.clone(),
+                });
+            }
+        }
         KeyCode::Char('p') => {
             if let Some(p) = app.opencode_profiles.get(app.opencode_index) {
                 return Some(Action::PreviewOpenCodeApply { id: p.id.clone() });
@@ -6,6 +6,19 @@ pub(super) fn handle_pi_key(app: &mut app::App, code: KeyCode) -> Option<Action>
         KeyCode::Down => app.pi_index = app.pi_index.saturating_add(1),
         KeyCode::Up => app.pi_index = app.pi_index.saturating_sub(1),
         KeyCode::Char('r') => refresh_pi(app),
+        KeyCode::Char('T') => {
+            if let Some(p) = app.pi_profiles.get(app.pi_index) {
+                return Some(Action::TestProfileConnections {
+                    tool: droidgear_core::profiles::ProfileTool::Pi,
+                    id: p.id.clone(),
+                });
+            }
+        }
+        KeyCode::Char('p') => {
+            if let Some(p) = app.pi_profiles.get(app.pi_index) {
+                return Some(Action::PreviewPiApply { id: p.id.clone() });
+            }
+        }
         KeyCode::Char('n') => {
             app.modal = Some(app::Modal::Input {
                 title: "New Pi profile name".to_string(),
@@ -27,3 +27,27 @@ pub(super) fn handle_sessions_key(app: &mut app::App, code: KeyCode) -> Option<A
     }
     None
 }
+
+pub(super) fn handle_usage_key(app: &mut app::App, code: KeyCode) -> Option<Action> {
+    let dimensions = droidgear_core::session_analytics::UsageDimension::ALL;
+    let current = dimensions
+        .iter()
+        .position(|d| *d == app.usage_dimension)
+        .unwrap_or(0);
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => app.screen = app::Screen::Main,
+        KeyCode::Down => app.usage_index = app.usage_index.saturating_add(1),
+        KeyCode::Up => app.usage_index = app.usage_index.saturating_sub(1),
+        KeyCode::Char('r') => refresh_usage(app),
+        KeyCode::Tab | KeyCode::Right => {
+            app.usage_dimension = dimensions[(current + 1) % dimensions.len()];
+            app.usage_index = 0;
+        }
+        KeyCode::BackTab | KeyCode::Left => {
+            app.usage_dimension = dimensions[(current + dimensions.len() - 1) % dimensions.len()];
+            app.usage_index = 0;
+        }
+        _ => {}
+    }
+    None
+}
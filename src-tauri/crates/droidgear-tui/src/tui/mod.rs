@@ -13,10 +13,12 @@ use similar::TextDiff;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::mpsc;
 use std::time::Duration;
-use tempfile::{NamedTempFile, TempDir};
+use tempfile::NamedTempFile;
 
 mod actions;
+mod keys_backups;
 mod keys_channels;
 mod keys_claude;
 mod keys_codex;
@@ -48,8 +50,9 @@ pub use utils::run_claude_temporary_run_for_selector;
 pub use utils::run_codex_temporary_run_for_selector;
 pub use utils::run_droid_temporary_run_for_settings_name;
 
-use actions::{read_to_string_if_exists, run_action};
-use keys_channels::{handle_channels_edit_key, handle_channels_key};
+use actions::run_action;
+use keys_backups::handle_backups_key;
+use keys_channels::{handle_channels_edit_key, handle_channels_key, show_channel_provision_models};
 use keys_claude::{handle_claude_key, handle_claude_profile_key};
 use keys_codex::{handle_codex_key, handle_codex_profile_key, handle_codex_provider_key};
 use keys_droid_settings::handle_droid_settings_files_key;
@@ -71,15 +74,17 @@ use keys_opencode::{
 };
 use keys_paths::handle_paths_key;
 use keys_pi::{handle_pi_key, handle_pi_model_key, handle_pi_profile_key, handle_pi_provider_key};
-use keys_sessions::handle_sessions_key;
+use keys_sessions::{handle_sessions_key, handle_usage_key};
 use keys_specs::handle_specs_key;
 use modal::handle_modal_key;
 use refresh::*;
 use utils::{
-    factory_model_id, insert_char_at, preview_claude_temporary_run, preview_codex_apply,
-    preview_codex_temporary_run, preview_droid_temporary_run, preview_openclaw_apply,
-    preview_opencode_apply, remove_char_at, run_claude_temporary_run, run_codex_temporary_run,
-    run_droid_temporary_run,
+    factory_model_id, format_availability_stats, format_capability_report,
+    format_connectivity_results, format_model_patch, format_profile_test_results, insert_char_at,
+    preview_claude_apply, preview_claude_temporary_run, preview_codex_apply,
+    preview_codex_temporary_run, preview_droid_temporary_run, preview_factory_save,
+    preview_hermes_apply, preview_openclaw_apply, preview_opencode_apply, preview_pi_apply,
+    remove_char_at, run_claude_temporary_run, run_codex_temporary_run, run_droid_temporary_run,
 };
 
 type UiTerminal = Terminal<CrosstermBackend<io::Stdout>>;
@@ -107,23 +112,83 @@ impl Drop for TerminalGuard {
 #[derive(Debug, Clone)]
 enum Action {
     EditFactoryModels,
-    EditCodexProfile { id: String },
-    EditOpenCodeProfile { id: String },
-    EditOpenClawProfile { id: String },
-    PreviewDroidRun { settings_path: String },
-    RunDroidRun { settings_path: String },
-    PreviewClaudeRun { id: String },
-    RunClaudeRun { id: String },
-    PreviewCodexApply { id: String },
-    PreviewCodexRun { id: String },
-    RunCodexRun { id: String },
-    PreviewOpenCodeApply { id: String },
-    PreviewOpenClawApply { id: String },
-    ViewSession { path: String },
-    EditSpec { path: String },
+    TestFactoryModels,
+    EditConnectivityConfig,
+    ViewConnectivityHistory,
+    ProbeFactoryModel {
+        index: usize,
+    },
+    AutofillFactoryModel {
+        index: usize,
+    },
+    TestProfileConnections {
+        tool: droidgear_core::profiles::ProfileTool,
+        id: String,
+    },
+    EditCodexProfile {
+        id: String,
+    },
+    EditOpenCodeProfile {
+        id: String,
+    },
+    EditOpenClawProfile {
+        id: String,
+    },
+    PreviewDroidRun {
+        settings_path: String,
+    },
+    RunDroidRun {
+        settings_path: String,
+    },
+    PreviewClaudeRun {
+        id: String,
+    },
+    RunClaudeRun {
+        id: String,
+    },
+    PreviewCodexApply {
+        id: String,
+    },
+    PreviewFactorySave {
+        models: Vec<droidgear_core::factory_settings::CustomModel>,
+    },
+    PreviewClaudeApply {
+        id: String,
+    },
+    PreviewPiApply {
+        id: String,
+    },
+    PreviewHermesApply {
+        id: String,
+    },
+    PreviewCodexRun {
+        id: String,
+    },
+    RunCodexRun {
+        id: String,
+    },
+    PreviewOpenCodeApply {
+        id: String,
+    },
+    PreviewOpenClawApply {
+        id: String,
+    },
+    ViewSession {
+        path: String,
+    },
+    EditSpec {
+        path: String,
+    },
+    ViewBackupDiff {
+        id: String,
+    },
     EditChannels,
-    EditChannelAuth { id: String },
-    SetActiveSettingsFile { name: Option<String> },
+    EditChannelAuth {
+        id: String,
+    },
+    SetActiveSettingsFile {
+        name: Option<String>,
+    },
 }
 
 pub fn run(app: &mut app::App) -> anyhow::Result<()> {
@@ -132,8 +197,14 @@ pub fn run(app: &mut app::App) -> anyhow::Result<()> {
     let mut terminal = Terminal::new(backend).context("create terminal")?;
 
     refresh_screen_data(app);
+    app.quota_statuses =
+        droidgear_core::quota_monitor::get_quota_status_for_home(&app.home_dir).unwrap_or_default();
+    let quota_reports = spawn_quota_monitor(app.home_dir.clone());
 
     while !app.should_quit {
+        while let Ok(report) = quota_reports.try_recv() {
+            app.quota_statuses = report.statuses;
+        }
         app.clamp_indices();
         terminal.draw(|f| ui::draw(f, app)).context("draw")?;
 
@@ -154,6 +225,27 @@ pub fn run(app: &mut app::App) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Refreshes channel balances in the background whenever the configured
+/// interval has passed; reports are picked up by the event loop.
+fn spawn_quota_monitor(
+    home_dir: PathBuf,
+) -> mpsc::Receiver<droidgear_core::quota_monitor::QuotaRefreshReport> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || loop {
+        if droidgear_core::quota_monitor::is_refresh_due_for_home(&home_dir) {
+            if let Ok(report) =
+                droidgear_core::quota_monitor::refresh_all_for_home_blocking(&home_dir)
+            {
+                if tx.send(report).is_err() {
+                    return;
+                }
+            }
+        }
+        std::thread::sleep(Duration::from_secs(60));
+    });
+    rx
+}
+
 fn run_action_with_terminal(
     terminal: &mut UiTerminal,
     app: &mut app::App,
@@ -237,7 +329,9 @@ fn refresh_screen_data(app: &mut app::App) {
             refresh_hermes_detail(app);
         }
         app::Screen::Sessions => refresh_sessions(app),
+        app::Screen::Usage => refresh_usage(app),
         app::Screen::Specs => refresh_specs(app),
+        app::Screen::Backups => refresh_backups(app),
         app::Screen::Channels => refresh_channels(app),
         app::Screen::ChannelsEdit => {}
         app::Screen::Missions => refresh_missions(app),
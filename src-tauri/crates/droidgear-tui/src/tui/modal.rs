@@ -579,32 +579,18 @@ pub(super) fn run_select_action(
                 return Ok(());
             };
 
-            let default_base_url = |t: &droidgear_core::channel::ChannelType| match t {
-                droidgear_core::channel::ChannelType::NewApi => "https://api.newapi.ai",
-                droidgear_core::channel::ChannelType::Sub2Api => "",
-                droidgear_core::channel::ChannelType::CliProxyApi => "",
-                droidgear_core::channel::ChannelType::Ollama => "http://localhost:11434",
-                droidgear_core::channel::ChannelType::General => "",
-                droidgear_core::channel::ChannelType::DeepSeek => "https://api.deepseek.com",
-            };
-
-            let old_default = default_base_url(&channel.channel_type);
+            let old_default = channel.channel_type.default_base_url();
             let is_existing = app.channels.iter().any(|c| c.id == channel.id);
             let should_set_default = !is_existing
                 && (channel.base_url.trim().is_empty() || channel.base_url.trim() == old_default);
 
-            let new_type = match selected.as_str() {
-                "new-api" => droidgear_core::channel::ChannelType::NewApi,
-                "sub-2-api" => droidgear_core::channel::ChannelType::Sub2Api,
-                "cli-proxy-api" => droidgear_core::channel::ChannelType::CliProxyApi,
-                "ollama" => droidgear_core::channel::ChannelType::Ollama,
-                _ => droidgear_core::channel::ChannelType::General,
-            };
+            let new_type = droidgear_core::channel::ChannelType::parse(&selected)
+                .unwrap_or(droidgear_core::channel::ChannelType::General);
 
-            channel.channel_type = new_type.clone();
             if should_set_default {
-                channel.base_url = default_base_url(&new_type).to_string();
+                channel.base_url = new_type.default_base_url().to_string();
             }
+            channel.channel_type = new_type;
 
             Ok(())
         }
@@ -737,12 +723,8 @@ pub(super) fn run_select_action(
             app.pi_import_pending_channel_id = Some(channel.id.clone());
             app.pi_import_pending_base_url = Some(channel.base_url.clone());
 
-            // Check if this is a token-based channel (NewApi/Sub2Api)
-            let is_token_based = matches!(
-                channel.channel_type,
-                droidgear_core::channel::ChannelType::NewApi
-                    | droidgear_core::channel::ChannelType::Sub2Api
-            );
+            // Check if this is a token-based channel (dashboard login)
+            let is_token_based = channel.channel_type.uses_login();
 
             if is_token_based {
                 // Token-based channel: fetch tokens for user to select
@@ -958,17 +940,7 @@ pub(super) fn run_select_action(
                 .into_iter()
                 .enumerate()
                 .filter(|(i, _)| i < &selected.len() && selected[*i])
-                .map(|(_, m)| droidgear_core::pi::PiModel {
-                    id: m.id,
-                    name: m.name,
-                    api: None,
-                    reasoning: false,
-                    input: vec!["text".to_string()],
-                    context_window: 128000,
-                    max_tokens: 16384,
-                    cost: None,
-                    compat: None,
-                })
+                .map(|(_, m)| droidgear_core::pi::pi_model_from_model_info(&m))
                 .collect();
 
             if let Some(provider) = profile.providers.get_mut(&provider_id) {
@@ -1007,12 +979,8 @@ pub(super) fn run_select_action(
             app.pi_import_pending_channel_id = Some(channel.id.clone());
             app.pi_import_pending_base_url = Some(channel.base_url.clone());
 
-            // Check if this is a token-based channel (NewApi/Sub2Api)
-            let is_token_based = matches!(
-                channel.channel_type,
-                droidgear_core::channel::ChannelType::NewApi
-                    | droidgear_core::channel::ChannelType::Sub2Api
-            );
+            // Check if this is a token-based channel (dashboard login)
+            let is_token_based = channel.channel_type.uses_login();
 
             if is_token_based {
                 // Token-based channel: fetch tokens for user to select
@@ -1147,6 +1115,52 @@ pub(super) fn run_select_action(
             }
             Ok(())
         }
+        app::SelectAction::ChannelProvisionSetToken => {
+            let token = app
+                .channel_provision_tokens
+                .take()
+                .and_then(|tokens| tokens.into_iter().nth(index))
+                .ok_or_else(|| anyhow::anyhow!("Token not found"))?;
+            show_channel_provision_models(app, token)
+        }
+        app::SelectAction::ChannelProvisionToggleModel => {
+            let channel = app.channel_provision_channel.take();
+            let token = app.channel_provision_token.take();
+            let models = app.channel_provision_models.take().unwrap_or_default();
+            let selected = app.pi_import_pending_selected.take().unwrap_or_default();
+            let (Some(channel), Some(token)) = (channel, token) else {
+                return Err(anyhow::anyhow!("No channel token selected"));
+            };
+
+            let models: Vec<_> = models
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| selected.get(*i).copied().unwrap_or(false))
+                .map(|(_, m)| m)
+                .collect();
+            let profiles = droidgear_core::channel_provisioning::provision_profiles_for_home(
+                &app.home_dir,
+                &channel,
+                &token,
+                &models,
+            )
+            .map_err(anyhow::Error::msg)?;
+            let claude = profiles.claude.is_some();
+            droidgear_core::channel_provisioning::save_provisioned_profiles_for_home(
+                &app.home_dir,
+                profiles,
+            )
+            .map_err(anyhow::Error::msg)?;
+            let tools = if claude { 6 } else { 5 };
+            app.set_toast(
+                format!(
+                    "Created {tools} profiles and {} custom models",
+                    models.len()
+                ),
+                false,
+            );
+            Ok(())
+        }
         app::SelectAction::HermesImportFromChannel { profile_id } => {
             let Some(selected) = selected else {
                 return Ok(());
@@ -1197,36 +1211,32 @@ fn resolve_channel_api_key(
         droidgear_core::channel::get_channel_credentials_for_home(&app.home_dir, channel_id)
     {
         if !password.is_empty() {
-            match channel_type {
-                droidgear_core::channel::ChannelType::NewApi
-                | droidgear_core::channel::ChannelType::Sub2Api => {
-                    // Token-based channel: fetch a live token from the API
-                    match droidgear_core::channel::fetch_channel_tokens_blocking(
-                        channel_type.clone(),
-                        base_url,
-                        &username,
-                        &password,
-                    ) {
-                        Ok(tokens) => {
-                            // Return the first active token's key
-                            for t in &tokens {
-                                if t.status == 1 && !t.key.is_empty() {
-                                    return Some(t.key.clone());
-                                }
+            if channel_type.uses_login() {
+                // Token-based channel: fetch a live token from the API
+                match droidgear_core::channel::fetch_channel_tokens_blocking(
+                    channel_type.clone(),
+                    base_url,
+                    &username,
+                    &password,
+                ) {
+                    Ok(tokens) => {
+                        // Return the first active token's key
+                        for t in &tokens {
+                            if t.status == 1 && !t.key.is_empty() {
+                                return Some(t.key.clone());
                             }
-                            // Fall back to first token if none active
-                            tokens.first().map(|t| t.key.clone())
-                        }
-                        Err(_) => {
-                            // Token fetch failed, try password as-is
-                            Some(password)
                         }
+                        // Fall back to first token if none active
+                        tokens.first().map(|t| t.key.clone())
+                    }
+                    Err(_) => {
+                        // Token fetch failed, try password as-is
+                        Some(password)
                     }
                 }
-                _ => {
-                    // API-key channel: password is the key
-                    Some(password)
-                }
+            } else {
+                // API-key channel: password is the key
+                Some(password)
             }
         } else {
             None
@@ -1434,8 +1444,15 @@ pub(super) fn run_confirm_action(
                 .map_err(anyhow::Error::msg)?;
             Ok(())
         }
+        app::ConfirmAction::FactoryApplyModelPatch { patch } => {
+            droidgear_core::model_autofill::accept_model_patch_for_home(&app.home_dir, &patch)
+                .map_err(anyhow::Error::msg)?;
+            app.set_toast(format!("Updated {}", patch.model_name), false);
+            Ok(())
+        }
         app::ConfirmAction::SessionDelete { path } => {
-            droidgear_core::sessions::delete_session(&path).map_err(anyhow::Error::msg)?;
+            droidgear_core::sessions::delete_session_for_home(&app.home_dir, &path)
+                .map_err(anyhow::Error::msg)?;
             Ok(())
         }
         app::ConfirmAction::SpecDelete { path } => {
@@ -1443,6 +1460,12 @@ pub(super) fn run_confirm_action(
                 .map_err(anyhow::Error::msg)?;
             Ok(())
         }
+        app::ConfirmAction::BackupRestore { id } => {
+            let entry = droidgear_core::backup::restore_backup_for_home(&app.home_dir, &id)
+                .map_err(anyhow::Error::msg)?;
+            app.set_toast(format!("Restored {}", entry.path), false);
+            Ok(())
+        }
         app::ConfirmAction::ChannelDelete { id } => {
             let mut channels = droidgear_core::channel::load_channels_for_home(&app.home_dir)
                 .map_err(anyhow::Error::msg)?;
@@ -1617,6 +1640,7 @@ pub(super) fn run_input_action(
                 thinking_mode: droidgear_core::claude::ClaudeThinkingMode::Inherit,
                 created_at: String::new(),
                 updated_at: String::new(),
+                channel_ref: None,
             };
 
             droidgear_core::claude::save_claude_profile_for_home(&app.home_dir, profile)
@@ -1748,6 +1772,7 @@ pub(super) fn run_input_action(
                     model: Some("gpt-5.2".to_string()),
                     model_reasoning_effort: Some("high".to_string()),
                     api_key: Some(String::new()),
+                    channel_ref: None,
                 },
             );
 
@@ -1868,6 +1893,7 @@ pub(super) fn run_input_action(
                     model: None,
                     model_reasoning_effort: Some("high".to_string()),
                     api_key: None,
+                    channel_ref: None,
                 },
             );
 
@@ -2052,6 +2078,7 @@ pub(super) fn run_input_action(
                     name: None,
                     options: Some(droidgear_core::opencode::OpenCodeProviderOptions::default()),
                     models: None,
+                    channel_ref: None,
                 },
             );
             droidgear_core::opencode::save_opencode_profile_for_home(&app.home_dir, profile)
@@ -2412,6 +2439,7 @@ pub(super) fn run_input_action(
                     api_key: None,
                     api: Some("openai-completions".to_string()),
                     models: Vec::new(),
+                    channel_ref: None,
                 },
             );
             droidgear_core::openclaw::save_openclaw_profile_for_home(&app.home_dir, profile)
@@ -3060,6 +3088,7 @@ pub(super) fn run_input_action(
                     provider: Some(String::new()),
                     base_url: Some(String::new()),
                     api_key: Some(String::new()),
+                    channel_ref: None,
                 },
             };
 
@@ -319,12 +319,20 @@ pub(super) fn pi_load_from_live_config(app: &mut app::App, profile_id: &str) ->
 }
 
 pub(super) fn refresh_sessions(app: &mut app::App) {
-    match droidgear_core::sessions::list_sessions_for_home(&app.home_dir, None) {
+    match droidgear_core::sessions::list_agent_sessions_for_home(&app.home_dir, None, None) {
         Ok(list) => app.sessions = list,
         Err(e) => app.set_toast(e, true),
     }
 }
 
+pub(super) fn refresh_usage(app: &mut app::App) {
+    let query = droidgear_core::session_analytics::UsageQuery::default();
+    match droidgear_core::session_analytics::usage_report_for_home(&app.home_dir, &query) {
+        Ok(report) => app.usage_report = Some(report),
+        Err(e) => app.set_toast(e, true),
+    }
+}
+
 pub(super) fn refresh_specs(app: &mut app::App) {
     match droidgear_core::specs::list_specs_for_home(&app.home_dir) {
         Ok(list) => app.specs = list,
@@ -332,6 +340,25 @@ pub(super) fn refresh_specs(app: &mut app::App) {
     }
 }
 
+pub(super) fn refresh_backups(app: &mut app::App) {
+    let files = match droidgear_core::backup::list_backup_files_for_home(&app.home_dir) {
+        Ok(files) => files,
+        Err(e) => {
+            app.set_toast(e, true);
+            return;
+        }
+    };
+    let mut list = Vec::new();
+    for f in files {
+        match droidgear_core::backup::list_backups_for_home(&app.home_dir, Path::new(&f.path)) {
+            Ok(entries) => list.extend(entries),
+            Err(e) => app.set_toast(e, true),
+        }
+    }
+    list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    app.backups = list;
+}
+
 pub(super) fn refresh_channels(app: &mut app::App) {
     match droidgear_core::channel::load_channels_for_home(&app.home_dir) {
         Ok(list) => app.channels = list,
@@ -29,6 +29,7 @@ fn make_claude_profile(id: &str, name: &str) -> droidgear_core::claude::ClaudeCo
         thinking_mode: droidgear_core::claude::ClaudeThinkingMode::Inherit,
         created_at: "2026-01-01T00:00:00Z".to_string(),
         updated_at: "2026-01-01T00:00:00Z".to_string(),
+        channel_ref: None,
     }
 }
 
@@ -47,6 +48,7 @@ fn normalize_factory_models_sets_index_and_id() {
             no_image_support: None,
             extra_args: None,
             extra_headers: None,
+            channel_ref: None,
         },
         droidgear_core::factory_settings::CustomModel {
             model: "m2".to_string(),
@@ -60,6 +62,7 @@ fn normalize_factory_models_sets_index_and_id() {
             no_image_support: None,
             extra_args: None,
             extra_headers: None,
+            channel_ref: None,
         },
     ];
 
@@ -554,6 +557,7 @@ fn list_claude_temporary_run_targets_lists_index_name_and_id() {
             thinking_mode: droidgear_core::claude::ClaudeThinkingMode::Inherit,
             created_at: "2026-01-01T00:00:00Z".to_string(),
             updated_at: "2026-01-01T00:00:00Z".to_string(),
+            channel_ref: None,
         },
     )
     .unwrap();
@@ -572,6 +576,7 @@ fn list_claude_temporary_run_targets_lists_index_name_and_id() {
             thinking_mode: droidgear_core::claude::ClaudeThinkingMode::Inherit,
             created_at: "2026-01-01T00:00:00Z".to_string(),
             updated_at: "2026-01-01T00:00:00Z".to_string(),
+            channel_ref: None,
         },
     )
     .unwrap();
@@ -668,3 +673,141 @@ fn format_claude_temporary_run_preview_includes_overlay_and_sensitive_notice() {
     assert!(output.contains("token-a"));
     assert!(output.contains("example warning"));
 }
+
+#[test]
+fn quota_status_line_lists_most_severe_alert_first() {
+    use droidgear_core::quota_monitor::{ChannelQuotaStatus, QuotaAlert, QuotaAlertKind};
+
+    let status = |id: &str, kind: Option<QuotaAlertKind>| ChannelQuotaStatus {
+        channel_id: id.to_string(),
+        channel_name: id.to_string(),
+        latest: None,
+        burn_rate_per_day: None,
+        days_remaining: None,
+        alert: kind.map(|kind| QuotaAlert {
+            channel_id: id.to_string(),
+            channel_name: id.to_string(),
+            kind,
+            message: format!("{id} alert"),
+        }),
+        error: None,
+    };
+
+    let mut app = app::App::new(std::env::temp_dir());
+    assert!(app.quota_status_line().is_none());
+
+    app.quota_statuses = vec![
+        status("a", Some(QuotaAlertKind::RunningOut)),
+        status("b", None),
+        status("c", Some(QuotaAlertKind::Exhausted)),
+    ];
+    assert_eq!(
+        app.quota_status_line().as_deref(),
+        Some("Quota: c alert (+1 more)")
+    );
+}
+
+#[test]
+fn channel_provision_without_stored_key_reports_error() {
+    let home = TempDir::new().unwrap();
+    let mut app = app::App::new(home.path().to_path_buf());
+    app.channels = vec![droidgear_core::channel::Channel {
+        id: "ch-1".to_string(),
+        name: "Local".to_string(),
+        channel_type: droidgear_core::channel::ChannelType::General,
+        base_url: "http://127.0.0.1:1".to_string(),
+        enabled: true,
+        created_at: 0.0,
+    }];
+
+    let action = super::keys_channels::handle_channels_key(&mut app, KeyCode::Char('p'));
+
+    assert!(action.is_none());
+    assert!(app.modal.is_none());
+    let toast = app.toast.as_ref().expect("expected an error toast");
+    assert!(toast.is_error);
+    assert!(toast.message.contains("No API key"));
+}
+
+#[test]
+fn session_detail_format_shows_tool_calls_and_truncated_results() {
+    use droidgear_core::sessions::{ContentBlock, SessionDetail, SessionMessage, TokenUsage};
+
+    let block = |content_type: &str| ContentBlock {
+        content_type: content_type.to_string(),
+        text: None,
+        thinking: None,
+        tool_use_id: Some("call-1".to_string()),
+        tool_name: Some("Execute".to_string()),
+        tool_input: None,
+        tool_result: None,
+        is_error: None,
+    };
+    let detail = SessionDetail {
+        source: Default::default(),
+        id: "s1".to_string(),
+        title: "Tools".to_string(),
+        project: "-work".to_string(),
+        model: "gpt-5".to_string(),
+        cwd: "/work".to_string(),
+        modified_at: 0.0,
+        token_usage: TokenUsage::default(),
+        messages: vec![SessionMessage {
+            id: "m1".to_string(),
+            role: "assistant".to_string(),
+            timestamp: "2026-01-10T10:00:00Z".to_string(),
+            content: vec![
+                ContentBlock {
+                    tool_input: Some(serde_json::json!({ "command": "ls" })),
+                    ..block("tool_use")
+                },
+                ContentBlock {
+                    tool_result: Some((1..=25).map(|i| format!("line {i}\n")).collect()),
+                    is_error: Some(true),
+                    ..block("tool_result")
+                },
+            ],
+        }],
+    };
+
+    let text = actions::format_session_detail(&detail);
+
+    assert!(text.contains("-> Execute {\"command\":\"ls\"}\n"));
+    assert!(text.contains("<- Execute (error)\n   line 1\n"));
+    assert!(text.contains("   line 20\n   ... (5 more lines)\n"));
+    assert!(!text.contains("line 21"));
+}
+
+#[test]
+fn factory_draft_preview_shows_masked_save_plan() {
+    let temp = TempDir::new().unwrap();
+    let mut app = app::App::new(temp.path().to_path_buf());
+    app.screen = app::Screen::FactoryModel;
+    app.factory_draft = Some(droidgear_core::factory_settings::CustomModel {
+        model: "glm-4.6".to_string(),
+        id: None,
+        index: None,
+        display_name: None,
+        base_url: "https://api.example.test".to_string(),
+        api_key: "sk-live-0123456789abcdef".to_string(),
+        provider: droidgear_core::factory_settings::Provider::Openai,
+        max_output_tokens: None,
+        no_image_support: None,
+        extra_args: None,
+        extra_headers: None,
+        channel_ref: None,
+    });
+
+    let Some(Action::PreviewFactorySave { models }) = handle_key(&mut app, KeyCode::Char('p'))
+    else {
+        panic!("expected a Factory save preview");
+    };
+    assert_eq!(models[0].id.as_deref(), Some("custom:glm-4.6-0"));
+    // Previewing leaves the draft open
+    assert!(app.factory_draft.is_some());
+
+    let preview = preview_factory_save(temp.path(), models).unwrap();
+    assert!(preview.contains("+ customModels"));
+    assert!(preview.contains("sk-****cdef"));
+    assert!(!preview.contains("sk-live-0123456789abcdef"));
+}
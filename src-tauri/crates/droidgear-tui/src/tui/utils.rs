@@ -1,13 +1,5 @@
 use super::*;
 
-pub(super) fn write_string(path: &Path, content: &str) -> anyhow::Result<()> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
-    }
-    std::fs::write(path, content).with_context(|| format!("write {}", path.display()))?;
-    Ok(())
-}
-
 #[derive(Debug, Default, serde::Deserialize)]
 pub(super) struct StoredPreferences {
     #[serde(default)]
@@ -289,67 +281,241 @@ pub fn list_droid_temporary_run_targets(home_dir: &Path) -> anyhow::Result<Strin
     Ok(out)
 }
 
-pub(super) fn preview_codex_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
-    let status = droidgear_core::codex::get_codex_config_status_for_home(home_dir)
-        .map_err(anyhow::Error::msg)?;
-    let real_config_path = PathBuf::from(status.config_path);
-    let real_auth_path = PathBuf::from(status.auth_path);
-    let real_active_path = home_dir
-        .join(".droidgear")
-        .join("codex")
-        .join("active-profile.txt");
+pub(super) fn format_connectivity_results(
+    results: &[droidgear_core::connectivity::ModelTestResult],
+) -> String {
+    let summary = droidgear_core::connectivity::get_connectivity_summary(results);
+    let mut out = format!(
+        "Connectivity: {}/{} available, avg latency {:.0}ms\n\n",
+        summary.available_models, summary.total_models, summary.avg_latency_ms
+    );
+    for r in results {
+        let d = &r.diagnostics;
+        let status = if r.is_available { "OK  " } else { "FAIL" };
+        out.push_str(&format!(
+            "[{status}] {} ({}) {}ms, {} attempt(s)\n",
+            r.model_name,
+            d.provider,
+            d.latency_ms,
+            d.attempts.len().max(1)
+        ));
+        for a in d.attempts.iter().filter(|a| !a.success) {
+            out.push_str(&format!(
+                "    #{}: {}\n",
+                a.attempt,
+                a.error.as_deref().unwrap_or("failed")
+            ));
+        }
+    }
+    out
+}
 
-    let before_config = read_to_string_if_exists(&real_config_path)?;
-    let before_auth = read_to_string_if_exists(&real_auth_path)?;
-    let before_active = read_to_string_if_exists(&real_active_path)?;
+pub(super) fn format_profile_test_results(
+    results: &[droidgear_core::profile_connectivity::ProfileTestResult],
+) -> String {
+    let Some(first) = results.first() else {
+        return "No endpoints configured in this profile\n".to_string();
+    };
+    let available = results.iter().filter(|r| r.is_available).count();
+    let mut out = format!(
+        "Connectivity of {} profile '{}': {available}/{} available\n\n",
+        first.endpoint.tool.as_str(),
+        first.endpoint.profile_name,
+        results.len()
+    );
+    for r in results {
+        let e = &r.endpoint;
+        let d = &r.diagnostics;
+        let status = if r.is_available { "OK  " } else { "FAIL" };
+        let model = if e.model_id.is_empty() {
+            "(no model)"
+        } else {
+            e.model_id.as_str()
+        };
+        let provider = e.provider_id.as_deref().unwrap_or("-");
+        out.push_str(&format!(
+            "[{status}] {provider} / {model} via {} {}ms\n    {}\n",
+            e.protocol.name(),
+            d.latency_ms,
+            e.base_url
+        ));
+        if let Some(error) = &d.error {
+            out.push_str(&format!("    {error}\n"));
+        }
+    }
+    out
+}
+
+pub(super) fn format_capability_report(
+    report: &droidgear_core::connectivity::CapabilityReport,
+) -> String {
+    let mark = |v: Option<bool>| match v {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "unknown",
+    };
+    let caps = &report.capabilities;
+    let mut out = format!(
+        "Capabilities of {}\n\n  streaming: {}\n  tool use:  {}\n  vision:    {}\n\n",
+        report.model_name,
+        mark(caps.streaming),
+        mark(caps.tool_use),
+        mark(caps.vision)
+    );
+    for d in &report.probes {
+        let detail = match (&d.error, &d.response_text) {
+            (Some(e), _) => e.clone(),
+            (None, Some(text)) => text.clone(),
+            (None, None) => String::new(),
+        };
+        out.push_str(&format!(
+            "[{:?}] {}ms: {}\n",
+            d.test_mode, d.latency_ms, detail
+        ));
+    }
+    out
+}
 
-    let temp = TempDir::new().context("create temp home")?;
-    let temp_home = temp.path();
+pub(super) fn format_model_patch(
+    patch: &droidgear_core::model_autofill::ModelPatch,
+    plan: &droidgear_core::apply_plan::ApplyPlan,
+) -> String {
+    let mut out = format!("Proposed changes for {}\n\n", patch.model_name);
+    for c in &patch.changes {
+        out.push_str(&format!(
+            "  {}: {} -> {}\n      {}\n",
+            c.field,
+            c.before.as_deref().unwrap_or("-"),
+            c.after.as_deref().unwrap_or("-"),
+            c.reason
+        ));
+    }
+    out.push('\n');
+    out.push_str(&format_apply_plan("Factory settings", plan));
+    out
+}
 
-    let temp_config_path = temp_home.join(".codex").join("config.toml");
-    let temp_auth_path = temp_home.join(".codex").join("auth.json");
-    if let Some(ref s) = before_config {
-        write_string(&temp_config_path, s)?;
+pub(super) fn format_availability_stats(
+    stats: &[droidgear_core::connectivity_history::ModelAvailabilityStats],
+) -> String {
+    if stats.is_empty() {
+        return "No connectivity history in the last 7 days. Press t to run a test.\n".to_string();
     }
-    if let Some(ref s) = before_auth {
-        write_string(&temp_auth_path, s)?;
+    let fmt_ms = |v: Option<u32>| {
+        v.map(|ms| format!("{ms}ms"))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let mut out = String::from("Availability (last 7 days, worst first)\n\n");
+    for s in stats {
+        out.push_str(&format!(
+            "{:>6.1}%  {} ({}/{} ok)  p50 {}  p95 {}\n",
+            s.uptime_percent,
+            s.model_name,
+            s.successful_tests,
+            s.total_tests,
+            fmt_ms(s.p50_latency_ms),
+            fmt_ms(s.p95_latency_ms)
+        ));
+        if let Some(url) = &s.base_url {
+            out.push_str(&format!("         {url}\n"));
+        }
+        if let (Some(reason), Some(at)) = (&s.last_failure_reason, &s.last_failure_at) {
+            out.push_str(&format!("         last failure {at}: {reason}\n"));
+        }
     }
+    out
+}
 
-    let profile = droidgear_core::codex::get_codex_profile_for_home(home_dir, profile_id)
+pub(super) fn format_apply_plan(
+    title: &str,
+    plan: &droidgear_core::apply_plan::ApplyPlan,
+) -> String {
+    use droidgear_core::apply_plan::KeyChangeKind;
+
+    let mut out = String::new();
+    let changes: Vec<_> = plan.files.iter().flat_map(|f| f.changes.iter()).collect();
+    if !changes.is_empty() {
+        out.push_str("Key changes (secrets masked):\n");
+        for c in changes {
+            let none = "-";
+            let line = match c.kind {
+                KeyChangeKind::Added => {
+                    format!("  + {}: {}", c.key, c.after.as_deref().unwrap_or(none))
+                }
+                KeyChangeKind::Removed => {
+                    format!("  - {}: {}", c.key, c.before.as_deref().unwrap_or(none))
+                }
+                KeyChangeKind::Changed => format!(
+                    "  ~ {}: {} -> {}",
+                    c.key,
+                    c.before.as_deref().unwrap_or(none),
+                    c.after.as_deref().unwrap_or(none)
+                ),
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    let report = format_diff_report(
+        title,
+        plan.files
+            .iter()
+            .map(|f| (f.path.clone(), f.before.clone(), Some(f.after.clone())))
+            .collect(),
+    );
+    match report.split_once("\n\n") {
+        Some((head, rest)) => format!("{head}\n\n{out}{rest}"),
+        None => report,
+    }
+}
+
+pub(super) fn preview_codex_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
+    let plan = droidgear_core::codex::plan_apply_codex_profile_for_home(home_dir, profile_id)
+        .map_err(anyhow::Error::msg)?;
+    Ok(format_apply_plan("Codex apply preview", &plan))
+}
+
+pub(super) fn preview_factory_save(
+    home_dir: &Path,
+    models: Vec<droidgear_core::factory_settings::CustomModel>,
+) -> anyhow::Result<String> {
+    let plan = droidgear_core::factory_settings::plan_save_custom_models_for_home(home_dir, models)
+        .map_err(anyhow::Error::msg)?;
+    Ok(format_apply_plan("Factory save preview", &plan))
+}
+
+pub(super) fn preview_claude_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
+    let plan = droidgear_core::claude::plan_apply_claude_profile_for_home(home_dir, profile_id)
+        .map_err(anyhow::Error::msg)?;
+    Ok(format_apply_plan("Claude apply preview", &plan))
+}
+
+pub(super) fn preview_opencode_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
+    let plan = droidgear_core::opencode::plan_apply_opencode_profile_for_home(home_dir, profile_id)
+        .map_err(anyhow::Error::msg)?;
+    Ok(format_apply_plan("OpenCode apply preview", &plan))
+}
+
+pub(super) fn preview_openclaw_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
+    let profile = droidgear_core::openclaw::get_openclaw_profile_for_home(home_dir, profile_id)
         .map_err(anyhow::Error::msg)?;
-    droidgear_core::codex::save_codex_profile_for_home(temp_home, profile)
+    let plan = droidgear_core::openclaw::plan_apply_openclaw_profile_for_home(home_dir, &profile)
         .map_err(anyhow::Error::msg)?;
-    droidgear_core::codex::apply_codex_profile_for_home(temp_home, profile_id)
+    Ok(format_apply_plan("OpenClaw apply preview", &plan))
+}
+
+pub(super) fn preview_pi_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
+    let plan = droidgear_core::pi::plan_apply_pi_profile_for_home(home_dir, profile_id)
         .map_err(anyhow::Error::msg)?;
+    Ok(format_apply_plan("Pi apply preview", &plan))
+}
 
-    let after_config = read_to_string_if_exists(&temp_config_path)?;
-    let after_auth = read_to_string_if_exists(&temp_auth_path)?;
-    let temp_active_path = temp_home
-        .join(".droidgear")
-        .join("codex")
-        .join("active-profile.txt");
-    let after_active = read_to_string_if_exists(&temp_active_path)?;
-
-    Ok(format_diff_report(
-        "Codex apply preview",
-        vec![
-            (
-                real_config_path.to_string_lossy().to_string(),
-                before_config,
-                after_config,
-            ),
-            (
-                real_auth_path.to_string_lossy().to_string(),
-                before_auth,
-                after_auth,
-            ),
-            (
-                real_active_path.to_string_lossy().to_string(),
-                before_active,
-                after_active,
-            ),
-        ],
-    ))
+pub(super) fn preview_hermes_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
+    let plan = droidgear_core::hermes::plan_apply_hermes_profile_for_home(home_dir, profile_id)
+        .map_err(anyhow::Error::msg)?;
+    Ok(format_apply_plan("Hermes apply preview", &plan))
 }
 
 pub(super) fn build_codex_temporary_run_plan(
@@ -623,135 +789,6 @@ pub fn preview_claude_temporary_run_for_selector(
     preview_claude_temporary_run(home_dir, &profile.id)
 }
 
-pub(super) fn preview_opencode_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
-    let status = droidgear_core::opencode::get_opencode_config_status_for_home(home_dir)
-        .map_err(anyhow::Error::msg)?;
-    let real_config_path = PathBuf::from(status.config_path);
-    let real_auth_path = PathBuf::from(status.auth_path);
-    let real_active_path = home_dir
-        .join(".droidgear")
-        .join("opencode")
-        .join("active-profile.txt");
-
-    let before_config = read_to_string_if_exists(&real_config_path)?;
-    let before_auth = read_to_string_if_exists(&real_auth_path)?;
-    let before_active = read_to_string_if_exists(&real_active_path)?;
-
-    let temp = TempDir::new().context("create temp home")?;
-    let temp_home = temp.path();
-
-    let config_file_name = real_config_path
-        .file_name()
-        .unwrap_or(std::ffi::OsStr::new("opencode.json"));
-    let auth_file_name = real_auth_path
-        .file_name()
-        .unwrap_or(std::ffi::OsStr::new("auth.json"));
-
-    let temp_config_path = temp_home
-        .join(".config")
-        .join("opencode")
-        .join(config_file_name);
-    let temp_auth_path = temp_home
-        .join(".local")
-        .join("share")
-        .join("opencode")
-        .join(auth_file_name);
-
-    if let Some(ref s) = before_config {
-        write_string(&temp_config_path, s)?;
-    }
-    if let Some(ref s) = before_auth {
-        write_string(&temp_auth_path, s)?;
-    }
-
-    let profile = droidgear_core::opencode::get_opencode_profile_for_home(home_dir, profile_id)
-        .map_err(anyhow::Error::msg)?;
-    droidgear_core::opencode::save_opencode_profile_for_home(temp_home, profile)
-        .map_err(anyhow::Error::msg)?;
-    droidgear_core::opencode::apply_opencode_profile_for_home(temp_home, profile_id)
-        .map_err(anyhow::Error::msg)?;
-
-    let after_config = read_to_string_if_exists(&temp_config_path)?;
-    let after_auth = read_to_string_if_exists(&temp_auth_path)?;
-    let temp_active_path = temp_home
-        .join(".droidgear")
-        .join("opencode")
-        .join("active-profile.txt");
-    let after_active = read_to_string_if_exists(&temp_active_path)?;
-
-    Ok(format_diff_report(
-        "OpenCode apply preview",
-        vec![
-            (
-                real_config_path.to_string_lossy().to_string(),
-                before_config,
-                after_config,
-            ),
-            (
-                real_auth_path.to_string_lossy().to_string(),
-                before_auth,
-                after_auth,
-            ),
-            (
-                real_active_path.to_string_lossy().to_string(),
-                before_active,
-                after_active,
-            ),
-        ],
-    ))
-}
-
-pub(super) fn preview_openclaw_apply(home_dir: &Path, profile_id: &str) -> anyhow::Result<String> {
-    let status = droidgear_core::openclaw::get_openclaw_config_status_for_home(home_dir)
-        .map_err(anyhow::Error::msg)?;
-    let real_config_path = PathBuf::from(status.config_path);
-    let real_active_path = home_dir
-        .join(".droidgear")
-        .join("openclaw")
-        .join("active-profile.txt");
-
-    let before_config = read_to_string_if_exists(&real_config_path)?;
-    let before_active = read_to_string_if_exists(&real_active_path)?;
-
-    let temp = TempDir::new().context("create temp home")?;
-    let temp_home = temp.path();
-
-    let temp_config_path = temp_home.join(".openclaw").join("openclaw.json");
-    if let Some(ref s) = before_config {
-        write_string(&temp_config_path, s)?;
-    }
-
-    let profile = droidgear_core::openclaw::get_openclaw_profile_for_home(home_dir, profile_id)
-        .map_err(anyhow::Error::msg)?;
-    droidgear_core::openclaw::save_openclaw_profile_for_home(temp_home, profile.clone())
-        .map_err(anyhow::Error::msg)?;
-    droidgear_core::openclaw::apply_openclaw_profile_for_home(temp_home, &profile)
-        .map_err(anyhow::Error::msg)?;
-
-    let after_config = read_to_string_if_exists(&temp_config_path)?;
-    let temp_active_path = temp_home
-        .join(".droidgear")
-        .join("openclaw")
-        .join("active-profile.txt");
-    let after_active = read_to_string_if_exists(&temp_active_path)?;
-
-    Ok(format_diff_report(
-        "OpenClaw apply preview",
-        vec![
-            (
-                real_config_path.to_string_lossy().to_string(),
-                before_config,
-                after_config,
-            ),
-            (
-                real_active_path.to_string_lossy().to_string(),
-                before_active,
-                after_active,
-            ),
-        ],
-    ))
-}
-
 pub(super) fn byte_index_for_char(value: &str, char_idx: usize) -> usize {
     value
         .char_indices()
@@ -335,7 +335,16 @@ fn render_list(frame: &mut Frame, list: List<'_>, area: Rect, selected: Option<u
 }
 
 pub fn draw(frame: &mut Frame, app: &app::App) {
-    let area = frame.area();
+    let mut area = frame.area();
+    if let Some(line) = app.quota_status_line() {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+            .split(area);
+        area = rows[0];
+        let p = Paragraph::new(Line::from(Span::styled(line, theme().warning_style())));
+        frame.render_widget(p, rows[1]);
+    }
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Length(18), Constraint::Min(0)].as_ref())
@@ -400,7 +409,9 @@ fn draw_main(frame: &mut Frame, app: &app::App, area: Rect) {
         app::Screen::HermesProfile => draw_hermes_profile(frame, app, area),
         app::Screen::HermesProvider => draw_hermes_provider(frame, app, area),
         app::Screen::Sessions => draw_sessions(frame, app, area),
+        app::Screen::Usage => draw_usage(frame, app, area),
         app::Screen::Specs => draw_specs(frame, app, area),
+        app::Screen::Backups => draw_backups(frame, app, area),
         app::Screen::Channels => draw_channels(frame, app, area),
         app::Screen::ChannelsEdit => draw_channels_edit(frame, app, area),
         app::Screen::Missions => draw_missions(frame, app, area),
@@ -599,7 +610,7 @@ fn draw_factory(frame: &mut Frame, app: &app::App, area: Rect) {
     render_list(frame, list, chunks[0], selected);
 
     let help = help_paragraph(
-        "Up/Down: select  Enter/e: open  n: new  c: copy  x: delete  d: set default  E: raw edit  r: refresh  q/Esc: back",
+        "Up/Down: select  Enter/e: open  n: new  c: copy  x: delete  d: set default  t: test all  P: probe caps  A: auto-fill  T: test config  h: history  E: raw edit  r: refresh  q/Esc: back",
     );
     frame.render_widget(help, chunks[1]);
 }
@@ -739,7 +750,9 @@ fn draw_factory_model(frame: &mut Frame, app: &app::App, area: Rect) {
         .highlight_style(t.selected_row_style());
     render_list(frame, list, chunks[0], Some(app.factory_model_field_index));
 
-    let help = help_paragraph("Up/Down: select  Enter/e: edit/toggle  s: save  q/Esc: back");
+    let help = help_paragraph(
+        "Up/Down: select  Enter/e: edit/toggle  p: preview save  s: save  q/Esc: back",
+    );
     frame.render_widget(help, chunks[1]);
 }
 
@@ -980,7 +993,7 @@ fn draw_codex_profiles(frame: &mut Frame, app: &app::App, area: Rect) {
             .map(|p| (p.name.as_str(), p.id.as_str())),
         active,
         selected_index,
-        "Up/Down: select  Enter/e: open  E: raw edit  p: preview  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
+        "Up/Down: select  Enter/e: open  E: raw edit  p: preview  T: test  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
     );
 }
 
@@ -996,7 +1009,7 @@ fn draw_claude_profiles(frame: &mut Frame, app: &app::App, area: Rect) {
             .map(|profile| (profile.name.as_str(), profile.id.as_str())),
         active,
         selected_index,
-        "Up/Down: select  Enter/e: open  t: run preview  x: run+exit  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
+        "Up/Down: select  Enter/e: open  p: preview  T: test  t: run preview  x: run+exit  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
     );
 }
 
@@ -1127,7 +1140,7 @@ fn draw_opencode_profiles(frame: &mut Frame, app: &app::App, area: Rect) {
             .map(|p| (p.name.as_str(), p.id.as_str())),
         active,
         selected_index,
-        "Up/Down: select  Enter/e: open  E: raw edit  p: preview  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
+        "Up/Down: select  Enter/e: open  E: raw edit  p: preview  T: test  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
     );
 }
 
@@ -1143,7 +1156,7 @@ fn draw_openclaw_profiles(frame: &mut Frame, app: &app::App, area: Rect) {
             .map(|p| (p.name.as_str(), p.id.as_str())),
         active,
         selected_index,
-        "Up/Down: select  Enter/e: open  E: raw edit  p: preview  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
+        "Up/Down: select  Enter/e: open  E: raw edit  p: preview  T: test  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
     );
 }
 
@@ -2260,6 +2273,7 @@ fn draw_sessions(frame: &mut Frame, app: &app::App, area: Rect) {
         let selected = i == app.sessions_index;
         if selected {
             items.push(ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<11} ", s.source.label())),
                 Span::raw(s.title.clone()),
                 Span::raw("  "),
                 Span::raw(format!("[{}]", s.project)),
@@ -2268,6 +2282,7 @@ fn draw_sessions(frame: &mut Frame, app: &app::App, area: Rect) {
             ])));
         } else {
             items.push(ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<11} ", s.source.label()), t.dim_style()),
                 Span::raw(s.title.clone()),
                 Span::raw("  "),
                 Span::styled(format!("[{}]", s.project), t.dim_style()),
@@ -2293,6 +2308,64 @@ fn draw_sessions(frame: &mut Frame, app: &app::App, area: Rect) {
     frame.render_widget(help, chunks[1]);
 }
 
+fn draw_usage(frame: &mut Frame, app: &app::App, area: Rect) {
+    use crate::session_cli::{usage_table_header, usage_table_row};
+
+    let t = theme();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)].as_ref())
+        .split(area);
+
+    let dimension = app.usage_dimension;
+    let buckets = app
+        .usage_report
+        .as_ref()
+        .map(|r| r.buckets(dimension))
+        .unwrap_or_default();
+
+    // The first row is the column header
+    let mut items = vec![ListItem::new(Line::from(Span::styled(
+        usage_table_header(&dimension.label().to_uppercase()),
+        t.dim_style(),
+    )))];
+    for bucket in buckets {
+        items.push(ListItem::new(Line::from(Span::raw(usage_table_row(
+            &bucket.key,
+            &bucket.totals,
+        )))));
+    }
+    match &app.usage_report {
+        Some(report) if !buckets.is_empty() => {
+            items.push(ListItem::new(Line::from(Span::styled(
+                usage_table_row("TOTAL", &report.totals),
+                t.key_style(),
+            ))));
+            if !report.unpriced_models.is_empty() {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("* No price for: {}", report.unpriced_models.join(", ")),
+                    t.dim_style(),
+                ))));
+            }
+        }
+        _ => items.push(ListItem::new(Line::from(Span::styled(
+            "No sessions with token usage",
+            t.placeholder_style(),
+        )))),
+    }
+
+    let selected = (!buckets.is_empty()).then_some(app.usage_index + 1);
+    let list = List::new(items)
+        .block(block(format!("Usage by {}", dimension.label())))
+        .highlight_style(t.selected_row_style());
+    render_list(frame, list, chunks[0], selected);
+
+    let help = help_paragraph(
+        "Up/Down: select  Tab/Left/Right: day, week, project, model, agent  r: refresh  q/Esc: back",
+    );
+    frame.render_widget(help, chunks[1]);
+}
+
 fn draw_specs(frame: &mut Frame, app: &app::App, area: Rect) {
     let t = theme();
     let chunks = Layout::default()
@@ -2321,6 +2394,47 @@ fn draw_specs(frame: &mut Frame, app: &app::App, area: Rect) {
     frame.render_widget(help, chunks[1]);
 }
 
+fn draw_backups(frame: &mut Frame, app: &app::App, area: Rect) {
+    let t = theme();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)].as_ref())
+        .split(area);
+
+    let mut items: Vec<ListItem> = Vec::new();
+    for b in app.backups.iter() {
+        let created = b
+            .created_at
+            .get(..19)
+            .unwrap_or(&b.created_at)
+            .replace('T', " ");
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(created, t.dim_style()),
+            Span::raw("  "),
+            Span::raw(b.path.clone()),
+            Span::raw("  "),
+            Span::styled(format!("[{}]", b.source), t.key_style()),
+        ])));
+    }
+    if items.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "No backups",
+            t.placeholder_style(),
+        ))));
+    }
+
+    let selected = (!app.backups.is_empty()).then_some(app.backups_index);
+    let list = List::new(items)
+        .block(block("Backups"))
+        .highlight_style(t.selected_row_style());
+    render_list(frame, list, chunks[0], selected);
+
+    let help = help_paragraph(
+        "Up/Down: select  Enter/v: diff vs current  R: restore  r: refresh  q/Esc: back",
+    );
+    frame.render_widget(help, chunks[1]);
+}
+
 fn draw_channels(frame: &mut Frame, app: &app::App, area: Rect) {
     let t = theme();
     let chunks = Layout::default()
@@ -2344,6 +2458,7 @@ fn draw_channels(frame: &mut Frame, app: &app::App, area: Rect) {
                 Span::styled(format!("[{enabled}]"), enabled_style),
                 Span::raw("  "),
                 Span::raw(c.base_url.clone()),
+                Span::styled(channel_quota_label(app, &c.id), t.dim_style()),
             ])));
         } else {
             let enabled_style = if c.enabled {
@@ -2357,6 +2472,7 @@ fn draw_channels(frame: &mut Frame, app: &app::App, area: Rect) {
                 Span::styled(format!("[{enabled}]"), enabled_style),
                 Span::raw("  "),
                 Span::styled(c.base_url.clone(), t.dim_style()),
+                Span::styled(channel_quota_label(app, &c.id), t.dim_style()),
             ])));
         }
     }
@@ -2374,11 +2490,35 @@ fn draw_channels(frame: &mut Frame, app: &app::App, area: Rect) {
     render_list(frame, list, chunks[0], selected);
 
     let help = help_paragraph(
-        "Up/Down: select  Enter/e: open  n: new  t: toggle  d: delete  E: raw list  A: raw auth  r: refresh  q/Esc: back",
+        "Up/Down: select  Enter/e: open  n: new  t: toggle  d: delete  p: provision profiles  E: raw list  A: raw auth  r: refresh  q/Esc: back",
     );
     frame.render_widget(help, chunks[1]);
 }
 
+/// `  12.30 USD (~4.2d)` from the last quota snapshot, empty if unknown.
+fn channel_quota_label(app: &app::App, channel_id: &str) -> String {
+    let Some(status) = app
+        .quota_statuses
+        .iter()
+        .find(|s| s.channel_id == channel_id)
+    else {
+        return String::new();
+    };
+    let Some(latest) = status.latest.as_ref() else {
+        return String::new();
+    };
+    if latest.unlimited {
+        return "  unlimited".to_string();
+    }
+    let Some(remaining) = latest.remaining else {
+        return String::new();
+    };
+    match status.days_remaining {
+        Some(days) => format!("  {remaining:.2} {} (~{days:.1}d)", latest.unit),
+        None => format!("  {remaining:.2} {}", latest.unit),
+    }
+}
+
 fn draw_channels_edit(frame: &mut Frame, app: &app::App, area: Rect) {
     let t = theme();
     let Some(channel) = app.channels_edit_draft.as_ref() else {
@@ -2392,28 +2532,15 @@ fn draw_channels_edit(frame: &mut Frame, app: &app::App, area: Rect) {
         return;
     };
 
-    let channel_type = match channel.channel_type {
-        droidgear_core::channel::ChannelType::NewApi => "new-api",
-        droidgear_core::channel::ChannelType::Sub2Api => "sub-2-api",
-        droidgear_core::channel::ChannelType::CliProxyApi => "cli-proxy-api",
-        droidgear_core::channel::ChannelType::Ollama => "ollama",
-        droidgear_core::channel::ChannelType::General => "general",
-        droidgear_core::channel::ChannelType::DeepSeek => "deep-seek",
-    };
-
-    let uses_api_key = matches!(
-        channel.channel_type,
-        droidgear_core::channel::ChannelType::CliProxyApi
-            | droidgear_core::channel::ChannelType::Ollama
-            | droidgear_core::channel::ChannelType::General
-    );
+    let channel_type = channel.channel_type.id();
+    let uses_api_key = !channel.channel_type.uses_login();
     let enabled = if channel.enabled { "yes" } else { "no" };
     let api_key_set = !app.channels_edit_api_key.trim().is_empty();
     let password_set = !app.channels_edit_password.trim().is_empty();
 
     let mut fields: Vec<(&str, String)> = vec![
         ("Name", not_set_if_blank(&channel.name)),
-        ("Type", channel_type.to_string()),
+        ("Type", channel_type),
         ("Base URL", not_set_if_blank(&channel.base_url)),
         ("Enabled", enabled.to_string()),
     ];
@@ -2805,7 +2932,7 @@ fn draw_pi_profiles(frame: &mut Frame, app: &app::App, area: Rect) {
             .map(|p| (p.name.as_str(), p.id.as_str())),
         active,
         selected_index,
-        "Up/Down: select  Enter/e: open  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
+        "Up/Down: select  Enter/e: open  p: preview  T: test  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
     );
 }
 
@@ -3128,7 +3255,7 @@ fn draw_hermes_profiles(frame: &mut Frame, app: &app::App, area: Rect) {
             .map(|p| (p.name.as_str(), p.id.as_str())),
         active,
         selected_index,
-        "Up/Down: select  Enter/e: open  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
+        "Up/Down: select  Enter/e: open  p: preview  T: test  a: apply  n: new  c: copy  d: delete  r: refresh  q/Esc: back",
     );
 }
 
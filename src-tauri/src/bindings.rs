@@ -2,9 +2,9 @@ use tauri_specta::{collect_commands, Builder};
 
 pub fn generate_bindings() -> Builder<tauri::Wry> {
     use crate::commands::{
-        channel, channel_export, claude, claude_settings, codex, config, connectivity,
+        backup, channel, channel_export, claude, claude_settings, codex, config, connectivity,
         droid_settings, env, factory_auth_profiles, hermes, mcp, notifications, openclaw, opencode,
-        paths, pi, preferences, recovery, sessions, specs, updater, window,
+        paths, pi, preferences, quota, recovery, secrets, sessions, specs, updater, window,
     };
 
     Builder::<tauri::Wry>::new().commands(collect_commands![
@@ -20,9 +20,11 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         config::reset_config_file,
         config::load_custom_models,
         config::save_custom_models,
+        config::plan_save_custom_models,
         config::check_legacy_config,
         config::delete_legacy_config,
         config::fetch_models,
+        config::custom_model_from_channel_model,
         config::get_default_model,
         config::save_default_model,
         config::get_cloud_session_sync,
@@ -47,6 +49,12 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         config::save_compaction_token_limit,
         config::get_compaction_token_limit_per_model,
         config::save_compaction_token_limit_per_model,
+        backup::list_backup_files,
+        backup::list_backups,
+        backup::diff_backup,
+        backup::restore_backup,
+        backup::get_backup_retention,
+        backup::save_backup_retention,
         channel::load_channels,
         channel::save_channels,
         channel::save_channel_credentials,
@@ -56,7 +64,29 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         channel::delete_channel_credentials,
         channel::detect_channel_type,
         channel::fetch_channel_tokens,
+        channel::fetch_channel_quota,
+        channel::create_channel_token,
+        channel::set_channel_token_enabled,
+        channel::delete_channel_token,
+        channel::rotate_channel_token,
+        channel::provision_channel_profiles,
+        channel::save_provisioned_profiles,
+        channel::sync_linked_profiles,
         channel::fetch_models_by_api_key,
+        quota::get_quota_monitor_settings,
+        quota::save_quota_monitor_settings,
+        quota::get_quota_status,
+        quota::get_quota_history,
+        quota::refresh_channel_quotas,
+        secrets::get_vault_status,
+        secrets::init_vault,
+        secrets::unlock_vault,
+        secrets::lock_vault,
+        secrets::change_vault_passphrase,
+        secrets::list_secrets,
+        secrets::set_secret,
+        secrets::delete_secret,
+        secrets::migrate_plaintext_secrets,
         channel_export::load_export_templates,
         channel_export::save_export_template,
         channel_export::delete_export_template,
@@ -85,6 +115,7 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         claude::get_active_claude_profile_id,
         claude::set_active_claude_profile_id,
         claude::apply_claude_profile,
+        claude::plan_apply_claude_profile,
         claude::get_claude_config_status,
         claude::read_claude_current_config,
         claude::get_claude_temporary_run_plan,
@@ -106,6 +137,7 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         codex::create_default_codex_profile,
         codex::get_active_codex_profile_id,
         codex::apply_codex_profile,
+        codex::plan_apply_codex_profile,
         codex::get_codex_config_status,
         codex::read_codex_current_config,
         codex::get_codex_cli_capability,
@@ -119,6 +151,7 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         hermes::create_default_hermes_profile,
         hermes::get_active_hermes_profile_id,
         hermes::apply_hermes_profile,
+        hermes::plan_apply_hermes_profile,
         hermes::get_hermes_config_status,
         hermes::read_hermes_current_config,
         pi::list_pi_profiles,
@@ -130,8 +163,10 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         pi::get_active_pi_profile_id,
         pi::set_active_pi_profile_id,
         pi::apply_pi_profile,
+        pi::plan_apply_pi_profile,
         pi::get_pi_config_status,
         pi::read_pi_current_config,
+        pi::pi_models_from_channel,
         opencode::list_opencode_profiles,
         opencode::get_opencode_profile,
         opencode::save_opencode_profile,
@@ -140,10 +175,12 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         opencode::create_default_profile,
         opencode::get_active_opencode_profile_id,
         opencode::apply_opencode_profile,
+        opencode::plan_apply_opencode_profile,
         opencode::get_opencode_config_status,
         opencode::get_opencode_provider_templates,
         opencode::test_opencode_provider_connection,
         opencode::read_opencode_current_config,
+        opencode::opencode_models_from_channel,
         openclaw::list_openclaw_profiles,
         openclaw::get_openclaw_profile,
         openclaw::save_openclaw_profile,
@@ -152,13 +189,22 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         openclaw::create_default_openclaw_profile,
         openclaw::get_active_openclaw_profile_id,
         openclaw::apply_openclaw_profile,
+        openclaw::plan_apply_openclaw_profile,
         openclaw::get_openclaw_config_status,
         openclaw::read_openclaw_current_config,
         openclaw::read_openclaw_subagents,
         openclaw::save_openclaw_subagents,
+        openclaw::openclaw_models_from_channel,
         sessions::list_session_projects,
         sessions::list_sessions,
+        sessions::list_agent_session_projects,
+        sessions::list_agent_sessions,
         sessions::get_session_detail,
+        sessions::search_sessions,
+        sessions::export_session,
+        sessions::get_usage_report,
+        sessions::get_model_prices,
+        sessions::save_model_prices,
         sessions::start_sessions_watcher,
         sessions::stop_sessions_watcher,
         sessions::delete_session,
@@ -166,8 +212,24 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         connectivity::test_all_model_connections_command,
         connectivity::get_connectivity_summary,
         connectivity::test_provider_connection,
+        connectivity::test_endpoint_connection,
+        connectivity::list_profile_endpoints,
+        connectivity::test_profile_connections,
+        connectivity::test_all_profile_connections,
         connectivity::test_model_connection_with_mode,
         connectivity::test_all_model_connections_with_mode,
+        connectivity::test_model_connection_with_config,
+        connectivity::test_all_model_connections_with_config,
+        connectivity::probe_model_capabilities,
+        connectivity::propose_model_patch,
+        connectivity::plan_model_patch,
+        connectivity::accept_model_patch,
+        connectivity::get_connectivity_test_config,
+        connectivity::save_connectivity_test_config,
+        connectivity::get_connectivity_history,
+        connectivity::get_connectivity_stats,
+        connectivity::get_model_availability,
+        connectivity::clear_connectivity_history,
         paths::get_config_paths,
         paths::get_effective_paths,
         paths::save_config_path,
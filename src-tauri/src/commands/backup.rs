@@ -0,0 +1,49 @@
+//! Config backup commands (Tauri wrappers).
+//!
+//! Snapshot/retention logic lives in `droidgear-core`.
+
+use std::path::PathBuf;
+
+pub use droidgear_core::backup::{BackupDiff, BackupEntry, BackupFileSummary};
+
+/// Lists all config files that have backups.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_backup_files() -> Result<Vec<BackupFileSummary>, String> {
+    droidgear_core::backup::list_backup_files()
+}
+
+/// Lists backups of a single config file, newest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_backups(path: String) -> Result<Vec<BackupEntry>, String> {
+    droidgear_core::backup::list_backups(&PathBuf::from(path))
+}
+
+/// Diffs a backup against the current file content.
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_backup(id: String) -> Result<BackupDiff, String> {
+    droidgear_core::backup::diff_backup(&id)
+}
+
+/// Restores a backup over its original file (the current content is backed up first).
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_backup(id: String) -> Result<BackupEntry, String> {
+    droidgear_core::backup::restore_backup(&id)
+}
+
+/// Gets the number of backups kept per file.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_backup_retention() -> Result<u32, String> {
+    droidgear_core::backup::get_backup_retention()
+}
+
+/// Saves the number of backups kept per file.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_backup_retention(retention: u32) -> Result<(), String> {
+    droidgear_core::backup::save_backup_retention(retention)
+}
@@ -4,7 +4,12 @@
 
 use super::config::ModelInfo;
 
-pub use droidgear_core::channel::{Channel, ChannelToken, ChannelType};
+pub use droidgear_core::channel::{
+    Channel, ChannelQuota, ChannelToken, ChannelType, NewChannelToken,
+};
+pub use droidgear_core::channel_provisioning::ProvisionedProfiles;
+pub use droidgear_core::channel_sync::LinkSyncReport;
+pub use droidgear_core::token_rotation::{RevokeMode, TokenRotationReport};
 
 /// Loads all channels from ~/.droidgear/channels.json
 /// Falls back to ~/.factory/settings.json for migration
@@ -14,14 +19,30 @@ pub async fn load_channels() -> Result<Vec<Channel>, String> {
     droidgear_core::channel::load_channels()
 }
 
-/// Saves all channels to ~/.droidgear/channels.json
+/// Saves all channels to ~/.droidgear/channels.json and syncs the entries
+/// linked to channels whose base URL changed
 #[tauri::command]
 #[specta::specta]
 pub async fn save_channels(channels: Vec<Channel>) -> Result<(), String> {
-    droidgear_core::channel::save_channels(channels)
+    let previous = droidgear_core::channel::load_channels().unwrap_or_default();
+    let moved: Vec<String> = channels
+        .iter()
+        .filter(|c| {
+            previous
+                .iter()
+                .any(|p| p.id == c.id && p.base_url != c.base_url)
+        })
+        .map(|c| c.id.clone())
+        .collect();
+    droidgear_core::channel::save_channels(channels)?;
+    for channel_id in moved {
+        sync_channel_links(&channel_id).await;
+    }
+    Ok(())
 }
 
-/// Saves a channel's credentials to ~/.droidgear/auth/
+/// Saves a channel's credentials to ~/.droidgear/auth/ and syncs the entries
+/// linked to the channel
 #[tauri::command]
 #[specta::specta]
 pub async fn save_channel_credentials(
@@ -29,7 +50,9 @@ pub async fn save_channel_credentials(
     username: String,
     password: String,
 ) -> Result<(), String> {
-    droidgear_core::channel::save_channel_credentials(&channel_id, &username, &password)
+    droidgear_core::channel::save_channel_credentials(&channel_id, &username, &password)?;
+    sync_channel_links(&channel_id).await;
+    Ok(())
 }
 
 /// Gets a channel's credentials from ~/.droidgear/auth/
@@ -41,11 +64,14 @@ pub async fn get_channel_credentials(
     droidgear_core::channel::get_channel_credentials(&channel_id)
 }
 
-/// Saves a channel's API key to ~/.droidgear/auth/
+/// Saves a channel's API key to ~/.droidgear/auth/ and syncs the entries
+/// linked to the channel
 #[tauri::command]
 #[specta::specta]
 pub async fn save_channel_api_key(channel_id: String, api_key: String) -> Result<(), String> {
-    droidgear_core::channel::save_channel_api_key(&channel_id, &api_key)
+    droidgear_core::channel::save_channel_api_key(&channel_id, &api_key)?;
+    sync_channel_links(&channel_id).await;
+    Ok(())
 }
 
 /// Gets a channel's API key from ~/.droidgear/auth/
@@ -82,6 +108,135 @@ pub async fn fetch_channel_tokens(
         .await
 }
 
+/// Fetches a channel's account balance (None if the backend has no quota API)
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_channel_quota(
+    channel_type: ChannelType,
+    base_url: String,
+    username: String,
+    password: String,
+) -> Result<Option<ChannelQuota>, String> {
+    droidgear_core::channel::fetch_channel_quota(channel_type, &base_url, &username, &password)
+        .await
+}
+
+/// Creates a token on the channel's dashboard and returns it with its full key
+#[tauri::command]
+#[specta::specta]
+pub async fn create_channel_token(
+    channel_type: ChannelType,
+    base_url: String,
+    username: String,
+    password: String,
+    token: NewChannelToken,
+) -> Result<ChannelToken, String> {
+    droidgear_core::channel::create_channel_token(
+        channel_type,
+        &base_url,
+        &username,
+        &password,
+        &token,
+    )
+    .await
+}
+
+/// Enables or disables a token on the channel's dashboard
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_token_enabled(
+    channel_type: ChannelType,
+    base_url: String,
+    username: String,
+    password: String,
+    token_id: f64,
+    enabled: bool,
+) -> Result<(), String> {
+    droidgear_core::channel::set_channel_token_enabled(
+        channel_type,
+        &base_url,
+        &username,
+        &password,
+        token_id,
+        enabled,
+    )
+    .await
+}
+
+/// Deletes a token on the channel's dashboard
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_channel_token(
+    channel_type: ChannelType,
+    base_url: String,
+    username: String,
+    password: String,
+    token_id: f64,
+) -> Result<(), String> {
+    droidgear_core::channel::delete_channel_token(
+        channel_type,
+        &base_url,
+        &username,
+        &password,
+        token_id,
+    )
+    .await
+}
+
+/// Replaces a token with a new one, rewrites every profile, custom model and
+/// vault secret holding the old key, then revokes the old token
+#[tauri::command]
+#[specta::specta]
+pub async fn rotate_channel_token(
+    channel_id: String,
+    token_id: f64,
+    revoke: RevokeMode,
+) -> Result<TokenRotationReport, String> {
+    droidgear_core::token_rotation::rotate_channel_token(&channel_id, token_id, revoke).await
+}
+
+/// Builds Codex, Claude Code, OpenCode, OpenClaw, Pi and Hermes profiles plus
+/// Factory custom models for a channel token and the selected models. With an
+/// initialized vault, profiles reference the key as a `secret://` entry
+#[tauri::command]
+#[specta::specta]
+pub fn provision_channel_profiles(
+    channel: Channel,
+    token: ChannelToken,
+    models: Vec<ModelInfo>,
+) -> Result<ProvisionedProfiles, String> {
+    droidgear_core::channel_provisioning::provision_channel_profiles(&channel, &token, &models)
+}
+
+/// Saves profiles built by `provision_channel_profiles`
+#[tauri::command]
+#[specta::specta]
+pub fn save_provisioned_profiles(profiles: ProvisionedProfiles) -> Result<(), String> {
+    droidgear_core::channel_provisioning::save_provisioned_profiles(profiles)
+}
+
+/// Applies the drift of entries linked to a channel that just changed. Sync
+/// problems are logged rather than failing the save that triggered them.
+async fn sync_channel_links(channel_id: &str) {
+    match droidgear_core::channel_sync::sync_channel_links(channel_id).await {
+        Ok(report) => {
+            for error in report.errors {
+                log::warn!("Channel {channel_id} link sync: {error}");
+            }
+        }
+        Err(error) => log::warn!("Failed to sync entries linked to channel {channel_id}: {error}"),
+    }
+}
+
+/// Compares channel-linked profile entries and custom models with their
+/// channels; with `apply`, rewrites base URLs and keys and drops models the
+/// channel no longer offers
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_linked_profiles(apply: bool) -> Result<LinkSyncReport, String> {
+    droidgear_core::channel_sync::sync_linked_profiles(apply).await
+}
+
 /// Fetches models using an API key (for quick model addition from channels)
 #[tauri::command]
 #[specta::specta]
@@ -2,6 +2,7 @@
 //!
 //! Core logic lives in `droidgear-core`.
 
+pub use droidgear_core::apply_plan::ApplyPlan;
 pub use droidgear_core::claude::{ClaudeCodeProfile, ClaudeConfigStatus, ClaudeCurrentConfig};
 use droidgear_core::claude_runtime::{self, ClaudeTemporaryLaunchPlan, ClaudeTemporaryRunPlan};
 
@@ -80,6 +81,13 @@ pub async fn apply_claude_profile(id: String) -> Result<(), String> {
     droidgear_core::claude::apply_claude_profile(&id)
 }
 
+/// Dry-run of `apply_claude_profile`: planned file writes with a key-level diff
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_apply_claude_profile(id: String) -> Result<ApplyPlan, String> {
+    droidgear_core::claude::plan_apply_claude_profile(&id)
+}
+
 /// Get Claude Code config status
 #[tauri::command]
 #[specta::specta]
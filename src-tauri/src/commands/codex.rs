@@ -2,6 +2,7 @@
 //!
 //! Core logic lives in `droidgear-core`.
 
+pub use droidgear_core::apply_plan::ApplyPlan;
 pub use droidgear_core::codex::{CodexConfigStatus, CodexCurrentConfig, CodexProfile};
 
 use droidgear_core::codex_runtime::{
@@ -68,6 +69,13 @@ pub async fn apply_codex_profile(id: String) -> Result<(), String> {
     droidgear_core::codex::apply_codex_profile(&id)
 }
 
+/// Dry-run of `apply_codex_profile`: planned file writes with a key-level diff
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_apply_codex_profile(id: String) -> Result<ApplyPlan, String> {
+    droidgear_core::codex::plan_apply_codex_profile(&id)
+}
+
 /// Get Codex config status
 #[tauri::command]
 #[specta::specta]
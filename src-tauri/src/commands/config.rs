@@ -2,6 +2,7 @@
 //!
 //! Core logic lives in `droidgear-core`.
 
+pub use droidgear_core::apply_plan::ApplyPlan;
 pub use droidgear_core::factory_settings::{
     CustomModel, MissionModelSettings, ModelInfo, Provider, SessionDefaultSettings,
 };
@@ -36,6 +37,13 @@ pub async fn save_custom_models(models: Vec<CustomModel>) -> Result<(), String>
     droidgear_core::factory_settings::save_custom_models(models)
 }
 
+/// Dry-run of `save_custom_models`: planned settings.json write with a key-level diff
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_save_custom_models(models: Vec<CustomModel>) -> Result<ApplyPlan, String> {
+    droidgear_core::factory_settings::plan_save_custom_models(models)
+}
+
 /// Checks if legacy config.json exists and settings.json has customModels
 #[tauri::command]
 #[specta::specta]
@@ -62,6 +70,20 @@ pub async fn fetch_models(
     droidgear_core::factory_settings::fetch_models(provider, &base_url, &api_key).await
 }
 
+/// Builds a custom model from a fetched channel model's metadata
+#[tauri::command]
+#[specta::specta]
+pub fn custom_model_from_channel_model(
+    info: ModelInfo,
+    base_url: String,
+    api_key: String,
+    provider: Provider,
+) -> CustomModel {
+    droidgear_core::factory_settings::custom_model_from_model_info(
+        &info, &base_url, &api_key, provider,
+    )
+}
+
 /// Gets the default model ID from sessionDefaultSettings.model
 #[tauri::command]
 #[specta::specta]
@@ -1,8 +1,14 @@
 //! Model connectivity testing commands.
 
+use droidgear_core::apply_plan::ApplyPlan;
 use droidgear_core::connectivity::{
-    self, ConnectionDiagnostics, ConnectivitySummary, ModelTestResult, TestMode,
+    self, CapabilityReport, ConnectionDiagnostics, ConnectivitySummary, ModelTestResult,
+    TestConfig, TestMode, WireProtocol,
 };
+use droidgear_core::connectivity_history::{self, HistoryRecord, ModelAvailabilityStats};
+use droidgear_core::model_autofill::{self, ModelPatch};
+use droidgear_core::profile_connectivity::{self, ProfileEndpoint, ProfileTestResult};
+use droidgear_core::profiles::ProfileTool;
 use tauri::command;
 
 #[command]
@@ -33,10 +39,72 @@ pub async fn test_provider_connection(
     api_key: String,
     model_id: String,
 ) -> Result<ConnectionDiagnostics, String> {
-    let tester = connectivity::ModelTester::new();
-    Ok(tester
+    let tester = connectivity::ModelTester::with_config(connectivity::get_test_config()?);
+    let diagnostics = tester
         .test_model_direct(&provider, &base_url, &api_key, &model_id)
-        .await)
+        .await;
+    connectivity::record_diagnostics(&diagnostics, &base_url);
+    Ok(diagnostics)
+}
+
+/// Tests an endpoint over an explicit wire protocol (including Gemini and the
+/// OpenAI Responses API) in any mode.
+#[command]
+#[specta::specta]
+pub async fn test_endpoint_connection(
+    protocol: WireProtocol,
+    mode: TestMode,
+    base_url: String,
+    api_key: String,
+    model_id: String,
+    prompt: Option<String>,
+) -> Result<ConnectionDiagnostics, String> {
+    let tester = connectivity::ModelTester::with_config(connectivity::get_test_config()?);
+    let diagnostics = tester
+        .test_endpoint(
+            protocol,
+            &mode,
+            &base_url,
+            &api_key,
+            &model_id,
+            prompt.as_deref().unwrap_or("Hi"),
+        )
+        .await;
+    connectivity::record_diagnostics(&diagnostics, &base_url);
+    Ok(diagnostics)
+}
+
+/// Lists the model endpoints configured in a tool profile.
+#[command]
+#[specta::specta]
+pub fn list_profile_endpoints(
+    tool: ProfileTool,
+    profile_id: String,
+) -> Result<Vec<ProfileEndpoint>, String> {
+    profile_connectivity::list_profile_endpoints(tool, &profile_id)
+}
+
+/// Tests every endpoint of a tool profile with its wire protocol.
+#[command]
+#[specta::specta]
+pub async fn test_profile_connections(
+    tool: ProfileTool,
+    profile_id: String,
+    mode: TestMode,
+    prompt: Option<String>,
+) -> Result<Vec<ProfileTestResult>, String> {
+    profile_connectivity::test_profile_connections(tool, &profile_id, mode, prompt).await
+}
+
+/// Tests the endpoints of all profiles, optionally limited to one tool.
+#[command]
+#[specta::specta]
+pub async fn test_all_profile_connections(
+    tool: Option<ProfileTool>,
+    mode: TestMode,
+    prompt: Option<String>,
+) -> Result<Vec<ProfileTestResult>, String> {
+    profile_connectivity::test_all_profile_connections(tool, mode, prompt).await
 }
 
 #[command]
@@ -57,3 +125,128 @@ pub async fn test_all_model_connections_with_mode(
 ) -> Result<Vec<ModelTestResult>, String> {
     connectivity::test_all_model_connections_with_mode(mode, prompt).await
 }
+
+/// Tests one model with an explicit retry/concurrency config (saved config when omitted).
+#[command]
+#[specta::specta]
+pub async fn test_model_connection_with_config(
+    model_id: String,
+    mode: TestMode,
+    prompt: Option<String>,
+    config: Option<TestConfig>,
+) -> Result<ModelTestResult, String> {
+    let config = match config {
+        Some(c) => c,
+        None => connectivity::get_test_config()?,
+    };
+    connectivity::test_specific_model_connection_with_config(&model_id, mode, prompt, config).await
+}
+
+/// Tests all models concurrently with an explicit config (saved config when omitted).
+#[command]
+#[specta::specta]
+pub async fn test_all_model_connections_with_config(
+    mode: TestMode,
+    prompt: Option<String>,
+    config: Option<TestConfig>,
+) -> Result<Vec<ModelTestResult>, String> {
+    let config = match config {
+        Some(c) => c,
+        None => connectivity::get_test_config()?,
+    };
+    connectivity::test_all_model_connections_with_config(mode, prompt, config).await
+}
+
+/// Probes streaming, tool use and vision support of one model.
+#[command]
+#[specta::specta]
+pub async fn probe_model_capabilities(
+    model_id: String,
+    config: Option<TestConfig>,
+) -> Result<CapabilityReport, String> {
+    let config = match config {
+        Some(c) => c,
+        None => connectivity::get_test_config()?,
+    };
+    connectivity::probe_model_capabilities_with_config(&model_id, config).await
+}
+
+/// Probes a custom model and proposes updates to its capability fields.
+/// Nothing is saved until the patch is accepted.
+#[command]
+#[specta::specta]
+pub async fn propose_model_patch(
+    model_id: String,
+    config: Option<TestConfig>,
+) -> Result<ModelPatch, String> {
+    let config = match config {
+        Some(c) => c,
+        None => connectivity::get_test_config()?,
+    };
+    model_autofill::propose_model_patch_for_id(&model_id, config).await
+}
+
+/// Dry-run: shows the settings.json changes accepting a model patch would make.
+#[command]
+#[specta::specta]
+pub fn plan_model_patch(patch: ModelPatch) -> Result<ApplyPlan, String> {
+    model_autofill::plan_model_patch(&patch)
+}
+
+/// Applies a reviewed model patch and saves the custom models.
+#[command]
+#[specta::specta]
+pub fn accept_model_patch(patch: ModelPatch) -> Result<(), String> {
+    model_autofill::accept_model_patch(&patch)
+}
+
+/// Gets the saved connectivity test config.
+#[command]
+#[specta::specta]
+pub fn get_connectivity_test_config() -> Result<TestConfig, String> {
+    connectivity::get_test_config()
+}
+
+/// Saves the connectivity test config used by default for all tests.
+#[command]
+#[specta::specta]
+pub fn save_connectivity_test_config(config: TestConfig) -> Result<(), String> {
+    connectivity::save_test_config(config)
+}
+
+/// Gets the persisted test history of one model (newest first).
+/// `window_hours` limits the history to recent tests; omit for all.
+#[command]
+#[specta::specta]
+pub fn get_connectivity_history(
+    model_id: String,
+    window_hours: Option<u32>,
+) -> Result<Vec<HistoryRecord>, String> {
+    connectivity_history::get_model_history(&model_id, window_hours)
+}
+
+/// Gets uptime, latency percentiles and last failure for every tested model.
+#[command]
+#[specta::specta]
+pub fn get_connectivity_stats(
+    window_hours: Option<u32>,
+) -> Result<Vec<ModelAvailabilityStats>, String> {
+    connectivity_history::get_availability_stats(window_hours)
+}
+
+/// Gets availability statistics of one model.
+#[command]
+#[specta::specta]
+pub fn get_model_availability(
+    model_id: String,
+    window_hours: Option<u32>,
+) -> Result<Option<ModelAvailabilityStats>, String> {
+    connectivity_history::get_model_availability(&model_id, window_hours)
+}
+
+/// Deletes the persisted connectivity history.
+#[command]
+#[specta::specta]
+pub fn clear_connectivity_history() -> Result<(), String> {
+    connectivity_history::clear_history()
+}
@@ -2,6 +2,7 @@
 //!
 //! Core logic lives in `droidgear-core`.
 
+pub use droidgear_core::apply_plan::ApplyPlan;
 pub use droidgear_core::hermes::{HermesConfigStatus, HermesCurrentConfig, HermesProfile};
 
 /// List all Hermes profiles
@@ -63,6 +64,13 @@ pub async fn apply_hermes_profile(id: String) -> Result<(), String> {
     droidgear_core::hermes::apply_hermes_profile(&id)
 }
 
+/// Dry-run of `apply_hermes_profile`: planned file writes with a key-level diff
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_apply_hermes_profile(id: String) -> Result<ApplyPlan, String> {
+    droidgear_core::hermes::plan_apply_hermes_profile(&id)
+}
+
 /// Get Hermes config status
 #[tauri::command]
 #[specta::specta]
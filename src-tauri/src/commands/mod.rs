@@ -3,6 +3,7 @@
 //! Each submodule contains related commands and their helper functions.
 //! Import specific commands via their submodule (e.g., `commands::preferences::greet`).
 
+pub mod backup;
 pub mod channel;
 pub mod channel_export;
 pub mod claude;
@@ -21,7 +22,9 @@ pub mod opencode;
 pub mod paths;
 pub mod pi;
 pub mod preferences;
+pub mod quota;
 pub mod recovery;
+pub mod secrets;
 pub mod sessions;
 pub mod specs;
 pub mod updater;
@@ -2,8 +2,10 @@
 //!
 //! Core logic lives in `droidgear-core`.
 
+pub use droidgear_core::apply_plan::ApplyPlan;
+pub use droidgear_core::factory_settings::ModelInfo;
 pub use droidgear_core::openclaw::{
-    OpenClawConfigStatus, OpenClawCurrentConfig, OpenClawProfile, OpenClawSubAgent,
+    OpenClawConfigStatus, OpenClawCurrentConfig, OpenClawModel, OpenClawProfile, OpenClawSubAgent,
 };
 
 /// List all OpenClaw profiles
@@ -65,6 +67,13 @@ pub async fn apply_openclaw_profile(profile: OpenClawProfile) -> Result<(), Stri
     droidgear_core::openclaw::apply_openclaw_profile(&profile)
 }
 
+/// Dry-run of `apply_openclaw_profile`: planned file writes with a key-level diff
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_apply_openclaw_profile(profile: OpenClawProfile) -> Result<ApplyPlan, String> {
+    droidgear_core::openclaw::plan_apply_openclaw_profile(&profile)
+}
+
 /// Get OpenClaw config status
 #[tauri::command]
 #[specta::specta]
@@ -92,3 +101,13 @@ pub async fn read_openclaw_subagents() -> Result<Vec<OpenClawSubAgent>, String>
 pub async fn save_openclaw_subagents(subagents: Vec<OpenClawSubAgent>) -> Result<(), String> {
     droidgear_core::openclaw::save_openclaw_subagents(subagents)
 }
+
+/// Builds OpenClaw models from fetched channel model metadata
+#[tauri::command]
+#[specta::specta]
+pub fn openclaw_models_from_channel(models: Vec<ModelInfo>) -> Vec<OpenClawModel> {
+    models
+        .iter()
+        .map(droidgear_core::openclaw::openclaw_model_from_model_info)
+        .collect()
+}
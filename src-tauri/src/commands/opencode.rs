@@ -2,9 +2,13 @@
 //!
 //! Core logic lives in `droidgear-core`.
 
+pub use droidgear_core::apply_plan::ApplyPlan;
+pub use droidgear_core::factory_settings::ModelInfo;
 pub use droidgear_core::opencode::{
-    OpenCodeConfigStatus, OpenCodeCurrentConfig, OpenCodeProfile, ProviderTemplate,
+    OpenCodeConfigStatus, OpenCodeCurrentConfig, OpenCodeModelConfig, OpenCodeProfile,
+    ProviderTemplate,
 };
+use std::collections::HashMap;
 
 /// List all OpenCode profiles
 #[tauri::command]
@@ -65,6 +69,13 @@ pub async fn apply_opencode_profile(id: String) -> Result<(), String> {
     droidgear_core::opencode::apply_opencode_profile(&id)
 }
 
+/// Dry-run of `apply_opencode_profile`: planned file writes with a key-level diff
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_apply_opencode_profile(id: String) -> Result<ApplyPlan, String> {
+    droidgear_core::opencode::plan_apply_opencode_profile(&id)
+}
+
 /// Get OpenCode config status
 #[tauri::command]
 #[specta::specta]
@@ -97,3 +108,20 @@ pub async fn test_opencode_provider_connection(
 pub async fn read_opencode_current_config() -> Result<OpenCodeCurrentConfig, String> {
     droidgear_core::opencode::read_opencode_current_config()
 }
+
+/// Builds OpenCode model entries (keyed by model ID) from fetched channel model metadata
+#[tauri::command]
+#[specta::specta]
+pub fn opencode_models_from_channel(
+    models: Vec<ModelInfo>,
+) -> HashMap<String, OpenCodeModelConfig> {
+    models
+        .iter()
+        .map(|m| {
+            (
+                m.id.clone(),
+                droidgear_core::opencode::opencode_model_from_model_info(m),
+            )
+        })
+        .collect()
+}
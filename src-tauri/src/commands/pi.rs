@@ -2,7 +2,9 @@
 //!
 //! Core logic lives in `droidgear-core`.
 
-pub use droidgear_core::pi::{PiConfigStatus, PiCurrentConfig, PiProfile};
+pub use droidgear_core::apply_plan::ApplyPlan;
+pub use droidgear_core::factory_settings::ModelInfo;
+pub use droidgear_core::pi::{PiConfigStatus, PiCurrentConfig, PiModel, PiProfile};
 
 /// List all Pi profiles
 #[tauri::command]
@@ -67,6 +69,13 @@ pub async fn apply_pi_profile(id: String) -> Result<(), String> {
     droidgear_core::pi::apply_pi_profile(&id)
 }
 
+/// Dry-run of `apply_pi_profile`: planned file writes with a key-level diff
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_apply_pi_profile(id: String) -> Result<ApplyPlan, String> {
+    droidgear_core::pi::plan_apply_pi_profile(&id)
+}
+
 /// Get Pi config status
 #[tauri::command]
 #[specta::specta]
@@ -80,3 +89,13 @@ pub async fn get_pi_config_status() -> Result<PiConfigStatus, String> {
 pub async fn read_pi_current_config() -> Result<PiCurrentConfig, String> {
     droidgear_core::pi::read_pi_current_config()
 }
+
+/// Builds Pi models from fetched channel model metadata
+#[tauri::command]
+#[specta::specta]
+pub fn pi_models_from_channel(models: Vec<ModelInfo>) -> Vec<PiModel> {
+    models
+        .iter()
+        .map(droidgear_core::pi::pi_model_from_model_info)
+        .collect()
+}
@@ -0,0 +1,88 @@
+//! Channel quota monitoring commands (Tauri wrappers + scheduler).
+//!
+//! Snapshot storage, burn rate and alert logic live in `droidgear-core`. The
+//! background scheduler remains in the Tauri layer: it refreshes when the
+//! configured interval has passed, emits `quota-updated` and raises a native
+//! notification for each new alert.
+
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use super::notifications::send_native_notification;
+
+pub use droidgear_core::quota_monitor::{
+    ChannelQuotaStatus, QuotaMonitorSettings, QuotaRefreshReport, QuotaSnapshot,
+};
+
+/// How often the scheduler checks whether a refresh is due.
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+/// Gets the quota monitor settings.
+#[tauri::command]
+#[specta::specta]
+pub fn get_quota_monitor_settings() -> Result<QuotaMonitorSettings, String> {
+    droidgear_core::quota_monitor::get_settings()
+}
+
+/// Saves the quota monitor settings.
+#[tauri::command]
+#[specta::specta]
+pub fn save_quota_monitor_settings(settings: QuotaMonitorSettings) -> Result<(), String> {
+    droidgear_core::quota_monitor::save_settings(settings)
+}
+
+/// Gets the latest balance, burn rate and alert of every enabled channel
+/// from recorded snapshots (no network access).
+#[tauri::command]
+#[specta::specta]
+pub fn get_quota_status() -> Result<Vec<ChannelQuotaStatus>, String> {
+    droidgear_core::quota_monitor::get_quota_status()
+}
+
+/// Gets the recorded balances of one channel (oldest first).
+/// `window_days` limits the history to recent snapshots; omit for all.
+#[tauri::command]
+#[specta::specta]
+pub fn get_quota_history(
+    channel_id: String,
+    window_days: Option<u32>,
+) -> Result<Vec<QuotaSnapshot>, String> {
+    droidgear_core::quota_monitor::get_quota_history(&channel_id, window_days)
+}
+
+/// Refreshes the balance of every enabled channel now.
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_channel_quotas(app: AppHandle) -> Result<QuotaRefreshReport, String> {
+    let report = droidgear_core::quota_monitor::refresh_all().await?;
+    publish_report(&app, &report).await;
+    Ok(report)
+}
+
+async fn publish_report(app: &AppHandle, report: &QuotaRefreshReport) {
+    let _ = app.emit("quota-updated", report);
+    for alert in &report.new_alerts {
+        if let Err(e) = send_native_notification(
+            app.clone(),
+            "Channel balance alert".to_string(),
+            Some(alert.message.clone()),
+        )
+        .await
+        {
+            log::warn!("Failed to send quota alert notification: {e}");
+        }
+    }
+}
+
+/// Starts the background quota scheduler (called once from app setup).
+pub fn spawn_quota_scheduler(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        if droidgear_core::quota_monitor::is_refresh_due() {
+            match tauri::async_runtime::block_on(droidgear_core::quota_monitor::refresh_all()) {
+                Ok(report) => tauri::async_runtime::block_on(publish_report(&app, &report)),
+                Err(e) => log::warn!("Scheduled quota refresh failed: {e}"),
+            }
+        }
+        std::thread::sleep(SCHEDULER_TICK);
+    });
+}
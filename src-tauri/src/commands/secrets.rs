@@ -0,0 +1,71 @@
+//! Secret vault commands (Tauri wrappers).
+//!
+//! Encryption and `secret://` resolution live in `droidgear-core`.
+
+pub use droidgear_core::secrets::{SecretInfo, SecretMigrationReport, VaultStatus};
+
+/// Gets whether the vault exists and is unlocked.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_vault_status() -> Result<VaultStatus, String> {
+    droidgear_core::secrets::get_vault_status()
+}
+
+/// Creates the vault with a passphrase and unlocks it.
+#[tauri::command]
+#[specta::specta]
+pub async fn init_vault(passphrase: String) -> Result<(), String> {
+    droidgear_core::secrets::init_vault(&passphrase)
+}
+
+/// Unlocks the vault for the rest of the session.
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_vault(passphrase: String) -> Result<(), String> {
+    droidgear_core::secrets::unlock_vault(&passphrase)
+}
+
+/// Locks the vault (forgets the derived key).
+#[tauri::command]
+#[specta::specta]
+pub async fn lock_vault() -> Result<(), String> {
+    droidgear_core::secrets::lock_vault()
+}
+
+/// Re-encrypts the vault under a new passphrase.
+#[tauri::command]
+#[specta::specta]
+pub async fn change_vault_passphrase(
+    old_passphrase: String,
+    new_passphrase: String,
+) -> Result<(), String> {
+    droidgear_core::secrets::change_vault_passphrase(&old_passphrase, &new_passphrase)
+}
+
+/// Lists secret names and references (never the values).
+#[tauri::command]
+#[specta::specta]
+pub async fn list_secrets() -> Result<Vec<SecretInfo>, String> {
+    droidgear_core::secrets::list_secrets()
+}
+
+/// Stores a secret and returns its `secret://` reference.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_secret(name: String, value: String) -> Result<String, String> {
+    droidgear_core::secrets::set_secret(&name, &value)
+}
+
+/// Deletes a secret.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_secret(name: String) -> Result<(), String> {
+    droidgear_core::secrets::delete_secret(&name)
+}
+
+/// Moves plaintext keys from channel auth files and profiles into the vault.
+#[tauri::command]
+#[specta::specta]
+pub async fn migrate_plaintext_secrets() -> Result<SecretMigrationReport, String> {
+    droidgear_core::secrets::migrate_plaintext_secrets()
+}
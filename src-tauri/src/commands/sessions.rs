@@ -3,11 +3,18 @@
 //! Listing/parsing logic lives in `droidgear-core`. The watcher remains in the Tauri layer.
 
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 
-pub use droidgear_core::sessions::{SessionDetail, SessionProject, SessionSummary};
+pub use droidgear_core::pi::PiModelCost;
+pub use droidgear_core::session_analytics::{UsageQuery, UsageReport};
+pub use droidgear_core::session_export::{SessionExport, SessionExportOptions};
+pub use droidgear_core::session_search::{SessionSearchFilters, SessionSearchHit};
+pub use droidgear_core::sessions::{
+    SessionDetail, SessionDetailOptions, SessionProject, SessionSource, SessionSummary,
+};
 
 fn sessions_dir() -> Result<PathBuf, String> {
     Ok(droidgear_core::paths::get_factory_home()?.join("sessions"))
@@ -27,14 +34,88 @@ pub async fn list_sessions(project: Option<String>) -> Result<Vec<SessionSummary
     droidgear_core::sessions::list_sessions(project.as_deref())
 }
 
-/// Gets detailed session information including messages.
+/// Lists session projects of one agent, or of every agent when `source` is omitted.
 #[tauri::command]
 #[specta::specta]
-pub async fn get_session_detail(session_path: String) -> Result<SessionDetail, String> {
-    droidgear_core::sessions::get_session_detail(&session_path)
+pub async fn list_agent_session_projects(
+    source: Option<SessionSource>,
+) -> Result<Vec<SessionProject>, String> {
+    droidgear_core::sessions::list_agent_session_projects(source)
 }
 
-/// Deletes a session by removing its .jsonl and .settings.json files.
+/// Lists sessions of one agent (or all agents), optionally filtered by project.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_agent_sessions(
+    source: Option<SessionSource>,
+    project: Option<String>,
+) -> Result<Vec<SessionSummary>, String> {
+    droidgear_core::sessions::list_agent_sessions(source, project.as_deref())
+}
+
+/// Gets detailed session information including messages, for any agent.
+/// Tool calls and results are only included when `include_tools` is set.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_session_detail(
+    session_path: String,
+    include_tools: Option<bool>,
+) -> Result<SessionDetail, String> {
+    let options = SessionDetailOptions {
+        include_tools: include_tools.unwrap_or(false),
+    };
+    droidgear_core::sessions::get_session_detail_with_options(&session_path, &options)
+}
+
+/// Full-text searches session transcripts, refreshing the search index first.
+#[tauri::command]
+#[specta::specta]
+pub async fn search_sessions(
+    query: String,
+    filters: Option<SessionSearchFilters>,
+) -> Result<Vec<SessionSearchHit>, String> {
+    droidgear_core::session_search::search_sessions(&query, &filters.unwrap_or_default())
+}
+
+/// Exports a session as Markdown, HTML or a JSON bundle (redacted by default).
+/// The export is written to `output_path` when given, otherwise only returned.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_session(
+    session_path: String,
+    options: Option<SessionExportOptions>,
+    output_path: Option<String>,
+) -> Result<SessionExport, String> {
+    droidgear_core::session_export::export_session(
+        &session_path,
+        output_path.as_deref(),
+        &options.unwrap_or_default(),
+    )
+}
+
+/// Rolls session token usage up by day, week, project, model and agent,
+/// with estimated cost. Covers every agent's sessions when `query` is omitted.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_usage_report(query: Option<UsageQuery>) -> Result<UsageReport, String> {
+    droidgear_core::session_analytics::usage_report(&query.unwrap_or_default())
+}
+
+/// Gets the user-configured model prices (USD per million tokens), keyed by model ID.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_model_prices() -> Result<HashMap<String, PiModelCost>, String> {
+    droidgear_core::session_analytics::load_model_prices()
+}
+
+/// Saves the user-configured model prices used by the usage report.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_model_prices(prices: HashMap<String, PiModelCost>) -> Result<(), String> {
+    droidgear_core::session_analytics::save_model_prices(&prices)
+}
+
+/// Deletes a session's files (for Droid, its .jsonl and .settings.json).
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_session(session_path: String) -> Result<(), String> {
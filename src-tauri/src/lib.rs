@@ -211,6 +211,9 @@ pub fn run() {
                 }
             }
 
+            // Refresh channel balances in the background and alert when low
+            commands::quota::spawn_quota_scheduler(app.handle().clone());
+
             // NOTE: Application menu is built from JavaScript for i18n support
             // See src/lib/menu.ts for the menu implementation
 
@@ -208,40 +208,43 @@ export function ChannelDetail({ channel, onEdit }: ChannelDetailProps) {
                 selectedKey?.platform
               )
 
-      let displayName = modelId
+      // Start from the channel's metadata, then apply the user's overrides
+      const model = await commands.customModelFromChannelModel(
+        availableModels.find(m => m.id === modelId) ?? {
+          id: modelId,
+          name: null,
+          inputModalities: [],
+        },
+        baseUrl,
+        selectedKey.key,
+        config.provider
+      )
+
       if (config.alias) {
-        displayName = config.alias
+        model.displayName = config.alias
       } else if (prefix || suffix) {
-        displayName = `${prefix}${modelId}${suffix}`
+        model.displayName = `${prefix}${modelId}${suffix}`
+      } else {
+        model.displayName ??= modelId
       }
 
       // Determine max tokens
-      let maxOutputTokens: number | undefined
       if (config.maxTokens !== undefined) {
-        maxOutputTokens = config.maxTokens
+        model.maxOutputTokens = config.maxTokens
       } else if (batchMaxTokens) {
-        maxOutputTokens = parseInt(batchMaxTokens)
+        model.maxOutputTokens = parseInt(batchMaxTokens)
       } else {
-        maxOutputTokens = getDefaultMaxOutputTokens(modelId)
+        model.maxOutputTokens ??= getDefaultMaxOutputTokens(modelId)
       }
 
       // Determine no image support
-      let noImageSupport: boolean | undefined
       if (config.noImageSupport !== undefined) {
-        noImageSupport = config.noImageSupport
+        model.noImageSupport = config.noImageSupport || undefined
       } else if (batchNoImageSupport) {
-        noImageSupport = true
+        model.noImageSupport = true
       }
 
-      addModel({
-        model: modelId,
-        baseUrl,
-        apiKey: selectedKey.key,
-        provider: config.provider,
-        displayName,
-        maxOutputTokens,
-        noImageSupport: noImageSupport || undefined,
-      })
+      addModel(model)
     }
 
     await saveModels()
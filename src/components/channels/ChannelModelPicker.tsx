@@ -40,6 +40,8 @@ export interface ChannelProviderContext {
   apiKey: string
   platform: string | null
   channelType: ChannelType
+  /** Channel metadata for the selected models, in selection order */
+  models: ModelInfo[]
 }
 
 interface ChannelModelPickerProps {
@@ -184,34 +186,44 @@ export function ChannelModelPicker({
     )
   }
 
-  const buildCustomModel = (modelId: string): CustomModel => {
+  const getModelInfo = (modelId: string): ModelInfo =>
+    availableModels.find(m => m.id === modelId) ?? {
+      id: modelId,
+      name: null,
+      inputModalities: [],
+    }
+
+  const buildCustomModel = async (modelId: string): Promise<CustomModel> => {
     const provider = inferProvider(modelId)
-    const baseUrl = getBaseUrl(provider)
+    const model = await commands.customModelFromChannelModel(
+      getModelInfo(modelId),
+      getBaseUrl(provider),
+      selectedKey?.key ?? '',
+      provider
+    )
 
-    let displayName = modelId
     if (prefix || suffix) {
-      displayName = `${prefix}${modelId}${suffix}`
+      model.displayName = `${prefix}${modelId}${suffix}`
+    } else {
+      model.displayName ??= modelId
     }
 
-    let maxOutputTokens: number | undefined
     if (batchMaxTokens) {
-      maxOutputTokens = parseInt(batchMaxTokens)
+      model.maxOutputTokens = parseInt(batchMaxTokens)
     } else {
-      maxOutputTokens = getDefaultMaxOutputTokens(modelId)
+      model.maxOutputTokens ??= getDefaultMaxOutputTokens(modelId)
     }
 
-    return {
-      model: modelId,
-      baseUrl,
-      apiKey: selectedKey?.key ?? '',
-      provider,
-      displayName,
-      maxOutputTokens,
-      noImageSupport: batchNoImageSupport || undefined,
+    if (batchNoImageSupport) {
+      model.noImageSupport = true
     }
+
+    return model
   }
 
-  const buildProviderContext = (): ChannelProviderContext | null => {
+  const buildProviderContext = (
+    modelIds: string[]
+  ): ChannelProviderContext | null => {
     if (!selectedChannel || !selectedKey) return null
     return {
       channelName: selectedChannel.name,
@@ -219,14 +231,15 @@ export function ChannelModelPicker({
       apiKey: selectedKey.key,
       platform: selectedKey.platform,
       channelType: selectedChannel.type,
+      models: modelIds.map(getModelInfo),
     }
   }
 
-  const handleSingleSelect = (modelId: string) => {
+  const handleSingleSelect = async (modelId: string) => {
     setSingleSelectedId(modelId)
-    const model = buildCustomModel(modelId)
+    const model = await buildCustomModel(modelId)
     if (onSelectWithContext) {
-      const context = buildProviderContext()
+      const context = buildProviderContext([modelId])
       if (context) {
         onSelectWithContext([model], context)
         return
@@ -257,12 +270,13 @@ export function ChannelModelPicker({
     }
   }
 
-  const handleConfirmSelection = () => {
-    const models = Array.from(selectedModelIds)
-      .filter(id => !isModelExisting(id))
-      .map(id => buildCustomModel(id))
+  const handleConfirmSelection = async () => {
+    const modelIds = Array.from(selectedModelIds).filter(
+      id => !isModelExisting(id)
+    )
+    const models = await Promise.all(modelIds.map(buildCustomModel))
     if (onSelectWithContext) {
-      const context = buildProviderContext()
+      const context = buildProviderContext(modelIds)
       if (context) {
         onSelectWithContext(models, context)
         return
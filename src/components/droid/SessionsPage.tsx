@@ -168,7 +168,7 @@ export function SessionsPage() {
       }
       setSelectedSessionPath(path)
       try {
-        const result = await commands.getSessionDetail(path)
+        const result = await commands.getSessionDetail(path, null)
         if (result.status === 'ok') {
           setSelectedSession(result.data)
         } else {
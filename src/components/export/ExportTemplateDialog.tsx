@@ -66,9 +66,23 @@ const AVAILABLE_SOURCE_FIELDS = [
   { value: 'model.protocol', label: 'Inferred Protocol' },
 ]
 
+const FORMAT_EXTENSIONS: Record<ExportFormat, string> = {
+  json: 'json',
+  yaml: 'yaml',
+  toml: 'toml',
+  'codex-config': 'toml',
+  'opencode-config': 'json',
+  'pi-models': 'json',
+  'openclaw-providers': 'json',
+  'hermes-config': 'yaml',
+  env: 'env',
+  csv: 'csv',
+  text: 'txt',
+}
+
 function defaultTemplate(name: string, format?: ExportFormat): ExportTemplate {
   const fmt = format ?? 'yaml'
-  const ext = fmt === 'json' ? 'json' : fmt === 'toml' ? 'toml' : 'yaml'
+  const ext = FORMAT_EXTENSIONS[fmt]
   return {
     name,
     description: '',
@@ -121,9 +135,8 @@ export function ExportTemplateDialog({
       const next = { ...prev, [key]: value }
       // Auto-update file extension when format changes
       if (key === 'format') {
-        const ext =
-          value === 'json' ? 'json' : value === 'toml' ? 'toml' : 'yaml'
-        const extMatch = prev.outputPath.match(/\.(json|yaml|toml)$/i)
+        const ext = FORMAT_EXTENSIONS[value as ExportFormat]
+        const extMatch = prev.outputPath.match(/\.(json|yaml|toml|env|csv)$/i)
         const matchedExt = extMatch?.[1]
         if (matchedExt && matchedExt.toLowerCase() !== ext) {
           next.outputPath = prev.outputPath.replace(/\.[a-z0-9]+$/i, `.${ext}`)
@@ -214,6 +227,10 @@ export function ExportTemplateDialog({
       toast.error(t('export.pathRequired'))
       return
     }
+    if (form.format === 'text' && !form.templatePath?.trim()) {
+      toast.error(t('export.templatePathRequired'))
+      return
+    }
 
     // If editing but name changed, we need to delete old first
     const finalTemplate = { ...form }
@@ -510,6 +527,22 @@ export function ExportTemplateDialog({
                     <SelectItem value="json">JSON</SelectItem>
                     <SelectItem value="yaml">YAML</SelectItem>
                     <SelectItem value="toml">TOML</SelectItem>
+                    <SelectItem value="csv">CSV</SelectItem>
+                    <SelectItem value="env">.env</SelectItem>
+                    <SelectItem value="codex-config">
+                      Codex config.toml
+                    </SelectItem>
+                    <SelectItem value="opencode-config">
+                      OpenCode opencode.json
+                    </SelectItem>
+                    <SelectItem value="pi-models">Pi models.json</SelectItem>
+                    <SelectItem value="openclaw-providers">
+                      OpenClaw models.providers
+                    </SelectItem>
+                    <SelectItem value="hermes-config">
+                      Hermes config.yaml
+                    </SelectItem>
+                    <SelectItem value="text">{t('export.textTemplate')}</SelectItem>
                   </SelectContent>
                 </Select>
               </div>
@@ -533,6 +566,20 @@ export function ExportTemplateDialog({
               </div>
             </div>
 
+            {form.format === 'text' && (
+              <div className="grid gap-2">
+                <Label>{t('export.templatePath')}</Label>
+                <Input
+                  value={form.templatePath ?? ''}
+                  onChange={e => updateField('templatePath', e.target.value)}
+                  placeholder="templates/litellm.yaml.j2"
+                />
+                <p className="text-xs text-muted-foreground">
+                  {t('export.templatePathHint')}
+                </p>
+              </div>
+            )}
+
             <div className="grid gap-2">
               <Label>{t('export.outputPath')}</Label>
               <Input
@@ -343,7 +343,7 @@ export function ImportFromChannelDialog({
                             {tk.platform ? (
                               <Badge variant="outline">{tk.platform}</Badge>
                             ) : (
-                              <span className="text-muted-foreground">—</span>
+                              <span className="ml-2 text-muted-foreground">—</span>
                             )}
                           </TableCell>
                           <TableCell>
@@ -401,6 +401,11 @@ export function ImportFromChannelDialog({
                         {models.map(m => (
                           <SelectItem key={m.id} value={m.id}>
                             {m.id}
+                            {m.name && m.name !== m.id && (
+                              <span className="ml-2 text-muted-foreground">
+                                {m.name}
+                              </span>
+                            )}
                           </SelectItem>
                         ))}
                       </SelectContent>
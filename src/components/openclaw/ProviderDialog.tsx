@@ -144,19 +144,22 @@ function ProviderForm({
     })
   }
 
-  const handleAddSelectedModels = () => {
-    const newModels: OpenClawModel[] = Array.from(selectedModelIds).map(id => {
-      const info = availableModels.find(m => m.id === id)
-      const registry = findModelByIdOrAlias(id)
-      return {
-        id,
-        name: registry?.name ?? info?.name ?? null,
-        reasoning: true,
-        input: ['text', 'image'],
-        contextWindow: registry?.contextWindow ?? 200000,
-        maxTokens: registry?.maxOutputTokens ?? 8192,
-      }
-    })
+  // Fetched metadata wins; the local registry fills what the API omits
+  const withRegistryDefaults = (model: OpenClawModel): OpenClawModel => {
+    const registry = findModelByIdOrAlias(model.id)
+    return {
+      ...model,
+      name: model.name ?? registry?.name ?? null,
+      contextWindow: model.contextWindow ?? registry?.contextWindow ?? 200000,
+      maxTokens: model.maxTokens ?? registry?.maxOutputTokens ?? 8192,
+    }
+  }
+
+  const handleAddSelectedModels = async () => {
+    const fetched = await commands.openclawModelsFromChannel(
+      availableModels.filter(m => selectedModelIds.has(m.id))
+    )
+    const newModels = fetched.map(withRegistryDefaults)
     // Merge with existing models, avoiding duplicates
     const existingIds = new Set(models.map(m => m.id))
     const uniqueNewModels = newModels.filter(m => !existingIds.has(m.id))
@@ -164,8 +167,8 @@ function ProviderForm({
     setSelectedModelIds(new Set())
   }
 
-  const handleImportFromChannel = (
-    importedModels: CustomModel[],
+  const handleImportFromChannel = async (
+    _importedModels: CustomModel[],
     context: ChannelProviderContext
   ) => {
     // Fill provider-level fields
@@ -193,17 +196,8 @@ function ProviderForm({
     setBaseUrl(importedBaseUrl)
 
     // Import models
-    const newModels: OpenClawModel[] = importedModels.map(m => {
-      const registry = findModelByIdOrAlias(m.model)
-      return {
-        id: m.model,
-        name: registry?.name ?? m.displayName ?? null,
-        reasoning: true,
-        input: ['text', 'image'],
-        contextWindow: registry?.contextWindow ?? 200000,
-        maxTokens: registry?.maxOutputTokens ?? m.maxOutputTokens ?? 8192,
-      }
-    })
+    const imported = await commands.openclawModelsFromChannel(context.models)
+    const newModels = imported.map(withRegistryDefaults)
     // Merge with existing models, avoiding duplicates
     const existingIds = new Set(models.map(m => m.id))
     const uniqueNewModels = newModels.filter(m => !existingIds.has(m.id))
@@ -126,7 +126,7 @@ export function ProviderDialog({
       .replace(/^-+|-+$/g, '')
   }
 
-  const handleImportFromChannel = (
+  const handleImportFromChannel = async (
     selectedModels: CustomModel[],
     context: ChannelProviderContext
   ) => {
@@ -159,13 +159,13 @@ export function ProviderDialog({
       setNpm(npmPackage)
     }
 
-    // Import selected models into provider config
+    // Import selected models with their channel-reported limits
+    const fromChannel = await commands.opencodeModelsFromChannel(
+      context.models
+    )
     const importedModels: Record<string, OpenCodeModelConfig> = {}
-    for (const m of selectedModels) {
-      importedModels[m.model] = {
-        name: m.displayName || null,
-        limit: null,
-      }
+    for (const [modelId, config] of Object.entries(fromChannel)) {
+      if (config) importedModels[modelId] = config
     }
     setModels(prev =>
       isEditing ? { ...prev, ...importedModels } : importedModels
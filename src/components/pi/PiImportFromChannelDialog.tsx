@@ -274,22 +274,15 @@ export function PiImportFromChannelDialog({
     }
   }
 
-  const handleImport = () => {
+  const handleImport = async () => {
     if (!providerId.trim()) return
     const firstModelId =
       selectedModelIds.size > 0 ? Array.from(selectedModelIds)[0] : undefined
     const api = inferPiApiType(resolvedPlatform, firstModelId)
-    const piModels: PiModel[] = Array.from(selectedModelIds).map(id => ({
-      id,
-      name: null,
-      api: null,
-      reasoning: false,
-      input: ['text'],
-      contextWindow: 128000,
-      maxTokens: 16384,
-      cost: null,
-      compat: null,
-    }))
+    // Context window, limits, modalities and pricing come from the channel
+    const piModels = await commands.piModelsFromChannel(
+      models.filter(m => selectedModelIds.has(m.id))
+    )
     onImported({
       providerId: providerId.trim(),
       baseUrl: resolvedBaseUrl,
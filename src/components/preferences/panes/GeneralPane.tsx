@@ -1,6 +1,7 @@
 import { useState, useEffect, useRef } from 'react'
 import { useTranslation } from 'react-i18next'
-import { useQuery } from '@tanstack/react-query'
+import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query'
+import { toast } from 'sonner'
 import { Button } from '@/components/ui/button'
 import { Label } from '@/components/ui/label'
 import { Switch } from '@/components/ui/switch'
@@ -12,7 +13,7 @@ import {
   SelectValue,
 } from '@/components/ui/select'
 import { SettingsField, SettingsSection } from '../shared/SettingsComponents'
-import { commands } from '@/lib/tauri-bindings'
+import { commands, type QuotaMonitorSettings } from '@/lib/tauri-bindings'
 import { logger } from '@/lib/logger'
 import { useUIStore } from '@/store/ui-store'
 import {
@@ -51,6 +52,33 @@ export function GeneralPane() {
     staleTime: Infinity,
   })
 
+  // Quota monitor settings live in DroidGear settings, not app preferences
+  const queryClient = useQueryClient()
+  const { data: quotaSettings } = useQuery({
+    queryKey: ['quota-monitor-settings'],
+    queryFn: async () => {
+      const result = await commands.getQuotaMonitorSettings()
+      if (result.status === 'error') throw new Error(result.error)
+      return result.data
+    },
+  })
+  const saveQuotaSettings = useMutation({
+    mutationFn: async (settings: QuotaMonitorSettings) => {
+      const result = await commands.saveQuotaMonitorSettings(settings)
+      if (result.status === 'error') throw new Error(result.error)
+      return settings
+    },
+    onSuccess: settings => {
+      queryClient.setQueryData(['quota-monitor-settings'], settings)
+    },
+    onError: error => {
+      toast.error(t('preferences.general.quotaMonitorSaveFailed'), {
+        description: error.message,
+      })
+    },
+  })
+  const quotaMonitorEnabled = quotaSettings?.enabled ?? false
+
   // Auto-update disabled state
   const disableAutoUpdate = preferences?.disable_auto_update ?? false
 
@@ -239,6 +267,32 @@ export function GeneralPane() {
         </SettingsField>
       </SettingsSection>
 
+      <SettingsSection title={t('preferences.general.quotaMonitor')}>
+        <SettingsField
+          label={t('preferences.general.quotaMonitorEnable')}
+          description={t('preferences.general.quotaMonitorDescription')}
+        >
+          <div className="flex items-center space-x-2">
+            <Switch
+              id="quota-monitor-toggle"
+              checked={quotaMonitorEnabled}
+              onCheckedChange={checked => {
+                if (quotaSettings) {
+                  saveQuotaSettings.mutate({
+                    ...quotaSettings,
+                    enabled: checked,
+                  })
+                }
+              }}
+              disabled={!quotaSettings || saveQuotaSettings.isPending}
+            />
+            <Label htmlFor="quota-monitor-toggle" className="text-sm">
+              {quotaMonitorEnabled ? t('common.enabled') : t('common.disabled')}
+            </Label>
+          </div>
+        </SettingsField>
+      </SettingsSection>
+
       <SettingsSection title={t('preferences.general.terminal')}>
         <SettingsField
           label={t('preferences.general.preferredTerminal')}
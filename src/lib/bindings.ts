@@ -141,6 +141,17 @@ async saveCustomModels(models: CustomModel[]) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Dry-run of `save_custom_models`: planned settings.json write with a key-level diff
+ */
+async planSaveCustomModels(models: CustomModel[]) : Promise<Result<ApplyPlan, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_save_custom_models", { models }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Checks if legacy config.json exists and settings.json has customModels
  */
@@ -174,6 +185,12 @@ async fetchModels(provider: Provider, baseUrl: string, apiKey: string) : Promise
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Builds a custom model from a fetched channel model's metadata
+ */
+async customModelFromChannelModel(info: ModelInfo, baseUrl: string, apiKey: string, provider: Provider) : Promise<CustomModel> {
+    return await TAURI_INVOKE("custom_model_from_channel_model", { info, baseUrl, apiKey, provider });
+},
 /**
  * Gets the default model ID from sessionDefaultSettings.model
  */
@@ -449,6 +466,72 @@ async saveCompactionTokenLimitPerModel(overrides: Partial<{ [key in string]: num
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Lists all config files that have backups.
+ */
+async listBackupFiles() : Promise<Result<BackupFileSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_backup_files") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists backups of a single config file, newest first.
+ */
+async listBackups(path: string) : Promise<Result<BackupEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_backups", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Diffs a backup against the current file content.
+ */
+async diffBackup(id: string) : Promise<Result<BackupDiff, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_backup", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Restores a backup over its original file (the current content is backed up first).
+ */
+async restoreBackup(id: string) : Promise<Result<BackupEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_backup", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the number of backups kept per file.
+ */
+async getBackupRetention() : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_backup_retention") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves the number of backups kept per file.
+ */
+async saveBackupRetention(retention: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_backup_retention", { retention }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Loads all channels from ~/.droidgear/channels.json
  * Falls back to ~/.factory/settings.json for migration
@@ -462,7 +545,8 @@ async loadChannels() : Promise<Result<Channel[], string>> {
 }
 },
 /**
- * Saves all channels to ~/.droidgear/channels.json
+ * Saves all channels to ~/.droidgear/channels.json and syncs the entries
+ * linked to channels whose base URL changed
  */
 async saveChannels(channels: Channel[]) : Promise<Result<null, string>> {
     try {
@@ -473,7 +557,8 @@ async saveChannels(channels: Channel[]) : Promise<Result<null, string>> {
 }
 },
 /**
- * Saves a channel's credentials to ~/.droidgear/auth/
+ * Saves a channel's credentials to ~/.droidgear/auth/ and syncs the entries
+ * linked to the channel
  */
 async saveChannelCredentials(channelId: string, username: string, password: string) : Promise<Result<null, string>> {
     try {
@@ -495,7 +580,8 @@ async getChannelCredentials(channelId: string) : Promise<Result<[string, string]
 }
 },
 /**
- * Saves a channel's API key to ~/.droidgear/auth/
+ * Saves a channel's API key to ~/.droidgear/auth/ and syncs the entries
+ * linked to the channel
  */
 async saveChannelApiKey(channelId: string, apiKey: string) : Promise<Result<null, string>> {
     try {
@@ -549,6 +635,99 @@ async fetchChannelTokens(channelType: ChannelType, baseUrl: string, username: st
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Fetches a channel's account balance (None if the backend has no quota API)
+ */
+async fetchChannelQuota(channelType: ChannelType, baseUrl: string, username: string, password: string) : Promise<Result<ChannelQuota | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_channel_quota", { channelType, baseUrl, username, password }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Creates a token on the channel's dashboard and returns it with its full key
+ */
+async createChannelToken(channelType: ChannelType, baseUrl: string, username: string, password: string, token: NewChannelToken) : Promise<Result<ChannelToken, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_channel_token", { channelType, baseUrl, username, password, token }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Enables or disables a token on the channel's dashboard
+ */
+async setChannelTokenEnabled(channelType: ChannelType, baseUrl: string, username: string, password: string, tokenId: number, enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_channel_token_enabled", { channelType, baseUrl, username, password, tokenId, enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes a token on the channel's dashboard
+ */
+async deleteChannelToken(channelType: ChannelType, baseUrl: string, username: string, password: string, tokenId: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_channel_token", { channelType, baseUrl, username, password, tokenId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replaces a token with a new one, rewrites every profile, custom model and
+ * vault secret holding the old key, then revokes the old token
+ */
+async rotateChannelToken(channelId: string, tokenId: number, revoke: RevokeMode) : Promise<Result<TokenRotationReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rotate_channel_token", { channelId, tokenId, revoke }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Builds Codex, Claude Code, OpenCode, OpenClaw, Pi and Hermes profiles plus
+ * Factory custom models for a channel token and the selected models. With an
+ * initialized vault, profiles reference the key as a `secret://` entry
+ */
+async provisionChannelProfiles(channel: Channel, token: ChannelToken, models: ModelInfo[]) : Promise<Result<ProvisionedProfiles, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("provision_channel_profiles", { channel, token, models }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves profiles built by `provision_channel_profiles`
+ */
+async saveProvisionedProfiles(profiles: ProvisionedProfiles) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_provisioned_profiles", { profiles }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compares channel-linked profile entries and custom models with their
+ * channels; with `apply`, rewrites base URLs and keys and drops models the
+ * channel no longer offers
+ */
+async syncLinkedProfiles(apply: boolean) : Promise<Result<LinkSyncReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sync_linked_profiles", { apply }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Fetches models using an API key (for quick model addition from channels)
  */
@@ -560,6 +739,162 @@ async fetchModelsByApiKey(baseUrl: string, apiKey: string, platform: string | nu
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Gets the quota monitor settings.
+ */
+async getQuotaMonitorSettings() : Promise<Result<QuotaMonitorSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_quota_monitor_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves the quota monitor settings.
+ */
+async saveQuotaMonitorSettings(settings: QuotaMonitorSettings) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_quota_monitor_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the latest balance, burn rate and alert of every enabled channel
+ * from recorded snapshots (no network access).
+ */
+async getQuotaStatus() : Promise<Result<ChannelQuotaStatus[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_quota_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the recorded balances of one channel (oldest first).
+ * `window_days` limits the history to recent snapshots; omit for all.
+ */
+async getQuotaHistory(channelId: string, windowDays: number | null) : Promise<Result<QuotaSnapshot[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_quota_history", { channelId, windowDays }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Refreshes the balance of every enabled channel now.
+ */
+async refreshChannelQuotas() : Promise<Result<QuotaRefreshReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("refresh_channel_quotas") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets whether the vault exists and is unlocked.
+ */
+async getVaultStatus() : Promise<Result<VaultStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_vault_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Creates the vault with a passphrase and unlocks it.
+ */
+async initVault(passphrase: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("init_vault", { passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Unlocks the vault for the rest of the session.
+ */
+async unlockVault(passphrase: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_vault", { passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Locks the vault (forgets the derived key).
+ */
+async lockVault() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_vault") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-encrypts the vault under a new passphrase.
+ */
+async changeVaultPassphrase(oldPassphrase: string, newPassphrase: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_vault_passphrase", { oldPassphrase, newPassphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists secret names and references (never the values).
+ */
+async listSecrets() : Promise<Result<SecretInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_secrets") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stores a secret and returns its `secret://` reference.
+ */
+async setSecret(name: string, value: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_secret", { name, value }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes a secret.
+ */
+async deleteSecret(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_secret", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Moves plaintext keys from channel auth files and profiles into the vault.
+ */
+async migratePlaintextSecrets() : Promise<Result<SecretMigrationReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("migrate_plaintext_secrets") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Load all export templates from config file.
  */
@@ -859,6 +1194,17 @@ async applyClaudeProfile(id: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Dry-run of `apply_claude_profile`: planned file writes with a key-level diff
+ */
+async planApplyClaudeProfile(id: string) : Promise<Result<ApplyPlan, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_apply_claude_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Get Claude Code config status
  */
@@ -1094,6 +1440,17 @@ async applyCodexProfile(id: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Dry-run of `apply_codex_profile`: planned file writes with a key-level diff
+ */
+async planApplyCodexProfile(id: string) : Promise<Result<ApplyPlan, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_apply_codex_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Get Codex config status
  */
@@ -1239,18 +1596,29 @@ async applyHermesProfile(id: string) : Promise<Result<null, string>> {
 }
 },
 /**
- * Get Hermes config status
+ * Dry-run of `apply_hermes_profile`: planned file writes with a key-level diff
  */
-async getHermesConfigStatus() : Promise<Result<HermesConfigStatus, string>> {
+async planApplyHermesProfile(id: string) : Promise<Result<ApplyPlan, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_hermes_config_status") };
+    return { status: "ok", data: await TAURI_INVOKE("plan_apply_hermes_profile", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Read current Hermes configuration from config files
+ * Get Hermes config status
+ */
+async getHermesConfigStatus() : Promise<Result<HermesConfigStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_hermes_config_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Read current Hermes configuration from config files
  */
 async readHermesCurrentConfig() : Promise<Result<HermesCurrentConfig, string>> {
     try {
@@ -1359,6 +1727,17 @@ async applyPiProfile(id: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Dry-run of `apply_pi_profile`: planned file writes with a key-level diff
+ */
+async planApplyPiProfile(id: string) : Promise<Result<ApplyPlan, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_apply_pi_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Get Pi config status
  */
@@ -1381,6 +1760,12 @@ async readPiCurrentConfig() : Promise<Result<PiCurrentConfig, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Builds Pi models from fetched channel model metadata
+ */
+async piModelsFromChannel(models: ModelInfo[]) : Promise<PiModel[]> {
+    return await TAURI_INVOKE("pi_models_from_channel", { models });
+},
 /**
  * List all OpenCode profiles
  */
@@ -1469,6 +1854,17 @@ async applyOpencodeProfile(id: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Dry-run of `apply_opencode_profile`: planned file writes with a key-level diff
+ */
+async planApplyOpencodeProfile(id: string) : Promise<Result<ApplyPlan, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_apply_opencode_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Get OpenCode config status
  */
@@ -1513,6 +1909,12 @@ async readOpencodeCurrentConfig() : Promise<Result<OpenCodeCurrentConfig, string
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Builds OpenCode model entries (keyed by model ID) from fetched channel model metadata
+ */
+async opencodeModelsFromChannel(models: ModelInfo[]) : Promise<Partial<{ [key in string]: OpenCodeModelConfig }>> {
+    return await TAURI_INVOKE("opencode_models_from_channel", { models });
+},
 /**
  * List all OpenClaw profiles
  */
@@ -1601,6 +2003,17 @@ async applyOpenclawProfile(profile: OpenClawProfile) : Promise<Result<null, stri
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Dry-run of `apply_openclaw_profile`: planned file writes with a key-level diff
+ */
+async planApplyOpenclawProfile(profile: OpenClawProfile) : Promise<Result<ApplyPlan, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_apply_openclaw_profile", { profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Get OpenClaw config status
  */
@@ -1645,6 +2058,12 @@ async saveOpenclawSubagents(subagents: OpenClawSubAgent[]) : Promise<Result<null
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Builds OpenClaw models from fetched channel model metadata
+ */
+async openclawModelsFromChannel(models: ModelInfo[]) : Promise<OpenClawModel[]> {
+    return await TAURI_INVOKE("openclaw_models_from_channel", { models });
+},
 /**
  * Lists all session projects from ~/.factory/sessions directory.
  */
@@ -1668,11 +2087,91 @@ async listSessions(project: string | null) : Promise<Result<SessionSummary[], st
 }
 },
 /**
- * Gets detailed session information including messages.
+ * Lists session projects of one agent, or of every agent when `source` is omitted.
+ */
+async listAgentSessionProjects(source: SessionSource | null) : Promise<Result<SessionProject[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_agent_session_projects", { source }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists sessions of one agent (or all agents), optionally filtered by project.
+ */
+async listAgentSessions(source: SessionSource | null, project: string | null) : Promise<Result<SessionSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_agent_sessions", { source, project }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets detailed session information including messages, for any agent.
+ * Tool calls and results are only included when `include_tools` is set.
+ */
+async getSessionDetail(sessionPath: string, includeTools: boolean | null) : Promise<Result<SessionDetail, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_session_detail", { sessionPath, includeTools }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Full-text searches session transcripts, refreshing the search index first.
+ */
+async searchSessions(query: string, filters: SessionSearchFilters | null) : Promise<Result<SessionSearchHit[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_sessions", { query, filters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports a session as Markdown, HTML or a JSON bundle (redacted by default).
+ * The export is written to `output_path` when given, otherwise only returned.
+ */
+async exportSession(sessionPath: string, options: SessionExportOptions | null, outputPath: string | null) : Promise<Result<SessionExport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_session", { sessionPath, options, outputPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Rolls session token usage up by day, week, project, model and agent,
+ * with estimated cost. Covers every agent's sessions when `query` is omitted.
+ */
+async getUsageReport(query: UsageQuery | null) : Promise<Result<UsageReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_usage_report", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the user-configured model prices (USD per million tokens), keyed by model ID.
+ */
+async getModelPrices() : Promise<Result<Partial<{ [key in string]: PiModelCost }>, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_model_prices") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves the user-configured model prices used by the usage report.
  */
-async getSessionDetail(sessionPath: string) : Promise<Result<SessionDetail, string>> {
+async saveModelPrices(prices: Partial<{ [key in string]: PiModelCost }>) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_session_detail", { sessionPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_model_prices", { prices }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -1701,7 +2200,7 @@ async stopSessionsWatcher() : Promise<Result<null, string>> {
 }
 },
 /**
- * Deletes a session by removing its .jsonl and .settings.json files.
+ * Deletes a session's files (for Droid, its .jsonl and .settings.json).
  */
 async deleteSession(sessionPath: string) : Promise<Result<null, string>> {
     try {
@@ -1743,6 +2242,51 @@ async testProviderConnection(provider: string, baseUrl: string, apiKey: string,
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Tests an endpoint over an explicit wire protocol (including Gemini and the
+ * OpenAI Responses API) in any mode.
+ */
+async testEndpointConnection(protocol: WireProtocol, mode: TestMode, baseUrl: string, apiKey: string, modelId: string, prompt: string | null) : Promise<Result<ConnectionDiagnostics, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_endpoint_connection", { protocol, mode, baseUrl, apiKey, modelId, prompt }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists the model endpoints configured in a tool profile.
+ */
+async listProfileEndpoints(tool: ProfileTool, profileId: string) : Promise<Result<ProfileEndpoint[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_profile_endpoints", { tool, profileId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Tests every endpoint of a tool profile with its wire protocol.
+ */
+async testProfileConnections(tool: ProfileTool, profileId: string, mode: TestMode, prompt: string | null) : Promise<Result<ProfileTestResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_profile_connections", { tool, profileId, mode, prompt }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Tests the endpoints of all profiles, optionally limited to one tool.
+ */
+async testAllProfileConnections(tool: ProfileTool | null, mode: TestMode, prompt: string | null) : Promise<Result<ProfileTestResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_all_profile_connections", { tool, mode, prompt }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async testModelConnectionWithMode(modelId: string, mode: TestMode, prompt: string | null) : Promise<Result<ModelTestResult, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("test_model_connection_with_mode", { modelId, mode, prompt }) };
@@ -1759,6 +2303,140 @@ async testAllModelConnectionsWithMode(mode: TestMode, prompt: string | null) : P
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Tests one model with an explicit retry/concurrency config (saved config when omitted).
+ */
+async testModelConnectionWithConfig(modelId: string, mode: TestMode, prompt: string | null, config: TestConfig | null) : Promise<Result<ModelTestResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_model_connection_with_config", { modelId, mode, prompt, config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Tests all models concurrently with an explicit config (saved config when omitted).
+ */
+async testAllModelConnectionsWithConfig(mode: TestMode, prompt: string | null, config: TestConfig | null) : Promise<Result<ModelTestResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_all_model_connections_with_config", { mode, prompt, config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Probes streaming, tool use and vision support of one model.
+ */
+async probeModelCapabilities(modelId: string, config: TestConfig | null) : Promise<Result<CapabilityReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("probe_model_capabilities", { modelId, config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Probes a custom model and proposes updates to its capability fields.
+ * Nothing is saved until the patch is accepted.
+ */
+async proposeModelPatch(modelId: string, config: TestConfig | null) : Promise<Result<ModelPatch, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("propose_model_patch", { modelId, config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Dry-run: shows the settings.json changes accepting a model patch would make.
+ */
+async planModelPatch(patch: ModelPatch) : Promise<Result<ApplyPlan, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_model_patch", { patch }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Applies a reviewed model patch and saves the custom models.
+ */
+async acceptModelPatch(patch: ModelPatch) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("accept_model_patch", { patch }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the saved connectivity test config.
+ */
+async getConnectivityTestConfig() : Promise<Result<TestConfig, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_connectivity_test_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves the connectivity test config used by default for all tests.
+ */
+async saveConnectivityTestConfig(config: TestConfig) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_connectivity_test_config", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the persisted test history of one model (newest first).
+ * `window_hours` limits the history to recent tests; omit for all.
+ */
+async getConnectivityHistory(modelId: string, windowHours: number | null) : Promise<Result<HistoryRecord[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_connectivity_history", { modelId, windowHours }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets uptime, latency percentiles and last failure for every tested model.
+ */
+async getConnectivityStats(windowHours: number | null) : Promise<Result<ModelAvailabilityStats[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_connectivity_stats", { windowHours }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets availability statistics of one model.
+ */
+async getModelAvailability(modelId: string, windowHours: number | null) : Promise<Result<ModelAvailabilityStats | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_model_availability", { modelId, windowHours }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes the persisted connectivity history.
+ */
+async clearConnectivityHistory() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_connectivity_history") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Gets the current configuration paths (custom values only)
  */
@@ -2062,48 +2740,112 @@ preferred_terminal?: string | null;
  * Droid temporary-run runtime policy.
  */
 droid_run?: DroidRunPreferences | null }
+/**
+ * Result of a dry-run apply
+ */
+export type ApplyPlan = { 
+/**
+ * Tool name ("codex", "claude", "opencode", "openclaw", "pi", "hermes", "factory")
+ */
+tool: string; profileId?: string | null; files: FilePlan[] }
+/**
+ * Outcome of a single attempt within a (possibly retried) test.
+ */
+export type AttemptDiagnostics = { 
+/**
+ * 1-based attempt number
+ */
+attempt: number; success: boolean; latencyMs: number; error?: string | null; timestamp: string }
 export type AuthProfile = { name: string; label: string; createdAt: string }
 export type AuthProfileState = { active: string | null; profiles: AuthProfile[] }
 /**
- * Block streaming chunk configuration
+ * Diff between a backup and the current file content
  */
-export type BlockStreamingChunk = { minChars?: number | null; maxChars?: number | null }
+export type BackupDiff = { entry: BackupEntry; backupContent: string; 
 /**
- * Block streaming coalesce configuration
+ * Current file content (None if the file no longer exists)
  */
-export type BlockStreamingCoalesce = { idleMs?: number | null }
+currentContent?: string | null; 
 /**
- * Block streaming configuration
+ * Unified diff from backup to current
  */
-export type BlockStreamingConfig = { blockStreamingDefault?: string | null; blockStreamingBreak?: string | null; blockStreamingChunk?: BlockStreamingChunk | null; blockStreamingCoalesce?: BlockStreamingCoalesce | null; telegramChannel?: TelegramChannelConfig | null }
+unifiedDiff: string }
 /**
- * Channel configuration
+ * A single snapshot of a config file
  */
-export type Channel = { 
+export type BackupEntry = { 
 /**
- * Unique identifier (UUID)
+ * Backup id (`<file-key>/<stamp>`)
  */
 id: string; 
 /**
- * User-defined name
+ * Original file path
  */
-name: string; 
+path: string; 
 /**
- * Channel type
+ * Creation time (RFC 3339)
  */
-type: ChannelType; 
+createdAt: string; 
 /**
- * API base URL
+ * What triggered the snapshot (e.g. "codex", "mcp", "restore")
  */
-baseUrl: string; 
+source: string; 
 /**
- * Whether the channel is enabled
+ * Snapshot size in bytes
  */
-enabled: boolean; 
+size: number }
 /**
- * Creation timestamp (milliseconds) - use f64 for JS compatibility
+ * Per-file overview of available backups
  */
-createdAt: number }
+export type BackupFileSummary = { path: string; count: number; latest?: BackupEntry | null }
+/**
+ * Block streaming chunk configuration
+ */
+export type BlockStreamingChunk = { minChars?: number | null; maxChars?: number | null }
+/**
+ * Block streaming coalesce configuration
+ */
+export type BlockStreamingCoalesce = { idleMs?: number | null }
+/**
+ * Block streaming configuration
+ */
+export type BlockStreamingConfig = { blockStreamingDefault?: string | null; blockStreamingBreak?: string | null; blockStreamingChunk?: BlockStreamingChunk | null; blockStreamingCoalesce?: BlockStreamingCoalesce | null; telegramChannel?: TelegramChannelConfig | null }
+/**
+ * Result of probing all capabilities of one model.
+ */
+export type CapabilityReport = { modelId: string; modelName: string; capabilities: ModelCapabilities; 
+/**
+ * Diagnostics of each probe (streaming, tool use, vision)
+ */
+probes: ConnectionDiagnostics[] }
+/**
+ * Channel configuration
+ */
+export type Channel = { 
+/**
+ * Unique identifier (UUID)
+ */
+id: string; 
+/**
+ * User-defined name
+ */
+name: string; 
+/**
+ * Channel type
+ */
+type: ChannelType; 
+/**
+ * API base URL
+ */
+baseUrl: string; 
+/**
+ * Whether the channel is enabled
+ */
+enabled: boolean; 
+/**
+ * Creation timestamp (milliseconds) - use f64 for JS compatibility
+ */
+createdAt: number }
 /**
  * Channel filter conditions
  */
@@ -2120,6 +2862,47 @@ enabledOnly?: boolean;
  * Only include channels with these IDs (empty = all)
  */
 ids?: string[] }
+/**
+ * Account balance reported by a channel's dashboard API
+ */
+export type ChannelQuota = { 
+/**
+ * Remaining balance, in `unit`
+ */
+remaining: number | null; 
+/**
+ * Amount used so far, in `unit`
+ */
+used: number | null; 
+/**
+ * Whether the account has no spending limit
+ */
+unlimited: boolean; 
+/**
+ * Currency of the amounts (e.g. "USD", "CNY")
+ */
+unit: string }
+/**
+ * Latest balance and trend of one channel
+ */
+export type ChannelQuotaStatus = { channelId: string; channelName: string; latest?: QuotaSnapshot | null; 
+/**
+ * Average spend per day over the last week, in the snapshot unit
+ */
+burnRatePerDay?: number | null; daysRemaining?: number | null; alert?: QuotaAlert | null; 
+/**
+ * Error of the last refresh, if it failed
+ */
+error?: string | null }
+/**
+ * Link from a profile entry back to the channel (and token) it was
+ * provisioned from, so base URL and key can be re-synced later
+ */
+export type ChannelRef = { channelId: string; 
+/**
+ * Dashboard token id; `None` for API-key channels
+ */
+tokenId?: number | null }
 /**
  * Token from channel API
  */
@@ -2163,11 +2946,15 @@ groupName: string | null }
 /**
  * Channel types supported
  */
-export type ChannelType = "new-api" | "sub-2-api" | "cli-proxy-api" | "ollama" | "general" | "deep-seek"
+export type ChannelType = "new-api" | "sub-2-api" | "cli-proxy-api" | "ollama" | "general" | "deep-seek" | "open-router" | "litellm" | "one-api"
 /**
  * Claude Code profile stored in DroidGear.
  */
-export type ClaudeCodeProfile = { id: string; name: string; description?: string | null; baseUrl?: string | null; bearerToken?: string | null; model?: string | null; smallModelUsesMainModel?: boolean; smallModel?: string | null; reasoningEffort?: ClaudeReasoningEffort | null; thinkingMode?: ClaudeThinkingMode; createdAt: string; updatedAt: string }
+export type ClaudeCodeProfile = { id: string; name: string; description?: string | null; baseUrl?: string | null; bearerToken?: string | null; model?: string | null; smallModelUsesMainModel?: boolean; smallModel?: string | null; reasoningEffort?: ClaudeReasoningEffort | null; thinkingMode?: ClaudeThinkingMode; 
+/**
+ * Channel this entry was provisioned from (DroidGear-only)
+ */
+channelRef?: ChannelRef | null; createdAt: string; updatedAt: string }
 /**
  * Claude Code live config status.
  */
@@ -2219,13 +3006,17 @@ export type CodexProfile = { id: string; name: string; description?: string | nu
 /**
  * Codex Provider 配置（对应 config.toml 中的 [model_providers.<id>]）
  */
-export type CodexProviderConfig = { name?: string | null; baseUrl?: string | null; wireApi?: string | null; requiresOpenaiAuth?: boolean | null; envKey?: string | null; envKeyInstructions?: string | null; httpHeaders?: Partial<{ [key in string]: string }> | null; queryParams?: Partial<{ [key in string]: string }> | null; model?: string | null; modelReasoningEffort?: string | null; apiKey?: string | null }
+export type CodexProviderConfig = { name?: string | null; baseUrl?: string | null; wireApi?: string | null; requiresOpenaiAuth?: boolean | null; envKey?: string | null; envKeyInstructions?: string | null; httpHeaders?: Partial<{ [key in string]: string }> | null; queryParams?: Partial<{ [key in string]: string }> | null; model?: string | null; modelReasoningEffort?: string | null; apiKey?: string | null; channelRef?: ChannelRef | null }
 export type CodexTemporaryRunPlan = { program: string; args: string[]; env: ([string, string])[]; unsetEnv: string[]; secretEnvKeys: string[]; warnings: string[] }
 /**
  * User-defined configuration paths (only stores explicitly set paths)
  */
 export type ConfigPaths = { factory?: string | null; opencode?: string | null; opencodeAuth?: string | null; codex?: string | null; claude?: string | null; openclaw?: string | null; hermes?: string | null; pi?: string | null }
-export type ConnectionDiagnostics = { success: boolean; provider: string; modelId: string; latencyMs: number; error?: string | null; timestamp: string; testMode: TestMode; 
+export type ConnectionDiagnostics = { success: boolean; provider: string; modelId: string; latencyMs: number; error?: string | null; 
+/**
+ * HTTP status of the failed request, when the endpoint answered
+ */
+statusCode?: number | null; timestamp: string; testMode: TestMode; 
 /**
  * Actual model response text (inference mode only).
  */
@@ -2233,12 +3024,40 @@ responseText?: string | null;
 /**
  * The prompt that was sent (inference mode only).
  */
-promptUsed?: string | null }
+promptUsed?: string | null; 
+/**
+ * Every attempt made for this test, including retries (oldest first).
+ */
+attempts?: AttemptDiagnostics[]; 
+/**
+ * Capability detected by a probe mode (None for ping/inference).
+ */
+capabilities?: ModelCapabilities | null }
 export type ConnectivitySummary = { totalModels: number; availableModels: number; unavailableModels: number; avgLatencyMs: number; lastUpdated: string }
 /**
  * Message content block
  */
-export type ContentBlock = { type: string; text?: string | null; thinking?: string | null }
+export type ContentBlock = { type: string; text?: string | null; thinking?: string | null; 
+/**
+ * Tool call ID (`tool_use`), or the call a `tool_result` answers
+ */
+toolUseId?: string | null; 
+/**
+ * Tool name (`tool_use`; copied onto the linked `tool_result`)
+ */
+toolName?: string | null; 
+/**
+ * Tool input (`tool_use`)
+ */
+toolInput?: JsonValue | null; 
+/**
+ * Result text (`tool_result`)
+ */
+toolResult?: string | null; 
+/**
+ * Whether the tool reported an error (`tool_result`)
+ */
+isError?: boolean | null }
 /**
  * Custom model configuration
  */
@@ -2286,7 +3105,11 @@ extraArgs?: Partial<{ [key in string]: JsonValue }> | null;
 /**
  * Additional HTTP headers
  */
-extraHeaders?: Partial<{ [key in string]: string }> | null }
+extraHeaders?: Partial<{ [key in string]: string }> | null; 
+/**
+ * Channel this model was provisioned from
+ */
+channelRef?: ChannelRef | null }
 export type DroidRunPreferences = { disableAutoUpdateEnv?: boolean | null; unsetAnthropicAuthToken?: boolean | null }
 /**
  * Effective path info with default indicator
@@ -2299,7 +3122,39 @@ export type EffectivePaths = { factory: EffectivePath; opencode: EffectivePath;
 /**
  * Output format
  */
-export type ExportFormat = "json" | "yaml" | "toml"
+export type ExportFormat = "json" | "yaml" | "toml" | 
+/**
+ * Codex `config.toml` `[model_providers.*]` fragment
+ */
+"codex-config" | 
+/**
+ * OpenCode `opencode.json` with a `provider` block
+ */
+"opencode-config" | 
+/**
+ * Pi `models.json`
+ */
+"pi-models" | 
+/**
+ * OpenClaw `models` block (`mode` + `providers`)
+ */
+"openclaw-providers" | 
+/**
+ * Hermes `config.yaml` `model` section
+ */
+"hermes-config" | 
+/**
+ * `.env` file with one API key and base URL per token
+ */
+"env" | 
+/**
+ * CSV with one row per flat record
+ */
+"csv" | 
+/**
+ * Rendered from the user text template at `templatePath`
+ */
+"text"
 /**
  * Result of an export run.
  */
@@ -2325,7 +3180,8 @@ modelsCount: number;
  */
 outputPath: string; 
 /**
- * Record count (rows written)
+ * Record count (rows written; top-level channel entries for nested output
+ * and text templates, providers for tool-native formats)
  */
 recordCount: number; 
 /**
@@ -2362,7 +3218,24 @@ fields?: Partial<{ [key in string]: string }>; format: ExportFormat; outputStruc
 /**
  * Output file path (supports ~ and {timestamp})
  */
-outputPath: string }
+outputPath: string; 
+/**
+ * MiniJinja template rendered when `format` is `text`
+ * (supports ~; relative paths resolve against `~/.droidgear/`)
+ */
+templatePath?: string | null }
+/**
+ * Planned write of one file
+ */
+export type FilePlan = { path: string; 
+/**
+ * Current content with secrets masked (None if the file does not exist)
+ */
+before?: string | null; 
+/**
+ * Content that would be written, with secrets masked
+ */
+after: string; changed: boolean; changes: KeyChange[] }
 /**
  * Hermes Live 配置状态
  */
@@ -2374,12 +3247,76 @@ export type HermesCurrentConfig = { model: HermesModelConfig }
 /**
  * Hermes model 配置（对应 config.yaml 中的 model 节）
  */
-export type HermesModelConfig = { default?: string | null; provider?: string | null; baseUrl?: string | null; apiKey?: string | null }
+export type HermesModelConfig = { default?: string | null; provider?: string | null; baseUrl?: string | null; apiKey?: string | null; 
+/**
+ * Channel this entry was provisioned from (DroidGear-only)
+ */
+channelRef?: ChannelRef | null }
 /**
  * Hermes Profile（用于在 DroidGear 内部保存并切换）
  */
 export type HermesProfile = { id: string; name: string; description?: string | null; createdAt: string; updatedAt: string; model: HermesModelConfig }
+/**
+ * Highlighted range within a snippet, in UTF-16 code units (end exclusive)
+ * so it indexes JavaScript strings directly
+ */
+export type HighlightRange = { start: number; end: number }
+/**
+ * One persisted test result
+ */
+export type HistoryRecord = { timestamp: string; modelId: string; modelName: string; provider: string; 
+/**
+ * Endpoint the model was tested against (identifies the relay channel)
+ */
+baseUrl?: string | null; testMode: TestMode; success: boolean; latencyMs: number; error?: string | null; 
+/**
+ * Number of attempts made (including retries)
+ */
+attempts?: number }
 export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+/**
+ * A single key-level change (values rendered as JSON, secrets masked)
+ */
+export type KeyChange = { 
+/**
+ * Dotted key path, e.g. `model_providers.custom.base_url` or `customModels[0].apiKey`
+ */
+key: string; kind: KeyChangeKind; before?: string | null; after?: string | null }
+export type KeyChangeKind = "added" | "removed" | "changed"
+export type LinkDrift = { 
+/**
+ * Tool name (`codex`, `claude`, ...) or `factory` for custom models
+ */
+target: string; profileId: string; profileName: string; 
+/**
+ * Provider key inside the profile (model id for custom models)
+ */
+entry: string; field: LinkField; 
+/**
+ * Current value (keys masked; missing model ids for `models`)
+ */
+current?: string | null; 
+/**
+ * Value resolved from the channel (keys masked)
+ */
+expected?: string | null; 
+/**
+ * Whether the fix was written (single selected models are only reported)
+ */
+applied: boolean }
+/**
+ * Linked value that no longer matches the channel
+ */
+export type LinkField = "baseUrl" | "apiKey" | 
+/**
+ * Models the channel no longer offers
+ */
+"models"
+export type LinkSyncReport = { 
+/**
+ * Number of linked entries checked
+ */
+checked: number; drift: LinkDrift[]; errors: string[] }
 /**
  * MCP server entry with name
  */
@@ -2432,11 +3369,95 @@ export type McpServerType = "stdio" | "http"
  * Mission model settings for Mission mode workers
  */
 export type MissionModelSettings = { workerModel?: string | null; workerReasoningEffort?: string | null; validationWorkerModel?: string | null; validationWorkerReasoningEffort?: string | null }
+/**
+ * Availability statistics of one model over a time window
+ */
+export type ModelAvailabilityStats = { modelId: string; modelName: string; provider: string; baseUrl?: string | null; totalTests: number; successfulTests: number; 
+/**
+ * Successful tests / total tests, 0-100
+ */
+uptimePercent: number; 
+/**
+ * Latency percentiles of successful tests
+ */
+p50LatencyMs?: number | null; p95LatencyMs?: number | null; lastFailureReason?: string | null; lastFailureAt?: string | null; lastTestedAt: string }
+/**
+ * Capabilities detected by the probe modes.
+ * 
+ * `None` means not probed or inconclusive (e.g. network error); `Some(false)`
+ * means the endpoint rejected the request or answered with the wrong shape.
+ */
+export type ModelCapabilities = { streaming?: boolean | null; toolUse?: boolean | null; vision?: boolean | null }
+/**
+ * One proposed field update, for review (secrets masked)
+ */
+export type ModelFieldChange = { 
+/**
+ * Field name as stored in settings.json (e.g. `noImageSupport`)
+ */
+field: string; before?: string | null; after?: string | null; 
+/**
+ * Why the change is proposed (probe evidence)
+ */
+reason: string }
 /**
  * Model info returned from API
  */
-export type ModelInfo = { id: string; name: string | null }
+export type ModelInfo = { id: string; name: string | null; 
+/**
+ * Context window in tokens
+ */
+contextWindow?: number | null; 
+/**
+ * Maximum output tokens per response
+ */
+maxOutputTokens?: number | null; 
+/**
+ * Accepted input modalities (e.g. "text", "image")
+ */
+inputModalities: string[]; 
+/**
+ * Whether the model supports extended thinking / reasoning
+ */
+supportsReasoning?: boolean | null; pricing?: ModelPricing | null; 
+/**
+ * Release timestamp as reported by the provider (e.g. Anthropic's `created_at`)
+ */
+createdAt?: string | null }
+/**
+ * Proposed updates to one custom model
+ */
+export type ModelPatch = { 
+/**
+ * `CustomModel.id`, or `model` when the entry has no id
+ */
+modelId: string; modelName: string; noImageSupport?: boolean | null; maxOutputTokens?: number | null; 
+/**
+ * Headers merged into the model's `extraHeaders`
+ */
+extraHeaders?: Partial<{ [key in string]: string }> | null; changes: ModelFieldChange[]; capabilities: ModelCapabilities; probes: ConnectionDiagnostics[] }
+/**
+ * Model prices in USD per million tokens
+ */
+export type ModelPricing = { input?: number | null; output?: number | null; cacheRead?: number | null; cacheWrite?: number | null }
 export type ModelTestResult = { modelId: string; modelName: string; diagnostics: ConnectionDiagnostics; isAvailable: boolean }
+/**
+ * Token to create on a relay dashboard (New API, one-api, Sub2API)
+ */
+export type NewChannelToken = { name: string; 
+/**
+ * Quota in the backend's own units (as in `ChannelToken::remain_quota`);
+ * `None` for unlimited. Sub2API keys draw on the account balance and ignore it.
+ */
+remainQuota?: number | null; 
+/**
+ * New API group name, or Sub2API group id or name
+ */
+group?: string | null; 
+/**
+ * Sub2API platform; picks the first group of that platform when `group` is unset
+ */
+platform?: string | null }
 /**
  * OpenClaw config status
  */
@@ -2456,7 +3477,11 @@ export type OpenClawProfile = { id: string; name: string; description?: string |
 /**
  * OpenClaw Provider configuration
  */
-export type OpenClawProviderConfig = { baseUrl?: string | null; apiKey?: string | null; api?: string | null; models?: OpenClawModel[] }
+export type OpenClawProviderConfig = { baseUrl?: string | null; apiKey?: string | null; api?: string | null; models?: OpenClawModel[]; 
+/**
+ * Channel this entry was provisioned from (DroidGear-only)
+ */
+channelRef?: ChannelRef | null }
 /**
  * OpenClaw SubAgent definition
  */
@@ -2500,7 +3525,11 @@ export type OpenCodeProfile = { id: string; name: string; description?: string |
 /**
  * OpenCode Provider configuration
  */
-export type OpenCodeProviderConfig = { npm?: string | null; name?: string | null; options?: OpenCodeProviderOptions | null; models?: Partial<{ [key in string]: OpenCodeModelConfig }> | null }
+export type OpenCodeProviderConfig = { npm?: string | null; name?: string | null; options?: OpenCodeProviderOptions | null; models?: Partial<{ [key in string]: OpenCodeModelConfig }> | null; 
+/**
+ * Channel this entry was provisioned from (DroidGear-only)
+ */
+channelRef?: ChannelRef | null }
 /**
  * OpenCode Provider options
  */
@@ -2544,8 +3573,28 @@ export type PiProfile = { id: string; name: string; description?: string | null;
 /**
  * Pi provider configuration
  */
-export type PiProviderConfig = { baseUrl?: string | null; api?: string | null; apiKey?: string | null; headers?: Partial<{ [key in string]: string }> | null; authHeader?: boolean | null; models: PiModel[]; modelOverrides?: Partial<{ [key in string]: PiModelOverride }> | null; compat?: PiCompatConfig | null }
+export type PiProviderConfig = { baseUrl?: string | null; api?: string | null; apiKey?: string | null; headers?: Partial<{ [key in string]: string }> | null; authHeader?: boolean | null; models: PiModel[]; modelOverrides?: Partial<{ [key in string]: PiModelOverride }> | null; compat?: PiCompatConfig | null; 
+/**
+ * Channel this entry was provisioned from (DroidGear-only)
+ */
+channelRef?: ChannelRef | null }
 export type PortableUpdateInfo = { version: string; body: string | null; pubDate: string | null; url: string; signature: string; sha256: string; releaseUrl: string }
+/**
+ * One model endpoint configured in a profile
+ */
+export type ProfileEndpoint = { tool: ProfileTool; profileId: string; profileName: string; 
+/**
+ * Provider key inside the profile (None for single-endpoint profiles)
+ */
+providerId?: string | null; modelId: string; protocol: WireProtocol; baseUrl: string; hasApiKey: boolean }
+/**
+ * Connectivity result for one profile endpoint
+ */
+export type ProfileTestResult = { endpoint: ProfileEndpoint; diagnostics: ConnectionDiagnostics; isAvailable: boolean }
+/**
+ * Tool whose profiles DroidGear manages
+ */
+export type ProfileTool = "codex" | "claude" | "opencode" | "openclaw" | "pi" | "hermes"
 /**
  * Provider types supported by Factory BYOK
  */
@@ -2554,6 +3603,60 @@ export type Provider = "anthropic" | "openai" | "generic-chat-completion-api"
  * Provider template for quick setup
  */
 export type ProviderTemplate = { id: string; name: string; npm?: string | null; defaultBaseUrl?: string | null; requiresApiKey: boolean }
+/**
+ * Profiles generated from one channel token
+ */
+export type ProvisionedProfiles = { codex: CodexProfile; 
+/**
+ * Only generated when at least one selected model speaks the Anthropic protocol
+ */
+claude?: ClaudeCodeProfile | null; opencode: OpenCodeProfile; openclaw: OpenClawProfile; pi: PiProfile; hermes: HermesProfile; customModels: CustomModel[] }
+export type QuotaAlert = { channelId: string; channelName: string; kind: QuotaAlertKind; message: string }
+export type QuotaAlertKind = 
+/**
+ * Balance is used up
+ */
+"exhausted" | 
+/**
+ * Balance is below the low-balance threshold
+ */
+"lowBalance" | 
+/**
+ * At the current burn rate the balance runs out soon
+ */
+"runningOut"
+/**
+ * Quota monitor settings (stored under `quotaMonitor` in DroidGear settings)
+ */
+export type QuotaMonitorSettings = { enabled: boolean; 
+/**
+ * Minutes between automatic refreshes
+ */
+intervalMinutes: number; 
+/**
+ * Alert when the remaining balance drops below this amount
+ */
+lowBalanceThreshold: number | null; 
+/**
+ * Alert when the estimated days remaining drop below this
+ */
+daysRemainingThreshold: number | null; 
+/**
+ * Per-channel overrides of `low_balance_threshold`, keyed by channel id
+ */
+channelThresholds: Partial<{ [key in string]: number }> }
+/**
+ * Result of a refresh
+ */
+export type QuotaRefreshReport = { checkedAt: string; statuses: ChannelQuotaStatus[]; 
+/**
+ * Alerts that were not active before this refresh (to notify about)
+ */
+newAlerts: QuotaAlert[] }
+/**
+ * One recorded balance of a channel
+ */
+export type QuotaSnapshot = { timestamp: string; channelId: string; remaining?: number | null; used?: number | null; unlimited?: boolean; unit: string }
 /**
  * Error types for recovery operations (typed for frontend matching)
  */
@@ -2578,6 +3681,50 @@ export type RecoveryError =
  * JSON serialization/deserialization error
  */
 { type: "ParseError"; message: string }
+/**
+ * What happens to the old token once references point at the new one
+ */
+export type RevokeMode = "disable" | "delete"
+/**
+ * One matching piece of a session
+ */
+export type SearchSnippet = { 
+/**
+ * Message ID (empty for title matches)
+ */
+messageId: string; 
+/**
+ * Message role ("session" for title matches)
+ */
+role: string; 
+/**
+ * "title", "text", "thinking", "tool_use" or "tool_result"
+ */
+kind: string; timestamp: string; 
+/**
+ * Excerpt around the first match, newlines flattened to spaces
+ */
+text: string; highlights: HighlightRange[] }
+/**
+ * A stored secret (the value itself is never listed)
+ */
+export type SecretInfo = { name: string; 
+/**
+ * Reference to put in a profile field (`secret://name`)
+ */
+reference: string; updatedAt: string }
+/**
+ * Result of moving plaintext keys into the vault
+ */
+export type SecretMigrationReport = { 
+/**
+ * Names of the secrets that were created
+ */
+migrated: string[]; 
+/**
+ * Items that could not be migrated, with the reason
+ */
+errors: string[] }
 /**
  * Session default settings for mixed models configuration
  */
@@ -2585,7 +3732,51 @@ export type SessionDefaultSettings = { model?: string | null; reasoningEffort?:
 /**
  * Session detail with messages
  */
-export type SessionDetail = { id: string; title: string; project: string; model: string; cwd: string; modifiedAt: number; tokenUsage: TokenUsage; messages: SessionMessage[] }
+export type SessionDetail = { source?: SessionSource; id: string; title: string; project: string; model: string; cwd: string; modifiedAt: number; tokenUsage: TokenUsage; messages: SessionMessage[] }
+/**
+ * A rendered export
+ */
+export type SessionExport = { 
+/**
+ * Suggested file name (`session-<id>.<ext>`)
+ */
+fileName: string; content: string; 
+/**
+ * Number of values masked by redaction
+ */
+redactions: number; 
+/**
+ * Where the export was written, if it was
+ */
+outputPath?: string | null }
+/**
+ * Session export format
+ */
+export type SessionExportFormat = "markdown" | 
+/**
+ * Self-contained HTML page (inline styles, no scripts)
+ */
+"html" | 
+/**
+ * JSON document with the session detail and export metadata
+ */
+"bundle"
+/**
+ * What to include in an export
+ */
+export type SessionExportOptions = { format?: SessionExportFormat; 
+/**
+ * Include thinking blocks
+ */
+includeThinking?: boolean; 
+/**
+ * Include tool calls and results
+ */
+includeTools?: boolean; 
+/**
+ * Mask API keys, emails and home paths
+ */
+redact?: boolean }
 /**
  * Session message
  */
@@ -2593,13 +3784,15 @@ export type SessionMessage = { id: string; role: string; content: ContentBlock[]
 /**
  * Session project (directory containing sessions)
  */
-export type SessionProject = { 
+export type SessionProject = { source?: SessionSource; 
 /**
- * Directory name (e.g., "-Users-sunshow-GIT-sunshow-quickcast-api")
+ * Directory name (e.g., "-Users-sunshow-GIT-sunshow-quickcast-api"); for
+ * sources without per-project directories, the working directory in the
+ * same form
  */
 name: string; 
 /**
- * Full path to the directory
+ * Full path to the directory (empty when the source has none)
  */
 path: string; 
 /**
@@ -2610,10 +3803,66 @@ sessionCount: number;
  * Last modified timestamp in milliseconds
  */
 modifiedAt: number }
+/**
+ * Search filters (all optional)
+ */
+export type SessionSearchFilters = { 
+/**
+ * Agent the session belongs to
+ */
+source?: SessionSource | null; 
+/**
+ * Project directory name
+ */
+project?: string | null; 
+/**
+ * Case-insensitive substring of the session model
+ */
+model?: string | null; 
+/**
+ * Only match entries at or after this timestamp (milliseconds)
+ */
+from?: number | null; 
+/**
+ * Only match entries at or before this timestamp (milliseconds)
+ */
+to?: number | null; 
+/**
+ * Message role ("user" or "assistant")
+ */
+role?: string | null; 
+/**
+ * Maximum number of sessions returned (default 50)
+ */
+limit?: number | null }
+/**
+ * A session matching a search
+ */
+export type SessionSearchHit = { source: SessionSource; id: string; title: string; project: string; model: string; 
+/**
+ * Last modified timestamp in milliseconds
+ */
+modifiedAt: number; 
+/**
+ * Full path to the session files (without extension)
+ */
+path: string; 
+/**
+ * Number of matching entries
+ */
+matchCount: number; 
+/**
+ * The first few matching entries
+ */
+snippets: SearchSnippet[] }
+/**
+ * Agent whose history a session belongs to
+ */
+export type SessionSource = "factory" | "claude" | "codex" | "opencode" | "pi"
 /**
  * Session summary for list view
  */
-export type SessionSummary = { 
+export type SessionSummary = { source?: SessionSource; 
 /**
  * Session UUID
  */
@@ -2690,10 +3939,31 @@ modifiedAt: number }
  * Telegram channel configuration
  */
 export type TelegramChannelConfig = { blockStreaming?: boolean | null; chunkMode?: string | null }
+export type TestConfig = { timeoutSeconds?: number; retryOnFailure?: boolean; maxRetries?: number; 
+/**
+ * Delay before the first retry; doubled for every further retry.
+ */
+retryBackoffMs?: number; 
+/**
+ * Maximum number of models tested at the same time.
+ */
+maxConcurrency?: number }
 /**
- * Test mode: ping (HTTP probe) or inference (real model call).
+ * Test mode: ping (HTTP probe), inference (real model call) or a capability probe.
  */
-export type TestMode = "ping" | "inference"
+export type TestMode = "ping" | "inference" | 
+/**
+ * SSE streaming request; validates the event stream shape
+ */
+"streaming" | 
+/**
+ * Forced tool call; validates the returned tool call
+ */
+"toolUse" | 
+/**
+ * Image input; validates the model answers a prompt with an image attached
+ */
+"vision"
 /**
  * Token filter conditions
  */
@@ -2706,11 +3976,109 @@ status: number | null;
  * Only include tokens matching these platforms (empty = all)
  */
 platforms?: string[] }
+export type TokenRotationReport = { newToken: ChannelToken; updated: string[]; 
+/**
+ * Whether the old token was revoked (it stays active when any
+ * reference could not be rewritten)
+ */
+revoked: boolean; errors: string[] }
 /**
  * Token usage statistics
  */
 export type TokenUsage = { inputTokens: number; outputTokens: number; cacheCreationTokens: number; cacheReadTokens: number; thinkingTokens: number }
 export type UpdateChannel = "managed" | "portable"
+/**
+ * Totals of one day, week, project, model or agent
+ */
+export type UsageBucket = { key: string; totals: UsageTotals }
+/**
+ * Which sessions a usage report covers
+ */
+export type UsageQuery = { 
+/**
+ * Only sessions of this agent
+ */
+source?: SessionSource | null; 
+/**
+ * Only sessions of this project
+ */
+project?: string | null; 
+/**
+ * First day included (`YYYY-MM-DD`)
+ */
+from?: string | null; 
+/**
+ * Last day included (`YYYY-MM-DD`)
+ */
+to?: string | null; 
+/**
+ * Offset used to assign sessions to days; defaults to the local offset
+ */
+utcOffsetMinutes?: number | null }
+/**
+ * Usage rolled up along every dimension.
+ * 
+ * Sessions only record their total usage, so a session's whole usage lands
+ * on the day (and week) it was last modified, even if it spanned several
+ * days. The `from`/`to` filters apply to that day as well.
+ */
+export type UsageReport = { totals: UsageTotals; 
+/**
+ * Oldest day first (`YYYY-MM-DD`, the day each session was last modified)
+ */
+byDay: UsageBucket[]; 
+/**
+ * Oldest week first (ISO week, `2026-W03`, of that same day)
+ */
+byWeek: UsageBucket[]; 
+/**
+ * Most expensive first
+ */
+byProject: UsageBucket[]; byModel: UsageBucket[]; bySource: UsageBucket[]; 
+/**
+ * Models seen without a price
+ */
+unpricedModels: string[] }
+/**
+ * Token counts and estimated cost of a group of sessions
+ */
+export type UsageTotals = { sessions: number; inputTokens: number; outputTokens: number; cacheCreationTokens: number; cacheReadTokens: number; thinkingTokens: number; 
+/**
+ * Estimated cost in USD of the priced sessions
+ */
+cost: number; 
+/**
+ * Sessions whose model has no known price (not included in `cost`)
+ */
+unpricedSessions: number; 
+/**
+ * Cache reads / all prompt tokens (input + cache reads + cache writes), 0-1
+ */
+cacheHitRatio: number }
+/**
+ * Vault state as shown to the user
+ */
+export type VaultStatus = { initialized: boolean; unlocked: boolean; secretCount: number; path: string }
+/**
+ * HTTP API shape used to talk to an endpoint.
+ */
+export type WireProtocol = 
+/**
+ * Anthropic `/v1/messages`
+ */
+"anthropicMessages" | 
+/**
+ * OpenAI-compatible `/v1/chat/completions`
+ */
+"chatCompletions" | 
+/**
+ * OpenAI Responses API `/v1/responses` (Codex `wire_api = "responses"`)
+ */
+"responses" | 
+/**
+ * Google Gemini `models/{model}:generateContent`
+ */
+"gemini"
 /**
  * WSL distribution info
  */